@@ -15,11 +15,13 @@ fn main() {
 
     // Fast CDC Feature
     println!("cargo:rerun-if-env-changed=CARGO_FEATURE_FASTCDC");
+    println!("cargo:rerun-if-env-changed=CARGO_FEATURE_FOUR_BYTE_ROLLING");
 
     if env::var("CARGO_FEATURE_FASTCDC").is_ok() {
         println!("cargo:rerun-if-env-changed=GEAR_SEED");
         let gear_table = generate_gear_table();
         generate_mask_table(&gear_table);
+        generate_mask_table32(&gear_table);
     }
 }
 
@@ -70,13 +72,46 @@ fn generate_gear_table() -> [u64; GEAR_TABLE_SIZE] {
 
     writeln!(file_buf, "];").unwrap();
 
+    writeln!(file_buf).unwrap();
+
+    // Write the Gear Tables shifted by 2 and 3 for the four-byte scan
+    if env::var("CARGO_FEATURE_FOUR_BYTE_ROLLING").is_ok() {
+        for shift in [2, 3] {
+            writeln!(
+                file_buf,
+                "pub const GEAR_LS{}: [u64; {}] = [",
+                shift, GEAR_TABLE_SIZE
+            )
+            .unwrap();
+
+            for &val in gear_table.iter() {
+                writeln!(file_buf, "    {:#018x},", val << shift).unwrap();
+            }
+
+            writeln!(file_buf, "];").unwrap();
+
+            writeln!(file_buf).unwrap();
+        }
+    }
+
+    // Write 32-bit Gear Table (the high half of each entry)
+    writeln!(file_buf, "pub const GEAR32: [u32; {}] = [", GEAR_TABLE_SIZE).unwrap();
+
+    for &val in gear_table.iter() {
+        writeln!(file_buf, "    {:#010x},", val >> 32).unwrap();
+    }
+
+    writeln!(file_buf, "];").unwrap();
+
     gear_table
 }
 
 // --- Mask Table Generator ---
 
 const HIGH_BIT_RISK_START: usize = 60;
-const MASK_TABLE_SIZE: usize = 26;
+const HIGH_BIT_RISK_START_32: usize = 28;
+const MASK_TABLE_SIZE: usize = 32;
+const MASK_TABLE32_SIZE: usize = 26;
 const MASK_PADDING_SLOTS: usize = 5;
 
 fn generate_mask_table(gear_table: &[u64; GEAR_TABLE_SIZE]) {
@@ -91,7 +126,7 @@ fn generate_mask_table(gear_table: &[u64; GEAR_TABLE_SIZE]) {
     )
     .unwrap();
 
-    let stats = analyze_bits(gear_table);
+    let stats = analyze_bits(gear_table, 64, HIGH_BIT_RISK_START);
 
     for idx in 0..MASK_TABLE_SIZE {
         if idx < MASK_PADDING_SLOTS {
@@ -115,6 +150,34 @@ fn generate_mask_table(gear_table: &[u64; GEAR_TABLE_SIZE]) {
     writeln!(file_buf, "];").unwrap();
 }
 
+fn generate_mask_table32(gear_table: &[u64; GEAR_TABLE_SIZE]) {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let dest_path = Path::new(&out_dir).join("mask_table32.rs");
+    let mut file_buf = BufWriter::new(File::create(&dest_path).unwrap());
+
+    writeln!(
+        file_buf,
+        "pub const MASK_TABLE32: [u32; {}] = [",
+        MASK_TABLE32_SIZE
+    )
+    .unwrap();
+
+    let gear_table32: Vec<u64> = gear_table.iter().map(|&val| val >> 32).collect();
+    let stats = analyze_bits(&gear_table32, 32, HIGH_BIT_RISK_START_32);
+
+    for idx in 0..MASK_TABLE32_SIZE {
+        if idx < MASK_PADDING_SLOTS {
+            writeln!(file_buf, "    {:#x},", 0).unwrap();
+            continue;
+        }
+
+        let mask = find_optimal_mask(&stats, idx);
+        writeln!(file_buf, "    {:#x},", mask).unwrap();
+    }
+
+    writeln!(file_buf, "];").unwrap();
+}
+
 #[derive(Clone, Debug)]
 struct BitStat {
     position: usize,
@@ -122,11 +185,11 @@ struct BitStat {
     column: Vec<f64>,
 }
 
-fn analyze_bits(gear_table: &[u64; GEAR_TABLE_SIZE]) -> Vec<BitStat> {
+fn analyze_bits(gear_table: &[u64], width: usize, risk_start: usize) -> Vec<BitStat> {
     let mut stats = Vec::new();
 
-    for bit_pos in 0..64 {
-        let is_high_bit_risk = bit_pos >= HIGH_BIT_RISK_START;
+    for bit_pos in 0..width {
+        let is_high_bit_risk = bit_pos >= risk_start;
 
         let mut ones_count = 0;
         let mut raw_column = Vec::with_capacity(GEAR_TABLE_SIZE);
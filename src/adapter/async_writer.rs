@@ -0,0 +1,94 @@
+use crate::fastcdc::{Chunk, FastCDC, FastCDCSink};
+use bytes::Bytes;
+use futures::{Sink, ready};
+use std::{
+    error::Error,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::AsyncWrite;
+
+///
+/// An async writer that chunks the bytes written to it and forwards every chunk to a sink.
+///
+/// Can be handed to `tokio::io::copy`. The sink may be a `futures::channel::mpsc::Sender`,
+/// or a closure wrapped with `futures::sink::unfold` (pinned with `Box::pin`). Its errors
+/// are returned as they are if they are `io::Error`s, and wrapped with kind `Other` otherwise.
+///
+/// `poll_flush` only forwards chunks whose cut point is known; the buffered tail is
+/// emitted on shutdown, as cutting it early would change the chunks.
+///
+pub struct AsyncChunkWriter<'a, Si> {
+    sink: FastCDCSink<'a, Si>,
+}
+
+impl<'a, Si> AsyncChunkWriter<'a, Si>
+where
+    Si: Sink<Chunk> + Unpin,
+    Si::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    ///
+    /// Creates a writer that chunks with `chunker`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The chunker to use.
+    /// * `sink`: Receives every chunk, in order (must implement `Sink<Chunk>`).
+    ///
+    pub fn new(chunker: &'a FastCDC, sink: Si) -> Self {
+        Self {
+            sink: chunker.chunks_sink(sink),
+        }
+    }
+
+    /// Returns the number of bytes written but not yet forwarded as chunks.
+    pub fn buffered(&self) -> usize {
+        self.sink.buffered()
+    }
+
+    /// Consumes the writer, returning the sink.
+    pub fn into_inner(self) -> Si {
+        self.sink.into_inner()
+    }
+}
+
+impl<'a, Si> AsyncWrite for AsyncChunkWriter<'a, Si>
+where
+    Si: Sink<Chunk> + Unpin,
+    Si::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut sink = Pin::new(&mut self.get_mut().sink);
+
+        // Hand the previous chunks on first, so a slow sink applies backpressure.
+        ready!(sink.as_mut().poll_ready(cx)).map_err(into_io)?;
+        sink.start_send(Bytes::copy_from_slice(buf))
+            .map_err(into_io)?;
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Sink::<Bytes>::poll_flush(Pin::new(&mut self.get_mut().sink), cx).map_err(into_io)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Sink::<Bytes>::poll_close(Pin::new(&mut self.get_mut().sink), cx).map_err(into_io)
+    }
+}
+
+fn into_io<E: Into<Box<dyn Error + Send + Sync>>>(e: E) -> io::Error {
+    match e.into().downcast::<io::Error>() {
+        Ok(e) => *e,
+        Err(e) => io::Error::other(e),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/async_writer_tests.rs"]
+mod tests;
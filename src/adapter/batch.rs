@@ -0,0 +1,91 @@
+use crate::fastcdc::Chunk;
+use futures::Stream;
+use std::{
+    io, mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+///
+/// A stream that groups chunks into batches of a fixed size.
+///
+/// Each batch holds exactly `size` chunks, except the last one which holds whatever remains.
+/// When the underlying stream fails, the chunks collected so far are yielded first and the
+/// error follows on the next poll.
+///
+/// Created by [`ChunkStreamExt::batched`](crate::adapter::ChunkStreamExt::batched).
+///
+pub struct Batched<S> {
+    stream: S,
+    batch: Vec<Chunk>,
+    size: usize,
+    error: Option<io::Error>,
+    done: bool,
+}
+
+impl<S> Batched<S>
+where
+    S: Stream<Item = io::Result<Chunk>> + Unpin,
+{
+    pub(crate) fn new(stream: S, size: usize) -> Self {
+        assert!(size > 0, "batch size must be greater than zero");
+
+        Self {
+            stream,
+            batch: Vec::with_capacity(size),
+            size,
+            error: None,
+            done: false,
+        }
+    }
+
+    fn take_batch(&mut self) -> Vec<Chunk> {
+        mem::replace(&mut self.batch, Vec::with_capacity(self.size))
+    }
+}
+
+impl<S> Stream for Batched<S>
+where
+    S: Stream<Item = io::Result<Chunk>> + Unpin,
+{
+    type Item = io::Result<Vec<Chunk>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(e) = this.error.take() {
+            return Poll::Ready(Some(Err(e)));
+        }
+
+        while !this.done {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    this.batch.push(chunk);
+                    if this.batch.len() >= this.size {
+                        return Poll::Ready(Some(Ok(this.take_batch())));
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    if this.batch.is_empty() {
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    // Hand out the chunks read before the failure, then the error.
+                    this.error = Some(e);
+                    return Poll::Ready(Some(Ok(this.take_batch())));
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        if this.batch.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Ready(Some(Ok(this.take_batch())))
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/batch_tests.rs"]
+mod tests;
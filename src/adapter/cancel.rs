@@ -0,0 +1,202 @@
+use crate::fastcdc::Chunk;
+use std::{
+    io,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::Waker,
+};
+
+///
+/// A cloneable handle used to request cancellation of a long-running operation.
+///
+/// All clones share the same flag, so one clone can be handed to the operation and
+/// another kept by the caller (e.g. a signal handler or request timeout). Cancelling
+/// wakes every stream waiting on the token, so cancellation is prompt even while a
+/// stream is waiting on a slow reader.
+///
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<TokenInner>,
+}
+
+#[derive(Debug, Default)]
+struct TokenInner {
+    cancelled: AtomicBool,
+    // One slot per waiting stream, freed when the stream is dropped.
+    wakers: Mutex<Vec<Option<Waker>>>,
+}
+
+impl CancelToken {
+    /// Constructs a new token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of every operation observing this token.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+
+        for waker in self.wakers().iter_mut().filter_map(Option::take) {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` if cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Stores `waker` in `slot`, claiming a free slot first if needed, to be woken on cancellation.
+    #[cfg(feature = "async")]
+    pub(crate) fn register(&self, slot: &mut Option<usize>, waker: &Waker) {
+        let mut wakers = self.wakers();
+        let idx = *slot.get_or_insert_with(|| match wakers.iter().position(Option::is_none) {
+            Some(idx) => idx,
+            None => {
+                wakers.push(None);
+                wakers.len() - 1
+            }
+        });
+
+        match &mut wakers[idx] {
+            Some(current) if current.will_wake(waker) => {}
+            entry => *entry = Some(waker.clone()),
+        }
+    }
+
+    /// Frees a slot claimed by `register`.
+    pub(crate) fn unregister(&self, slot: usize) {
+        self.wakers()[slot] = None;
+    }
+
+    fn wakers(&self) -> MutexGuard<'_, Vec<Option<Waker>>> {
+        self.inner.wakers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+///
+/// An adapter that stops an iterator or stream of chunks once its token is cancelled.
+///
+/// The token is checked before each chunk is requested, so cancellation takes effect at
+/// the next chunk boundary. Chunks yielded before that are complete and can be kept as
+/// partial results. After cancellation a single `ErrorKind::Interrupted` error is yielded,
+/// distinguishing it from the end of the input, and the adapter then ends.
+///
+/// A stream waiting on its reader is woken by cancellation and ends without waiting for
+/// the read to complete. The wrapped iterator or stream remains accessible through
+/// [`WithCancel::get_ref`] or [`WithCancel::into_inner`], so statistics such as its
+/// buffer usage or cut-point counters can be reported for the partial pass.
+///
+/// Created by [`ChunkIterExt::with_cancel`](crate::adapter::ChunkIterExt::with_cancel)
+/// or `ChunkStreamExt::with_cancel`.
+///
+pub struct WithCancel<I> {
+    inner: I,
+    waiter: Waiter,
+    done: bool,
+}
+
+/// A token and the waker slot claimed on it, freed when dropped.
+struct Waiter {
+    token: CancelToken,
+    slot: Option<usize>,
+}
+
+impl Drop for Waiter {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot {
+            self.token.unregister(slot);
+        }
+    }
+}
+
+impl<I> WithCancel<I> {
+    pub(crate) fn new(inner: I, token: CancelToken) -> Self {
+        Self {
+            inner,
+            waiter: Waiter { token, slot: None },
+            done: false,
+        }
+    }
+
+    /// Returns the wrapped iterator or stream.
+    pub fn get_ref(&self) -> &I {
+        &self.inner
+    }
+
+    /// Returns the wrapped iterator or stream mutably.
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    /// Consumes the adapter, returning the wrapped iterator or stream.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    fn check(&mut self) -> Option<Option<io::Result<Chunk>>> {
+        if self.done {
+            return Some(None);
+        }
+        if self.waiter.token.is_cancelled() {
+            self.done = true;
+            return Some(Some(Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "operation cancelled",
+            ))));
+        }
+        None
+    }
+}
+
+impl<I> Iterator for WithCancel<I>
+where
+    I: Iterator<Item = io::Result<Chunk>>,
+{
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.check() {
+            return item;
+        }
+        self.inner.next()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S> futures::Stream for WithCancel<S>
+where
+    S: futures::Stream<Item = io::Result<Chunk>> + Unpin,
+{
+    type Item = io::Result<Chunk>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(item) = this.check() {
+            return std::task::Poll::Ready(item);
+        }
+
+        match std::pin::Pin::new(&mut this.inner).poll_next(cx) {
+            std::task::Poll::Pending => {
+                // Check again after registering, in case the token was cancelled meanwhile.
+                let waiter = &mut this.waiter;
+                waiter.token.register(&mut waiter.slot, cx.waker());
+                match this.check() {
+                    Some(item) => std::task::Poll::Ready(item),
+                    None => std::task::Poll::Pending,
+                }
+            }
+            ready => ready,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/cancel_tests.rs"]
+mod tests;
@@ -0,0 +1,90 @@
+use crate::fastcdc::Chunk;
+use futures::{
+    Stream, StreamExt,
+    future::{Either, Ready, ready},
+    stream::FuturesOrdered,
+};
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+///
+/// A stream that runs per-chunk futures with bounded concurrency while preserving emission order.
+///
+/// Created by [`ChunkStreamExt::map_concurrent`](crate::adapter::ChunkStreamExt::map_concurrent).
+///
+pub struct MapConcurrent<S, F, Fut>
+where
+    Fut: Future,
+{
+    stream: S,
+    f: F,
+    in_flight: FuturesOrdered<Either<Fut, Ready<Fut::Output>>>,
+    limit: usize,
+    done: bool,
+}
+
+// `F` is only ever accessed through `&mut` and the in-flight futures are pinned by
+// `FuturesOrdered` itself, so neither needs to be `Unpin`.
+impl<S: Unpin, F, Fut: Future> Unpin for MapConcurrent<S, F, Fut> {}
+
+impl<S, F, Fut, T> MapConcurrent<S, F, Fut>
+where
+    S: Stream<Item = io::Result<Chunk>> + Unpin,
+    F: FnMut(Chunk) -> Fut,
+    Fut: Future<Output = io::Result<T>>,
+{
+    pub(crate) fn new(stream: S, limit: usize, f: F) -> Self {
+        assert!(limit > 0, "concurrency limit must be greater than zero");
+
+        Self {
+            stream,
+            f,
+            in_flight: FuturesOrdered::new(),
+            limit,
+            done: false,
+        }
+    }
+}
+
+impl<S, F, Fut, T> Stream for MapConcurrent<S, F, Fut>
+where
+    S: Stream<Item = io::Result<Chunk>> + Unpin,
+    F: FnMut(Chunk) -> Fut,
+    Fut: Future<Output = io::Result<T>>,
+{
+    type Item = io::Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        // Top up the in-flight queue before polling it.
+        while !this.done && this.in_flight.len() < this.limit {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    this.in_flight.push_back(Either::Left((this.f)(chunk)));
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    // Queue the error behind the work already in flight to keep ordering.
+                    this.in_flight.push_back(Either::Right(ready(Err(e))));
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        match this.in_flight.poll_next_unpin(cx) {
+            Poll::Ready(Some(item)) => Poll::Ready(Some(item)),
+            // An empty queue only ends the stream once the source is exhausted.
+            Poll::Ready(None) if this.done => Poll::Ready(None),
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/concurrent_tests.rs"]
+mod tests;
@@ -0,0 +1,152 @@
+use crate::digest::Sha256;
+use crate::fastcdc::{Chunk, FastCDC};
+use std::io::{self, Write};
+
+/// The header row written by [`WithCsv`].
+pub const CSV_HEADER: &str = "offset,length,fp_hash,digest,cut_reason";
+
+/// A record whose cut reason is known only once the next chunk (or the end) arrives.
+struct Pending {
+    offset: u64,
+    length: usize,
+    fp_hash: u64,
+    digest: [u8; 32],
+}
+
+///
+/// An adapter that writes one CSV row per chunk of an iterator or stream.
+///
+/// Each row holds the offset, length, hex fingerprint, hex SHA-256 digest, and cut
+/// reason of a chunk: `content` for a content-defined cut point, `max` for a cut forced
+/// at `max_size`, or `eof` for a final chunk ended by the data. A row is written once the
+/// following item arrives, since only then is it known whether the chunk was the last.
+///
+/// Chunks pass through unchanged. A write error is yielded in place of the chunk being
+/// observed, and rows are written synchronously even when adapting a stream.
+///
+/// Created by [`ChunkIterExt::with_csv`](crate::adapter::ChunkIterExt::with_csv)
+/// or `ChunkStreamExt::with_csv`.
+///
+pub struct WithCsv<I, W> {
+    inner: I,
+    writer: W,
+    max_size: usize,
+    header_written: bool,
+    pending: Option<Pending>,
+    finished: bool,
+}
+
+impl<I, W: Write> WithCsv<I, W> {
+    pub(crate) fn new(inner: I, chunker: &FastCDC, writer: W) -> Self {
+        Self {
+            inner,
+            writer,
+            max_size: chunker.max_size(),
+            header_written: false,
+            pending: None,
+            finished: false,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+
+    fn write_pending(&mut self, last: bool) -> io::Result<()> {
+        if !self.header_written {
+            writeln!(self.writer, "{}", CSV_HEADER)?;
+            self.header_written = true;
+        }
+
+        if let Some(record) = self.pending.take() {
+            let reason = if record.length == self.max_size {
+                "max"
+            } else if last {
+                "eof"
+            } else {
+                "content"
+            };
+            let digest: String = record.digest.iter().map(|b| format!("{:02x}", b)).collect();
+
+            writeln!(
+                self.writer,
+                "{},{},{:016x},{},{}",
+                record.offset, record.length, record.fp_hash, digest, reason
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn observe(&mut self, item: Option<io::Result<Chunk>>) -> Option<io::Result<Chunk>> {
+        match item {
+            Some(Ok(chunk)) => {
+                if let Err(e) = self.write_pending(false) {
+                    return Some(Err(e));
+                }
+                self.pending = Some(Pending {
+                    offset: chunk.offset,
+                    length: chunk.length,
+                    fp_hash: chunk.fp_hash,
+                    digest: Sha256::digest(&chunk.data),
+                });
+                Some(Ok(chunk))
+            }
+            Some(Err(e)) => Some(Err(e)),
+            None => {
+                self.finished = true;
+                match self.write_pending(true).and_then(|_| self.writer.flush()) {
+                    Ok(()) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            }
+        }
+    }
+}
+
+impl<I, W> Iterator for WithCsv<I, W>
+where
+    I: Iterator<Item = io::Result<Chunk>>,
+    W: Write,
+{
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let item = self.inner.next();
+        self.observe(item)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S, W> futures::Stream for WithCsv<S, W>
+where
+    S: futures::Stream<Item = io::Result<Chunk>> + Unpin,
+    W: Write + Unpin,
+{
+    type Item = io::Result<Chunk>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.finished {
+            return std::task::Poll::Ready(None);
+        }
+
+        match std::pin::Pin::new(&mut this.inner).poll_next(cx) {
+            std::task::Poll::Ready(item) => std::task::Poll::Ready(this.observe(item)),
+            std::task::Poll::Pending => std::task::Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/csv_tests.rs"]
+mod tests;
@@ -0,0 +1,60 @@
+use crate::adapter::{CancelToken, Progress, WithCancel, WithCsv, WithLazyDigest, WithProgress};
+use crate::fastcdc::{Chunk, FastCDC};
+use std::io::{self, Write};
+
+/// Extension methods for iterators of chunks.
+pub trait ChunkIterExt: Iterator<Item = io::Result<Chunk>> + Sized {
+    ///
+    /// Pairs every chunk with a digest computed by `hasher` on first access.
+    ///
+    /// ## Arguments
+    ///
+    /// * `hasher`: Computes the strong digest of a chunk's data.
+    ///
+    fn with_lazy_digest<F, D>(self, hasher: F) -> WithLazyDigest<Self, F>
+    where
+        F: Fn(&[u8]) -> D + Clone,
+    {
+        WithLazyDigest::new(self, hasher)
+    }
+
+    ///
+    /// Calls `callback` with the running chunk and byte totals after every chunk.
+    ///
+    /// ## Arguments
+    ///
+    /// * `callback`: Receives the totals, including the chunk just produced.
+    ///
+    fn with_progress<F>(self, callback: F) -> WithProgress<Self, F>
+    where
+        F: FnMut(&Progress),
+    {
+        WithProgress::new(self, callback)
+    }
+
+    ///
+    /// Stops at the next chunk boundary once `token` is cancelled, yielding an
+    /// `ErrorKind::Interrupted` error in place of the remaining chunks.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token`: The token observed before each chunk.
+    ///
+    fn with_cancel(self, token: CancelToken) -> WithCancel<Self> {
+        WithCancel::new(self, token)
+    }
+
+    ///
+    /// Writes a CSV row describing every chunk to `writer`, preceded by a header row.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The chunker that produced the chunks, used to classify cut reasons.
+    /// * `writer`: The destination of the rows (e.g. a `BufWriter<File>`).
+    ///
+    fn with_csv<W: Write>(self, chunker: &FastCDC, writer: W) -> WithCsv<Self, W> {
+        WithCsv::new(self, chunker, writer)
+    }
+}
+
+impl<I> ChunkIterExt for I where I: Iterator<Item = io::Result<Chunk>> {}
@@ -0,0 +1,105 @@
+use crate::fastcdc::Chunk;
+use std::{cell::OnceCell, io};
+
+///
+/// A chunk paired with a strong digest that is only computed on first access.
+///
+/// Consumers that discard duplicates early (for example by an `fp_hash` prefilter)
+/// never pay for hashing the chunks they drop.
+///
+pub struct LazyDigest<D, F> {
+    chunk: Chunk,
+    hasher: F,
+    digest: OnceCell<D>,
+}
+
+impl<D, F> LazyDigest<D, F>
+where
+    F: Fn(&[u8]) -> D,
+{
+    /// Returns the underlying chunk.
+    pub fn chunk(&self) -> &Chunk {
+        &self.chunk
+    }
+
+    /// Returns the digest of the chunk data, computing it on the first call.
+    pub fn digest(&self) -> &D {
+        self.digest.get_or_init(|| (self.hasher)(&self.chunk.data))
+    }
+
+    /// Returns `true` if the digest has already been computed.
+    pub fn is_computed(&self) -> bool {
+        self.digest.get().is_some()
+    }
+
+    /// Consumes the wrapper, returning the chunk.
+    pub fn into_chunk(self) -> Chunk {
+        self.chunk
+    }
+}
+
+///
+/// An adapter that wraps every chunk of an iterator or stream in a [`LazyDigest`].
+///
+/// Created by [`ChunkIterExt::with_lazy_digest`](crate::adapter::ChunkIterExt::with_lazy_digest)
+/// or `ChunkStreamExt::with_lazy_digest`.
+///
+pub struct WithLazyDigest<I, F> {
+    inner: I,
+    hasher: F,
+}
+
+impl<I, F> WithLazyDigest<I, F> {
+    pub(crate) fn new(inner: I, hasher: F) -> Self {
+        Self { inner, hasher }
+    }
+
+    fn wrap<D>(&self, chunk: Chunk) -> LazyDigest<D, F>
+    where
+        F: Clone,
+    {
+        LazyDigest {
+            chunk,
+            hasher: self.hasher.clone(),
+            digest: OnceCell::new(),
+        }
+    }
+}
+
+impl<I, F, D> Iterator for WithLazyDigest<I, F>
+where
+    I: Iterator<Item = io::Result<Chunk>>,
+    F: Fn(&[u8]) -> D + Clone,
+{
+    type Item = io::Result<LazyDigest<D, F>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|res| res.map(|chunk| self.wrap(chunk)))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S, F, D> futures::Stream for WithLazyDigest<S, F>
+where
+    S: futures::Stream<Item = io::Result<Chunk>> + Unpin,
+    F: Fn(&[u8]) -> D + Clone + Unpin,
+{
+    type Item = io::Result<LazyDigest<D, F>>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        std::pin::Pin::new(&mut this.inner)
+            .poll_next(cx)
+            .map(|item| item.map(|res| res.map(|chunk| this.wrap(chunk))))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/lazy_tests.rs"]
+mod tests;
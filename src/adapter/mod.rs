@@ -0,0 +1,67 @@
+//!
+//! Adapters that compose with the chunk iterators and streams.
+//!
+
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+mod async_writer;
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+mod batch;
+#[cfg(feature = "fastcdc")]
+mod cancel;
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+mod concurrent;
+#[cfg(feature = "fastcdc")]
+mod csv;
+#[cfg(all(feature = "direct-io", target_os = "linux"))]
+mod direct;
+#[cfg(feature = "fastcdc")]
+mod iter_ext;
+#[cfg(feature = "fastcdc")]
+mod lazy;
+#[cfg(feature = "fastcdc")]
+mod progress;
+mod read_ahead;
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+mod segment;
+#[cfg(all(feature = "sparse", target_os = "linux"))]
+mod sparse;
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+mod stream_ext;
+#[cfg(feature = "async")]
+mod tee;
+#[cfg(all(feature = "uring", target_os = "linux"))]
+mod uring;
+#[cfg(feature = "fastcdc")]
+mod writer;
+
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+pub use async_writer::AsyncChunkWriter;
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+pub use batch::Batched;
+#[cfg(feature = "fastcdc")]
+pub use cancel::{CancelToken, WithCancel};
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+pub use concurrent::MapConcurrent;
+#[cfg(feature = "fastcdc")]
+pub use csv::{CSV_HEADER, WithCsv};
+#[cfg(all(feature = "direct-io", target_os = "linux"))]
+pub use direct::{DIRECT_IO_ALIGN, DIRECT_IO_BUF_SIZE, DirectReader};
+#[cfg(feature = "fastcdc")]
+pub use iter_ext::ChunkIterExt;
+#[cfg(feature = "fastcdc")]
+pub use lazy::{LazyDigest, WithLazyDigest};
+#[cfg(feature = "fastcdc")]
+pub use progress::{Progress, WithProgress};
+pub use read_ahead::ReadAhead;
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+pub use segment::{Segment, SegmentEntry, Segments};
+#[cfg(all(feature = "sparse", target_os = "linux"))]
+pub use sparse::{SparseChunks, SparseItem};
+#[cfg(all(feature = "fastcdc", feature = "async"))]
+pub use stream_ext::ChunkStreamExt;
+#[cfg(feature = "async")]
+pub use tee::TeeReader;
+#[cfg(all(feature = "uring", target_os = "linux"))]
+pub use uring::{URING_BUF_SIZE, UringChunks};
+#[cfg(feature = "fastcdc")]
+pub use writer::ChunkWriter;
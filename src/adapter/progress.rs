@@ -0,0 +1,92 @@
+use crate::fastcdc::Chunk;
+use std::io;
+
+/// Running totals reported by [`WithProgress`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of chunks produced so far.
+    pub chunks: u64,
+    /// The number of bytes chunked so far.
+    pub bytes: u64,
+}
+
+///
+/// An adapter that reports running totals after every chunk of an iterator or stream.
+///
+/// The callback runs inline and should stay cheap; forward the totals to a channel
+/// (e.g. `tokio::sync::watch` or `std::sync::mpsc`) to drive a progress display on another task.
+///
+/// Created by [`ChunkIterExt::with_progress`](crate::adapter::ChunkIterExt::with_progress)
+/// or `ChunkStreamExt::with_progress`.
+///
+pub struct WithProgress<I, F> {
+    inner: I,
+    callback: F,
+    progress: Progress,
+}
+
+impl<I, F> WithProgress<I, F>
+where
+    F: FnMut(&Progress),
+{
+    pub(crate) fn new(inner: I, callback: F) -> Self {
+        Self {
+            inner,
+            callback,
+            progress: Progress::default(),
+        }
+    }
+
+    /// Returns the totals reported so far.
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    fn observe(&mut self, item: &io::Result<Chunk>) {
+        if let Ok(chunk) = item {
+            self.progress.chunks += 1;
+            self.progress.bytes += chunk.length as u64;
+            (self.callback)(&self.progress);
+        }
+    }
+}
+
+impl<I, F> Iterator for WithProgress<I, F>
+where
+    I: Iterator<Item = io::Result<Chunk>>,
+    F: FnMut(&Progress),
+{
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.inner.next()?;
+        self.observe(&item);
+        Some(item)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S, F> futures::Stream for WithProgress<S, F>
+where
+    S: futures::Stream<Item = io::Result<Chunk>> + Unpin,
+    F: FnMut(&Progress) + Unpin,
+{
+    type Item = io::Result<Chunk>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let poll = std::pin::Pin::new(&mut this.inner).poll_next(cx);
+        if let std::task::Poll::Ready(Some(item)) = &poll {
+            this.observe(item);
+        }
+        poll
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/progress_tests.rs"]
+mod tests;
@@ -0,0 +1,127 @@
+use std::{
+    io::{self, Read},
+    sync::mpsc::{self, Receiver, SyncSender},
+    thread,
+};
+
+///
+/// A `Read` adapter that pulls from the inner reader on a dedicated thread.
+///
+/// Filled buffers are handed over through a bounded channel, so disk reads overlap
+/// with cut-point scanning instead of strictly alternating with it. Consumed buffers
+/// are sent back to the reader thread and reused.
+///
+/// Dropping the adapter stops the reader thread after its current read completes.
+///
+pub struct ReadAhead {
+    filled: Receiver<io::Result<Vec<u8>>>,
+    recycled: SyncSender<Vec<u8>>,
+    current: Vec<u8>,
+    pos: usize,
+}
+
+impl ReadAhead {
+    ///
+    /// Constructs a new `ReadAhead` and starts its reader thread.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    /// * `depth`: The number of filled buffers that may be queued ahead of the consumer.
+    /// * `buf_size`: The size of each buffer handed to `reader.read`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `depth` or `buf_size` is zero, or if the thread cannot be spawned.
+    ///
+    pub fn new<R>(reader: R, depth: usize, buf_size: usize) -> Self
+    where
+        R: Read + Send + 'static,
+    {
+        assert!(depth > 0, "read-ahead depth must be greater than zero");
+        assert!(
+            buf_size > 0,
+            "read-ahead buffer size must be greater than zero"
+        );
+
+        let (filled_tx, filled) = mpsc::sync_channel(depth);
+        let (recycled, recycled_rx) = mpsc::sync_channel(depth + 1);
+
+        thread::Builder::new()
+            .name("clast-read-ahead".into())
+            .spawn(move || fill(reader, buf_size, filled_tx, recycled_rx))
+            .expect("failed to spawn read-ahead thread");
+
+        Self {
+            filled,
+            recycled,
+            current: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+fn fill<R: Read>(
+    mut reader: R,
+    buf_size: usize,
+    filled: SyncSender<io::Result<Vec<u8>>>,
+    recycled: Receiver<Vec<u8>>,
+) {
+    loop {
+        let mut buf = recycled.try_recv().unwrap_or_default();
+        buf.resize(buf_size, 0);
+
+        let result = loop {
+            match reader.read(&mut buf) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                other => break other,
+            }
+        };
+
+        match result {
+            // End of stream: dropping the sender signals it to the consumer.
+            Ok(0) => return,
+            Ok(n) => {
+                buf.truncate(n);
+                if filled.send(Ok(buf)).is_err() {
+                    return;
+                }
+            }
+            Err(e) => {
+                let _ = filled.send(Err(e));
+                return;
+            }
+        }
+    }
+}
+
+impl Read for ReadAhead {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.pos >= self.current.len() {
+            match self.filled.recv() {
+                Ok(Ok(next)) => {
+                    let used = std::mem::replace(&mut self.current, next);
+                    // The reader thread may already be gone; the buffer is simply dropped then.
+                    let _ = self.recycled.try_send(used);
+                    self.pos = 0;
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_) => return Ok(0),
+            }
+        }
+
+        let n = buf.len().min(self.current.len() - self.pos);
+        buf[..n].copy_from_slice(&self.current[self.pos..self.pos + n]);
+        self.pos += n;
+
+        Ok(n)
+    }
+}
+
+#[cfg(all(test, feature = "fastcdc"))]
+#[path = "tests/read_ahead_tests.rs"]
+mod tests;
@@ -0,0 +1,152 @@
+use crate::fastcdc::Chunk;
+use bytes::{Bytes, BytesMut};
+use futures::Stream;
+use std::{
+    io, mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A chunk's position inside a [`Segment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentEntry {
+    /// The absolute offset of the chunk in the source stream.
+    pub offset: u64,
+    /// The offset of the chunk within the segment data.
+    pub start: usize,
+    /// The length of the chunk in bytes.
+    pub length: usize,
+    /// The fingerprint (Gear Hash) of the chunk.
+    pub fp_hash: u64,
+}
+
+/// Consecutive chunks concatenated into one object, with an index of its contents.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    /// The absolute offset of the first chunk in the source stream.
+    pub offset: u64,
+    /// The concatenated chunk data.
+    pub data: Bytes,
+    /// The contained chunks, in stream order.
+    pub index: Vec<SegmentEntry>,
+}
+
+impl Segment {
+    /// Returns the data of the `i`-th contained chunk, without copying.
+    pub fn chunk_data(&self, i: usize) -> Bytes {
+        let entry = &self.index[i];
+        self.data.slice(entry.start..entry.start + entry.length)
+    }
+}
+
+///
+/// A stream that packs consecutive chunks into segments of approximately `target` bytes.
+///
+/// A segment is closed before the chunk that would make it exceed `target`, so segments
+/// never exceed it unless a single chunk does. Chunks stay addressable through the segment
+/// index, keeping chunk-level dedup while uploading far fewer, larger objects. As with
+/// [`Batched`](crate::adapter::Batched), a failure of the underlying stream first yields
+/// the segment collected so far, and the error follows on the next poll.
+///
+/// Created by [`ChunkStreamExt::segments`](crate::adapter::ChunkStreamExt::segments).
+///
+pub struct Segments<S> {
+    stream: S,
+    target: usize,
+    data: BytesMut,
+    index: Vec<SegmentEntry>,
+    offset: u64,
+    error: Option<io::Error>,
+    done: bool,
+}
+
+impl<S> Segments<S>
+where
+    S: Stream<Item = io::Result<Chunk>> + Unpin,
+{
+    pub(crate) fn new(stream: S, target: usize) -> Self {
+        assert!(target > 0, "segment size must be greater than zero");
+
+        Self {
+            stream,
+            target,
+            data: BytesMut::new(),
+            index: Vec::new(),
+            offset: 0,
+            error: None,
+            done: false,
+        }
+    }
+
+    fn push(&mut self, chunk: Chunk) {
+        if self.index.is_empty() {
+            self.offset = chunk.offset;
+            self.data.reserve(self.target.max(chunk.length));
+        }
+        self.index.push(SegmentEntry {
+            offset: chunk.offset,
+            start: self.data.len(),
+            length: chunk.length,
+            fp_hash: chunk.fp_hash,
+        });
+        self.data.extend_from_slice(&chunk.data);
+    }
+
+    fn take_segment(&mut self) -> Segment {
+        Segment {
+            offset: self.offset,
+            data: self.data.split().freeze(),
+            index: mem::take(&mut self.index),
+        }
+    }
+}
+
+impl<S> Stream for Segments<S>
+where
+    S: Stream<Item = io::Result<Chunk>> + Unpin,
+{
+    type Item = io::Result<Segment>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(e) = this.error.take() {
+            return Poll::Ready(Some(Err(e)));
+        }
+
+        while !this.done {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    let full =
+                        !this.index.is_empty() && this.data.len() + chunk.length > this.target;
+                    let segment = full.then(|| this.take_segment());
+
+                    this.push(chunk);
+                    if let Some(segment) = segment {
+                        return Poll::Ready(Some(Ok(segment)));
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    if this.index.is_empty() {
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    // Hand out the chunks read before the failure, then the error.
+                    this.error = Some(e);
+                    return Poll::Ready(Some(Ok(this.take_segment())));
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        if this.index.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Ready(Some(Ok(this.take_segment())))
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/segment_tests.rs"]
+mod tests;
@@ -0,0 +1,126 @@
+use crate::fastcdc::{Chunk, FastCDC, FastCDCIter};
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    os::unix::io::AsRawFd,
+};
+
+/// An item produced by [`SparseChunks`].
+#[derive(Debug)]
+pub enum SparseItem {
+    /// A chunk of a data region.
+    Data(Chunk),
+    /// A hole (or unwritten extent), which reads as zeros and is not read at all.
+    Hole {
+        /// The absolute offset of the hole in the file.
+        offset: u64,
+        /// The length of the hole in bytes.
+        length: u64,
+    },
+}
+
+///
+/// An iterator that chunks the data regions of a sparse file and reports holes as records.
+///
+/// Regions are located with `lseek(2)` and `SEEK_DATA` / `SEEK_HOLE`, so gigabytes of
+/// unallocated space in a VM image are skipped instead of read. Chunking restarts at every
+/// data region, so a chunk never spans a hole. Filesystems without hole support report the
+/// whole file as a single data region.
+///
+/// Created by [`FastCDC::chunks_sparse`]. Only available on Linux.
+///
+pub struct SparseChunks<'a> {
+    chunker: &'a FastCDC,
+    file: File,
+    len: u64,
+    pos: u64,
+    region: Option<FastCDCIter<'a, io::Take<File>>>,
+}
+
+impl<'a> SparseChunks<'a> {
+    pub(crate) fn new(chunker: &'a FastCDC, file: File) -> io::Result<Self> {
+        let len = file.metadata()?.len();
+        Ok(Self {
+            chunker,
+            file,
+            len,
+            pos: 0,
+            region: None,
+        })
+    }
+
+    /// Seeks to the next data (`SEEK_DATA`) or hole (`SEEK_HOLE`) at or after `offset`.
+    fn seek(&self, offset: u64, whence: libc::c_int) -> io::Result<Option<u64>> {
+        // SAFETY: `lseek` only reads the descriptor, which `self.file` keeps open.
+        let result = unsafe { libc::lseek(self.file.as_raw_fd(), offset as libc::off_t, whence) };
+
+        if result >= 0 {
+            return Ok(Some(result as u64));
+        }
+
+        let err = io::Error::last_os_error();
+        match err.raw_os_error() {
+            // No data past `offset`: the rest of the file is a hole.
+            Some(libc::ENXIO) => Ok(None),
+            _ => Err(err),
+        }
+    }
+
+    fn next_region(&mut self) -> io::Result<Option<SparseItem>> {
+        let data = self.seek(self.pos, libc::SEEK_DATA)?.unwrap_or(self.len);
+
+        if data > self.pos {
+            let hole = SparseItem::Hole {
+                offset: self.pos,
+                length: data - self.pos,
+            };
+            self.pos = data;
+            return Ok(Some(hole));
+        }
+
+        let end = self
+            .seek(data, libc::SEEK_HOLE)?
+            .unwrap_or(self.len)
+            .min(self.len);
+        let mut file = self.file.try_clone()?;
+        file.seek(SeekFrom::Start(data))?;
+
+        self.region = Some(self.chunker.chunks_at(file.take(end - data), data));
+        self.pos = end;
+
+        Ok(None)
+    }
+}
+
+impl Iterator for SparseChunks<'_> {
+    type Item = io::Result<SparseItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(region) = self.region.as_mut() {
+                match region.next() {
+                    Some(chunk) => return Some(chunk.map(SparseItem::Data)),
+                    None => self.region = None,
+                }
+            }
+
+            if self.pos >= self.len {
+                return None;
+            }
+
+            match self.next_region() {
+                Ok(Some(hole)) => return Some(Ok(hole)),
+                Ok(None) => continue,
+                Err(e) => {
+                    // Stop after reporting the error, as the position is unknown.
+                    self.pos = self.len;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sparse_tests.rs"]
+mod tests;
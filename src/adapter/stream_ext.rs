@@ -0,0 +1,143 @@
+use crate::adapter::{
+    Batched, CancelToken, MapConcurrent, Progress, Segments, WithCancel, WithCsv, WithLazyDigest,
+    WithProgress,
+};
+use crate::fastcdc::{Chunk, FastCDC};
+use futures::Stream;
+use std::{
+    future::Future,
+    io::{self, Write},
+};
+
+/// Extension methods for streams of chunks.
+pub trait ChunkStreamExt: Stream<Item = io::Result<Chunk>> + Sized {
+    ///
+    /// Runs `f` on every chunk with at most `limit` invocations in flight,
+    /// yielding the results in the order the chunks were emitted.
+    ///
+    /// Errors from the underlying stream are passed through in their original position.
+    ///
+    /// ## Arguments
+    ///
+    /// * `limit`: The maximum number of futures polled concurrently.
+    /// * `f`: The per-chunk work (hashing, compression, upload, ...).
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `limit` is zero.
+    ///
+    fn map_concurrent<F, Fut, T>(self, limit: usize, f: F) -> MapConcurrent<Self, F, Fut>
+    where
+        Self: Unpin,
+        F: FnMut(Chunk) -> Fut,
+        Fut: Future<Output = io::Result<T>>,
+    {
+        MapConcurrent::new(self, limit, f)
+    }
+
+    ///
+    /// Groups chunks into `Vec<Chunk>` batches of `size` chunks each,
+    /// amortizing per-item overhead in high-throughput pipelines.
+    ///
+    /// The final batch may be shorter than `size`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `size`: The number of chunks per batch.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    fn batched(self, size: usize) -> Batched<Self>
+    where
+        Self: Unpin,
+    {
+        Batched::new(self, size)
+    }
+
+    ///
+    /// Packs consecutive chunks into segments of approximately `target` bytes, each with
+    /// an index of the chunks it contains.
+    ///
+    /// ## Arguments
+    ///
+    /// * `target`: The maximum segment size in bytes (e.g. 64 MiB), exceeded only by a
+    ///   single chunk larger than it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `target` is zero.
+    ///
+    fn segments(self, target: usize) -> Segments<Self>
+    where
+        Self: Unpin,
+    {
+        Segments::new(self, target)
+    }
+
+    ///
+    /// Pairs every chunk with a digest computed by `hasher` on first access.
+    ///
+    /// ## Arguments
+    ///
+    /// * `hasher`: Computes the strong digest of a chunk's data.
+    ///
+    fn with_lazy_digest<F, D>(self, hasher: F) -> WithLazyDigest<Self, F>
+    where
+        Self: Unpin,
+        F: Fn(&[u8]) -> D + Clone + Unpin,
+    {
+        WithLazyDigest::new(self, hasher)
+    }
+
+    ///
+    /// Calls `callback` with the running chunk and byte totals after every chunk.
+    ///
+    /// ## Arguments
+    ///
+    /// * `callback`: Receives the totals, including the chunk just produced.
+    ///
+    fn with_progress<F>(self, callback: F) -> WithProgress<Self, F>
+    where
+        Self: Unpin,
+        F: FnMut(&Progress) + Unpin,
+    {
+        WithProgress::new(self, callback)
+    }
+
+    ///
+    /// Stops at the next chunk boundary once `token` is cancelled, yielding an
+    /// `ErrorKind::Interrupted` error in place of the remaining chunks.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token`: The token observed before each chunk.
+    ///
+    fn with_cancel(self, token: CancelToken) -> WithCancel<Self>
+    where
+        Self: Unpin,
+    {
+        WithCancel::new(self, token)
+    }
+
+    ///
+    /// Writes a CSV row describing every chunk to `writer`, preceded by a header row.
+    ///
+    /// Rows are written synchronously; use an in-memory or buffered writer.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The chunker that produced the chunks, used to classify cut reasons.
+    /// * `writer`: The destination of the rows (e.g. a `BufWriter<File>`).
+    ///
+    fn with_csv<W>(self, chunker: &FastCDC, writer: W) -> WithCsv<Self, W>
+    where
+        Self: Unpin,
+        W: Write + Unpin,
+    {
+        WithCsv::new(self, chunker, writer)
+    }
+}
+
+impl<S> ChunkStreamExt for S where S: Stream<Item = io::Result<Chunk>> {}
@@ -0,0 +1,128 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+///
+/// An `AsyncRead` adapter that copies every byte read from the inner reader
+/// into a passthrough `AsyncWrite`.
+///
+/// Wrapping a source with `TeeReader` before handing it to `chunks_async` lets
+/// the raw stream be archived and its chunks indexed in a single read of the data.
+///
+/// Bytes are forwarded to the writer before the next read is issued, so the
+/// writer never lags behind by more than a single read. End-of-file is only
+/// reported once every byte has been written and the writer has been flushed.
+///
+pub struct TeeReader<R, W> {
+    reader: R,
+    writer: W,
+    pending: Vec<u8>,
+    written: usize,
+    eof: bool,
+}
+
+impl<R, W> TeeReader<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    ///
+    /// Constructs a new `TeeReader`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    /// * `writer`: The passthrough destination (must implement `AsyncWrite`).
+    ///
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader,
+            writer,
+            pending: Vec::new(),
+            written: 0,
+            eof: false,
+        }
+    }
+
+    /// Consumes the adapter, returning the inner reader and writer.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.written < self.pending.len() {
+            match Pin::new(&mut self.writer).poll_write(cx, &self.pending[self.written..]) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "passthrough writer accepted no bytes",
+                    )));
+                }
+                Poll::Ready(Ok(n)) => self.written += n,
+            }
+        }
+
+        self.pending.clear();
+        self.written = 0;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R, W> AsyncRead for TeeReader<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        // Forward the previous read before pulling more data to bound the backlog.
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+
+        if this.eof {
+            // Report end-of-file only after the passthrough has been flushed.
+            return Pin::new(&mut this.writer).poll_flush(cx);
+        }
+
+        let filled = buf.filled().len();
+
+        match Pin::new(&mut this.reader).poll_read(cx, buf) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Ready(Ok(())) => {
+                let new_bytes = &buf.filled()[filled..];
+
+                if new_bytes.is_empty() {
+                    this.eof = true;
+                    return Pin::new(&mut this.writer).poll_flush(cx);
+                }
+
+                this.pending.extend_from_slice(new_bytes);
+
+                // Start writing eagerly; anything left over is drained on the next poll.
+                if let Poll::Ready(Err(e)) = this.poll_drain(cx) {
+                    return Poll::Ready(Err(e));
+                }
+
+                Poll::Ready(Ok(()))
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "fastcdc"))]
+#[path = "tests/tee_tests.rs"]
+mod tests;
@@ -0,0 +1,87 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use futures::{StreamExt, channel::mpsc, sink};
+use tokio::io::AsyncWriteExt;
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+// --- Writer Tests ---
+
+#[tokio::test]
+async fn test_copy_into_channel() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // `tokio::io::copy` feeds a bounded channel drained concurrently
+    let (tx, rx) = mpsc::channel(1);
+    let copy = async {
+        let mut writer = AsyncChunkWriter::new(&chunker, tx);
+        tokio::io::copy(&mut &data[..], &mut writer).await.unwrap();
+        writer.shutdown().await.unwrap();
+    };
+    let collect = rx
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect::<Vec<_>>();
+    let (_, actual) = tokio::join!(copy, collect);
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn test_shutdown_emits_tail() {
+    let data = generate_noise(MIN_SIZE - 1);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let mut writer = AsyncChunkWriter::new(&chunker, Vec::new());
+
+    // Flushing keeps the bytes after the last cut point
+    writer.write_all(&data).await.unwrap();
+    writer.flush().await.unwrap();
+    assert_eq!(writer.buffered(), data.len());
+
+    // Shutting down forwards them as the last chunk
+    writer.shutdown().await.unwrap();
+    let chunks = writer.into_inner();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].length, data.len());
+}
+
+#[tokio::test]
+async fn test_closure_error() {
+    let data = generate_noise(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let failing = sink::unfold((), |(), _: Chunk| async {
+        Err::<(), _>(io::Error::new(io::ErrorKind::StorageFull, "quota exceeded"))
+    });
+    let mut writer = AsyncChunkWriter::new(&chunker, Box::pin(failing));
+
+    // The sink's `io::Error` surfaces from the writer unchanged
+    let err = async {
+        writer.write_all(&data).await?;
+        writer.shutdown().await
+    }
+    .await
+    .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::StorageFull);
+    assert_eq!(err.to_string(), "quota exceeded");
+
+    // Other errors are wrapped
+    let (tx, rx) = mpsc::channel::<Chunk>(1);
+    drop(rx);
+    let mut writer = AsyncChunkWriter::new(&chunker, tx);
+    let err = async {
+        writer.write_all(&data).await?;
+        writer.shutdown().await
+    }
+    .await
+    .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+}
@@ -0,0 +1,98 @@
+use super::*;
+use crate::adapter::ChunkStreamExt;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::generate_patterned_data;
+use bytes::Bytes;
+use futures::{StreamExt, stream};
+
+const MIN_SIZE: usize = 256;
+const AVG_SIZE: usize = 1_024;
+const MAX_SIZE: usize = 4_096;
+
+fn make_chunk(offset: u64) -> Chunk {
+    Chunk {
+        fp_hash: 0,
+        data: Bytes::from_static(b"chunk"),
+        offset,
+        length: 5,
+    }
+}
+
+// --- Batching Tests ---
+
+#[tokio::test]
+async fn test_batch_sizes() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let expected = chunker
+        .chunks(&data[..])
+        .map(|c| c.expect("Failed to read chunk").offset)
+        .collect::<Vec<_>>();
+
+    let batches = chunker
+        .chunks_async(&data[..])
+        .batched(8)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to batch chunks");
+
+    // Every batch except the last must be full
+    let (last, full) = batches.split_last().expect("Expected at least one batch");
+    assert!(full.iter().all(|b| b.len() == 8), "Found a short batch");
+    assert!(!last.is_empty() && last.len() <= 8);
+
+    // Batching must not drop or reorder chunks
+    let offsets = batches
+        .iter()
+        .flatten()
+        .map(|c| c.offset)
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, expected, "Batched chunks do not match the source");
+}
+
+#[tokio::test]
+async fn test_empty_input() {
+    let data: [u8; 0] = [];
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut stream = chunker.chunks_async(&data[..]).batched(4);
+
+    // Empty input should not produce any batches
+    assert!(
+        stream.next().await.is_none(),
+        "Empty input should not yield any batches"
+    );
+}
+
+// --- Error Test ---
+
+#[tokio::test]
+async fn test_error_after_partial_batch() {
+    let items = vec![
+        Ok(make_chunk(0)),
+        Ok(make_chunk(5)),
+        Err(io::Error::other("simulated read error")),
+    ];
+
+    let mut stream = stream::iter(items).batched(4);
+
+    // The chunks read before the failure must be delivered first
+    let first = stream
+        .next()
+        .await
+        .expect("Stream expected to yield a batch")
+        .expect("First item should be a batch");
+    assert_eq!(first.len(), 2);
+
+    // The error must follow the partial batch
+    let second = stream
+        .next()
+        .await
+        .expect("Stream expected to yield an error");
+    assert!(second.is_err(), "Stream failed to propagate the read error");
+
+    assert!(stream.next().await.is_none());
+}
@@ -0,0 +1,135 @@
+use super::*;
+use crate::adapter::ChunkIterExt;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+
+// --- Cancellation Tests ---
+
+#[test]
+fn test_uncancelled_passthrough() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let expected = chunker.chunks(&data[..]).count();
+    let actual = chunker
+        .chunks(&data[..])
+        .with_cancel(CancelToken::new())
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Uncancelled chunking must succeed");
+
+    // Without cancellation, every chunk is yielded unchanged
+    assert_eq!(actual.len(), expected);
+}
+
+#[test]
+fn test_stops_at_chunk_boundary() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let token = CancelToken::new();
+
+    let mut iter = chunker.chunks(&data[..]).with_cancel(token.clone());
+    let first = iter.next().unwrap().expect("First chunk must be yielded");
+    let second = iter.next().unwrap().expect("Second chunk must be yielded");
+
+    token.cancel();
+
+    // The partial results are contiguous and complete
+    assert_eq!(second.offset, first.length as u64);
+
+    // Cancellation is reported once, then the adapter ends
+    let err = iter.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    assert!(iter.next().is_none(), "Adapter must end after cancellation");
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_stream_cancel() {
+    use crate::adapter::ChunkStreamExt;
+    use futures::StreamExt;
+
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let token = CancelToken::new();
+    token.cancel();
+
+    let items = chunker
+        .chunks_async(&data[..])
+        .with_cancel(token)
+        .collect::<Vec<_>>()
+        .await;
+
+    // A token cancelled up front stops the stream before any chunk is read
+    assert_eq!(items.len(), 1);
+    assert_eq!(
+        items[0].as_ref().unwrap_err().kind(),
+        io::ErrorKind::Interrupted
+    );
+}
+
+#[test]
+fn test_partial_statistics() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let token = CancelToken::new();
+
+    let mut iter = chunker.chunks(&data[..]).with_cancel(token.clone());
+    let first = iter.next().unwrap().expect("First chunk must be yielded");
+    token.cancel();
+    assert!(iter.next().unwrap().is_err());
+
+    // The wrapped iterator reports on the partial pass
+    assert!(iter.get_ref().buffer_usage().peak >= first.length);
+    let inner = iter.into_inner();
+    assert!(inner.buffer_usage().peak >= first.length);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_cancel_wakes_pending_stream() {
+    use crate::adapter::ChunkStreamExt;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    // A reader that never yields data keeps the stream pending
+    let (_writer, reader) = tokio::io::duplex(64);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let token = CancelToken::new();
+    let mut stream = chunker.chunks_async(reader).with_cancel(token.clone());
+
+    let canceller = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+    });
+
+    // Cancellation ends the stream without waiting for the read
+    let item = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("Cancellation must wake the stream");
+    assert_eq!(
+        item.unwrap().unwrap_err().kind(),
+        io::ErrorKind::Interrupted
+    );
+    canceller.await.unwrap();
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_waker_slots_freed() {
+    use crate::adapter::ChunkStreamExt;
+    use futures::{FutureExt, StreamExt};
+
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let token = CancelToken::new();
+
+    // Each pending stream holds one slot, released when it is dropped and then reused
+    for _ in 0..3 {
+        let (_writer, reader) = tokio::io::duplex(64);
+        let mut stream = chunker.chunks_async(reader).with_cancel(token.clone());
+        assert!(stream.next().now_or_never().is_none());
+        assert!(stream.next().now_or_never().is_none());
+        assert_eq!(token.wakers().iter().flatten().count(), 1);
+    }
+    assert_eq!(token.wakers().len(), 1);
+    assert_eq!(token.wakers().iter().flatten().count(), 0);
+}
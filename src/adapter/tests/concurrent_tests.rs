@@ -0,0 +1,112 @@
+use super::*;
+use crate::adapter::ChunkStreamExt;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+// --- Ordering Tests ---
+
+#[tokio::test]
+async fn test_preserves_emission_order() {
+    let data = generate_patterned_data(200_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let expected = chunker
+        .chunks(&data[..])
+        .map(|c| c.expect("Failed to read chunk").offset)
+        .collect::<Vec<_>>();
+
+    // Later chunks finish first, so any reordering would be visible
+    let offsets = chunker
+        .chunks_async(&data[..])
+        .map_concurrent(4, |chunk| async move {
+            let delay = 20u64.saturating_sub(chunk.offset / 10_000);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            Ok(chunk.offset)
+        })
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to process chunks");
+
+    assert_eq!(offsets, expected, "Results were not emitted in chunk order");
+}
+
+#[tokio::test]
+async fn test_respects_concurrency_limit() {
+    let data = generate_patterned_data(200_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let active = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let stream = chunker.chunks_async(&data[..]).map_concurrent(3, |chunk| {
+        let active = active.clone();
+        let peak = peak.clone();
+        async move {
+            let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(2)).await;
+            active.fetch_sub(1, Ordering::SeqCst);
+            Ok(chunk.length)
+        }
+    });
+
+    let total: usize = stream
+        .map(|len| len.expect("Failed to process chunk"))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .sum();
+
+    // Every chunk must be processed exactly once
+    assert_eq!(total, data.len());
+
+    // The number of futures in flight must never exceed the limit
+    assert!(
+        peak.load(Ordering::SeqCst) <= 3,
+        "Concurrency limit was exceeded"
+    );
+}
+
+// --- Error Test ---
+
+#[tokio::test]
+async fn test_task_error_is_yielded() {
+    let data = generate_patterned_data(50_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let results = chunker
+        .chunks_async(&data[..])
+        .map_concurrent(2, |chunk| async move {
+            if chunk.offset == 0 {
+                Err(io::Error::other("simulated task error"))
+            } else {
+                Ok(chunk.offset)
+            }
+        })
+        .collect::<Vec<_>>()
+        .await;
+
+    // The failing task's error must appear in its original position
+    assert!(results[0].is_err(), "First result should be the task error");
+    assert!(results[1..].iter().all(|r| r.is_ok()));
+}
+
+#[test]
+#[should_panic]
+fn test_zero_limit() {
+    let data = generate_patterned_data(1_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let _ = chunker
+        .chunks_async(&data[..])
+        .map_concurrent(0, |chunk| async move { Ok(chunk.length) });
+}
@@ -0,0 +1,74 @@
+use super::*;
+use crate::adapter::ChunkIterExt;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_noise_data};
+
+// --- Export Tests ---
+
+#[test]
+fn test_writes_one_row_per_chunk() {
+    let data = generate_noise_data(200_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut chunks = Vec::new();
+    let mut adapter = chunker.chunks(&data[..]).with_csv(&chunker, Vec::new());
+    for chunk in adapter.by_ref() {
+        chunks.push(chunk.unwrap());
+    }
+    let csv = String::from_utf8(adapter.into_writer()).unwrap();
+    let rows: Vec<&str> = csv.lines().collect();
+
+    // A header row followed by one row per chunk
+    assert_eq!(rows[0], CSV_HEADER);
+    assert_eq!(rows.len(), chunks.len() + 1);
+
+    for (row, chunk) in rows[1..].iter().zip(&chunks) {
+        let fields: Vec<&str> = row.split(',').collect();
+
+        // Fields match the chunk they describe
+        assert_eq!(fields[0], chunk.offset.to_string());
+        assert_eq!(fields[1], chunk.length.to_string());
+        assert_eq!(fields[2], format!("{:016x}", chunk.fp_hash));
+        assert_eq!(fields[3].len(), 64, "Digest must be 32 hex-encoded bytes");
+    }
+
+    // Only the final row is classified as the end of the data
+    let reasons: Vec<&str> = rows[1..]
+        .iter()
+        .map(|r| r.rsplit(',').next().unwrap())
+        .collect();
+    assert_eq!(*reasons.last().unwrap(), "eof");
+    assert!(reasons[..reasons.len() - 1].iter().all(|&r| r != "eof"));
+    assert!(reasons.contains(&"content"));
+}
+
+#[test]
+fn test_forced_cuts() {
+    // Constant data never matches a cut point, so every full chunk is forced
+    let data = vec![0u8; MAX_SIZE * 3];
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut adapter = chunker.chunks(&data[..]).with_csv(&chunker, Vec::new());
+    assert_eq!(adapter.by_ref().count(), 3);
+    let csv = String::from_utf8(adapter.into_writer()).unwrap();
+
+    // All rows report a forced cut, including a final chunk of exactly max_size
+    assert_eq!(
+        csv.lines().skip(1).filter(|r| r.ends_with(",max")).count(),
+        3
+    );
+}
+
+#[test]
+fn test_empty_input_writes_header() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut adapter = chunker.chunks(&[][..]).with_csv(&chunker, Vec::new());
+    assert!(adapter.next().is_none());
+
+    // The header is written even when there are no chunks
+    assert_eq!(
+        adapter.into_writer(),
+        format!("{}\n", CSV_HEADER).into_bytes()
+    );
+}
@@ -0,0 +1,101 @@
+use super::*;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+use std::{env, fs, path::PathBuf};
+
+fn write_temp_file(name: &str, data: &[u8]) -> PathBuf {
+    let path = env::temp_dir().join(format!("clast-{}-{}", std::process::id(), name));
+    fs::write(&path, data).expect("Failed to write temp file");
+    path
+}
+
+fn open_or_skip(path: &PathBuf, capacity: usize) -> Option<DirectReader> {
+    match DirectReader::with_capacity(path, capacity) {
+        Ok(reader) => Some(reader),
+        Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {
+            eprintln!(
+                "O_DIRECT unsupported at {:?}. Skipping direct I/O test.",
+                path
+            );
+            None
+        }
+        Err(e) => panic!("Failed to open {:?}: {}", path, e),
+    }
+}
+
+// --- Read Tests ---
+
+#[test]
+fn test_unaligned_file_size() {
+    let data = generate_patterned_data(50_001);
+    let path = write_temp_file("direct-unaligned", &data);
+
+    let Some(mut reader) = open_or_skip(&path, 8_192) else {
+        fs::remove_file(&path).ok();
+        return;
+    };
+
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).expect("Failed to read");
+    fs::remove_file(&path).ok();
+
+    // The trailing partial block must be returned intact
+    assert_eq!(out, data, "Direct read does not match file contents");
+}
+
+#[test]
+fn test_capacity_is_aligned() {
+    let data = generate_patterned_data(1_000);
+    let path = write_temp_file("direct-capacity", &data);
+
+    let Some(reader) = open_or_skip(&path, 1_000) else {
+        fs::remove_file(&path).ok();
+        return;
+    };
+    fs::remove_file(&path).ok();
+
+    // The buffer window must be aligned and a multiple of the block size
+    assert_eq!(reader.capacity % DIRECT_IO_ALIGN, 0);
+    assert_eq!(
+        reader.buf[reader.start..].as_ptr() as usize % DIRECT_IO_ALIGN,
+        0
+    );
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_image_chunking() {
+    let base_path = env!("CARGO_MANIFEST_DIR");
+    let file_path = PathBuf::from(base_path).join("assets/test_image.jpg");
+
+    if !file_path.exists() {
+        eprintln!(
+            "Test file not found at {:?}. Skipping image test.",
+            file_path
+        );
+        return;
+    }
+
+    if open_or_skip(&file_path, DIRECT_IO_BUF_SIZE).is_none() {
+        return;
+    }
+
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let expected = chunker
+        .chunks(fs::File::open(&file_path).expect("Failed to open test file"))
+        .map(|c| c.map(|c| (c.offset, c.length, c.fp_hash)))
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk with buffered I/O");
+
+    let actual = chunker
+        .chunks_direct(&file_path)
+        .expect("Failed to open for direct I/O")
+        .map(|c| c.map(|c| (c.offset, c.length, c.fp_hash)))
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk with direct I/O");
+
+    // Bypassing the page cache must not change the boundaries
+    assert_eq!(actual, expected, "Direct I/O changed chunk boundaries");
+}
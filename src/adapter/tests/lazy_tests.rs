@@ -0,0 +1,86 @@
+use super::*;
+use crate::adapter::ChunkIterExt;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+use std::{cell::Cell, rc::Rc};
+
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().map(|&b| b as u64).sum()
+}
+
+// --- Laziness Tests ---
+
+#[test]
+fn test_digest_is_lazy() {
+    let data = generate_patterned_data(50_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let hasher = move |data: &[u8]| {
+        counter.set(counter.get() + 1);
+        checksum(data)
+    };
+
+    let items = chunker
+        .chunks(&data[..])
+        .with_lazy_digest(hasher)
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to read chunks");
+
+    // Nothing must be hashed until a digest is requested
+    assert_eq!(calls.get(), 0, "Digest was computed eagerly");
+
+    let first = &items[0];
+    assert!(!first.is_computed());
+
+    // Repeated access must hash only once
+    let digest = *first.digest();
+    let _ = first.digest();
+    assert_eq!(calls.get(), 1, "Digest was computed more than once");
+    assert!(first.is_computed());
+
+    // The digest must cover exactly the chunk data
+    assert_eq!(digest, checksum(&first.chunk().data));
+}
+
+#[test]
+fn test_chunks_are_unchanged() {
+    let data = generate_patterned_data(50_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut reconstructed = Vec::with_capacity(data.len());
+
+    for item in chunker.chunks(&data[..]).with_lazy_digest(checksum) {
+        let chunk = item.expect("Failed to read chunk").into_chunk();
+        reconstructed.extend_from_slice(&chunk.data);
+    }
+
+    // Wrapping must not alter the chunk payloads
+    assert_eq!(
+        reconstructed, data,
+        "Reconstructed data does not match original"
+    );
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_stream_digest() {
+    use crate::adapter::ChunkStreamExt;
+    use futures::StreamExt;
+
+    let data = generate_patterned_data(50_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let items = chunker
+        .chunks_async(&data[..])
+        .with_lazy_digest(checksum)
+        .collect::<Vec<_>>()
+        .await;
+
+    // The stream variant must compute the same digests on demand
+    for item in items {
+        let item = item.expect("Failed to read chunk");
+        assert_eq!(*item.digest(), checksum(&item.chunk().data));
+    }
+}
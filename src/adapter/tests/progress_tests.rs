@@ -0,0 +1,68 @@
+use super::*;
+use crate::adapter::ChunkIterExt;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+
+// --- Reporting Tests ---
+
+#[test]
+fn test_reports_running_totals() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut reports = Vec::new();
+    let lengths: Vec<usize> = chunker
+        .chunks(&data[..])
+        .with_progress(|p| reports.push(*p))
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // One report per chunk, each including the chunk just produced
+    assert_eq!(reports.len(), lengths.len());
+    for (i, report) in reports.iter().enumerate() {
+        assert_eq!(report.chunks, i as u64 + 1);
+        assert_eq!(report.bytes, lengths[..=i].iter().sum::<usize>() as u64);
+    }
+
+    // The final report covers the whole input
+    assert_eq!(reports.last().unwrap().bytes, data.len() as u64);
+}
+
+#[test]
+fn test_errors_are_not_counted() {
+    let items = vec![Err(io::Error::other("boom"))];
+    let mut calls = 0;
+
+    let mut iter = items.into_iter().with_progress(|_| calls += 1);
+    assert!(iter.next().unwrap().is_err());
+    let progress = iter.progress();
+    drop(iter);
+
+    // Errors pass through without advancing the totals
+    assert_eq!(calls, 0);
+    assert_eq!(progress, Progress::default());
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_stream_progress() {
+    use crate::adapter::ChunkStreamExt;
+    use futures::StreamExt;
+
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let count = chunker
+        .chunks_async(&data[..])
+        .with_progress(move |p| {
+            let _ = tx.send(*p);
+        })
+        .count()
+        .await;
+
+    // The last published totals match the consumed stream
+    let last = rx.try_iter().last().unwrap();
+    assert_eq!(last.chunks, count as u64);
+    assert_eq!(last.bytes, data.len() as u64);
+}
@@ -0,0 +1,63 @@
+use super::*;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+use std::io::Cursor;
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_matches_direct_chunking() {
+    let data = generate_patterned_data(200_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let expected = chunker
+        .chunks(&data[..])
+        .map(|c| c.map(|c| (c.offset, c.length, c.fp_hash)))
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk directly");
+
+    let actual = chunker
+        .chunks_read_ahead(Cursor::new(data.clone()), 2)
+        .map(|c| c.map(|c| (c.offset, c.length, c.fp_hash)))
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk with read-ahead");
+
+    // Reading on a separate thread must not change the boundaries
+    assert_eq!(actual, expected, "Read-ahead changed chunk boundaries");
+}
+
+#[test]
+fn test_small_buffers() {
+    let data = generate_patterned_data(10_000);
+    let mut reader = ReadAhead::new(Cursor::new(data.clone()), 1, 7);
+
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).expect("Failed to read");
+
+    // Buffers smaller than the consumer's requests must be stitched in order
+    assert_eq!(out, data, "Read-ahead data does not match source");
+}
+
+// --- Error Test ---
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("simulated read error"))
+    }
+}
+
+#[test]
+fn test_reader_error() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut iter = chunker.chunks_read_ahead(FailingReader, 2);
+    let result = iter.next().expect("Iterator expected to yield a result");
+
+    // Errors raised on the reader thread must reach the iterator
+    assert!(
+        result.is_err(),
+        "Iterator failed to propagate the read error"
+    );
+}
@@ -0,0 +1,98 @@
+use super::*;
+use crate::adapter::ChunkStreamExt;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::generate_noise_data;
+use futures::{StreamExt, stream};
+
+const MIN_SIZE: usize = 256;
+const AVG_SIZE: usize = 1_024;
+const MAX_SIZE: usize = 4_096;
+
+fn make_chunk(offset: u64) -> Chunk {
+    Chunk {
+        fp_hash: 0,
+        data: Bytes::from_static(b"chunk"),
+        offset,
+        length: 5,
+    }
+}
+
+// --- Packing Tests ---
+
+#[tokio::test]
+async fn test_segments_cover_stream() {
+    const TARGET: usize = 16_384;
+
+    let data = generate_noise_data(200_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let expected: Vec<Chunk> = chunker.chunks(&data[..]).map(|c| c.unwrap()).collect();
+    let segments: Vec<Segment> = chunker
+        .chunks_async(&data[..])
+        .segments(TARGET)
+        .map(|s| s.expect("Failed to pack"))
+        .collect()
+        .await;
+
+    // Segments concatenate to the input and index every chunk in order
+    let joined: Vec<u8> = segments.iter().flat_map(|s| s.data.to_vec()).collect();
+    assert_eq!(joined, data);
+
+    let indexed: Vec<(u64, usize, u64)> = segments
+        .iter()
+        .flat_map(|s| s.index.iter().map(|e| (e.offset, e.length, e.fp_hash)))
+        .collect();
+    let chunks: Vec<(u64, usize, u64)> = expected
+        .iter()
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+    assert_eq!(indexed, chunks);
+
+    for (i, segment) in segments.iter().enumerate() {
+        // Segments stay within the target, and all but the last are nearly full
+        assert!(segment.data.len() <= TARGET);
+        if i + 1 < segments.len() {
+            assert!(segment.data.len() > TARGET - MAX_SIZE);
+        }
+
+        // Each indexed chunk is addressable within its segment
+        assert_eq!(segment.offset, segment.index[0].offset);
+        for (j, entry) in segment.index.iter().enumerate() {
+            let start = entry.offset as usize;
+            assert_eq!(segment.chunk_data(j), data[start..start + entry.length]);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_oversized_chunk_gets_own_segment() {
+    // A target smaller than a chunk yields one chunk per segment
+    let segments: Vec<Segment> = stream::iter((0..3).map(|i| Ok(make_chunk(i * 5))))
+        .segments(4)
+        .map(|s| s.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(segments.len(), 3);
+    assert!(
+        segments
+            .iter()
+            .all(|s| s.index.len() == 1 && s.data.len() == 5)
+    );
+}
+
+#[tokio::test]
+async fn test_error_flushes_partial_segment() {
+    let items = vec![
+        Ok(make_chunk(0)),
+        Ok(make_chunk(5)),
+        Err(io::Error::other("boom")),
+    ];
+    let mut packed = stream::iter(items).segments(1_000);
+
+    // Packed chunks come first, then the error, then the end
+    let first = packed.next().await.unwrap().unwrap();
+    assert_eq!(first.index.len(), 2);
+    assert!(packed.next().await.unwrap().is_err());
+    assert!(packed.next().await.is_none());
+}
@@ -0,0 +1,126 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_noise_data};
+use std::{env, fs, io::Write, path::PathBuf};
+
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("clast-{}-{}", std::process::id(), name))
+}
+
+/// Writes `regions` at their offsets into a file of `len` bytes, leaving holes between.
+fn write_sparse_file(path: &PathBuf, len: u64, regions: &[(u64, &[u8])]) {
+    let mut file = File::create(path).expect("Failed to create temp file");
+    file.set_len(len).unwrap();
+    for (offset, data) in regions {
+        file.seek(SeekFrom::Start(*offset)).unwrap();
+        file.write_all(data).unwrap();
+    }
+}
+
+// --- Sparse Tests ---
+
+#[test]
+fn test_covers_file_in_order() {
+    let path = temp_path("sparse-cover");
+    let data = generate_noise_data(200_000);
+    write_sparse_file(
+        &path,
+        64 << 20,
+        &[(0, &data[..]), (32 << 20, &data[..100_000])],
+    );
+
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let items: Vec<SparseItem> = chunker
+        .chunks_sparse(File::open(&path).unwrap())
+        .unwrap()
+        .map(|item| item.unwrap())
+        .collect();
+
+    // Items are contiguous and cover the whole file
+    let mut offset = 0;
+    let mut data_bytes = 0;
+    for item in &items {
+        match item {
+            SparseItem::Data(chunk) => {
+                assert_eq!(chunk.offset, offset);
+                offset += chunk.length as u64;
+                data_bytes += chunk.length as u64;
+            }
+            SparseItem::Hole {
+                offset: hole,
+                length,
+            } => {
+                assert_eq!(*hole, offset);
+                offset += length;
+            }
+        }
+    }
+    assert_eq!(offset, 64 << 20);
+
+    // Data chunks hold the file contents; holes are not read
+    let contents = fs::read(&path).unwrap();
+    for item in &items {
+        if let SparseItem::Data(chunk) = item {
+            let start = chunk.offset as usize;
+            assert_eq!(&chunk.data[..], &contents[start..start + chunk.length]);
+        }
+    }
+    assert!(data_bytes < 64 << 20, "Holes must be skipped");
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_dense_file_matches_chunks() {
+    let path = temp_path("sparse-dense");
+    let data = generate_noise_data(300_000);
+    fs::write(&path, &data).unwrap();
+
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let sparse: Vec<(u64, usize, u64)> = chunker
+        .chunks_sparse(File::open(&path).unwrap())
+        .unwrap()
+        .map(|item| match item.unwrap() {
+            SparseItem::Data(c) => (c.offset, c.length, c.fp_hash),
+            SparseItem::Hole { offset, .. } => panic!("Unexpected hole at {}", offset),
+        })
+        .collect();
+    let dense: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // A file without holes chunks exactly like a plain reader
+    assert_eq!(sparse, dense);
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_empty_and_all_hole_files() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let empty = temp_path("sparse-empty");
+    write_sparse_file(&empty, 0, &[]);
+    let hole = temp_path("sparse-hole");
+    write_sparse_file(&hole, 1 << 20, &[]);
+
+    // An empty file yields nothing
+    let mut items = chunker.chunks_sparse(File::open(&empty).unwrap()).unwrap();
+    assert!(items.next().is_none());
+
+    // A file without data is covered by holes only
+    let total: u64 = chunker
+        .chunks_sparse(File::open(&hole).unwrap())
+        .unwrap()
+        .map(|item| match item.unwrap() {
+            SparseItem::Hole { length, .. } => length,
+            SparseItem::Data(chunk) => panic!("Unexpected data chunk {:?}", chunk.offset),
+        })
+        .sum();
+    assert_eq!(total, 1 << 20);
+
+    fs::remove_file(&empty).ok();
+    fs::remove_file(&hole).ok();
+}
@@ -0,0 +1,133 @@
+use super::*;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+use futures::StreamExt;
+
+/// A writer that accepts at most one byte per call and is pending every other poll.
+struct TrickleWriter {
+    inner: Vec<u8>,
+    ready: bool,
+}
+
+impl AsyncWrite for TrickleWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.ready = !this.ready;
+
+        if !this.ready {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        this.inner.push(buf[0]);
+        Poll::Ready(Ok(1))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct FailingWriter;
+
+impl AsyncWrite for FailingWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        _buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(Err(io::Error::other("simulated write error")))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+// --- Passthrough Tests ---
+
+#[tokio::test]
+async fn test_passthrough_matches_source() {
+    let data = generate_patterned_data(50_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut archive = Vec::new();
+    let mut reconstructed = Vec::with_capacity(data.len());
+
+    let mut stream = chunker.chunks_async(TeeReader::new(&data[..], &mut archive));
+
+    while let Some(chunk_res) = stream.next().await {
+        let chunk = chunk_res.expect("Failed to read chunk");
+        reconstructed.extend_from_slice(chunk.data.as_ref());
+    }
+    drop(stream);
+
+    // The passthrough writer must receive the source bytes unchanged
+    assert_eq!(archive, data, "Passthrough data does not match source");
+
+    // Chunking must be unaffected by the passthrough
+    assert_eq!(
+        reconstructed, data,
+        "Reconstructed data does not match original"
+    );
+}
+
+#[tokio::test]
+async fn test_slow_writer() {
+    let data = generate_patterned_data(20_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let writer = TrickleWriter {
+        inner: Vec::new(),
+        ready: false,
+    };
+    let mut tee = TeeReader::new(&data[..], writer);
+
+    let chunks = chunker
+        .chunks_async(&mut tee)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk");
+
+    let (_, writer) = tee.into_inner();
+
+    // Partial and pending writes must not drop or reorder bytes
+    assert_eq!(writer.inner, data, "Passthrough data does not match source");
+
+    // Chunks must cover the whole input
+    assert_eq!(chunks.iter().map(|c| c.length).sum::<usize>(), data.len());
+}
+
+// --- Error Test ---
+
+#[tokio::test]
+async fn test_writer_error() {
+    let data = generate_patterned_data(20_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut stream = chunker.chunks_async(TeeReader::new(&data[..], FailingWriter));
+    let result = stream
+        .next()
+        .await
+        .expect("Stream expected to yield a result");
+
+    // Verify that a passthrough failure surfaces through the chunk stream
+    assert!(
+        result.is_err(),
+        "Stream failed to propagate the write error"
+    );
+}
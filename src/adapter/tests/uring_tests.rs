@@ -0,0 +1,119 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::{env, fs, path::PathBuf};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn write_temp_file(name: &str, data: &[u8]) -> PathBuf {
+    let path = env::temp_dir().join(format!("clast-{}-{}", std::process::id(), name));
+    fs::write(&path, data).expect("Failed to write temp file");
+    path
+}
+
+fn open_or_skip<'a>(chunker: &'a FastCDC, path: &PathBuf, depth: usize) -> Option<UringChunks<'a>> {
+    match chunker.chunks_uring(File::open(path).unwrap(), depth) {
+        Ok(chunks) => Some(chunks),
+        Err(e) if matches!(e.raw_os_error(), Some(libc::ENOSYS) | Some(libc::EPERM)) => {
+            eprintln!("io_uring unavailable ({}). Skipping io_uring test.", e);
+            None
+        }
+        Err(e) => panic!("Failed to set up io_uring: {}", e),
+    }
+}
+
+// --- Reading Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    // Spans several buffers and ends mid-buffer
+    let data = generate_noise(URING_BUF_SIZE * 3 + 12_345);
+    let path = write_temp_file("uring-match", &data);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // Completions are fed in file order at every depth
+    for depth in [1, 2, 5] {
+        let Some(chunks) = open_or_skip(&chunker, &path, depth) else {
+            break;
+        };
+        let actual: Vec<(u64, usize, u64)> = chunks
+            .map(|c| c.unwrap())
+            .map(|c| (c.offset, c.length, c.fp_hash))
+            .collect();
+        assert_eq!(actual, expected, "depth {}", depth);
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_empty_file_and_early_drop() {
+    let path = write_temp_file("uring-empty", &[]);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // An empty file yields no chunks
+    if let Some(mut chunks) = open_or_skip(&chunker, &path, 4) {
+        assert!(chunks.next().is_none());
+    }
+
+    // Dropping the iterator with reads in flight waits for them
+    fs::write(&path, generate_noise(URING_BUF_SIZE * 4)).unwrap();
+    if let Some(mut chunks) = open_or_skip(&chunker, &path, 4) {
+        assert!(chunks.next().unwrap().is_ok());
+        drop(chunks);
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "depth must be greater than zero")]
+fn test_zero_depth() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let _ = chunker.chunks_uring(File::open("/dev/null").unwrap(), 0);
+}
+
+#[cfg(feature = "direct-io")]
+#[test]
+fn test_direct_matches_iterator() {
+    // The last read is unaligned
+    let data = generate_noise(URING_BUF_SIZE * 2 + 4_097);
+    let path = write_temp_file("uring-direct", &data);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // `O_DIRECT` reads yield the same chunks where the filesystem supports them
+    match chunker.chunks_uring_direct(&path, 3) {
+        Ok(chunks) => {
+            let actual: Vec<(u64, usize, u64)> = chunks
+                .map(|c| c.unwrap())
+                .map(|c| (c.offset, c.length, c.fp_hash))
+                .collect();
+            assert_eq!(actual, expected);
+        }
+        Err(e)
+            if matches!(
+                e.raw_os_error(),
+                Some(libc::EINVAL | libc::ENOSYS | libc::EPERM)
+            ) =>
+        {
+            eprintln!("{} at {:?}. Skipping O_DIRECT io_uring test.", e, path);
+        }
+        Err(e) => panic!("Failed to open {:?}: {}", path, e),
+    }
+
+    fs::remove_file(&path).unwrap();
+}
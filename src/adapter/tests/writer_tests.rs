@@ -0,0 +1,66 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+// --- Writer Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // Chunks written through `io::copy` match the iterator
+    let mut actual = Vec::new();
+    let mut writer = ChunkWriter::new(&chunker, |chunk: Chunk| {
+        actual.push((chunk.offset, chunk.length, chunk.fp_hash));
+        Ok(())
+    });
+    io::copy(&mut &data[..], &mut writer).unwrap();
+    writer.flush().unwrap();
+    writer.finish().unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_finish_emits_tail() {
+    let data = generate_noise(MIN_SIZE - 1);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let mut lengths = Vec::new();
+    let mut writer = ChunkWriter::new(&chunker, |chunk: Chunk| {
+        lengths.push(chunk.length);
+        Ok(())
+    });
+
+    // Flushing keeps the bytes after the last cut point
+    writer.write_all(&data).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.buffered(), data.len());
+
+    // Finishing emits them as the last chunk
+    writer.finish().unwrap();
+    assert_eq!(lengths, vec![data.len()]);
+}
+
+#[test]
+fn test_callback_error() {
+    let data = generate_noise(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let mut writer = ChunkWriter::new(&chunker, |_: Chunk| {
+        Err(io::Error::new(io::ErrorKind::StorageFull, "quota exceeded"))
+    });
+
+    // The callback's error is returned from `write`
+    let err = writer.write_all(&data).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::StorageFull);
+}
@@ -0,0 +1,549 @@
+use crate::fastcdc::{Chunk, ChunkerState, FastCDC};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io, mem,
+    os::unix::io::{AsRawFd, FromRawFd, OwnedFd},
+    ptr,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// Size of each read issued to the ring, unless `max_size` is larger.
+pub const URING_BUF_SIZE: usize = 262_144; // 256 KB
+
+/// Alignment of the read buffers, which also satisfies `O_DIRECT`.
+const BUF_ALIGN: usize = 4096;
+
+// Constants and layouts from `linux/io_uring.h`.
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+const IORING_OFF_CQ_RING: libc::off_t = 0x800_0000;
+const IORING_OFF_SQES: libc::off_t = 0x1000_0000;
+const IORING_ENTER_GETEVENTS: u32 = 1;
+const IORING_REGISTER_BUFFERS: u32 = 0;
+const IORING_OP_READ_FIXED: u8 = 4;
+const IORING_OP_READ: u8 = 22;
+
+#[repr(C)]
+#[derive(Default)]
+struct SqRingOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct CqRingOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqRingOffsets,
+    cq_off: CqRingOffsets,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    rw_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    addr3: u64,
+    pad: u64,
+}
+
+#[repr(C)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+const _: () = assert!(mem::size_of::<Params>() == 120);
+const _: () = assert!(mem::size_of::<Sqe>() == 64);
+const _: () = assert!(mem::size_of::<Cqe>() == 16);
+
+/// A heap buffer whose start is aligned to `BUF_ALIGN`.
+struct AlignedBuf {
+    storage: Vec<u8>,
+    start: usize,
+    len: usize,
+}
+
+impl AlignedBuf {
+    fn new(len: usize) -> Self {
+        let len = len.next_multiple_of(BUF_ALIGN);
+
+        // Over-allocate so an aligned window of `len` bytes always fits.
+        let storage = vec![0u8; len + BUF_ALIGN];
+        let start = storage.as_ptr().align_offset(BUF_ALIGN);
+
+        Self {
+            storage,
+            start,
+            len,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.storage[self.start..self.start + self.len]
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.storage[self.start..self.start + self.len]
+    }
+}
+
+/// A memory-mapped region of the ring, unmapped when dropped.
+struct Region {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl Region {
+    fn map(fd: &OwnedFd, len: usize, offset: libc::off_t) -> io::Result<Self> {
+        // SAFETY: Maps a region of the ring described by `io_uring_setup`; the result is checked.
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd.as_raw_fd(),
+                offset,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            ptr: ptr as *mut u8,
+            len,
+        })
+    }
+
+    /// Returns the ring field at byte offset `offset`.
+    fn atomic(&self, offset: u32) -> &AtomicU32 {
+        // SAFETY: The kernel places aligned `u32` ring fields at the offsets it reports.
+        unsafe { &*(self.ptr.add(offset as usize) as *const AtomicU32) }
+    }
+
+    fn get(&self, offset: u32) -> u32 {
+        // SAFETY: As in `atomic`; masks and sizes are never written after setup.
+        unsafe { *(self.ptr.add(offset as usize) as *const u32) }
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` and `len` describe a mapping created by `Region::map`.
+        unsafe {
+            libc::munmap(self.ptr as *mut libc::c_void, self.len);
+        }
+    }
+}
+
+/// A minimal io_uring instance: one submission and one completion queue.
+struct Ring {
+    sq: Region,
+    cq: Region,
+    sqes: Region,
+    params: Params,
+    fd: OwnedFd,
+}
+
+impl Ring {
+    fn new(entries: u32) -> io::Result<Self> {
+        let mut params = Params::default();
+        // SAFETY: `params` is a zeroed `io_uring_params` the kernel fills in.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_setup,
+                entries,
+                &mut params as *mut Params,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `io_uring_setup` returned a new descriptor owned by nobody else.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as i32) };
+
+        let sq_len = params.sq_off.array as usize + params.sq_entries as usize * 4;
+        let cq_len =
+            params.cq_off.cqes as usize + params.cq_entries as usize * mem::size_of::<Cqe>();
+        let sqes_len = params.sq_entries as usize * mem::size_of::<Sqe>();
+
+        Ok(Self {
+            sq: Region::map(&fd, sq_len, IORING_OFF_SQ_RING)?,
+            cq: Region::map(&fd, cq_len, IORING_OFF_CQ_RING)?,
+            sqes: Region::map(&fd, sqes_len, IORING_OFF_SQES)?,
+            params,
+            fd,
+        })
+    }
+
+    /// Registers `buffers` for fixed reads, returning whether the kernel accepted them.
+    fn register(&self, buffers: &[AlignedBuf]) -> bool {
+        let iovecs: Vec<libc::iovec> = buffers
+            .iter()
+            .map(|buf| libc::iovec {
+                iov_base: buf.as_slice().as_ptr() as *mut libc::c_void,
+                iov_len: buf.len,
+            })
+            .collect();
+
+        // SAFETY: `iovecs` describes live buffers, which outlive the ring's descriptor.
+        let result = unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_register,
+                self.fd.as_raw_fd(),
+                IORING_REGISTER_BUFFERS,
+                iovecs.as_ptr(),
+                iovecs.len() as u32,
+            )
+        };
+
+        // Registration fails under a low `RLIMIT_MEMLOCK`; plain reads are used instead.
+        result == 0
+    }
+
+    /// Queues `sqe`. The queue never fills, as at most one read per buffer is in flight.
+    fn push(&self, sqe: Sqe) {
+        let off = &self.params.sq_off;
+        let tail = self.sq.atomic(off.tail).load(Ordering::Relaxed);
+        let index = tail & self.sq.get(off.ring_mask);
+
+        // SAFETY: `index` is masked into the SQE array and the kernel does not read
+        // the entry until the tail is published below.
+        unsafe {
+            let slot = (self.sqes.ptr as *mut Sqe).add(index as usize);
+            ptr::write(slot, sqe);
+            let array = self.sq.ptr.add(off.array as usize) as *mut u32;
+            *array.add(index as usize) = index;
+        }
+
+        self.sq
+            .atomic(off.tail)
+            .store(tail.wrapping_add(1), Ordering::Release);
+    }
+
+    /// Submits `to_submit` entries and waits for at least `min_complete` completions.
+    fn enter(&self, to_submit: u32, min_complete: u32) -> io::Result<()> {
+        loop {
+            // SAFETY: The ring descriptor is valid and no signal mask is passed.
+            let result = unsafe {
+                libc::syscall(
+                    libc::SYS_io_uring_enter,
+                    self.fd.as_raw_fd(),
+                    to_submit,
+                    min_complete,
+                    IORING_ENTER_GETEVENTS,
+                    ptr::null::<libc::c_void>(),
+                    0usize,
+                )
+            };
+            if result >= 0 {
+                return Ok(());
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+
+    /// Pops a completion, if any is available.
+    fn pop(&self) -> Option<(u64, i32)> {
+        let off = &self.params.cq_off;
+        let head = self.cq.atomic(off.head).load(Ordering::Relaxed);
+        if head == self.cq.atomic(off.tail).load(Ordering::Acquire) {
+            return None;
+        }
+
+        let index = head & self.cq.get(off.ring_mask);
+        // SAFETY: Entries between head and tail are written by the kernel before it
+        // publishes the tail, which was loaded with `Acquire` above.
+        let cqe = unsafe {
+            ptr::read((self.cq.ptr.add(off.cqes as usize) as *const Cqe).add(index as usize))
+        };
+        self.cq
+            .atomic(off.head)
+            .store(head.wrapping_add(1), Ordering::Release);
+
+        Some((cqe.user_data, cqe.res))
+    }
+}
+
+/// A read buffer and the part of the file it is filling.
+struct Slot {
+    offset: u64,
+    len: usize,
+    filled: usize,
+    done: bool,
+}
+
+///
+/// An iterator that chunks a file read through io_uring.
+///
+/// Keeps `depth` reads of `URING_BUF_SIZE` bytes in flight, into page-aligned buffers
+/// registered with the kernel when the memlock limit allows, and feeds the completed reads
+/// in order to the sans-IO [`ChunkerState`]. Yields the same chunks as [`FastCDC::chunks`].
+///
+/// This is a blocking iterator, not an async stream: `next` waits in `io_uring_enter` until
+/// the oldest read completes, blocking the calling thread. Async applications should drive
+/// it with `tokio::task::spawn_blocking` (or a thread of their own) and pass the chunks to
+/// their tasks over a channel, rather than call it from a task.
+///
+/// Created by [`FastCDC::chunks_uring`], or `FastCDC::chunks_uring_direct` for `O_DIRECT`
+/// reads that bypass the page cache. Only available on Linux.
+///
+pub struct UringChunks<'a> {
+    state: ChunkerState<'a>,
+    ready: VecDeque<Chunk>,
+    // Declared before `buffers` so the ring, which may write into them, is torn down first.
+    ring: Ring,
+    buffers: Vec<AlignedBuf>,
+    slots: Vec<Option<Slot>>,
+    order: VecDeque<usize>,
+    fixed: bool,
+    direct: bool,
+    file: File,
+    file_len: u64,
+    next_offset: u64,
+    in_flight: usize,
+    queued: u32,
+    finished: bool,
+}
+
+impl<'a> UringChunks<'a> {
+    pub(crate) fn new(
+        chunker: &'a FastCDC,
+        file: File,
+        depth: usize,
+        direct: bool,
+    ) -> io::Result<Self> {
+        assert!(depth > 0, "depth must be greater than zero");
+
+        let file_len = file.metadata()?.len();
+        let ring = Ring::new(depth.next_power_of_two() as u32)?;
+        let buf_size = URING_BUF_SIZE.max(chunker.max_size());
+        let buffers: Vec<AlignedBuf> = (0..depth).map(|_| AlignedBuf::new(buf_size)).collect();
+        let fixed = ring.register(&buffers);
+
+        let mut chunks = Self {
+            state: chunker.state(),
+            ready: VecDeque::new(),
+            ring,
+            buffers,
+            slots: (0..depth).map(|_| None).collect(),
+            order: VecDeque::with_capacity(depth),
+            fixed,
+            direct,
+            file,
+            file_len,
+            next_offset: 0,
+            in_flight: 0,
+            queued: 0,
+            finished: false,
+        };
+        for index in 0..depth {
+            chunks.start(index);
+        }
+
+        Ok(chunks)
+    }
+
+    /// Returns whether the reads use registered buffers.
+    pub fn registered_buffers(&self) -> bool {
+        self.fixed
+    }
+
+    /// Assigns the next part of the file to buffer `index`, if any is left.
+    fn start(&mut self, index: usize) {
+        if self.next_offset >= self.file_len {
+            self.slots[index] = None;
+            return;
+        }
+
+        let len = (self.file_len - self.next_offset).min(self.buffers[index].len as u64) as usize;
+        self.slots[index] = Some(Slot {
+            offset: self.next_offset,
+            len,
+            filled: 0,
+            done: false,
+        });
+        self.next_offset += len as u64;
+        self.order.push_back(index);
+        self.submit(index);
+    }
+
+    /// Queues a read of the unfilled rest of buffer `index`.
+    fn submit(&mut self, index: usize) {
+        let slot = self.slots[index].as_ref().unwrap();
+        // `O_DIRECT` lengths must be aligned too; the read past the end of the file is short.
+        let end = if self.direct {
+            slot.len.next_multiple_of(BUF_ALIGN)
+        } else {
+            slot.len
+        };
+        let buf = &mut self.buffers[index].as_mut_slice()[slot.filled..end];
+
+        self.ring.push(Sqe {
+            opcode: if self.fixed {
+                IORING_OP_READ_FIXED
+            } else {
+                IORING_OP_READ
+            },
+            fd: self.file.as_raw_fd(),
+            off: slot.offset + slot.filled as u64,
+            addr: buf.as_mut_ptr() as u64,
+            len: buf.len() as u32,
+            user_data: index as u64,
+            buf_index: index as u16,
+            ..Default::default()
+        });
+        self.in_flight += 1;
+        self.queued += 1;
+    }
+
+    /// Records a completion, resubmitting short or interrupted reads.
+    ///
+    /// The length of the file is taken when the iterator is created; a read that ends
+    /// before it fails with `ErrorKind::UnexpectedEof`.
+    fn complete(&mut self, index: usize, res: i32) -> io::Result<()> {
+        self.in_flight -= 1;
+
+        if res < 0 {
+            let err = io::Error::from_raw_os_error(-res);
+            if matches!(
+                err.kind(),
+                io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+            ) {
+                self.submit(index);
+                return Ok(());
+            }
+            return Err(err);
+        }
+
+        let slot = self.slots[index].as_mut().unwrap();
+        slot.filled = (slot.filled + res as usize).min(slot.len);
+
+        if slot.filled == slot.len {
+            slot.done = true;
+        } else if res == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "file shrank while being read",
+            ));
+        } else {
+            // `O_DIRECT` offsets must stay aligned, so a partial block is read again.
+            if self.direct {
+                slot.filled -= slot.filled % BUF_ALIGN;
+            }
+            self.submit(index);
+        }
+
+        Ok(())
+    }
+
+    /// Waits for completions until the oldest read is done, then feeds it to the chunker.
+    fn advance(&mut self) -> io::Result<()> {
+        let Some(&index) = self.order.front() else {
+            while let Some(chunk) = self.state.finish() {
+                self.ready.push_back(chunk);
+            }
+            self.finished = true;
+            return Ok(());
+        };
+
+        while !self.slots[index].as_ref().unwrap().done {
+            let queued = mem::take(&mut self.queued);
+            self.ring.enter(queued, 1)?;
+            while let Some((user_data, res)) = self.ring.pop() {
+                self.complete(user_data as usize, res)?;
+            }
+        }
+
+        self.order.pop_front();
+        let filled = self.slots[index].as_ref().unwrap().filled;
+        let chunks = self.state.push(&self.buffers[index].as_slice()[..filled]);
+        self.ready.extend(chunks);
+        self.start(index);
+
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for UringChunks<'a> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = self.ready.pop_front() {
+                return Some(Ok(chunk));
+            }
+            if self.finished {
+                return None;
+            }
+            if let Err(e) = self.advance() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+impl Drop for UringChunks<'_> {
+    fn drop(&mut self) {
+        // The kernel may still write into the buffers; wait for every read to land.
+        while self.in_flight > 0 {
+            let queued = mem::take(&mut self.queued);
+            if self.ring.enter(queued, 1).is_err() {
+                // Reads that cannot be waited for may still land, so the buffers are leaked.
+                mem::forget(mem::take(&mut self.buffers));
+                break;
+            }
+            while self.ring.pop().is_some() {
+                self.in_flight -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/uring_tests.rs"]
+mod tests;
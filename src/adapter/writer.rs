@@ -0,0 +1,78 @@
+use crate::fastcdc::{Chunk, ChunkerState, FastCDC};
+use std::io::{self, Write};
+
+///
+/// A writer that chunks the bytes written to it and hands every chunk to a callback.
+///
+/// Lets code that produces its output through `Write` (archive builders, encoders) be
+/// chunked on the way through. The chunks match [`FastCDC::chunks`] over everything
+/// written, however the writes are split.
+///
+/// `flush` only forwards chunks whose cut point is known, as cutting the buffered tail
+/// early would change the chunks; call [`ChunkWriter::finish`] to emit it. An error
+/// returned by the callback is passed to the caller, after which the writer should be
+/// discarded.
+///
+pub struct ChunkWriter<'a, F> {
+    state: ChunkerState<'a>,
+    callback: F,
+}
+
+impl<'a, F> ChunkWriter<'a, F>
+where
+    F: FnMut(Chunk) -> io::Result<()>,
+{
+    ///
+    /// Creates a writer that chunks with `chunker`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The chunker to use.
+    /// * `callback`: Receives every chunk, in order.
+    ///
+    pub fn new(chunker: &'a FastCDC, callback: F) -> Self {
+        Self {
+            state: chunker.state(),
+            callback,
+        }
+    }
+
+    /// Returns the number of bytes written but not yet emitted as chunks.
+    pub fn buffered(&self) -> usize {
+        self.state.buffered()
+    }
+
+    ///
+    /// Ends the input, emitting the remaining chunks.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the first error raised by the callback.
+    ///
+    pub fn finish(mut self) -> io::Result<()> {
+        while let Some(chunk) = self.state.finish() {
+            (self.callback)(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, F> Write for ChunkWriter<'a, F>
+where
+    F: FnMut(Chunk) -> io::Result<()>,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for chunk in self.state.push(buf) {
+            (self.callback)(chunk)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/writer_tests.rs"]
+mod tests;
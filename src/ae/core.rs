@@ -0,0 +1,153 @@
+use crate::chunker::{ChunkIter, CutPoint, Sizes};
+use crate::error::ClastError;
+use std::f64::consts::E;
+use std::io::Read;
+
+/// Number of bytes each compared value is read from.
+const VALUE_LEN: usize = 8;
+
+///
+/// An AE (Asymmetric Extremum) chunker implementation.
+///
+/// Each position's value is the little-endian `u64` of the 8 bytes starting there, which keeps
+/// runs of similar bytes (e.g. text) from producing ties. AE computes no hash, so its chunks
+/// carry a zero `fp_hash`.
+///
+#[derive(Debug, Clone)]
+pub struct AE {
+    sizes: Sizes,
+    window: usize,
+}
+
+impl AE {
+    ///
+    /// Constructs a new `AE` instance.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`FastCDC::new`](crate::fastcdc::FastCDC::new).
+    ///
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        match Self::try_new(min_size, avg_size, max_size) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a new `AE` instance.
+    /// Unlike `new`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as
+    /// [`FastCDC::try_new`](crate::fastcdc::FastCDC::try_new).
+    ///
+    pub fn try_new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self, ClastError> {
+        let sizes = Sizes::new(min_size, avg_size, max_size)?;
+
+        // The search starts at `min_size` and expects `(e - 1) * window` bytes past it.
+        let window = (((avg_size - min_size) as f64) / (E - 1.0)).round() as usize;
+
+        Ok(Self {
+            sizes,
+            window: window.max(1),
+        })
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.sizes.min
+    }
+
+    /// Returns the target average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.sizes.avg
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    /// Returns the number of positions the extremum must hold for before a cut.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+}
+
+fn value_at(source: &[u8], idx: usize) -> u64 {
+    u64::from_le_bytes(source[idx..idx + VALUE_LEN].try_into().unwrap())
+}
+
+impl CutPoint for AE {
+    fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        let len = source.len().min(self.sizes.max);
+        if len <= self.sizes.min + VALUE_LEN {
+            return (0, len);
+        }
+
+        let start = self.sizes.min;
+        let mut max_value = value_at(source, start);
+        let mut max_pos = start;
+
+        for idx in start + 1..=len - VALUE_LEN {
+            let value = value_at(source, idx);
+
+            if value > max_value {
+                max_value = value;
+                max_pos = idx;
+            } else if idx == max_pos + self.window {
+                return (0, idx + 1);
+            }
+        }
+
+        (0, len)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/core_tests.rs"]
+mod tests;
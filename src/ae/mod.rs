@@ -0,0 +1,19 @@
+//!
+//! This module implements the **AE** (Asymmetric Extremum) chunking algorithm, as described in the paper:
+//! **"AE: An Asymmetric Extremum Content Defined Chunking Algorithm for Fast and Bandwidth-Efficient Data Deduplication"**.
+//!
+//! ## Reference
+//! * **Title**: AE: An Asymmetric Extremum Content Defined Chunking Algorithm for Fast and Bandwidth-Efficient Data Deduplication
+//! * **Authors**: Yucheng Zhang, Hong Jiang, Dan Feng, Wen Xia, Min Fu, Fangting Huang, and Yukun Zhou.
+//! * **Conference**: IEEE Conference on Computer Communications (INFOCOM), 2015.
+//!
+//! ## Algorithm
+//! AE is hash-free: it compares the values at successive positions and cuts once the current
+//! maximum has not been exceeded for a fixed window of positions after it. The window lies
+//! only on the right of the extremum, which is what makes the algorithm asymmetric and lets
+//! it decide with a single comparison per byte.
+//!
+
+mod core;
+
+pub use core::AE;
@@ -0,0 +1,75 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, assert_tiles, generate_noise_data, spans};
+
+// --- Constructor Tests ---
+
+#[test]
+fn test_window() {
+    let chunker = AE::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // The window covers the expected distance past min_size
+    assert_eq!(chunker.window(), 2_399);
+
+    // The size constraints are shared with FastCDC
+    let err = AE::try_new(MIN_SIZE, AVG_SIZE, AVG_SIZE).unwrap_err();
+    assert!(matches!(err, ClastError::InvalidOrdering { .. }));
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_size_constraints() {
+    let data = generate_noise_data(1_000_000);
+    let chunker = AE::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // Chunks tile the input and respect the size limits
+    let spans = assert_tiles(&chunker, &data, MIN_SIZE);
+
+    // Most chunks are content-defined rather than forced
+    let forced = spans.iter().filter(|s| s.1 == MAX_SIZE).count();
+    assert!(
+        forced * 4 < spans.len(),
+        "{} of {} forced",
+        forced,
+        spans.len()
+    );
+}
+
+#[test]
+fn test_cut_after_extremum() {
+    let mut data = vec![0u8; MAX_SIZE];
+    data[MIN_SIZE + 100] = 0xff;
+    let chunker = AE::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // The cut lands one window past the position holding the largest value
+    let chunk = chunker.chunks(&data[..]).next().unwrap().unwrap();
+    let max_pos = MIN_SIZE + 100 - (VALUE_LEN - 1);
+    assert_eq!(chunk.length, max_pos + chunker.window() + 1);
+    assert_eq!(chunk.fp_hash, 0);
+}
+
+#[test]
+fn test_boundaries_resynchronize() {
+    let data = generate_noise_data(500_000);
+    let mut edited = b"inserted prefix".to_vec();
+    edited.extend_from_slice(&data);
+
+    let chunker = AE::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+    let original: Vec<u64> = spans(&chunker, &data)
+        .iter()
+        .map(|&(o, l)| o + l as u64)
+        .collect();
+    let shifted: Vec<u64> = spans(&chunker, &edited)
+        .iter()
+        .map(|&(o, l)| o + l as u64 - 15)
+        .collect();
+
+    // After the edit, boundaries realign with the original ones shifted by the prefix
+    let shared = shifted.iter().filter(|end| original.contains(end)).count();
+    assert!(
+        shared + 3 >= original.len(),
+        "{} of {}",
+        shared,
+        original.len()
+    );
+}
@@ -0,0 +1,155 @@
+use clast::fastcdc::Normal;
+
+pub const USAGE: &str = "\
+Usage: clast chunk [OPTIONS] <FILE>
+       clast verify <FILE> <MANIFEST>
+
+Commands:
+  chunk     Splits FILE (or `-` for stdin) into content-defined chunks
+  verify    Re-chunks FILE and confirms it still matches MANIFEST
+
+Chunk options:
+  --min <BYTES>       Minimum chunk size (default: 2048)
+  --avg <BYTES>       Average chunk size (default: 8192)
+  --max <BYTES>       Maximum chunk size (default: 65536)
+  --normal <LEVEL>    Normalization level 0-3 (default: 2)
+  --format <FORMAT>   Output format: text, jsonl (default: text)
+  --manifest <PATH>   Also write a binary manifest to PATH (`-` for stdout)
+  -q, --quiet         Do not print chunk records
+  -h, --help          Print this help
+";
+
+/// Output format of the `chunk` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One human-readable line per chunk.
+    Text,
+    /// One JSON record per line.
+    Jsonl,
+}
+
+/// Options of the `chunk` subcommand.
+#[derive(Debug)]
+pub struct ChunkArgs {
+    pub min_size: usize,
+    pub avg_size: usize,
+    pub max_size: usize,
+    pub normal: Normal,
+    pub format: Format,
+    pub manifest: Option<String>,
+    pub quiet: bool,
+    pub input: String,
+}
+
+/// Arguments of the `verify` subcommand.
+#[derive(Debug)]
+pub struct VerifyArgs {
+    pub input: String,
+    pub manifest: String,
+}
+
+/// A parsed command line.
+#[derive(Debug)]
+pub enum Command {
+    Chunk(ChunkArgs),
+    Verify(VerifyArgs),
+    Help,
+}
+
+///
+/// Parses the command line arguments, excluding the program name.
+///
+/// ## Errors
+///
+/// Returns a message describing the first invalid or missing argument.
+///
+pub fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("chunk") => parse_chunk(args).map(Command::Chunk),
+        Some("verify") => parse_verify(args).map(Command::Verify),
+        Some("-h") | Some("--help") | None => Ok(Command::Help),
+        Some(other) => Err(format!("unknown subcommand `{}`", other)),
+    }
+}
+
+fn parse_chunk(mut args: impl Iterator<Item = String>) -> Result<ChunkArgs, String> {
+    let mut parsed = ChunkArgs {
+        min_size: 2_048,
+        avg_size: 8_192,
+        max_size: 65_536,
+        normal: Normal::Level2,
+        format: Format::Text,
+        manifest: None,
+        quiet: false,
+        input: String::new(),
+    };
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--min" => parsed.min_size = parse_size(&arg, args.next())?,
+            "--avg" => parsed.avg_size = parse_size(&arg, args.next())?,
+            "--max" => parsed.max_size = parse_size(&arg, args.next())?,
+            "--normal" => parsed.normal = parse_normal(args.next())?,
+            "--format" => parsed.format = parse_format(args.next())?,
+            "--manifest" => {
+                parsed.manifest = Some(args.next().ok_or("`--manifest` requires a value")?);
+            }
+            "-q" | "--quiet" => parsed.quiet = true,
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}`", flag)),
+            _ if input.is_some() => return Err(format!("unexpected argument `{}`", arg)),
+            _ => input = Some(arg),
+        }
+    }
+
+    parsed.input = input.ok_or("missing <FILE> argument")?;
+
+    Ok(parsed)
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<VerifyArgs, String> {
+    let input = args.next().ok_or("missing <FILE> argument")?;
+    let manifest = args.next().ok_or("missing <MANIFEST> argument")?;
+
+    if let Some(extra) = args.next() {
+        return Err(format!("unexpected argument `{}`", extra));
+    }
+
+    Ok(VerifyArgs { input, manifest })
+}
+
+fn parse_size(flag: &str, value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("`{}` requires a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{}` for `{}`", value, flag))
+}
+
+fn parse_normal(value: Option<String>) -> Result<Normal, String> {
+    match value.as_deref() {
+        Some("0") => Ok(Normal::None),
+        Some("1") => Ok(Normal::Level1),
+        Some("2") => Ok(Normal::Level2),
+        Some("3") => Ok(Normal::Level3),
+        Some(other) => Err(format!("invalid normalization level `{}`", other)),
+        None => Err("`--normal` requires a value".into()),
+    }
+}
+
+fn parse_format(value: Option<String>) -> Result<Format, String> {
+    match value.as_deref() {
+        Some("text") => Ok(Format::Text),
+        Some("jsonl") => Ok(Format::Jsonl),
+        Some(other) => Err(format!("unknown format `{}`", other)),
+        None => Err("`--format` requires a value".into()),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/args_tests.rs"]
+mod tests;
@@ -0,0 +1,145 @@
+//!
+//! Command-line interface for chunking files with **Clast**.
+//!
+
+mod args;
+
+use args::{ChunkArgs, Command, Format, VerifyArgs};
+use clast::digest::Sha256;
+use clast::fastcdc::{Chunk, FastCDC};
+use clast::manifest::Manifest;
+use std::{
+    env, fs,
+    io::{self, BufWriter, Read, Write},
+    process::ExitCode,
+};
+
+fn main() -> ExitCode {
+    let command = match args::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, args::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match command {
+        Command::Help => {
+            print!("{}", args::USAGE);
+            Ok(())
+        }
+        Command::Chunk(args) => run_chunk(&args),
+        Command::Verify(args) => run_verify(&args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // A closed pipe (e.g. `| head`) is not an error for a filter.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn open_input(path: &str) -> io::Result<Box<dyn Read>> {
+    if path == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(fs::File::open(path)?))
+    }
+}
+
+fn run_chunk(args: &ChunkArgs) -> io::Result<()> {
+    let chunker = FastCDC::try_new(args.min_size, args.avg_size, args.max_size, args.normal)?;
+    let reader = open_input(&args.input)?;
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut manifest = args.manifest.as_ref().map(|_| Manifest::new(&chunker));
+
+    for chunk in chunker.chunks(reader) {
+        let chunk = chunk?;
+
+        if let Some(manifest) = manifest.as_mut() {
+            manifest.push(&chunk);
+        }
+
+        if args.quiet {
+            continue;
+        }
+
+        match args.format {
+            Format::Text => writeln!(out, "{}", text_record(&chunk))?,
+            Format::Jsonl => writeln!(out, "{}", json_record(&chunk))?,
+        }
+    }
+
+    if let (Some(manifest), Some(path)) = (manifest, &args.manifest) {
+        write_manifest(&manifest, path, &mut out)?;
+    }
+
+    out.flush()
+}
+
+///
+/// Writes the manifest to `path`, or to `out` for `-`.
+///
+/// A regular file (or a missing one) is replaced atomically. Anything else, such as a FIFO
+/// or the `/dev/stdout` symlink, is written in place, as renaming over it would replace the
+/// target instead of writing to it.
+///
+fn write_manifest<W: Write>(manifest: &Manifest, path: &str, out: &mut W) -> io::Result<()> {
+    if path == "-" {
+        return manifest.write_to(out);
+    }
+
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.is_file() => {
+            let mut file = BufWriter::new(fs::File::create(path)?);
+            manifest.write_to(&mut file)?;
+            file.flush()
+        }
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => manifest.persist(path),
+    }
+}
+
+fn run_verify(args: &VerifyArgs) -> io::Result<()> {
+    let manifest = Manifest::read_from(io::BufReader::new(fs::File::open(&args.manifest)?))?;
+    manifest.verify(open_input(&args.input)?)?;
+
+    println!(
+        "ok: {} chunks ({} bytes) match",
+        manifest.entries().len(),
+        manifest.total_len()
+    );
+
+    Ok(())
+}
+
+/// Formats a chunk as a tab-separated line: offset, length, fingerprint.
+fn text_record(chunk: &Chunk) -> String {
+    format!("{}\t{}\t{:016x}", chunk.offset, chunk.length, chunk.fp_hash)
+}
+
+/// Formats a chunk as a single-line JSON object.
+///
+/// The fingerprint is emitted as a hex string because JSON numbers
+/// cannot represent every `u64` exactly in common consumers. The SHA-256
+/// digest of the chunk's contents identifies it for deduplication.
+fn json_record(chunk: &Chunk) -> String {
+    let digest: String = Sha256::digest(&chunk.data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    format!(
+        "{{\"offset\":{},\"length\":{},\"fp_hash\":\"{:016x}\",\"sha256\":\"{}\"}}",
+        chunk.offset, chunk.length, chunk.fp_hash, digest
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/main_tests.rs"]
+mod tests;
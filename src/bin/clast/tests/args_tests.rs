@@ -0,0 +1,87 @@
+use super::*;
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+// --- Parsing Tests ---
+
+#[test]
+fn test_chunk_defaults() {
+    let Command::Chunk(parsed) = parse(args(&["chunk", "file.bin"])).expect("Failed to parse")
+    else {
+        panic!("Expected the chunk subcommand");
+    };
+
+    // Unspecified options should fall back to their defaults
+    assert_eq!(parsed.input, "file.bin");
+    assert_eq!(parsed.format, Format::Text);
+    assert_eq!(
+        (parsed.min_size, parsed.avg_size, parsed.max_size),
+        (2_048, 8_192, 65_536)
+    );
+}
+
+#[test]
+fn test_chunk_options() {
+    let Command::Chunk(parsed) = parse(args(&[
+        "chunk", "--format", "jsonl", "--min", "64", "--avg", "256", "--max", "1024", "-",
+    ]))
+    .expect("Failed to parse") else {
+        panic!("Expected the chunk subcommand");
+    };
+
+    assert_eq!(parsed.input, "-");
+    assert_eq!(parsed.format, Format::Jsonl);
+    assert_eq!(
+        (parsed.min_size, parsed.avg_size, parsed.max_size),
+        (64, 256, 1_024)
+    );
+}
+
+#[test]
+fn test_chunk_manifest() {
+    let Command::Chunk(parsed) =
+        parse(args(&["chunk", "-q", "--manifest", "out.cbm", "f"])).expect("Failed to parse")
+    else {
+        panic!("Expected the chunk subcommand");
+    };
+
+    assert!(parsed.quiet);
+    assert_eq!(parsed.manifest.as_deref(), Some("out.cbm"));
+}
+
+#[test]
+fn test_verify() {
+    let Command::Verify(parsed) =
+        parse(args(&["verify", "file.bin", "file.cbm"])).expect("Failed to parse")
+    else {
+        panic!("Expected the verify subcommand");
+    };
+
+    assert_eq!(parsed.input, "file.bin");
+    assert_eq!(parsed.manifest, "file.cbm");
+}
+
+#[test]
+fn test_help() {
+    // No arguments and explicit help flags should both print usage
+    assert!(matches!(parse(args(&[])), Ok(Command::Help)));
+    assert!(matches!(parse(args(&["--help"])), Ok(Command::Help)));
+}
+
+// --- Error Tests ---
+
+#[test]
+fn test_invalid_arguments() {
+    // Every malformed command line must be rejected with a message
+    assert!(parse(args(&["split"])).is_err());
+    assert!(parse(args(&["chunk"])).is_err());
+    assert!(parse(args(&["chunk", "--format", "xml", "f"])).is_err());
+    assert!(parse(args(&["chunk", "--min", "abc", "f"])).is_err());
+    assert!(parse(args(&["chunk", "--normal", "4", "f"])).is_err());
+    assert!(parse(args(&["chunk", "a", "b"])).is_err());
+    assert!(parse(args(&["chunk", "--manifest"])).is_err());
+    assert!(parse(args(&["verify", "f"])).is_err());
+    assert!(parse(args(&["verify", "f", "m", "x"])).is_err());
+}
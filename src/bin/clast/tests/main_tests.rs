@@ -0,0 +1,75 @@
+use super::*;
+use bytes::Bytes;
+use clast::fastcdc::Normal;
+
+fn sample_chunk() -> Chunk {
+    Chunk {
+        fp_hash: 0xdead_beef,
+        data: Bytes::from_static(b"payload"),
+        offset: 4_096,
+        length: 7,
+    }
+}
+
+// --- Output Tests ---
+
+#[test]
+fn test_json_record() {
+    // Records must be single-line JSON with a zero-padded hex fingerprint and digest
+    assert_eq!(
+        json_record(&sample_chunk()),
+        concat!(
+            r#"{"offset":4096,"length":7,"fp_hash":"00000000deadbeef","#,
+            r#""sha256":"239f59ed55e737c77147cf55ad0c1b030b6d7ee748a7426952f9b852d5a935e5"}"#
+        )
+    );
+}
+
+#[test]
+fn test_text_record() {
+    assert_eq!(text_record(&sample_chunk()), "4096\t7\t00000000deadbeef");
+}
+
+// --- Manifest Tests ---
+
+fn sample_manifest() -> Manifest {
+    let chunker = FastCDC::new(2_048, 8_192, 65_536, Normal::Level2);
+    Manifest::from_reader(&chunker, &[7u8; 100_000][..]).unwrap()
+}
+
+#[test]
+fn test_manifest_to_stdout() {
+    let manifest = sample_manifest();
+    let mut out = Vec::new();
+
+    // `-` writes the manifest to the output stream
+    write_manifest(&manifest, "-", &mut out).unwrap();
+    assert_eq!(Manifest::read_from(&out[..]).unwrap(), manifest);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_manifest_through_symlink() {
+    let dir = env::temp_dir().join(format!("clast-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let target = dir.join("target");
+    let link = dir.join("link");
+    fs::write(&target, b"old").unwrap();
+    let _ = fs::remove_file(&link);
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    let manifest = sample_manifest();
+    write_manifest(&manifest, link.to_str().unwrap(), &mut io::sink()).unwrap();
+
+    // The link is written through, not replaced by a regular file
+    assert!(
+        fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink()
+    );
+    let written = fs::read(&target).unwrap();
+    assert_eq!(Manifest::read_from(&written[..]).unwrap(), manifest);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
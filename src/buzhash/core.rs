@@ -0,0 +1,190 @@
+use crate::chunker::{ChunkIter, CutPoint, Sizes};
+use crate::error::ClastError;
+use std::io::Read;
+
+/// Default size of the rolling hash window, in bytes.
+pub const DEFAULT_WINDOW: usize = 64;
+
+/// Seed of the substitution table.
+const TABLE_SEED: u64 = 0x6275_7a68_6173_6821;
+
+/// The substitution table mapping each byte to a random 32-bit value.
+pub const BUZHASH_TABLE: [u32; 256] = generate_table(TABLE_SEED);
+
+const fn generate_table(seed: u64) -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut state = seed;
+    let mut i = 0;
+
+    while i < 256 {
+        // SplitMix64
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = ((z ^ (z >> 31)) >> 32) as u32;
+        i += 1;
+    }
+
+    table
+}
+
+/// A Buzhash chunker implementation.
+#[derive(Debug, Clone)]
+pub struct BuzHash {
+    sizes: Sizes,
+    window: usize,
+    mask: u32,
+}
+
+impl BuzHash {
+    ///
+    /// Constructs a new `BuzHash` instance with the default window.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`FastCDC::new`](crate::fastcdc::FastCDC::new).
+    ///
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        match Self::try_new(min_size, avg_size, max_size) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a new `BuzHash` instance with the default window.
+    /// Unlike `new`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as
+    /// [`FastCDC::try_new`](crate::fastcdc::FastCDC::try_new).
+    ///
+    pub fn try_new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self, ClastError> {
+        let sizes = Sizes::new(min_size, avg_size, max_size)?;
+
+        // The mask with the bit count closest to log2(avg_size).
+        let bits = (avg_size as f64).log2().round() as u32;
+
+        Ok(Self {
+            sizes,
+            window: DEFAULT_WINDOW,
+            mask: (1u32 << bits) - 1,
+        })
+    }
+
+    ///
+    /// Sets the size of the rolling hash window.
+    ///
+    /// ## Arguments
+    ///
+    /// * `window`: The number of trailing bytes the hash covers.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `window` is zero or greater than `min_size`.
+    ///
+    pub fn with_window(mut self, window: usize) -> Self {
+        assert!(
+            (1..=self.sizes.min).contains(&window),
+            "window must be between 1 and min_size"
+        );
+
+        self.window = window;
+        self
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.sizes.min
+    }
+
+    /// Returns the target average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.sizes.avg
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    /// Returns the size of the rolling hash window.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+}
+
+impl CutPoint for BuzHash {
+    fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        let len = source.len().min(self.sizes.max);
+        if len <= self.sizes.min {
+            return (0, len);
+        }
+
+        // Only the window ending at `min_size` affects the first boundary check.
+        let start = self.sizes.min - self.window;
+        let shift = (self.window % 32) as u32;
+        let mut hash = 0u32;
+
+        for idx in start..len {
+            hash = hash.rotate_left(1) ^ BUZHASH_TABLE[source[idx] as usize];
+            if idx >= start + self.window {
+                hash ^= BUZHASH_TABLE[source[idx - self.window] as usize].rotate_left(shift);
+            }
+
+            if idx + 1 >= self.sizes.min && hash & self.mask == 0 {
+                return (hash as u64, idx + 1);
+            }
+        }
+
+        (hash as u64, len)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/core_tests.rs"]
+mod tests;
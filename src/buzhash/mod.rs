@@ -0,0 +1,20 @@
+//!
+//! This module implements content-defined chunking with **Buzhash**, the cyclic-polynomial
+//! rolling hash used by tools such as Borg.
+//!
+//! ## Reference
+//! * **Title**: Recursive Hashing Functions for n-Grams
+//! * **Authors**: Jonathan D. Cohen
+//! * **Journal**: ACM Transactions on Information Systems, Vol. 15, No. 3, July 1997.
+//! * **DOI**: 10.1145/256163.256168
+//!
+//! ## Algorithm
+//! The hash covers a sliding window of the last `window` bytes. Each step rotates the hash
+//! left by one bit, removes the outgoing byte by XOR-ing its table value rotated by the
+//! window size, and XORs in the incoming byte. A chunk ends where the low bits of the hash
+//! are all zero, once it is at least `min_size` long, or at `max_size` otherwise.
+//!
+
+mod core;
+
+pub use core::{BUZHASH_TABLE, BuzHash, DEFAULT_WINDOW};
@@ -0,0 +1,82 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, assert_tiles, generate_noise_data, spans};
+
+// --- Constructor Tests ---
+
+#[test]
+fn test_invalid_sizes() {
+    // The size constraints are shared with FastCDC
+    assert!(BuzHash::try_new(MIN_SIZE, AVG_SIZE, MAX_SIZE).is_ok());
+    assert!(BuzHash::try_new(32, AVG_SIZE, MAX_SIZE).is_err());
+    assert!(BuzHash::try_new(AVG_SIZE, MIN_SIZE, MAX_SIZE).is_err());
+
+    let err = BuzHash::try_new(MIN_SIZE, AVG_SIZE, AVG_SIZE).unwrap_err();
+    assert!(matches!(err, ClastError::InvalidOrdering { .. }));
+}
+
+#[test]
+#[should_panic(expected = "window must be between 1 and min_size")]
+fn test_window_too_large() {
+    BuzHash::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_window(MIN_SIZE + 1);
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_empty_input() {
+    let chunker = BuzHash::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // Empty input should not produce any chunks
+    assert!(chunker.chunks(&[][..]).next().is_none());
+}
+
+#[test]
+fn test_size_constraints() {
+    let data = generate_noise_data(1_000_000);
+    let chunker = BuzHash::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // Chunks tile the input and respect the size limits
+    let spans = assert_tiles(&chunker, &data, MIN_SIZE);
+
+    // The average lands between min_size and max_size
+    let avg = data.len() / spans.len();
+    assert!((MIN_SIZE..MAX_SIZE).contains(&avg), "average {}", avg);
+}
+
+#[test]
+fn test_boundaries_resynchronize() {
+    let data = generate_noise_data(500_000);
+    let mut edited = b"inserted prefix".to_vec();
+    edited.extend_from_slice(&data);
+
+    let chunker = BuzHash::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_window(48);
+    let original = spans(&chunker, &data);
+    let shifted = spans(&chunker, &edited);
+
+    // After the edit, boundaries realign with the original ones shifted by the prefix
+    let ends = |spans: &[(u64, usize)], shift: u64| -> Vec<u64> {
+        spans.iter().map(|&(o, l)| o + l as u64 - shift).collect()
+    };
+    let original_ends = ends(&original, 0);
+    let shared = ends(&shifted, 15)
+        .iter()
+        .filter(|end| original_ends.contains(end))
+        .count();
+    assert!(
+        shared + 3 >= original.len(),
+        "{} of {}",
+        shared,
+        original.len()
+    );
+}
+
+// --- Hash Tests ---
+
+#[test]
+fn test_table_values() {
+    // The substitution table has no repeated values
+    let mut values = BUZHASH_TABLE.to_vec();
+    values.sort_unstable();
+    values.dedup();
+    assert_eq!(values.len(), 256);
+}
@@ -0,0 +1,203 @@
+use crate::ae::AE;
+use crate::buzhash::BuzHash;
+use crate::chunker::Chunker;
+use crate::fastcdc::{FastCDC, FastCDC32, Normal};
+use crate::fixed::FixedSize;
+use crate::mii::MII;
+use crate::pci::PCI;
+use crate::rabin::Rabin;
+use crate::seqcdc::SeqCDC;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/// A chunking algorithm selectable by [`ChunkerConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    /// [`FastCDC`].
+    #[default]
+    FastCDC,
+    /// [`FastCDC32`].
+    FastCDC32,
+    /// [`BuzHash`].
+    BuzHash,
+    /// [`Rabin`].
+    Rabin,
+    /// [`AE`].
+    AE,
+    /// [`MII`].
+    MII,
+    /// [`SeqCDC`].
+    SeqCDC,
+    /// [`PCI`].
+    PCI,
+    /// [`FixedSize`], cutting every `avg_size` bytes.
+    Fixed,
+}
+
+impl Algorithm {
+    /// Returns the lowercase name used by [`FromStr`] and [`fmt::Display`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::FastCDC => "fastcdc",
+            Self::FastCDC32 => "fastcdc32",
+            Self::BuzHash => "buzhash",
+            Self::Rabin => "rabin",
+            Self::AE => "ae",
+            Self::MII => "mii",
+            Self::SeqCDC => "seqcdc",
+            Self::PCI => "pci",
+            Self::Fixed => "fixed",
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        [
+            Self::FastCDC,
+            Self::FastCDC32,
+            Self::BuzHash,
+            Self::Rabin,
+            Self::AE,
+            Self::MII,
+            Self::SeqCDC,
+            Self::PCI,
+            Self::Fixed,
+        ]
+        .into_iter()
+        .find(|algorithm| algorithm.name().eq_ignore_ascii_case(s))
+        .ok_or_else(|| invalid_input(format!("unknown chunking algorithm `{}`", s)))
+    }
+}
+
+///
+/// Chunker settings as plain data, e.g. loaded from an application's configuration file.
+///
+/// Nothing is validated until the configuration is turned into a chunker, with
+/// [`ChunkerConfig::build`] for any algorithm or `FastCDC::try_from` for FastCDC.
+///
+/// ## Example
+///
+/// ```
+/// use clast::chunker::ChunkerConfig;
+///
+/// let config = ChunkerConfig {
+///     algorithm: "buzhash".parse().unwrap(),
+///     ..ChunkerConfig::default()
+/// };
+///
+/// let chunker = config.build().unwrap();
+/// assert_eq!(chunker.max_size(), 65_536);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkerConfig {
+    /// The chunking algorithm.
+    pub algorithm: Algorithm,
+    /// The minimum chunk size.
+    pub min_size: usize,
+    /// The target average chunk size (the chunk size of `Algorithm::Fixed`).
+    pub avg_size: usize,
+    /// The maximum chunk size.
+    pub max_size: usize,
+    /// The normalization level, used by the FastCDC algorithms.
+    pub normal: Normal,
+    /// The Gear table seed, supported by `Algorithm::FastCDC` only.
+    pub seed: Option<u64>,
+}
+
+impl Default for ChunkerConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: Algorithm::FastCDC,
+            min_size: 2_048,
+            avg_size: 8_192,
+            max_size: 65_536,
+            normal: Normal::Level2,
+            seed: None,
+        }
+    }
+}
+
+impl ChunkerConfig {
+    ///
+    /// Builds the configured chunker.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if the algorithm rejects
+    /// the sizes, or if a seed is set for an algorithm other than `Algorithm::FastCDC`.
+    ///
+    pub fn build(&self) -> io::Result<Box<dyn Chunker + Send + Sync>> {
+        if self.seed.is_some() && self.algorithm != Algorithm::FastCDC {
+            return Err(invalid_input(format!(
+                "seed is not supported by {}",
+                self.algorithm
+            )));
+        }
+
+        let (min, avg, max) = (self.min_size, self.avg_size, self.max_size);
+
+        Ok(match self.algorithm {
+            Algorithm::FastCDC => Box::new(FastCDC::try_from(self)?),
+            Algorithm::FastCDC32 => Box::new(FastCDC32::try_new(min, avg, max, self.normal)?),
+            Algorithm::BuzHash => Box::new(BuzHash::try_new(min, avg, max)?),
+            Algorithm::Rabin => Box::new(Rabin::try_new(min, avg, max)?),
+            Algorithm::AE => Box::new(AE::try_new(min, avg, max)?),
+            Algorithm::MII => Box::new(MII::try_new(min, avg, max)?),
+            Algorithm::SeqCDC => Box::new(SeqCDC::try_new(min, avg, max)?),
+            Algorithm::PCI => Box::new(PCI::try_new(min, avg, max)?),
+            Algorithm::Fixed => Box::new(FixedSize::try_new(avg)?),
+        })
+    }
+}
+
+impl TryFrom<&ChunkerConfig> for FastCDC {
+    type Error = io::Error;
+
+    fn try_from(config: &ChunkerConfig) -> io::Result<Self> {
+        if config.algorithm != Algorithm::FastCDC {
+            return Err(invalid_input(format!(
+                "expected the fastcdc algorithm, found {}",
+                config.algorithm
+            )));
+        }
+
+        let chunker = FastCDC::try_new(
+            config.min_size,
+            config.avg_size,
+            config.max_size,
+            config.normal,
+        )?;
+
+        Ok(match config.seed {
+            Some(seed) => chunker.with_seed(seed),
+            None => chunker,
+        })
+    }
+}
+
+impl TryFrom<ChunkerConfig> for FastCDC {
+    type Error = io::Error;
+
+    fn try_from(config: ChunkerConfig) -> io::Result<Self> {
+        Self::try_from(&config)
+    }
+}
+
+fn invalid_input(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+#[cfg(test)]
+#[path = "tests/config_tests.rs"]
+mod tests;
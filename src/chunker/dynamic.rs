@@ -0,0 +1,116 @@
+use crate::chunker::{ChunkIter, CutPoint};
+use crate::fastcdc::{Chunk, FastCDC};
+use std::io::{self, Read};
+
+/// A boxed iterator over the chunks of a reader.
+pub type BoxedChunks<'a> = Box<dyn Iterator<Item = io::Result<Chunk>> + 'a>;
+
+///
+/// A chunking algorithm that can be selected at runtime.
+///
+/// Implemented by [`FastCDC`] and by every [`CutPoint`] algorithm, so code can be generic over
+/// the chunking strategy or hold one as a `Box<dyn Chunker>`:
+///
+/// ```
+/// use clast::buzhash::BuzHash;
+/// use clast::chunker::Chunker;
+/// use clast::fastcdc::{FastCDC, Normal};
+///
+/// let algorithm = "buzhash";
+/// let chunker: Box<dyn Chunker> = match algorithm {
+///     "buzhash" => Box::new(BuzHash::new(2_048, 8_192, 65_536)),
+///     _ => Box::new(FastCDC::new(2_048, 8_192, 65_536, Normal::Level2)),
+/// };
+///
+/// let data = vec![0u8; 100_000];
+/// let total: usize = chunker
+///     .boxed_chunks(Box::new(&data[..]))
+///     .map(|chunk| chunk.unwrap().length)
+///     .sum();
+/// assert_eq!(total, data.len());
+/// ```
+///
+pub trait Chunker {
+    /// Returns the maximum chunk size.
+    fn max_size(&self) -> usize;
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from.
+    ///
+    fn boxed_chunks<'a>(&'a self, reader: Box<dyn Read + 'a>) -> BoxedChunks<'a>;
+}
+
+impl Chunker for FastCDC {
+    fn max_size(&self) -> usize {
+        self.max_size()
+    }
+
+    fn boxed_chunks<'a>(&'a self, reader: Box<dyn Read + 'a>) -> BoxedChunks<'a> {
+        Box::new(self.chunks(reader))
+    }
+}
+
+impl<C: CutPoint> Chunker for C {
+    fn max_size(&self) -> usize {
+        CutPoint::max_size(self)
+    }
+
+    fn boxed_chunks<'a>(&'a self, reader: Box<dyn Read + 'a>) -> BoxedChunks<'a> {
+        Box::new(ChunkIter::new(self, reader))
+    }
+}
+
+/// A boxed stream over the chunks of an async reader.
+#[cfg(feature = "async")]
+pub type BoxedChunkStream<'a> = futures::stream::BoxStream<'a, io::Result<Chunk>>;
+
+/// A boxed async reader accepted by [`AsyncChunker`].
+#[cfg(feature = "async")]
+pub type BoxedAsyncRead<'a> = Box<dyn tokio::io::AsyncRead + Unpin + Send + 'a>;
+
+///
+/// The async counterpart of [`Chunker`], for chunking `AsyncRead` sources behind a trait object.
+///
+/// Implemented by [`FastCDC`] and by every [`CutPoint`] algorithm. The returned streams are
+/// `Send`, so they can be driven from a multi-threaded runtime.
+///
+#[cfg(feature = "async")]
+pub trait AsyncChunker {
+    /// Returns the maximum chunk size.
+    fn max_size(&self) -> usize;
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from.
+    ///
+    fn boxed_stream<'a>(&'a self, reader: BoxedAsyncRead<'a>) -> BoxedChunkStream<'a>;
+}
+
+#[cfg(feature = "async")]
+impl AsyncChunker for FastCDC {
+    fn max_size(&self) -> usize {
+        self.max_size()
+    }
+
+    fn boxed_stream<'a>(&'a self, reader: BoxedAsyncRead<'a>) -> BoxedChunkStream<'a> {
+        Box::pin(self.chunks_async(reader))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<C: CutPoint + Sync> AsyncChunker for C {
+    fn max_size(&self) -> usize {
+        CutPoint::max_size(self)
+    }
+
+    fn boxed_stream<'a>(&'a self, reader: BoxedAsyncRead<'a>) -> BoxedChunkStream<'a> {
+        Box::pin(crate::chunker::ChunkStream::new(self, reader))
+    }
+}
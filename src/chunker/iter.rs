@@ -0,0 +1,72 @@
+use crate::chunker::CutPoint;
+use crate::fastcdc::{Chunk, SpareInit};
+use bytes::BytesMut;
+use std::io::{self, Read};
+
+///
+/// An iterator that yields the chunks an algorithm finds in a reader.
+///
+/// Created by the `chunks` method of each chunker.
+///
+pub struct ChunkIter<'a, C, R> {
+    chunker: &'a C,
+    reader: R,
+    buf: BytesMut,
+    spare: SpareInit,
+    processed: u64,
+    eof: bool,
+}
+
+impl<'a, C: CutPoint, R: Read> ChunkIter<'a, C, R> {
+    pub(crate) fn new(chunker: &'a C, reader: R) -> Self {
+        Self {
+            chunker,
+            reader,
+            buf: BytesMut::with_capacity(chunker.max_size()),
+            spare: SpareInit::default(),
+            processed: 0,
+            eof: false,
+        }
+    }
+}
+
+impl<C: CutPoint, R: Read> Iterator for ChunkIter<'_, C, R> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let max_size = self.chunker.max_size();
+
+        while !self.eof && self.buf.len() < max_size {
+            let len = self.buf.len();
+            let dst = self.spare.spare(&mut self.buf, max_size - len);
+
+            match self.reader.read(dst) {
+                Ok(0) => self.eof = true,
+                Ok(n) => {
+                    // SAFETY: The spare capacity read into is initialized.
+                    unsafe { self.buf.set_len(len + n) };
+                    self.spare.filled(n);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        if self.buf.is_empty() {
+            return None;
+        }
+
+        let (fp_hash, cutpoint) = self.chunker.find_cutpoint_at(self.processed, &self.buf[..]);
+        let data = self.buf.split_to(cutpoint).freeze();
+
+        let chunk = Chunk {
+            fp_hash,
+            data,
+            offset: self.processed,
+            length: cutpoint,
+        };
+        self.processed += cutpoint as u64;
+
+        Some(Ok(chunk))
+    }
+}
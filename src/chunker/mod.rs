@@ -0,0 +1,56 @@
+//!
+//! Iterator and stream machinery shared by the chunking algorithms.
+//!
+//! An algorithm only implements [`CutPoint`]: given the bytes at the start of a chunk,
+//! find where it ends. [`ChunkIter`] and `ChunkStream` handle buffering, offsets, and
+//! the `Chunk` output, so every algorithm chunks `Read` and `AsyncRead` sources alike.
+//!
+//! [`Chunker`] and `AsyncChunker` erase the algorithm, so one can be chosen at runtime,
+//! e.g. from a [`ChunkerConfig`].
+//!
+
+mod config;
+mod dynamic;
+mod iter;
+mod sizes;
+#[cfg(feature = "async")]
+mod stream;
+
+pub use config::{Algorithm, ChunkerConfig};
+#[cfg(feature = "async")]
+pub use dynamic::{AsyncChunker, BoxedAsyncRead, BoxedChunkStream};
+pub use dynamic::{BoxedChunks, Chunker};
+pub use iter::ChunkIter;
+pub(crate) use sizes::Sizes;
+#[cfg(feature = "async")]
+pub use stream::ChunkStream;
+
+/// The boundary search of a chunking algorithm.
+pub trait CutPoint {
+    /// Returns the maximum chunk size, which bounds how much is buffered per chunk.
+    fn max_size(&self) -> usize;
+
+    ///
+    /// Finds the end of the chunk starting at the beginning of `source`.
+    ///
+    /// `source` holds `max_size` bytes, or fewer only at the end of the data.
+    /// Returns the fingerprint of the chunk and its length (at most `source.len()`).
+    ///
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize);
+
+    ///
+    /// Finds the end of the chunk starting at the beginning of `source`, which lies at
+    /// stream offset `offset`.
+    ///
+    /// Algorithms whose boundaries depend on absolute offsets (such as alignment) override
+    /// this; the default ignores `offset` and calls [`CutPoint::find_cutpoint`].
+    ///
+    fn find_cutpoint_at(&self, offset: u64, source: &[u8]) -> (u64, usize) {
+        let _ = offset;
+        self.find_cutpoint(source)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/chunker_tests.rs"]
+mod tests;
@@ -0,0 +1,28 @@
+use crate::error::ClastError;
+use crate::fastcdc::validate_sizes;
+
+///
+/// The minimum, average, and maximum chunk sizes of a chunker, checked against the
+/// constraints shared by every chunker.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Sizes {
+    pub(crate) min: usize,
+    pub(crate) avg: usize,
+    pub(crate) max: usize,
+}
+
+impl Sizes {
+    ///
+    /// Validates the sizes.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as
+    /// [`FastCDC::try_new`](crate::fastcdc::FastCDC::try_new).
+    ///
+    pub(crate) fn new(min: usize, avg: usize, max: usize) -> Result<Self, ClastError> {
+        validate_sizes(min, avg, max)?;
+        Ok(Self { min, avg, max })
+    }
+}
@@ -0,0 +1,97 @@
+use crate::chunker::CutPoint;
+use crate::fastcdc::Chunk;
+use bytes::BytesMut;
+use futures::{Stream, stream::FusedStream};
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, ReadBuf};
+
+///
+/// A stream that yields the chunks an algorithm finds in an async reader.
+///
+/// Each chunk is searched for once `max_size` bytes (or the rest of the data) are buffered,
+/// so the boundaries equal those of [`ChunkIter`](crate::chunker::ChunkIter) however the
+/// reader splits its data.
+///
+/// Created by the `chunks_async` method of each chunker.
+///
+pub struct ChunkStream<'a, C, R> {
+    chunker: &'a C,
+    reader: R,
+    buf: BytesMut,
+    processed: u64,
+    eof: bool,
+}
+
+impl<'a, C: CutPoint, R: AsyncRead + Unpin> ChunkStream<'a, C, R> {
+    pub(crate) fn new(chunker: &'a C, reader: R) -> Self {
+        Self {
+            chunker,
+            reader,
+            buf: BytesMut::with_capacity(chunker.max_size()),
+            processed: 0,
+            eof: false,
+        }
+    }
+}
+
+impl<C: CutPoint, R: AsyncRead + Unpin> Stream for ChunkStream<'_, C, R> {
+    type Item = io::Result<Chunk>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let max_size = this.chunker.max_size();
+
+        while !this.eof && this.buf.len() < max_size {
+            let wanted = max_size - this.buf.len();
+            this.buf.reserve(wanted);
+
+            let dst = this.buf.spare_capacity_mut();
+            let limit = dst.len().min(wanted);
+            let mut read_buf = ReadBuf::uninit(&mut dst[..limit]);
+
+            match Pin::new(&mut this.reader).poll_read(cx, &mut read_buf) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(Ok(())) => {
+                    let n = read_buf.filled().len();
+                    if n == 0 {
+                        this.eof = true;
+                    } else {
+                        // SAFETY: `read_buf` ensures `n` bytes were initialized/written.
+                        unsafe {
+                            let new_len = this.buf.len() + n;
+                            this.buf.set_len(new_len);
+                        }
+                    }
+                }
+            }
+        }
+
+        if this.buf.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        let (fp_hash, cutpoint) = this.chunker.find_cutpoint_at(this.processed, &this.buf[..]);
+        let data = this.buf.split_to(cutpoint).freeze();
+
+        let chunk = Chunk {
+            fp_hash,
+            data,
+            offset: this.processed,
+            length: cutpoint,
+        };
+        this.processed += cutpoint as u64;
+
+        Poll::Ready(Some(Ok(chunk)))
+    }
+}
+
+impl<C: CutPoint, R: AsyncRead + Unpin> FusedStream for ChunkStream<'_, C, R> {
+    fn is_terminated(&self) -> bool {
+        self.eof && self.buf.is_empty()
+    }
+}
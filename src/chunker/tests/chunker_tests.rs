@@ -0,0 +1,234 @@
+use super::*;
+use crate::test_util::generate_noise_data;
+use std::io::{self, Read};
+
+/// Cuts at every multiple of `step`, so boundaries are easy to predict.
+struct Fixed {
+    step: usize,
+    max_size: usize,
+}
+
+impl CutPoint for Fixed {
+    fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        let len = self.step.min(source.len());
+        (len as u64, len)
+    }
+}
+
+struct TrickleReader<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl Read for TrickleReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.step).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+/// Reads the buffer it is handed before writing to it, as `Read` implementations may.
+struct PeekingReader<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl Read for PeekingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        assert!(buf.iter().all(|&b| b == 0), "spare capacity must be zeroed");
+        TrickleReader {
+            data: self.data,
+            step: self.step,
+        }
+        .read(buf)
+        .inspect(|&n| self.data = &self.data[n..])
+    }
+}
+
+// --- Iterator Tests ---
+
+#[test]
+fn test_iter_offsets() {
+    let data = generate_noise_data(10_000);
+    let chunker = Fixed {
+        step: 3_000,
+        max_size: 4_096,
+    };
+
+    let chunks = ChunkIter::new(&chunker, &data[..])
+        .collect::<io::Result<Vec<_>>>()
+        .unwrap();
+
+    // Chunks follow the cut points and the tail forms the last chunk
+    let spans: Vec<_> = chunks.iter().map(|c| (c.offset, c.length)).collect();
+    assert_eq!(
+        spans,
+        [(0, 3_000), (3_000, 3_000), (6_000, 3_000), (9_000, 1_000)]
+    );
+
+    // The chunk data is the matching slice of the source
+    for chunk in &chunks {
+        let start = chunk.offset as usize;
+        assert_eq!(&chunk.data[..], &data[start..start + chunk.length]);
+    }
+}
+
+#[test]
+fn test_iter_short_reads() {
+    let data = generate_noise_data(10_000);
+    let chunker = Fixed {
+        step: 3_000,
+        max_size: 4_096,
+    };
+
+    let whole = ChunkIter::new(&chunker, &data[..])
+        .map(|c| c.map(|c| (c.offset, c.length)))
+        .collect::<io::Result<Vec<_>>>()
+        .unwrap();
+    let trickled = ChunkIter::new(
+        &chunker,
+        TrickleReader {
+            data: &data,
+            step: 7,
+        },
+    )
+    .map(|c| c.map(|c| (c.offset, c.length)))
+    .collect::<io::Result<Vec<_>>>()
+    .unwrap();
+
+    // Short reads are buffered up to max_size before each search
+    assert_eq!(whole, trickled);
+}
+
+#[test]
+fn test_iter_reader_sees_initialized_buffer() {
+    let data = generate_noise_data(10_000);
+    let chunker = Fixed {
+        step: 3_000,
+        max_size: 4_096,
+    };
+
+    // Every buffer handed to the reader is initialized, including across chunk splits
+    let peeked = ChunkIter::new(
+        &chunker,
+        PeekingReader {
+            data: &data,
+            step: 1_000,
+        },
+    )
+    .map(|c| c.map(|c| c.data))
+    .collect::<io::Result<Vec<_>>>()
+    .unwrap();
+    assert_eq!(peeked.concat(), data);
+}
+
+// --- Stream Tests ---
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_stream_matches_iter() {
+    use futures::{StreamExt, stream::FusedStream};
+
+    let data = generate_noise_data(10_000);
+    let chunker = Fixed {
+        step: 3_000,
+        max_size: 4_096,
+    };
+
+    let expected = ChunkIter::new(&chunker, &data[..])
+        .map(|c| c.map(|c| (c.offset, c.length)))
+        .collect::<io::Result<Vec<_>>>()
+        .unwrap();
+
+    let mut stream = ChunkStream::new(&chunker, &data[..]);
+    let mut actual = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.unwrap();
+        actual.push((chunk.offset, chunk.length));
+    }
+
+    // The stream yields the same chunks as the iterator and then terminates
+    assert_eq!(actual, expected);
+    assert!(stream.is_terminated());
+}
+
+// --- Chunker Tests ---
+
+#[test]
+fn test_dyn_chunkers() {
+    use crate::{ae::AE, buzhash::BuzHash, fastcdc::FastCDC, fastcdc::Normal, fixed::FixedSize};
+    use crate::{mii::MII, pci::PCI, rabin::Rabin, seqcdc::SeqCDC};
+
+    let data = generate_noise_data(200_000);
+    let chunkers: Vec<Box<dyn Chunker>> = vec![
+        Box::new(FastCDC::new(4_069, 8_192, 16_384, Normal::Level2)),
+        Box::new(BuzHash::new(4_069, 8_192, 16_384)),
+        Box::new(Rabin::new(4_069, 8_192, 16_384)),
+        Box::new(AE::new(4_069, 8_192, 16_384)),
+        Box::new(MII::new(4_069, 8_192, 16_384)),
+        Box::new(SeqCDC::new(4_069, 8_192, 16_384)),
+        Box::new(PCI::new(4_069, 8_192, 16_384)),
+        Box::new(FixedSize::new(8_192)),
+    ];
+
+    // Every algorithm tiles the input through the same trait object
+    for chunker in &chunkers {
+        let mut offset = 0;
+        for chunk in chunker.boxed_chunks(Box::new(&data[..])) {
+            let chunk = chunk.unwrap();
+            assert_eq!(chunk.offset, offset);
+            assert!(chunk.length <= chunker.max_size());
+            offset += chunk.length as u64;
+        }
+        assert_eq!(offset, data.len() as u64);
+    }
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_dyn_async_chunkers() {
+    use crate::{buzhash::BuzHash, fastcdc::FastCDC, fastcdc::Normal};
+    use futures::StreamExt;
+
+    let data = generate_noise_data(200_000);
+    let chunkers: Vec<Box<dyn AsyncChunker + Send + Sync>> = vec![
+        Box::new(FastCDC::new(4_069, 8_192, 16_384, Normal::Level2)),
+        Box::new(BuzHash::new(4_069, 8_192, 16_384)),
+    ];
+
+    for chunker in &chunkers {
+        let expected: u64 = data.len() as u64;
+        let lengths: Vec<u64> = chunker
+            .boxed_stream(Box::new(&data[..]))
+            .map(|chunk| chunk.unwrap().length as u64)
+            .collect()
+            .await;
+
+        // Each boxed stream covers the whole input
+        assert_eq!(lengths.iter().sum::<u64>(), expected);
+        assert!(
+            lengths
+                .iter()
+                .all(|&len| len as usize <= chunker.max_size())
+        );
+    }
+
+    // The streams can be sent to another task
+    let chunker: std::sync::Arc<dyn AsyncChunker + Send + Sync> =
+        std::sync::Arc::new(BuzHash::new(4_069, 8_192, 16_384));
+    let count = tokio::spawn(async move {
+        chunker
+            .boxed_stream(Box::new(io::Cursor::new(vec![7u8; 100_000])))
+            .count()
+            .await
+    })
+    .await
+    .unwrap();
+    assert!(count > 0);
+}
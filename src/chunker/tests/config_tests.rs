@@ -0,0 +1,100 @@
+use super::*;
+
+// --- Algorithm Tests ---
+
+#[test]
+fn test_algorithm_names() {
+    // Names round-trip through Display and FromStr, ignoring case
+    for name in [
+        "fastcdc",
+        "fastcdc32",
+        "buzhash",
+        "rabin",
+        "ae",
+        "mii",
+        "seqcdc",
+        "pci",
+        "fixed",
+    ] {
+        let algorithm: Algorithm = name.parse().unwrap();
+        assert_eq!(algorithm.to_string(), name);
+    }
+    assert_eq!("FastCDC".parse::<Algorithm>().unwrap(), Algorithm::FastCDC);
+
+    // Unknown names are rejected
+    let err = "gear".parse::<Algorithm>().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+// --- Config Tests ---
+
+#[test]
+fn test_build_every_algorithm() {
+    let data = vec![7u8; 200_000];
+
+    for name in [
+        "fastcdc",
+        "fastcdc32",
+        "buzhash",
+        "rabin",
+        "ae",
+        "mii",
+        "seqcdc",
+        "pci",
+        "fixed",
+    ] {
+        let config = ChunkerConfig {
+            algorithm: name.parse().unwrap(),
+            ..ChunkerConfig::default()
+        };
+        let chunker = config.build().unwrap();
+
+        // Every algorithm chunks the whole input within the configured maximum
+        let lengths: Vec<usize> = chunker
+            .boxed_chunks(Box::new(&data[..]))
+            .map(|c| c.unwrap().length)
+            .collect();
+        assert_eq!(lengths.iter().sum::<usize>(), data.len(), "{}", name);
+        assert!(
+            lengths.iter().all(|&len| len <= config.max_size),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_fastcdc_conversion() {
+    let config = ChunkerConfig {
+        seed: Some(7),
+        ..ChunkerConfig::default()
+    };
+
+    // The conversion applies the sizes and the seed
+    let chunker = FastCDC::try_from(&config).unwrap();
+    let expected = FastCDC::new(2_048, 8_192, 65_536, Normal::Level2).with_seed(7);
+    assert_eq!(chunker.masks(), expected.masks());
+    assert_eq!(chunker.avg_size(), 8_192);
+
+    // Other algorithms and invalid sizes are rejected
+    let buzhash = ChunkerConfig {
+        algorithm: Algorithm::BuzHash,
+        ..ChunkerConfig::default()
+    };
+    let unordered = ChunkerConfig {
+        min_size: 65_536,
+        max_size: 2_048,
+        ..ChunkerConfig::default()
+    };
+    for config in [buzhash.clone(), unordered] {
+        let err = FastCDC::try_from(config).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    // Seeds are only accepted for FastCDC
+    let seeded = ChunkerConfig {
+        seed: Some(7),
+        ..buzhash
+    };
+    assert!(seeded.build().is_err());
+}
@@ -0,0 +1,25 @@
+///
+/// Estimates the Shannon entropy of `data` in bits per byte (`0.0` ~ `8.0`).
+///
+/// Random, encrypted, and already-compressed data (JPEG, video) scores close to `8.0`.
+///
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = [0u32; 256];
+    for &byte in data {
+        counts[byte as usize] += 1;
+    }
+
+    let len = data.len() as f64;
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
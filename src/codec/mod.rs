@@ -0,0 +1,178 @@
+//!
+//! Pluggable compression codecs with content-size framing.
+//!
+//! A frame is the codec ID (`u8`), the uncompressed length (`u64`, little-endian),
+//! and the codec payload. Recording the length lets decoders allocate up front and
+//! detect truncated or corrupt payloads regardless of the codec.
+//!
+
+mod entropy;
+mod stored;
+
+pub use entropy::shannon_entropy;
+pub use stored::Stored;
+
+use std::io;
+
+/// Length of the frame header written by [`encode_frame`].
+pub const FRAME_HEADER_LEN: usize = 9;
+
+/// Largest uncompressed length accepted by [`decode_frame`], that of the largest chunk.
+pub const MAX_FRAME_LEN: usize = 1_073_741_824; // 1 GB
+
+/// Number of leading bytes sampled by [`encode_frame_adaptive`].
+pub const ENTROPY_SAMPLE_LEN: usize = 4_096;
+/// Sampled entropy (bits per byte) above which data is stored uncompressed.
+pub const INCOMPRESSIBLE_ENTROPY: f64 = 7.5;
+
+/// A compression codec usable wherever chunk data is stored.
+pub trait Codec: Send + Sync {
+    /// Returns the identifier recorded in frames produced with this codec.
+    fn id(&self) -> u8;
+
+    ///
+    /// Compresses `data`, appending the payload to `out`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the error raised by the codec.
+    ///
+    fn compress(&self, data: &[u8], out: &mut Vec<u8>) -> io::Result<()>;
+
+    ///
+    /// Decompresses a payload of `size` uncompressed bytes, appending them to `out`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidData` if the payload is corrupt.
+    ///
+    fn decompress(&self, payload: &[u8], size: usize, out: &mut Vec<u8>) -> io::Result<()>;
+}
+
+///
+/// Compresses `data` into a self-describing frame.
+///
+/// ## Arguments
+///
+/// * `codec`: The codec to compress with.
+/// * `data`: The uncompressed data.
+/// * `out`: The buffer the frame is appended to.
+///
+pub fn encode_frame(codec: &dyn Codec, data: &[u8], out: &mut Vec<u8>) -> io::Result<()> {
+    out.push(codec.id());
+    out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    codec.compress(data, out)
+}
+
+///
+/// Compresses `data` into a frame, storing it uncompressed when compression won't pay off.
+///
+/// The first `ENTROPY_SAMPLE_LEN` bytes are sampled first, and data whose entropy exceeds
+/// `INCOMPRESSIBLE_ENTROPY` is stored without invoking `codec`. Otherwise the data is
+/// compressed, and still stored if the payload turns out no smaller than the input.
+/// The decision is recorded in the frame's codec ID, so decoders must also accept [`Stored`].
+///
+/// Returns whether the frame was compressed with `codec`.
+///
+/// ## Arguments
+///
+/// * `codec`: The codec to compress with.
+/// * `data`: The uncompressed data.
+/// * `out`: The buffer the frame is appended to.
+///
+pub fn encode_frame_adaptive(
+    codec: &dyn Codec,
+    data: &[u8],
+    out: &mut Vec<u8>,
+) -> io::Result<bool> {
+    let sample = &data[..data.len().min(ENTROPY_SAMPLE_LEN)];
+
+    if shannon_entropy(sample) <= INCOMPRESSIBLE_ENTROPY {
+        let start = out.len();
+        encode_frame(codec, data, out)?;
+
+        if out.len() - start < FRAME_HEADER_LEN + data.len() {
+            return Ok(true);
+        }
+        out.truncate(start);
+    }
+
+    encode_frame(&Stored, data, out)?;
+    Ok(false)
+}
+
+///
+/// Decompresses a frame produced by [`encode_frame`] or [`encode_frame_adaptive`].
+///
+/// Frames recording more than [`MAX_FRAME_LEN`] uncompressed bytes are rejected before
+/// anything is allocated; see [`decode_frame_with_limit`] for a tighter bound.
+///
+/// ## Arguments
+///
+/// * `codecs`: The codecs that may have produced the frame, looked up by ID.
+/// * `frame`: The framed data.
+///
+/// ## Errors
+///
+/// Returns an `std::io::Error` with `ErrorKind::InvalidData` if the frame is truncated, uses
+/// an unknown codec, records too large a length, or does not decompress to the recorded length.
+///
+pub fn decode_frame(codecs: &[&dyn Codec], frame: &[u8]) -> io::Result<Vec<u8>> {
+    decode_frame_with_limit(codecs, frame, MAX_FRAME_LEN)
+}
+
+///
+/// Decompresses a frame, rejecting it if it records more than `max_len` uncompressed bytes.
+///
+/// The recorded length comes from the frame itself, so it is checked before the output is
+/// allocated; a corrupt or hostile header cannot make the decoder allocate more than
+/// `max_len`.
+///
+/// ## Arguments
+///
+/// * `codecs`: The codecs that may have produced the frame, looked up by ID.
+/// * `frame`: The framed data.
+/// * `max_len`: The largest uncompressed length accepted, e.g. the chunker's `max_size`.
+///
+/// ## Errors
+///
+/// Returns an `std::io::Error` with `ErrorKind::InvalidData` if the frame is truncated, uses
+/// an unknown codec, records too large a length, or does not decompress to the recorded length.
+///
+pub fn decode_frame_with_limit(
+    codecs: &[&dyn Codec],
+    frame: &[u8],
+    max_len: usize,
+) -> io::Result<Vec<u8>> {
+    if frame.len() < FRAME_HEADER_LEN {
+        return Err(invalid("truncated frame header"));
+    }
+
+    let codec = codecs
+        .iter()
+        .find(|c| c.id() == frame[0])
+        .ok_or_else(|| invalid("unknown codec"))?;
+    let size = u64::from_le_bytes(frame[1..FRAME_HEADER_LEN].try_into().unwrap());
+    let size = usize::try_from(size)
+        .ok()
+        .filter(|&size| size <= max_len)
+        .ok_or_else(|| invalid("frame too large"))?;
+
+    let payload = &frame[FRAME_HEADER_LEN..];
+    let mut out = Vec::with_capacity(size);
+    codec.decompress(payload, size, &mut out)?;
+
+    if out.len() != size {
+        return Err(invalid("decompressed length does not match the frame"));
+    }
+
+    Ok(out)
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+#[path = "tests/codec_tests.rs"]
+mod tests;
@@ -0,0 +1,38 @@
+use super::Codec;
+use std::io;
+
+///
+/// The identity codec, storing data uncompressed.
+///
+/// Useful for data that is already compressed or encrypted, where another
+/// codec would only spend CPU time.
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stored;
+
+impl Stored {
+    /// The identifier of the stored codec.
+    pub const ID: u8 = 0;
+}
+
+impl Codec for Stored {
+    fn id(&self) -> u8 {
+        Self::ID
+    }
+
+    fn compress(&self, data: &[u8], out: &mut Vec<u8>) -> io::Result<()> {
+        out.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn decompress(&self, payload: &[u8], size: usize, out: &mut Vec<u8>) -> io::Result<()> {
+        if payload.len() != size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stored payload length does not match the frame",
+            ));
+        }
+        out.extend_from_slice(payload);
+        Ok(())
+    }
+}
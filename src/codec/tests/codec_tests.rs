@@ -0,0 +1,162 @@
+use super::*;
+
+/// A toy run-length codec, standing in for a real compressor.
+struct RunLength;
+
+impl Codec for RunLength {
+    fn id(&self) -> u8 {
+        0x7f
+    }
+
+    fn compress(&self, data: &[u8], out: &mut Vec<u8>) -> io::Result<()> {
+        for run in data.chunk_by(|a, b| a == b) {
+            for part in run.chunks(255) {
+                out.extend_from_slice(&[part.len() as u8, part[0]]);
+            }
+        }
+        Ok(())
+    }
+
+    fn decompress(&self, payload: &[u8], _size: usize, out: &mut Vec<u8>) -> io::Result<()> {
+        for pair in payload.chunks(2) {
+            let [len, byte] = pair else {
+                return Err(invalid("odd payload"));
+            };
+            out.extend(std::iter::repeat_n(*byte, *len as usize));
+        }
+        Ok(())
+    }
+}
+
+// --- Framing Tests ---
+
+#[test]
+fn test_stored_round_trip() {
+    let data = b"LOREMIPSUMDOLOR".repeat(100);
+
+    let mut frame = Vec::new();
+    encode_frame(&Stored, &data, &mut frame).unwrap();
+
+    // The header records the codec and the uncompressed length
+    assert_eq!(frame[0], Stored::ID);
+    assert_eq!(&frame[1..9], &(data.len() as u64).to_le_bytes());
+    assert_eq!(decode_frame(&[&Stored], &frame).unwrap(), data);
+}
+
+#[test]
+fn test_codec_lookup() {
+    let data = vec![7u8; 1_000];
+
+    let mut frame = Vec::new();
+    encode_frame(&RunLength, &data, &mut frame).unwrap();
+
+    // The frame must be routed to the codec that produced it
+    assert!(frame.len() < data.len(), "Payload should be compressed");
+    assert_eq!(decode_frame(&[&Stored, &RunLength], &frame).unwrap(), data);
+
+    // An unregistered codec cannot be decoded
+    let err = decode_frame(&[&Stored], &frame).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+// --- Adaptive Tests ---
+
+fn noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+#[test]
+fn test_entropy_estimate() {
+    // Constant data carries no information; uniform bytes carry eight bits each
+    assert_eq!(shannon_entropy(&[0u8; 100]), 0.0);
+    let uniform: Vec<u8> = (0..=255).collect();
+    assert!((shannon_entropy(&uniform) - 8.0).abs() < 1e-9);
+    assert!(shannon_entropy(&noise(ENTROPY_SAMPLE_LEN)) > INCOMPRESSIBLE_ENTROPY);
+}
+
+#[test]
+fn test_adaptive_compresses_redundant_data() {
+    let data = vec![7u8; 10_000];
+
+    let mut frame = Vec::new();
+    let compressed = encode_frame_adaptive(&RunLength, &data, &mut frame).unwrap();
+
+    // Low-entropy data goes through the codec
+    assert!(compressed);
+    assert_eq!(frame[0], RunLength.id());
+    assert_eq!(decode_frame(&[&Stored, &RunLength], &frame).unwrap(), data);
+}
+
+#[test]
+fn test_adaptive_skips_noise() {
+    let data = noise(10_000);
+
+    let mut frame = Vec::new();
+    let compressed = encode_frame_adaptive(&RunLength, &data, &mut frame).unwrap();
+
+    // High-entropy data is stored, and the choice is visible in the frame
+    assert!(!compressed);
+    assert_eq!(frame[0], Stored::ID);
+    assert_eq!(frame.len(), FRAME_HEADER_LEN + data.len());
+    assert_eq!(decode_frame(&[&Stored, &RunLength], &frame).unwrap(), data);
+}
+
+#[test]
+fn test_adaptive_falls_back_when_payload_grows() {
+    // Low entropy, but no runs: run-length encoding doubles the size
+    let data = b"ab".repeat(5_000);
+
+    let mut frame = vec![0xaa];
+    let compressed = encode_frame_adaptive(&RunLength, &data, &mut frame).unwrap();
+
+    // The trial output is discarded without disturbing existing buffer contents
+    assert!(!compressed);
+    assert_eq!(frame[0], 0xaa);
+    assert_eq!(frame[1], Stored::ID);
+    assert_eq!(frame.len(), 1 + FRAME_HEADER_LEN + data.len());
+}
+
+// --- Error Tests ---
+
+#[test]
+fn test_corrupt_frames() {
+    let mut frame = Vec::new();
+    encode_frame(&Stored, b"hello", &mut frame).unwrap();
+
+    // Truncated header
+    assert!(decode_frame(&[&Stored], &frame[..4]).is_err());
+    // Truncated payload
+    assert!(decode_frame(&[&Stored], &frame[..frame.len() - 1]).is_err());
+
+    let mut frame = Vec::new();
+    encode_frame(&RunLength, b"aaab", &mut frame).unwrap();
+    frame[1] += 1;
+
+    // A payload that does not expand to the recorded size is rejected
+    let err = decode_frame(&[&RunLength], &frame).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_oversized_frame_header() {
+    let mut frame = Vec::new();
+    encode_frame(&Stored, b"hello", &mut frame).unwrap();
+
+    // A header claiming far more data than any chunk is rejected before allocating
+    frame[1..FRAME_HEADER_LEN].copy_from_slice(&(1u64 << 40).to_le_bytes());
+    let err = decode_frame(&[&Stored], &frame).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "frame too large");
+
+    // A configured limit tightens the bound
+    let mut frame = Vec::new();
+    encode_frame(&RunLength, &[7; 1_000], &mut frame).unwrap();
+    assert_eq!(
+        decode_frame_with_limit(&[&RunLength], &frame, 1_000).unwrap(),
+        [7; 1_000]
+    );
+    let err = decode_frame_with_limit(&[&RunLength], &frame, 999).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,123 @@
+use super::rng::SplitMix64;
+
+/// A single edit applied to a byte buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// Inserts `len` fresh bytes at `offset`.
+    Insert { offset: usize, len: usize },
+    /// Removes `len` bytes starting at `offset`.
+    Delete { offset: usize, len: usize },
+    /// Overwrites `len` bytes starting at `offset` in place.
+    Rewrite { offset: usize, len: usize },
+}
+
+/// The kinds of edits a generated script may contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditPattern {
+    /// Only insertions, which shift all following data.
+    Insertions,
+    /// Only deletions, which shift all following data.
+    Deletions,
+    /// Only in-place rewrites, which keep offsets stable.
+    Rewrites,
+    /// An even mix of all three.
+    Mixed,
+}
+
+///
+/// Generates a reproducible script of `count` edits, each at most `max_len` bytes,
+/// for a buffer of `len` bytes.
+///
+/// Edits are meant to be applied in order with [`apply_edits`]; offsets account for the
+/// length changes of earlier edits.
+///
+/// ## Arguments
+///
+/// * `seed`: The seed of the generator.
+/// * `len`: The length of the buffer the script is applied to.
+/// * `pattern`: The kinds of edits to generate.
+/// * `count`: The number of edits.
+/// * `max_len`: The maximum length of a single edit.
+///
+pub fn edit_script(
+    seed: u64,
+    len: usize,
+    pattern: EditPattern,
+    count: usize,
+    max_len: usize,
+) -> Vec<Edit> {
+    let mut rng = SplitMix64::new(seed);
+    let mut len = len;
+    let mut script = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let kind = match pattern {
+            EditPattern::Insertions => 0,
+            EditPattern::Deletions => 1,
+            EditPattern::Rewrites => 2,
+            EditPattern::Mixed => rng.below(3),
+        };
+        let edit_len = rng.range(1, max_len.max(1));
+
+        let edit = match kind {
+            0 => Edit::Insert {
+                offset: rng.range(0, len),
+                len: edit_len,
+            },
+            _ if len == 0 => continue,
+            1 => {
+                let edit_len = edit_len.min(len);
+                Edit::Delete {
+                    offset: rng.range(0, len - edit_len),
+                    len: edit_len,
+                }
+            }
+            _ => {
+                let edit_len = edit_len.min(len);
+                Edit::Rewrite {
+                    offset: rng.range(0, len - edit_len),
+                    len: edit_len,
+                }
+            }
+        };
+
+        len = match edit {
+            Edit::Insert { len: n, .. } => len + n,
+            Edit::Delete { len: n, .. } => len - n,
+            Edit::Rewrite { .. } => len,
+        };
+        script.push(edit);
+    }
+
+    script
+}
+
+///
+/// Applies `edits` in order, filling inserted and rewritten bytes from `seed`.
+///
+/// ## Panics
+///
+/// Panics if an edit lies outside the buffer as it stands when the edit is applied.
+///
+pub fn apply_edits(data: &[u8], edits: &[Edit], seed: u64) -> Vec<u8> {
+    let mut rng = SplitMix64::new(seed);
+    let mut out = data.to_vec();
+
+    for edit in edits {
+        match *edit {
+            Edit::Insert { offset, len } => {
+                let mut fresh = vec![0u8; len];
+                rng.fill(&mut fresh, 256);
+                out.splice(offset..offset, fresh);
+            }
+            Edit::Delete { offset, len } => {
+                out.drain(offset..offset + len);
+            }
+            Edit::Rewrite { offset, len } => {
+                rng.fill(&mut out[offset..offset + len], 256);
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,114 @@
+use super::edit::{Edit, apply_edits};
+use crate::digest::Sha256;
+use crate::fastcdc::FastCDC;
+use std::collections::HashSet;
+
+/// The outcome of chunking a baseline and its edited version with one configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditReport {
+    /// The number of chunks in the baseline.
+    pub baseline_chunks: usize,
+    /// The number of chunks in the edited version.
+    pub edited_chunks: usize,
+    /// The number of baseline chunks that also occur in the edited version.
+    pub retained_chunks: usize,
+    /// The bytes of edited chunks missing from the baseline, counted once per distinct chunk.
+    pub transferred_bytes: u64,
+    /// The length of the edited version.
+    pub edited_len: u64,
+}
+
+impl EditReport {
+    /// Returns the percentage of baseline chunks retained by the edited version.
+    pub fn retained_percent(&self) -> f64 {
+        if self.baseline_chunks == 0 {
+            return 100.0;
+        }
+        self.retained_chunks as f64 * 100.0 / self.baseline_chunks as f64
+    }
+}
+
+///
+/// Measures how well chunking configurations preserve chunks across synthetic edits.
+///
+/// The edit script is applied once; every configuration passed to
+/// [`EditEvaluator::evaluate`] then chunks the same baseline and edited version.
+///
+#[derive(Debug, Clone)]
+pub struct EditEvaluator {
+    baseline: Vec<u8>,
+    edited: Vec<u8>,
+}
+
+impl EditEvaluator {
+    ///
+    /// Constructs an evaluator by applying `edits` to `baseline`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `baseline`: The original data.
+    /// * `edits`: The edit script, e.g. from [`edit_script`](super::edit_script).
+    /// * `seed`: The seed for inserted and rewritten bytes.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if an edit lies outside the buffer, as with [`apply_edits`].
+    ///
+    pub fn new(baseline: Vec<u8>, edits: &[Edit], seed: u64) -> Self {
+        let edited = apply_edits(&baseline, edits, seed);
+        Self { baseline, edited }
+    }
+
+    /// Returns the original data.
+    pub fn baseline(&self) -> &[u8] {
+        &self.baseline
+    }
+
+    /// Returns the edited data.
+    pub fn edited(&self) -> &[u8] {
+        &self.edited
+    }
+
+    ///
+    /// Chunks both versions with `chunker` and compares them.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The configuration to evaluate.
+    ///
+    pub fn evaluate(&self, chunker: &FastCDC) -> EditReport {
+        let baseline = digests(chunker, &self.baseline);
+        let edited = digests(chunker, &self.edited);
+
+        let baseline_set: HashSet<_> = baseline.iter().map(|(digest, _)| *digest).collect();
+        let edited_set: HashSet<_> = edited.iter().map(|(digest, _)| *digest).collect();
+
+        let mut sent = HashSet::new();
+        let transferred_bytes = edited
+            .iter()
+            .filter(|(digest, _)| !baseline_set.contains(digest) && sent.insert(*digest))
+            .map(|(_, length)| *length as u64)
+            .sum();
+
+        EditReport {
+            baseline_chunks: baseline.len(),
+            edited_chunks: edited.len(),
+            retained_chunks: baseline
+                .iter()
+                .filter(|(digest, _)| edited_set.contains(digest))
+                .count(),
+            transferred_bytes,
+            edited_len: self.edited.len() as u64,
+        }
+    }
+}
+
+fn digests(chunker: &FastCDC, data: &[u8]) -> Vec<([u8; 32], usize)> {
+    chunker
+        .chunks(data)
+        .map(|chunk| {
+            let chunk = chunk.expect("reading from a slice cannot fail");
+            (Sha256::digest(&chunk.data), chunk.length)
+        })
+        .collect()
+}
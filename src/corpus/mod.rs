@@ -0,0 +1,118 @@
+//!
+//! Reproducible synthetic datasets for evaluating and benchmarking chunking.
+//!
+//! A [`Corpus`] is generated from a seed with a controllable duplication ratio and
+//! entropy, and edit scripts derive modified versions of it, so configurations can be
+//! compared without shipping private data. An [`EditEvaluator`] reports how many chunks
+//! each configuration retains across those edits.
+//!
+
+mod edit;
+#[cfg(feature = "fastcdc")]
+mod eval;
+mod rng;
+
+pub use edit::{Edit, EditPattern, apply_edits, edit_script};
+#[cfg(feature = "fastcdc")]
+pub use eval::{EditEvaluator, EditReport};
+
+pub(crate) use rng::SplitMix64;
+
+/// Minimum length of a generated segment.
+const SEGMENT_MIN: usize = 16_384; // 16 KB
+/// Maximum length of a generated segment.
+const SEGMENT_MAX: usize = 262_144; // 256 KB
+
+///
+/// A synthetic dataset generator.
+///
+/// Data is produced in segments of 16 KiB ~ 256 KiB, large enough to span several
+/// chunks. Each segment is either fresh random data or, with the configured probability,
+/// a copy of an earlier part of the output.
+///
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    seed: u64,
+    len: usize,
+    duplication: f64,
+    entropy: f64,
+}
+
+impl Corpus {
+    ///
+    /// Constructs a generator for `len` bytes of unique, full-entropy data.
+    ///
+    /// ## Arguments
+    ///
+    /// * `seed`: The seed; equal seeds and settings always produce equal data.
+    /// * `len`: The length of the dataset.
+    ///
+    pub fn new(seed: u64, len: usize) -> Self {
+        Self {
+            seed,
+            len,
+            duplication: 0.0,
+            entropy: 8.0,
+        }
+    }
+
+    ///
+    /// Sets the fraction of segments copied from earlier in the dataset.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `ratio` is not within `0.0 ~ 1.0`.
+    ///
+    pub fn with_duplication(mut self, ratio: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&ratio),
+            "ratio must be between 0 and 1"
+        );
+        self.duplication = ratio;
+        self
+    }
+
+    ///
+    /// Sets the entropy of fresh data in bits per byte.
+    ///
+    /// Fresh bytes are drawn uniformly from an alphabet of `2^bits` symbols, rounded
+    /// to the nearest whole symbol count.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `bits` is not within `0.0 ~ 8.0`.
+    ///
+    pub fn with_entropy(mut self, bits: f64) -> Self {
+        assert!((0.0..=8.0).contains(&bits), "bits must be between 0 and 8");
+        self.entropy = bits;
+        self
+    }
+
+    /// Generates the dataset.
+    pub fn generate(&self) -> Vec<u8> {
+        let mut rng = SplitMix64::new(self.seed);
+        let alphabet = (2f64.powf(self.entropy).round() as u64).clamp(1, 256);
+        let mut out = Vec::with_capacity(self.len);
+
+        while out.len() < self.len {
+            let segment = rng
+                .range(SEGMENT_MIN, SEGMENT_MAX)
+                .min(self.len - out.len());
+            let start = out.len();
+
+            if start >= segment && rng.unit() < self.duplication {
+                let src = rng.range(0, start - segment);
+                out.extend_from_within(src..src + segment);
+            } else {
+                out.resize(start + segment, 0);
+                rng.fill(&mut out[start..], alphabet);
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/corpus_tests.rs"]
+mod tests;
@@ -0,0 +1,44 @@
+///
+/// The SplitMix64 generator: tiny, fast, and stable across platforms and releases,
+/// which keeps seeded corpora reproducible.
+///
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..bound` (`bound` must be non-zero).
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        // Multiply-shift reduction; the bias is negligible for corpus generation.
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// Returns a value in `low..=high`.
+    pub(crate) fn range(&mut self, low: usize, high: usize) -> usize {
+        low + self.below((high - low) as u64 + 1) as usize
+    }
+
+    /// Returns a value in `0.0..1.0`.
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub(crate) fn fill(&mut self, buf: &mut [u8], alphabet: u64) {
+        for byte in buf {
+            *byte = self.below(alphabet) as u8;
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use super::*;
+use crate::codec::shannon_entropy;
+
+// --- Generation Tests ---
+
+#[test]
+fn test_reproducible() {
+    let corpus = Corpus::new(42, 500_000).with_duplication(0.3);
+
+    // Equal seeds produce equal data; different seeds do not
+    assert_eq!(corpus.generate(), corpus.generate());
+    assert_ne!(
+        corpus.generate(),
+        Corpus::new(43, 500_000).with_duplication(0.3).generate()
+    );
+    assert_eq!(corpus.generate().len(), 500_000);
+}
+
+#[test]
+fn test_entropy_control() {
+    let low = Corpus::new(1, 100_000).with_entropy(2.0).generate();
+    let high = Corpus::new(1, 100_000).generate();
+
+    // Fresh bytes follow the requested alphabet size
+    assert!((shannon_entropy(&low) - 2.0).abs() < 0.01);
+    assert!(shannon_entropy(&high) > 7.99);
+    assert!(low.iter().all(|&b| b < 4));
+}
+
+#[cfg(feature = "fastcdc")]
+#[test]
+fn test_duplication_control() {
+    use crate::fastcdc::{FastCDC, Normal};
+    use std::collections::HashSet;
+
+    // Fraction of bytes in chunks whose content appeared earlier
+    let repeated = |data: &[u8]| {
+        let chunker = FastCDC::new(2_048, 8_192, 65_536, Normal::Level2);
+        let mut seen = HashSet::new();
+        let duplicate: usize = chunker
+            .chunks(data)
+            .map(|c| c.unwrap())
+            .filter(|c| !seen.insert(c.data.to_vec()))
+            .map(|c| c.length)
+            .sum();
+        duplicate as f64 / data.len() as f64
+    };
+
+    let unique = Corpus::new(7, 4_000_000).generate();
+    let duplicated = Corpus::new(7, 4_000_000).with_duplication(0.5).generate();
+
+    // Fresh data never repeats; copied segments dedup at roughly the requested ratio
+    assert_eq!(repeated(&unique), 0.0, "Unique corpus must not repeat");
+    let ratio = repeated(&duplicated);
+    assert!(
+        (0.3..0.6).contains(&ratio),
+        "Unexpected duplication ratio {}",
+        ratio
+    );
+}
+
+// --- Edit Tests ---
+
+#[test]
+fn test_edit_scripts() {
+    let data = Corpus::new(3, 100_000).generate();
+
+    let inserts = edit_script(9, data.len(), EditPattern::Insertions, 10, 100);
+    let deletes = edit_script(9, data.len(), EditPattern::Deletions, 10, 100);
+    let rewrites = edit_script(9, data.len(), EditPattern::Rewrites, 10, 100);
+
+    let added: usize = inserts
+        .iter()
+        .map(|e| match e {
+            Edit::Insert { len, .. } => *len,
+            _ => panic!("Unexpected edit kind"),
+        })
+        .sum();
+
+    // Each pattern changes the length as its edits dictate
+    assert_eq!(apply_edits(&data, &inserts, 0).len(), data.len() + added);
+    assert!(apply_edits(&data, &deletes, 0).len() < data.len());
+    assert_eq!(apply_edits(&data, &rewrites, 0).len(), data.len());
+
+    let mixed = edit_script(9, data.len(), EditPattern::Mixed, 50, 1_000);
+
+    // Scripts stay within bounds as the buffer changes, and are reproducible
+    assert_eq!(
+        mixed,
+        edit_script(9, data.len(), EditPattern::Mixed, 50, 1_000)
+    );
+    assert_eq!(apply_edits(&data, &mixed, 5), apply_edits(&data, &mixed, 5));
+}
+
+// --- Evaluator Tests ---
+
+#[cfg(feature = "fastcdc")]
+#[test]
+fn test_evaluator_no_edits() {
+    use crate::fastcdc::{FastCDC, Normal};
+
+    let evaluator = EditEvaluator::new(Corpus::new(11, 500_000).generate(), &[], 0);
+    let report = evaluator.evaluate(&FastCDC::new(2_048, 8_192, 65_536, Normal::Level2));
+
+    // Unchanged data keeps every chunk and transfers nothing
+    assert_eq!(report.retained_chunks, report.baseline_chunks);
+    assert_eq!(report.edited_chunks, report.baseline_chunks);
+    assert_eq!(report.transferred_bytes, 0);
+    assert_eq!(report.retained_percent(), 100.0);
+}
+
+#[cfg(feature = "fastcdc")]
+#[test]
+fn test_evaluator_with_edits() {
+    use crate::fastcdc::{FastCDC, Normal};
+
+    let data = Corpus::new(11, 1_000_000).generate();
+    let edits = edit_script(4, data.len(), EditPattern::Mixed, 5, 64);
+    let evaluator = EditEvaluator::new(data, &edits, 4);
+
+    let chunker = FastCDC::new(2_048, 8_192, 65_536, Normal::Level2);
+    let report = evaluator.evaluate(&chunker);
+
+    // A handful of small edits keeps most chunks and transfers only a small fraction
+    assert!(
+        report.retained_percent() > 80.0,
+        "Retained {}%",
+        report.retained_percent()
+    );
+    assert!(report.transferred_bytes > 0);
+    assert!(report.transferred_bytes < report.edited_len / 4);
+    assert_eq!(report.edited_len, evaluator.edited().len() as u64);
+
+    // Near fixed-size chunking (min ≈ max) loses far more chunks to shifting edits
+    let fixed = evaluator.evaluate(&FastCDC::new(8_190, 8_191, 8_192, Normal::Level2));
+    assert!(fixed.retained_chunks < report.retained_chunks);
+}
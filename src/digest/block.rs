@@ -0,0 +1,68 @@
+pub(super) const BLOCK_LEN: usize = 64;
+
+/// Buffers input into 64-byte blocks for Merkle–Damgård hash functions.
+#[derive(Debug, Clone)]
+pub(super) struct BlockBuffer {
+    block: [u8; BLOCK_LEN],
+    len: usize,
+    total_len: u64,
+}
+
+impl BlockBuffer {
+    pub(super) const fn new() -> Self {
+        Self {
+            block: [0; BLOCK_LEN],
+            len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Feeds `data`, calling `compress` for every completed block.
+    pub(super) fn update(&mut self, mut data: &[u8], mut compress: impl FnMut(&[u8; BLOCK_LEN])) {
+        self.total_len = self.total_len.wrapping_add(data.len() as u64);
+
+        if self.len > 0 {
+            let take = (BLOCK_LEN - self.len).min(data.len());
+            self.block[self.len..self.len + take].copy_from_slice(&data[..take]);
+            self.len += take;
+            data = &data[take..];
+
+            if self.len < BLOCK_LEN {
+                return;
+            }
+
+            compress(&self.block);
+            self.len = 0;
+        }
+
+        let mut blocks = data.chunks_exact(BLOCK_LEN);
+        for block in &mut blocks {
+            compress(block.try_into().unwrap());
+        }
+
+        let rest = blocks.remainder();
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+    }
+
+    /// Appends the standard padding: a single 1 bit, zeros, then the message length in bits.
+    pub(super) fn pad(&mut self, big_endian: bool, compress: impl FnMut(&[u8; BLOCK_LEN])) {
+        let bit_len = self.total_len.wrapping_mul(8);
+
+        let mut padding = [0u8; BLOCK_LEN + 8];
+        padding[0] = 0x80;
+        let pad_len = if self.len < 56 {
+            56 - self.len
+        } else {
+            120 - self.len
+        };
+        let len_bytes = if big_endian {
+            bit_len.to_be_bytes()
+        } else {
+            bit_len.to_le_bytes()
+        };
+        padding[pad_len..pad_len + 8].copy_from_slice(&len_bytes);
+
+        self.update(&padding[..pad_len + 8], compress);
+    }
+}
@@ -0,0 +1,103 @@
+use super::Sha256;
+use super::block::BLOCK_LEN;
+
+/// Length of an HMAC-SHA256 tag in bytes.
+const TAG_LEN: usize = 32;
+
+///
+/// An incremental HMAC-SHA256 (RFC 2104) message authenticator.
+///
+/// ## Example
+///
+/// ```
+/// use clast::digest::HmacSha256;
+///
+/// let mut mac = HmacSha256::new(b"key");
+/// mac.update(b"hello ");
+/// mac.update(b"world");
+/// assert_eq!(mac.finalize(), HmacSha256::mac(b"key", b"hello world"));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    /// Constructs a new authenticator keyed with `key`.
+    pub fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; BLOCK_LEN];
+        if key.len() > BLOCK_LEN {
+            block[..TAG_LEN].copy_from_slice(&Sha256::digest(key));
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Sha256::new();
+        inner.update(&block.map(|b| b ^ 0x36));
+        let mut outer = Sha256::new();
+        outer.update(&block.map(|b| b ^ 0x5c));
+
+        Self { inner, outer }
+    }
+
+    /// Computes the tag of `data` under `key` in one call.
+    pub fn mac(key: &[u8], data: &[u8]) -> [u8; TAG_LEN] {
+        let mut mac = Self::new(key);
+        mac.update(data);
+        mac.finalize()
+    }
+
+    /// Feeds `data` into the authenticator.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Consumes the authenticator, returning the tag.
+    pub fn finalize(mut self) -> [u8; TAG_LEN] {
+        self.outer.update(&self.inner.finalize());
+        self.outer.finalize()
+    }
+}
+
+///
+/// Derives `okm.len()` bytes of key material with HKDF-SHA256 (RFC 5869).
+///
+/// ## Arguments
+///
+/// * `salt`: The optional salt; an empty slice uses the all-zero salt.
+/// * `ikm`: The input key material (the secret).
+/// * `info`: Context binding the output to its purpose.
+/// * `okm`: The buffer to fill with output key material.
+///
+/// ## Panics
+///
+/// Panics if `okm` is longer than 8,160 bytes (255 blocks).
+///
+pub fn hkdf_sha256(salt: &[u8], ikm: &[u8], info: &[u8], okm: &mut [u8]) {
+    assert!(
+        okm.len() <= 255 * TAG_LEN,
+        "HKDF output must be at most 8160 bytes"
+    );
+
+    let prk = HmacSha256::mac(salt, ikm);
+
+    let mut block = [0u8; TAG_LEN];
+    for (counter, out) in (1u8..).zip(okm.chunks_mut(TAG_LEN)) {
+        let mut mac = HmacSha256::new(&prk);
+        // Every block but the first chains on the previous one.
+        if counter > 1 {
+            mac.update(&block);
+        }
+        mac.update(info);
+        mac.update(&[counter]);
+        block = mac.finalize();
+
+        out.copy_from_slice(&block[..out.len()]);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/hmac_tests.rs"]
+mod tests;
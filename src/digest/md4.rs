@@ -0,0 +1,100 @@
+use super::block::{BLOCK_LEN, BlockBuffer};
+
+/// Length of an MD4 digest in bytes.
+const DIGEST_LEN: usize = 16;
+
+const ROUND2_ORDER: [usize; 16] = [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15];
+const ROUND3_ORDER: [usize; 16] = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+
+///
+/// An incremental MD4 hasher (RFC 1320).
+///
+/// MD4 is cryptographically broken; it exists here only because formats such as
+/// zsync use it for block checksums.
+///
+#[derive(Debug, Clone)]
+pub struct Md4 {
+    state: [u32; 4],
+    buffer: BlockBuffer,
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Md4 {
+    /// Constructs a new hasher.
+    pub fn new() -> Self {
+        Self {
+            state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476],
+            buffer: BlockBuffer::new(),
+        }
+    }
+
+    /// Computes the digest of `data` in one call.
+    pub fn digest(data: &[u8]) -> [u8; DIGEST_LEN] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Feeds `data` into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| compress(state, block));
+    }
+
+    /// Consumes the hasher, returning the digest.
+    pub fn finalize(mut self) -> [u8; DIGEST_LEN] {
+        let state = &mut self.state;
+        self.buffer.pad(false, |block| compress(state, block));
+
+        let mut out = [0u8; DIGEST_LEN];
+        for (dst, word) in out.chunks_exact_mut(4).zip(self.state) {
+            dst.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+fn compress(state: &mut [u32; 4], block: &[u8; BLOCK_LEN]) {
+    let mut x = [0u32; 16];
+    for (i, word) in block.chunks_exact(4).enumerate() {
+        x[i] = u32::from_le_bytes(word.try_into().unwrap());
+    }
+
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    // Each step updates one register, then the registers rotate roles (a, b, c, d) -> (d, a', b, c).
+    for i in 0..16 {
+        let f = (b & c) | (!b & d);
+        let t = a.wrapping_add(f).wrapping_add(x[i]);
+        (a, b, c, d) = (d, t.rotate_left([3, 7, 11, 19][i % 4]), b, c);
+    }
+    for (i, &k) in ROUND2_ORDER.iter().enumerate() {
+        let g = (b & c) | (b & d) | (c & d);
+        let t = a
+            .wrapping_add(g)
+            .wrapping_add(x[k])
+            .wrapping_add(0x5a827999);
+        (a, b, c, d) = (d, t.rotate_left([3, 5, 9, 13][i % 4]), b, c);
+    }
+    for (i, &k) in ROUND3_ORDER.iter().enumerate() {
+        let h = b ^ c ^ d;
+        let t = a
+            .wrapping_add(h)
+            .wrapping_add(x[k])
+            .wrapping_add(0x6ed9eba1);
+        (a, b, c, d) = (d, t.rotate_left([3, 9, 11, 15][i % 4]), b, c);
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/md4_tests.rs"]
+mod tests;
@@ -0,0 +1,51 @@
+//!
+//! Strong digests used to identify chunk contents.
+//!
+//! `Md4` and `Sha1` are provided only for interoperability with formats that
+//! mandate them (e.g. zsync) and must not be relied on for security.
+//! `HmacSha256` and `hkdf_sha256` derive keyed material from secrets.
+//!
+
+mod block;
+mod hmac;
+mod md4;
+mod sha1;
+mod sha256;
+
+pub use hmac::{HmacSha256, hkdf_sha256};
+pub use md4::Md4;
+pub use sha1::Sha1;
+pub use sha256::Sha256;
+
+/// A strong digest computed over a whole chunk at once.
+pub trait Digest {
+    /// The digest value.
+    type Output: Send + 'static;
+
+    /// Computes the digest of `data`.
+    fn digest(data: &[u8]) -> Self::Output;
+}
+
+impl Digest for Md4 {
+    type Output = [u8; 16];
+
+    fn digest(data: &[u8]) -> Self::Output {
+        Md4::digest(data)
+    }
+}
+
+impl Digest for Sha1 {
+    type Output = [u8; 20];
+
+    fn digest(data: &[u8]) -> Self::Output {
+        Sha1::digest(data)
+    }
+}
+
+impl Digest for Sha256 {
+    type Output = [u8; 32];
+
+    fn digest(data: &[u8]) -> Self::Output {
+        Sha256::digest(data)
+    }
+}
@@ -0,0 +1,54 @@
+use super::*;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// --- Test Vectors ---
+
+#[test]
+fn test_hmac_known_vectors() {
+    // RFC 4231 test cases 1 and 2
+    assert_eq!(
+        hex(&HmacSha256::mac(&[0x0b; 20], b"Hi There")),
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+    );
+    assert_eq!(
+        hex(&HmacSha256::mac(b"Jefe", b"what do ya want for nothing?")),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+
+    // Keys longer than a block are hashed first (RFC 4231 test case 6)
+    assert_eq!(
+        hex(&HmacSha256::mac(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        )),
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+    );
+}
+
+#[test]
+fn test_hkdf_known_vector() {
+    // RFC 5869 test case 1
+    let salt: Vec<u8> = (0x00..=0x0c).collect();
+    let info: Vec<u8> = (0xf0..=0xf9).collect();
+    let mut okm = [0u8; 42];
+    hkdf_sha256(&salt, &[0x0b; 22], &info, &mut okm);
+
+    assert_eq!(
+        hex(&okm),
+        "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+    );
+}
+
+#[test]
+fn test_hkdf_prefix() {
+    let mut short = [0u8; 40];
+    let mut long = [0u8; 100];
+    hkdf_sha256(b"", b"secret", b"info", &mut short);
+    hkdf_sha256(b"", b"secret", b"info", &mut long);
+
+    // Shorter outputs are prefixes of longer ones
+    assert_eq!(short[..], long[..40]);
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// --- Test Vectors ---
+
+#[test]
+fn test_known_vectors() {
+    // RFC 1320 test suite
+    let vectors: [(&[u8], &str); 5] = [
+        (b"", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+        (b"a", "bde52cb31de33e46245e05fbdbd6fb24"),
+        (b"abc", "a448017aaf21d8525fc10ae87aa6729d"),
+        (b"message digest", "d9130a8164549fe818874806e1c7014b"),
+        (
+            b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            "e33b4ddc9c38f2199c3e7b164fcc0536",
+        ),
+    ];
+
+    for (input, expected) in vectors {
+        assert_eq!(hex(&Md4::digest(input)), expected);
+    }
+}
+
+#[test]
+fn test_incremental_updates() {
+    let data = (0..300u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+    let expected = Md4::digest(&data);
+
+    let mut hasher = Md4::new();
+    for part in data.chunks(13) {
+        hasher.update(part);
+    }
+
+    assert_eq!(hasher.finalize(), expected);
+}
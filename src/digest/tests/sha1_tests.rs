@@ -0,0 +1,39 @@
+use super::*;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// --- Test Vectors ---
+
+#[test]
+fn test_known_vectors() {
+    // FIPS 180-4 examples and the empty message
+    assert_eq!(
+        hex(&Sha1::digest(b"")),
+        "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+    );
+    assert_eq!(
+        hex(&Sha1::digest(b"abc")),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+    assert_eq!(
+        hex(&Sha1::digest(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+    );
+}
+
+#[test]
+fn test_incremental_updates() {
+    let data = (0..300u32).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+    let expected = Sha1::digest(&data);
+
+    let mut hasher = Sha1::new();
+    for part in data.chunks(13) {
+        hasher.update(part);
+    }
+
+    assert_eq!(hasher.finalize(), expected);
+}
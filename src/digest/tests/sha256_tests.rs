@@ -0,0 +1,52 @@
+use super::*;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// --- Test Vectors ---
+
+#[test]
+fn test_known_vectors() {
+    // NIST FIPS 180-4 examples and the empty message
+    assert_eq!(
+        hex(&Sha256::digest(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        hex(&Sha256::digest(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex(&Sha256::digest(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+}
+
+#[test]
+fn test_million_a() {
+    let data = vec![b'a'; 1_000_000];
+
+    assert_eq!(
+        hex(&Sha256::digest(&data)),
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+    );
+}
+
+// --- Incremental Tests ---
+
+#[test]
+fn test_incremental_updates() {
+    let data = (0..1_000u32).map(|i| (i * 31) as u8).collect::<Vec<_>>();
+    let expected = Sha256::digest(&data);
+
+    // Every split point must produce the same digest as a one-shot call
+    for split in [0, 1, 55, 56, 63, 64, 65, 128, 999] {
+        let mut hasher = Sha256::new();
+        hasher.update(&data[..split]);
+        hasher.update(&data[split..]);
+        assert_eq!(hasher.finalize(), expected, "Mismatch at split {}", split);
+    }
+}
@@ -0,0 +1,111 @@
+//!
+//! The structured error returned when constructing a chunker.
+//!
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::ops::RangeInclusive;
+
+///
+/// An error raised by **Clast**.
+///
+/// Constructors (`try_new` and friends) return it directly. Iterators and streams keep
+/// yielding `std::io::Result`, and the `From` conversions move between the two: a
+/// `ClastError` becomes an `io::Error` of kind `InvalidInput` (or the original error for
+/// [`ClastError::ReadFailed`]), and converting such an `io::Error` back recovers the variant.
+///
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClastError {
+    /// `min_size` is outside its allowed bounds.
+    InvalidMinSize {
+        given: usize,
+        bounds: RangeInclusive<usize>,
+    },
+    /// `avg_size` is outside its allowed bounds.
+    InvalidAvgSize {
+        given: usize,
+        bounds: RangeInclusive<usize>,
+    },
+    /// `max_size` is outside its allowed bounds.
+    InvalidMaxSize {
+        given: usize,
+        bounds: RangeInclusive<usize>,
+    },
+    /// A fixed chunk size is outside its allowed bounds.
+    InvalidSize {
+        given: usize,
+        bounds: RangeInclusive<usize>,
+    },
+    /// The normalization offset has no masks for the chosen `avg_size`.
+    InvalidNormalization {
+        given: usize,
+        bounds: RangeInclusive<usize>,
+    },
+    /// `min_size < avg_size < max_size` is not satisfied.
+    InvalidOrdering {
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+    },
+    /// Reading the source failed.
+    ReadFailed(io::Error),
+}
+
+impl fmt::Display for ClastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, given, bounds) = match self {
+            Self::InvalidMinSize { given, bounds } => ("min_size", given, bounds),
+            Self::InvalidAvgSize { given, bounds } => ("avg_size", given, bounds),
+            Self::InvalidMaxSize { given, bounds } => ("max_size", given, bounds),
+            Self::InvalidSize { given, bounds } => ("size", given, bounds),
+            Self::InvalidNormalization { given, bounds } => ("normalization offset", given, bounds),
+            Self::InvalidOrdering { .. } => {
+                return f.write_str("must satisfy the condition: min_size < avg_size < max_size");
+            }
+            Self::ReadFailed(e) => return write!(f, "read failed: {}", e),
+        };
+
+        write!(
+            f,
+            "{} must be between {} and {} (got {})",
+            name,
+            bounds.start(),
+            bounds.end(),
+            given
+        )
+    }
+}
+
+impl Error for ClastError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ReadFailed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ClastError {
+    fn from(e: io::Error) -> Self {
+        if e.get_ref().is_some_and(|inner| inner.is::<Self>()) {
+            // Recovers an error converted by `From<ClastError> for io::Error`.
+            return *e.into_inner().unwrap().downcast::<Self>().unwrap();
+        }
+        Self::ReadFailed(e)
+    }
+}
+
+impl From<ClastError> for io::Error {
+    fn from(e: ClastError) -> Self {
+        match e {
+            ClastError::ReadFailed(e) => e,
+            other => io::Error::new(io::ErrorKind::InvalidInput, other),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/error_tests.rs"]
+mod tests;
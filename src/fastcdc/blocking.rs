@@ -0,0 +1,72 @@
+use crate::fastcdc::{Chunk, FastCDC};
+use futures::{SinkExt, Stream, channel::mpsc, executor};
+use std::{
+    io::{self, Read},
+    pin::Pin,
+    task::{Context, Poll},
+    thread,
+};
+
+impl FastCDC {
+    ///
+    /// Runs the synchronous chunker over a blocking reader on a dedicated thread
+    /// and exposes the chunks as a stream.
+    ///
+    /// This suits async applications whose sources are actually blocking
+    /// (e.g. `std::fs::File`, FUSE handles), keeping the executor threads free.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read` and `Send`).
+    /// * `capacity`: The number of chunks that may be queued ahead of the consumer.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the thread cannot be spawned.
+    ///
+    pub fn chunks_blocking<R>(&self, reader: R, capacity: usize) -> BlockingChunks
+    where
+        R: Read + Send + 'static,
+    {
+        let (mut tx, rx) = mpsc::channel(capacity);
+        let chunker = self.clone();
+
+        thread::Builder::new()
+            .name("clast-blocking-chunker".into())
+            .spawn(move || {
+                for chunk in chunker.chunks(reader) {
+                    let failed = chunk.is_err();
+
+                    // The receiver has been dropped; stop reading.
+                    if executor::block_on(tx.send(chunk)).is_err() || failed {
+                        break;
+                    }
+                }
+            })
+            .expect("failed to spawn chunker thread");
+
+        BlockingChunks { rx }
+    }
+}
+
+///
+/// A stream of chunks produced by a chunker running on a blocking thread.
+///
+/// Created by [`FastCDC::chunks_blocking`]. The stream ends after the first error.
+/// Dropping it stops the background thread once its pending read completes.
+///
+pub struct BlockingChunks {
+    rx: mpsc::Receiver<io::Result<Chunk>>,
+}
+
+impl Stream for BlockingChunks {
+    type Item = io::Result<Chunk>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().rx).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/blocking_tests.rs"]
+mod tests;
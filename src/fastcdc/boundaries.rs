@@ -0,0 +1,98 @@
+use crate::fastcdc::{Boundary, FastCDC, FastCDCDecoder};
+use std::{
+    borrow::Cow,
+    io::{self, Read},
+};
+
+impl FastCDC {
+    ///
+    /// Creates an iterator that yields only the boundaries of the chunks in the reader.
+    ///
+    /// The data is scanned in a single reused buffer of twice `max_size`, and no chunk
+    /// payload is ever allocated, suiting index builds over very large inputs. Yields the
+    /// offsets, lengths, and fingerprints of [`FastCDC::chunks`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn boundaries<R: Read>(&self, reader: R) -> FastCDCBoundaries<'_, R> {
+        FastCDCBoundaries {
+            buf: vec![0; self.max_size * 2],
+            decoder: FastCDCDecoder::new(Cow::Borrowed(self)),
+            reader,
+            start: 0,
+            end: 0,
+            eof: false,
+        }
+    }
+}
+
+pub struct FastCDCBoundaries<'a, R: Read> {
+    decoder: FastCDCDecoder<'a>,
+    reader: R,
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    eof: bool,
+}
+
+impl<'a, R: Read> FastCDCBoundaries<'a, R> {
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        self.decoder.stats()
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        // Move the unconsumed bytes to the front so a whole `max_size` window fits.
+        self.buf.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+
+        while self.end < self.buf.len() {
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(n) => self.end += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, R: Read> Iterator for FastCDCBoundaries<'a, R> {
+    type Item = io::Result<Boundary>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let window = &self.buf[self.start..self.end];
+            if let Some((cutpoint, fp_hash)) = self.decoder.next_cutpoint(window, self.eof) {
+                let boundary = Boundary {
+                    offset: self.decoder.processed,
+                    length: cutpoint,
+                    fp_hash,
+                };
+                self.decoder.advance(cutpoint);
+                self.start += cutpoint;
+                return Some(Ok(boundary));
+            }
+
+            if self.eof {
+                return None;
+            }
+            if let Err(e) = self.fill() {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/boundaries_tests.rs"]
+mod tests;
@@ -0,0 +1,104 @@
+use crate::fastcdc::{BufferUsage, Chunk, FastCDC, FastCDCDecoder};
+use bytes::{Bytes, BytesMut};
+use std::{
+    borrow::Cow,
+    io::{self, BufRead},
+};
+
+impl FastCDC {
+    ///
+    /// Creates an iterator that scans directly in the reader's own buffer.
+    ///
+    /// A chunk that lies entirely within the bytes returned by `fill_buf` is cut there and
+    /// copied once, into the chunk. Only chunks that span a refill are assembled in an
+    /// internal buffer, so a reader buffer larger than `max_size` avoids nearly all
+    /// intermediate copies. Yields the same chunks as [`FastCDC::chunks`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `BufRead`).
+    ///
+    pub fn chunks_buffered<R: BufRead>(&self, reader: R) -> FastCDCBufIter<'_, R> {
+        FastCDCBufIter {
+            decoder: FastCDCDecoder::new(Cow::Borrowed(self)),
+            reader,
+            carry: BytesMut::new(),
+            eof: false,
+            peak: 0,
+        }
+    }
+}
+
+pub struct FastCDCBufIter<'a, R: BufRead> {
+    decoder: FastCDCDecoder<'a>,
+    reader: R,
+    carry: BytesMut,
+    eof: bool,
+    peak: usize,
+}
+
+impl<'a, R: BufRead> FastCDCBufIter<'a, R> {
+    /// Returns the bytes currently carried between refills and the peak reached so far.
+    pub fn buffer_usage(&self) -> BufferUsage {
+        BufferUsage {
+            buffered: self.carry.len(),
+            peak: self.peak,
+        }
+    }
+
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        self.decoder.stats()
+    }
+}
+
+impl<'a, R: BufRead> Iterator for FastCDCBufIter<'a, R> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if !self.carry.is_empty() || self.eof {
+                if let Some(chunk) = self.decoder.next_chunk(&mut self.carry, self.eof) {
+                    return Some(Ok(chunk));
+                }
+                if self.eof {
+                    return None;
+                }
+            }
+
+            let max_size = self.decoder.chunker.max_size;
+            let available = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if available.is_empty() {
+                self.eof = true;
+                continue;
+            }
+
+            if self.carry.is_empty() {
+                // The chunk starts in the reader's buffer; cut it there if it also ends there.
+                if let Some((cutpoint, fp_hash)) = self.decoder.next_cutpoint(available, false) {
+                    let data = Bytes::copy_from_slice(&available[..cutpoint]);
+                    self.reader.consume(cutpoint);
+                    return Some(Ok(self.decoder.emit(data, fp_hash)));
+                }
+            }
+
+            // The chunk continues past the reader's buffer; carry the bytes over. The scan
+            // state is relative to the start of the chunk, which the carry also starts at.
+            // A full carry always yields a chunk above, so `take` is never zero.
+            let take = available.len().min(max_size - self.carry.len());
+            self.carry.extend_from_slice(&available[..take]);
+            self.reader.consume(take);
+            self.peak = self.peak.max(self.carry.len());
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/buffered_tests.rs"]
+mod tests;
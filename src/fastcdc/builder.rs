@@ -0,0 +1,240 @@
+use crate::fastcdc::{FastCDC, Masks, Normal};
+use std::io;
+
+/// Where the builder takes the Gear table from.
+#[derive(Debug, Clone)]
+enum TableSource {
+    Builtin,
+    Seed(u64),
+    Gear(Box<[u64; 256]>),
+    Key(Vec<u8>),
+}
+
+///
+/// A fluent builder for [`FastCDC`], created by [`FastCDC::builder`].
+///
+/// Every option starts at the default of the corresponding `FastCDC` method, and sizes
+/// default to 2 KB / 8 KB / 64 KB with `Normal::Level2`. Nothing is validated until
+/// [`FastCDCBuilder::build`], which reports every invalid option as an error instead of
+/// panicking.
+///
+/// ## Example
+///
+/// ```
+/// use clast::fastcdc::{FastCDC, Normal};
+///
+/// let chunker = FastCDC::builder()
+///     .min(4_096)
+///     .avg(16_384)
+///     .max(65_536)
+///     .normal(Normal::Level1)
+///     .seed(42)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(chunker.avg_size(), 16_384);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct FastCDCBuilder {
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    normal: Normal,
+    table: TableSource,
+    masks: Option<(u64, u64)>,
+    alignment: Option<(usize, usize)>,
+    delimiter: Option<(u8, usize)>,
+    utf8: bool,
+    one_byte: bool,
+    #[cfg(feature = "four-byte-rolling")]
+    four_byte: bool,
+    min_skip: bool,
+    read_size: Option<usize>,
+}
+
+impl Default for FastCDCBuilder {
+    fn default() -> Self {
+        Self {
+            min_size: 2_048,
+            avg_size: 8_192,
+            max_size: 65_536,
+            normal: Normal::Level2,
+            table: TableSource::Builtin,
+            masks: None,
+            alignment: None,
+            delimiter: None,
+            utf8: false,
+            one_byte: false,
+            #[cfg(feature = "four-byte-rolling")]
+            four_byte: false,
+            min_skip: true,
+            read_size: None,
+        }
+    }
+}
+
+impl FastCDCBuilder {
+    /// Sets the minimum chunk size.
+    pub fn min(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Sets the target average chunk size.
+    pub fn avg(mut self, avg_size: usize) -> Self {
+        self.avg_size = avg_size;
+        self
+    }
+
+    /// Sets the maximum chunk size.
+    pub fn max(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the normalization level.
+    pub fn normal(mut self, normal: Normal) -> Self {
+        self.normal = normal;
+        self
+    }
+
+    /// Generates the Gear table from `seed`, as [`FastCDC::with_seed`] does.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.table = TableSource::Seed(seed);
+        self
+    }
+
+    /// Hashes with a caller-supplied Gear table, as [`FastCDC::with_gear_table`] does.
+    pub fn gear_table(mut self, gear: &[u64; 256]) -> Self {
+        self.table = TableSource::Gear(Box::new(*gear));
+        self
+    }
+
+    /// Derives the Gear table from a secret key, as [`FastCDC::keyed`] does.
+    pub fn key(mut self, key: &[u8]) -> Self {
+        self.table = TableSource::Key(key.to_vec());
+        self
+    }
+
+    /// Overrides the derived masks with [`Masks::custom`], as [`FastCDC::with_masks`] does.
+    pub fn masks(mut self, mask_s: u64, mask_l: u64) -> Self {
+        self.masks = Some((mask_s, mask_l));
+        self
+    }
+
+    /// Biases cut points toward multiples of `alignment`, as [`FastCDC::with_alignment`] does.
+    pub fn alignment(mut self, alignment: usize, tolerance: usize) -> Self {
+        self.alignment = Some((alignment, tolerance));
+        self
+    }
+
+    /// Snaps cut points to a record delimiter, as [`FastCDC::with_delimiter`] does.
+    pub fn delimiter(mut self, delimiter: u8, slack: usize) -> Self {
+        self.delimiter = Some((delimiter, slack));
+        self
+    }
+
+    /// Keeps cut points from splitting UTF-8 code points, as [`FastCDC::with_utf8_boundaries`] does.
+    pub fn utf8_boundaries(mut self, enabled: bool) -> Self {
+        self.utf8 = enabled;
+        self
+    }
+
+    /// Rolls the gear hash one byte per iteration, as [`FastCDC::with_one_byte_rolling`] does.
+    pub fn one_byte_rolling(mut self, enabled: bool) -> Self {
+        self.one_byte = enabled;
+        self
+    }
+
+    /// Rolls the gear hash four bytes per iteration, as [`FastCDC::with_four_byte_rolling`] does.
+    #[cfg(feature = "four-byte-rolling")]
+    pub fn four_byte_rolling(mut self, enabled: bool) -> Self {
+        self.four_byte = enabled;
+        self
+    }
+
+    /// Enables or disables sub-minimum cut-point skipping, as [`FastCDC::with_min_size_skip`] does.
+    pub fn min_size_skip(mut self, enabled: bool) -> Self {
+        self.min_skip = enabled;
+        self
+    }
+
+    /// Sets the bytes requested per read, as [`FastCDC::with_read_size`] does.
+    pub fn read_size(mut self, read_size: usize) -> Self {
+        self.read_size = Some(read_size);
+        self
+    }
+
+    ///
+    /// Builds the configured `FastCDC` instance.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if the sizes are rejected by
+    /// [`FastCDC::try_new`], the alignment or read size is zero, or a mask uses bit 63.
+    ///
+    pub fn build(self) -> io::Result<FastCDC> {
+        let chunker = match &self.table {
+            TableSource::Key(key) => FastCDC::try_keyed(
+                key,
+                self.min_size,
+                self.avg_size,
+                self.max_size,
+                self.normal,
+            )?,
+            _ => FastCDC::try_new(self.min_size, self.avg_size, self.max_size, self.normal)?,
+        };
+        let mut chunker = match &self.table {
+            TableSource::Seed(seed) => chunker.with_seed(*seed),
+            TableSource::Gear(gear) => chunker.with_gear_table(gear),
+            TableSource::Builtin | TableSource::Key(_) => chunker,
+        };
+
+        if let Some((mask_s, mask_l)) = self.masks {
+            if (mask_s | mask_l) >> 63 != 0 {
+                return Err(invalid_input("masks must not use bit 63"));
+            }
+            chunker = chunker.with_masks(Masks::custom(mask_s, mask_l));
+        }
+        if let Some((alignment, tolerance)) = self.alignment {
+            if alignment == 0 {
+                return Err(invalid_input("alignment must be greater than zero"));
+            }
+            chunker = chunker.with_alignment(alignment, tolerance);
+        }
+        if let Some((delimiter, slack)) = self.delimiter {
+            chunker = chunker.with_delimiter(delimiter, slack);
+        }
+        if let Some(read_size) = self.read_size {
+            if read_size == 0 {
+                return Err(invalid_input("read size must be greater than zero"));
+            }
+            chunker = chunker.with_read_size(read_size);
+        }
+
+        let chunker = chunker
+            .with_utf8_boundaries(self.utf8)
+            .with_one_byte_rolling(self.one_byte)
+            .with_min_size_skip(self.min_skip);
+        #[cfg(feature = "four-byte-rolling")]
+        let chunker = chunker.with_four_byte_rolling(self.four_byte);
+
+        Ok(chunker)
+    }
+}
+
+impl FastCDC {
+    /// Returns a [`FastCDCBuilder`] with default options.
+    pub fn builder() -> FastCDCBuilder {
+        FastCDCBuilder::default()
+    }
+}
+
+fn invalid_input(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+#[cfg(test)]
+#[path = "tests/builder_tests.rs"]
+mod tests;
@@ -1,3 +1,5 @@
+use crate::fastcdc::Sketch;
+use crate::hash::{RsyncChecksum, fingerprint128};
 use bytes::Bytes;
 
 /// Represents a content-defined chunk.
@@ -12,3 +14,76 @@ pub struct Chunk {
     /// The length of the chunk in bytes.
     pub length: usize,
 }
+
+impl Chunk {
+    ///
+    /// Computes the rsync-compatible weak rolling checksum of the chunk data.
+    ///
+    /// This lets signatures generated from these chunks be consumed by
+    /// rsync-algorithm-based tooling alongside the gear fingerprint.
+    ///
+    pub fn weak_checksum(&self) -> u32 {
+        RsyncChecksum::new(&self.data).digest()
+    }
+
+    ///
+    /// Computes a 128-bit fingerprint of the whole chunk data.
+    ///
+    /// `fp_hash` only reflects the bytes around the cut point, so it is unsuitable as a
+    /// deduplication key. This fingerprint covers every byte and is wide enough to key
+    /// billions of chunks; see [`fingerprint128`] for its guarantees.
+    ///
+    pub fn fp_hash128(&self) -> u128 {
+        fingerprint128(&self.data)
+    }
+
+    ///
+    /// Computes a MinHash [`Sketch`] of the chunk data with `bins` bins.
+    ///
+    /// Sketches can be stored in a similarity index in place of the payload, so
+    /// near-duplicate chunks can be found later as delta compression bases.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `bins` is zero.
+    ///
+    pub fn sketch(&self, bins: usize) -> Sketch {
+        Sketch::from_data(&self.data, bins)
+    }
+}
+
+/// A content-defined chunk borrowed from an in-memory input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkRef<'a> {
+    /// The fingerprint (Gear Hash) of the chunk.
+    pub fp_hash: u64,
+    /// The chunk data, borrowed from the input.
+    pub data: &'a [u8],
+    /// The absolute offset of the chunk in the input.
+    pub offset: u64,
+    /// The length of the chunk in bytes.
+    pub length: usize,
+}
+
+impl ChunkRef<'_> {
+    /// Copies the data into an owned [`Chunk`].
+    pub fn to_chunk(&self) -> Chunk {
+        Chunk {
+            fp_hash: self.fp_hash,
+            data: Bytes::copy_from_slice(self.data),
+            offset: self.offset,
+            length: self.length,
+        }
+    }
+}
+
+/// The position and fingerprint of a chunk, without its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boundary {
+    /// The absolute offset of the chunk in the source stream.
+    pub offset: u64,
+    /// The length of the chunk in bytes.
+    pub length: usize,
+    /// The fingerprint (Gear Hash) of the chunk.
+    pub fp_hash: u64,
+}
@@ -1,10 +1,17 @@
-use crate::fastcdc::Normal;
+use crate::adapter::ReadAhead;
+use crate::digest::hkdf_sha256;
+use crate::error::ClastError;
 use crate::fastcdc::chunk::Chunk;
-use crate::fastcdc::cut::find_cutpoint_inner;
-use crate::fastcdc::mask::Masks;
-use bytes::BytesMut;
-use std::io::Read;
+#[cfg(feature = "four-byte-rolling")]
+use crate::fastcdc::cut::find_cutpoint_quad;
+use crate::fastcdc::cut::{TableGear, find_cutpoint_inner, find_cutpoint_single};
+use crate::fastcdc::table::{BUILTIN, GearTable, MASK_TABLE_SIZE, TableId};
+use crate::fastcdc::{BufferPool, BufferUsage, Masks, Normal, SpareInit};
+use bytes::{Buf, BytesMut};
+use std::borrow::Cow;
 use std::io::{self};
+use std::io::{IoSliceMut, Read};
+use std::sync::Arc;
 
 /// Lower limit for the `min_size` parameter.
 pub const MIN_CHUNK_SIZE_MIN: usize = 64;
@@ -21,12 +28,114 @@ pub const MAX_CHUNK_SIZE_MIN: usize = 1024;
 /// Upper limit for the `max_size` parameter.
 pub const MAX_CHUNK_SIZE_MAX: usize = 16_777_216; // 16 MB
 
+/// Upper limit for the `min_size` parameter of [`FastCDC::try_new_unchecked_bounds`].
+pub const UNCHECKED_MIN_CHUNK_SIZE_MAX: usize = 268_435_456; // 256 MB
+/// Upper limit for the `avg_size` parameter of [`FastCDC::try_new_unchecked_bounds`].
+pub const UNCHECKED_AVG_CHUNK_SIZE_MAX: usize = 268_435_456; // 256 MB
+/// Upper limit for the `max_size` parameter of [`FastCDC::try_new_unchecked_bounds`].
+pub const UNCHECKED_MAX_CHUNK_SIZE_MAX: usize = 1_073_741_824; // 1 GB
+
+// Every accepted average size has masks for the named normalization levels.
+const _: () = assert!(UNCHECKED_AVG_CHUNK_SIZE_MAX.ilog2() as usize + 3 < MASK_TABLE_SIZE);
+
+/// Minimum size of the read-ahead buffer filled alongside the window by `read_vectored`.
+const SPARE_SIZE_MIN: usize = 65_536; // 64 KB
+
+/// Option bit recorded for [`FastCDC::with_one_byte_rolling`].
+pub(crate) const OPTION_ONE_BYTE: u8 = 0b01;
+/// Option bit recorded for [`FastCDC::with_min_size_skip`] when the skip is disabled.
+pub(crate) const OPTION_NO_MIN_SKIP: u8 = 0b10;
+/// Option bit recorded for [`FastCDC::with_four_byte_rolling`].
+#[cfg(feature = "four-byte-rolling")]
+pub(crate) const OPTION_FOUR_BYTE: u8 = 0b100;
+/// Option bit recorded for [`FastCDC::with_utf8_boundaries`].
+pub(crate) const OPTION_UTF8: u8 = 0b1000;
+
+/// HKDF context for keyed Gear tables.
+const KEYED_GEAR_INFO: &[u8] = b"clast fastcdc gear table v1";
+
+///
+/// Checks the size constraints shared by every chunker.
+///
+/// ## Errors
+///
+/// Returns the `ClastError` describing the first size outside its bounds,
+/// or `ClastError::InvalidOrdering` if `min_size < avg_size < max_size` is not satisfied.
+///
+pub(crate) fn validate_sizes(
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+) -> Result<(), ClastError> {
+    validate_sizes_up_to(
+        min_size,
+        avg_size,
+        max_size,
+        [MIN_CHUNK_SIZE_MAX, AVG_CHUNK_SIZE_MAX, MAX_CHUNK_SIZE_MAX],
+    )
+}
+
+/// Checks the size constraints against the given upper limits for `min_size`, `avg_size`, and `max_size`.
+fn validate_sizes_up_to(
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    [min_limit, avg_limit, max_limit]: [usize; 3],
+) -> Result<(), ClastError> {
+    let bounds = MIN_CHUNK_SIZE_MIN..=min_limit;
+    if !bounds.contains(&min_size) {
+        return Err(ClastError::InvalidMinSize {
+            given: min_size,
+            bounds,
+        });
+    }
+
+    let bounds = AVG_CHUNK_SIZE_MIN..=avg_limit;
+    if !bounds.contains(&avg_size) {
+        return Err(ClastError::InvalidAvgSize {
+            given: avg_size,
+            bounds,
+        });
+    }
+
+    let bounds = MAX_CHUNK_SIZE_MIN..=max_limit;
+    if !bounds.contains(&max_size) {
+        return Err(ClastError::InvalidMaxSize {
+            given: max_size,
+            bounds,
+        });
+    }
+
+    if !(min_size < avg_size && avg_size < max_size) {
+        return Err(ClastError::InvalidOrdering {
+            min_size,
+            avg_size,
+            max_size,
+        });
+    }
+
+    Ok(())
+}
+
 /// A FastCDC chunker implementation.
+#[derive(Debug, Clone)]
 pub struct FastCDC {
     pub(super) min_size: usize,
     pub(super) avg_size: usize,
     pub(super) max_size: usize,
+    pub(super) normal: Normal,
     pub(super) masks: Masks,
+    pub(super) table: Option<Arc<GearTable>>,
+    pub(super) seed: Option<u64>,
+    pub(super) alignment: usize,
+    pub(super) align_tolerance: usize,
+    pub(super) delimiter: Option<(u8, usize)>,
+    pub(super) utf8: bool,
+    pub(super) one_byte: bool,
+    #[cfg(feature = "four-byte-rolling")]
+    pub(super) four_byte: bool,
+    pub(super) min_skip: bool,
+    pub(super) read_size: Option<usize>,
 }
 
 impl FastCDC {
@@ -43,7 +152,8 @@ impl FastCDC {
     /// ## Panics
     ///
     /// Panics if `min_size`, `avg_size`, or `max_size` are outside the allowed bounds,
-    /// or if `min_size < avg_size < max_size` is not satisfied.
+    /// if `min_size < avg_size < max_size` is not satisfied,
+    /// or if a `Normal::Custom` offset has no masks for `avg_size`.
     ///
     /// * `min_size`: 64 ~ 1,048,576 (1 MB)
     /// * `avg_size`: 256 ~ 4,194,304 (4 MB)
@@ -69,9 +179,10 @@ impl FastCDC {
     ///
     /// ## Errors
     ///
-    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput`
+    /// Returns a [`ClastError`] describing the problem
     /// if `min_size`, `avg_size`, or `max_size` are outside the allowed bounds,
-    /// or if `min_size < avg_size < max_size` is not satisfied.
+    /// if `min_size < avg_size < max_size` is not satisfied,
+    /// or if a `Normal::Custom` offset has no masks for `avg_size`.
     ///
     /// * `min_size`: 64 ~ 1,048,576 (1 MB)
     /// * `avg_size`: 256 ~ 4,194,304 (4 MB)
@@ -82,75 +193,847 @@ impl FastCDC {
         avg_size: usize,
         max_size: usize,
         normal: Normal,
-    ) -> io::Result<Self> {
-        if !(MIN_CHUNK_SIZE_MIN..=MIN_CHUNK_SIZE_MAX).contains(&min_size) {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "min_size must be between {} and {}",
-                    MIN_CHUNK_SIZE_MIN, MIN_CHUNK_SIZE_MAX
-                ),
-            ));
-        }
-
-        if !(AVG_CHUNK_SIZE_MIN..=AVG_CHUNK_SIZE_MAX).contains(&avg_size) {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "avg_size must be between {} and {}",
-                    AVG_CHUNK_SIZE_MIN, AVG_CHUNK_SIZE_MAX
-                ),
-            ));
-        }
-
-        if !(MAX_CHUNK_SIZE_MIN..=MAX_CHUNK_SIZE_MAX).contains(&max_size) {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!(
-                    "max_size must be between {} and {}",
-                    MAX_CHUNK_SIZE_MIN, MAX_CHUNK_SIZE_MAX
-                ),
-            ));
-        }
-
-        if !(min_size < avg_size && avg_size < max_size) {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                "must satisfy the condition: min_size < avg_size < max_size",
-            ));
-        }
+    ) -> Result<Self, ClastError> {
+        validate_sizes(min_size, avg_size, max_size)?;
+
+        Self::from_valid_sizes(min_size, avg_size, max_size, normal)
+    }
+
+    ///
+    /// Constructs a new `FastCDC` instance, accepting sizes beyond the default upper limits.
+    ///
+    /// The default limits keep buffering modest; this escape hatch is for stores that pack
+    /// very large objects (e.g. 64 MB blobs). Chunk buffers grow with `max_size`, so expect
+    /// each iterator to hold up to twice `max_size` in memory. The lower limits and the
+    /// `min_size < avg_size < max_size` condition still apply.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    /// * `normal`: The normalization level for chunk size distribution.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] describing the problem
+    /// if `min_size`, `avg_size`, or `max_size` are outside the extended bounds,
+    /// if `min_size < avg_size < max_size` is not satisfied,
+    /// or if a `Normal::Custom` offset has no masks for `avg_size`.
+    ///
+    /// * `min_size`: 64 ~ 268,435,456 (256 MB)
+    /// * `avg_size`: 256 ~ 268,435,456 (256 MB)
+    /// * `max_size`: 1,024 (1 KB) ~ 1,073,741,824 (1 GB)
+    ///
+    pub fn try_new_unchecked_bounds(
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+        normal: Normal,
+    ) -> Result<Self, ClastError> {
+        validate_sizes_up_to(
+            min_size,
+            avg_size,
+            max_size,
+            [
+                UNCHECKED_MIN_CHUNK_SIZE_MAX,
+                UNCHECKED_AVG_CHUNK_SIZE_MAX,
+                UNCHECKED_MAX_CHUNK_SIZE_MAX,
+            ],
+        )?;
+
+        Self::from_valid_sizes(min_size, avg_size, max_size, normal)
+    }
 
+    fn from_valid_sizes(
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+        normal: Normal,
+    ) -> Result<Self, ClastError> {
         Ok(Self {
             min_size,
             avg_size,
             max_size,
-            masks: Masks::new(avg_size, normal),
+            normal,
+            masks: Masks::new(avg_size, normal)?,
+            table: None,
+            seed: None,
+            alignment: 0,
+            align_tolerance: 0,
+            delimiter: None,
+            utf8: false,
+            one_byte: false,
+            #[cfg(feature = "four-byte-rolling")]
+            four_byte: false,
+            min_skip: true,
+            read_size: None,
         })
     }
 
+    ///
+    /// Constructs a `FastCDC` instance whose Gear table is derived from a secret key.
+    ///
+    /// The table is expanded from `key` with HKDF-SHA256 and its masks are derived from it,
+    /// so boundaries, and therefore chunk sizes, are unpredictable to anyone without the key.
+    /// This defends deduplicating stores against attacks that infer contents from chunk sizes.
+    /// Fingerprints are computed with the keyed table as well.
+    ///
+    /// ## Arguments
+    ///
+    /// * `key`: The secret the Gear table is derived from.
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    /// * `normal`: The normalization level for chunk size distribution.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`FastCDC::new`].
+    ///
+    pub fn keyed(
+        key: &[u8],
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+        normal: Normal,
+    ) -> Self {
+        match Self::try_keyed(key, min_size, avg_size, max_size, normal) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a `FastCDC` instance whose Gear table is derived from a secret key.
+    /// Unlike `keyed`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `key`: The secret the Gear table is derived from.
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    /// * `normal`: The normalization level for chunk size distribution.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as [`FastCDC::try_new`].
+    ///
+    pub fn try_keyed(
+        key: &[u8],
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+        normal: Normal,
+    ) -> Result<Self, ClastError> {
+        let chunker = Self::try_new(min_size, avg_size, max_size, normal)?;
+
+        let mut okm = [0u8; 256 * 8];
+        hkdf_sha256(b"", key, KEYED_GEAR_INFO, &mut okm);
+
+        let mut gear = [0u64; 256];
+        for (val, bytes) in gear.iter_mut().zip(okm.chunks_exact(8)) {
+            *val = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        Ok(chunker.with_gear_table(&gear))
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    /// Returns the target average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.avg_size
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Returns the normalization level.
+    pub fn normal(&self) -> Normal {
+        self.normal
+    }
+
+    ///
+    /// Biases cut points toward absolute offsets that are multiples of `alignment`.
+    ///
+    /// A cut point (including a forced `max_size` cut) is moved back to the preceding
+    /// multiple of `alignment` when it lies at most `tolerance` bytes away and the chunk
+    /// stays at least `min_size` long. The final chunk of a stream is never moved.
+    /// This lets chunk groups line up with multipart upload parts and ranged reads,
+    /// at the cost of some boundary stability around the snapped offsets.
+    ///
+    /// ## Arguments
+    ///
+    /// * `alignment`: The boundary multiple (e.g. the part size), in bytes.
+    /// * `tolerance`: The furthest a cut point may be moved, in bytes.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `alignment` is zero.
+    ///
+    pub fn with_alignment(mut self, alignment: usize, tolerance: usize) -> Self {
+        assert!(alignment > 0, "alignment must be greater than zero");
+
+        self.alignment = alignment;
+        self.align_tolerance = tolerance;
+        self
+    }
+
+    ///
+    /// Snaps cut points to just past a record delimiter (e.g. `b'\n'`), so every chunk but
+    /// the last ends with a complete record.
+    ///
+    /// A cut point (including a forced `max_size` cut) moves forward to just past the next
+    /// delimiter within `slack` bytes, without exceeding `max_size`; failing that, it moves
+    /// back to just past the preceding delimiter within `slack` bytes, keeping the chunk at
+    /// least `min_size` long. Without a delimiter in either window, the cut stays put.
+    /// The final chunk of a stream is never moved. Takes precedence over [`FastCDC::with_alignment`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `delimiter`: The byte that ends a record.
+    /// * `slack`: The furthest a cut point may be moved, in bytes.
+    ///
+    pub fn with_delimiter(mut self, delimiter: u8, slack: usize) -> Self {
+        self.delimiter = Some((delimiter, slack));
+        self
+    }
+
+    /// Returns the record delimiter and slack, if set.
+    pub fn delimiter(&self) -> Option<(u8, usize)> {
+        self.delimiter
+    }
+
+    ///
+    /// Keeps cut points from splitting a UTF-8 encoded code point.
+    ///
+    /// A cut point that falls inside a multi-byte sequence moves back to the start of that
+    /// sequence, or forward past its end if moving back would undercut `min_size`, so it
+    /// shifts by at most 3 bytes. Applied after delimiter snapping or alignment; bytes
+    /// that are not valid UTF-8 are treated as single characters.
+    ///
+    /// ## Arguments
+    ///
+    /// * `enabled`: Whether to keep code points whole.
+    ///
+    pub fn with_utf8_boundaries(mut self, enabled: bool) -> Self {
+        self.utf8 = enabled;
+        self
+    }
+
+    /// Returns `true` if cut points never split a UTF-8 code point.
+    pub fn utf8_boundaries(&self) -> bool {
+        self.utf8
+    }
+
+    ///
+    /// Selects the classic one-byte-per-iteration gear rolling instead of the two-byte
+    /// optimization.
+    ///
+    /// The two-byte scan visits the same hash sequence, but steps in whole pairs: with an
+    /// odd `min_size` or `avg_size` it starts hashing and switches masks one byte early,
+    /// and the fingerprint of a cut on the first byte of a pair is shifted left by one.
+    /// Use this when the canonical single-byte sequence is required, e.g. for compatibility
+    /// with other implementations or for research comparisons. Enabling it disables
+    /// four-byte rolling.
+    ///
+    /// ## Arguments
+    ///
+    /// * `enabled`: Whether to roll one byte per iteration.
+    ///
+    pub fn with_one_byte_rolling(mut self, enabled: bool) -> Self {
+        self.one_byte = enabled;
+        #[cfg(feature = "four-byte-rolling")]
+        if enabled {
+            self.four_byte = false;
+        }
+        self
+    }
+
+    /// Returns `true` if the gear hash rolls one byte per iteration.
+    pub fn one_byte_rolling(&self) -> bool {
+        self.one_byte
+    }
+
+    ///
+    /// Unrolls the gear hash to four bytes per iteration instead of two.
+    ///
+    /// The four-byte scan visits the same hash sequence, but steps in whole quads, so its
+    /// boundaries differ from the two-byte scan's around a few offsets:
+    ///
+    /// * With a `min_size` or `avg_size` that is not a multiple of 4, it starts hashing and
+    ///   switches masks up to 3 bytes early (the two-byte scan: 1 byte, if odd).
+    /// * The last `scan_len % 4` bytes before `max_size` or the end of the stream are never
+    ///   judged, so a cut there becomes a forced or tail cut.
+    /// * The fingerprint of a cut on byte `k` of a quad is shifted left by `3 - k` bits, and
+    ///   custom masks lose bits 61 and 62 on the first bytes of a quad. Derived masks never
+    ///   use them.
+    ///
+    /// Chunks are otherwise identical. The mode is recorded in manifests, which can only be
+    /// verified by builds with the `four-byte-rolling` feature. Enabling it disables
+    /// one-byte rolling.
+    ///
+    /// ## Arguments
+    ///
+    /// * `enabled`: Whether to roll four bytes per iteration.
+    ///
+    #[cfg(feature = "four-byte-rolling")]
+    pub fn with_four_byte_rolling(mut self, enabled: bool) -> Self {
+        self.four_byte = enabled;
+        if enabled {
+            self.one_byte = false;
+        }
+        self
+    }
+
+    /// Returns `true` if the gear hash rolls four bytes per iteration.
+    #[cfg(feature = "four-byte-rolling")]
+    pub fn four_byte_rolling(&self) -> bool {
+        self.four_byte
+    }
+
+    ///
+    /// Enables or disables sub-minimum cut-point skipping (enabled by default).
+    ///
+    /// When disabled, the gear hash is computed from the first byte of each chunk instead
+    /// of from `min_size`. Cut points are still only accepted from `min_size` on, but the
+    /// hash there includes the prefix, which moves boundaries relative to the skipping scan.
+    /// This matches implementations that hash the whole chunk, at the cost of hashing
+    /// `min_size` more bytes per chunk.
+    ///
+    /// ## Arguments
+    ///
+    /// * `enabled`: Whether to skip hashing the first `min_size` bytes.
+    ///
+    pub fn with_min_size_skip(mut self, enabled: bool) -> Self {
+        self.min_skip = enabled;
+        self
+    }
+
+    /// Returns `true` if hashing skips the first `min_size` bytes of each chunk.
+    pub fn min_size_skip(&self) -> bool {
+        self.min_skip
+    }
+
+    ///
+    /// Sets the number of bytes requested from the reader per read call.
+    ///
+    /// By default [`FastCDC::chunks`] asks for the rest of its `max_size` window plus at
+    /// least 64 KB of read-ahead, and [`FastCDC::chunks_async`] for 4 KB up to the rest of
+    /// the window. Larger reads suit storage with high per-request latency (e.g. 1 MB reads
+    /// from an object storage gateway), smaller ones bound each syscall. Chunks are the same
+    /// either way.
+    ///
+    /// ## Arguments
+    ///
+    /// * `read_size`: The bytes to request per read.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `read_size` is zero.
+    ///
+    pub fn with_read_size(mut self, read_size: usize) -> Self {
+        assert!(read_size > 0, "read size must be greater than zero");
+
+        self.read_size = Some(read_size);
+        self
+    }
+
+    /// Returns the bytes requested per read, if set.
+    pub fn read_size(&self) -> Option<usize> {
+        self.read_size
+    }
+
+    ///
+    /// Hashes with a Gear table generated at runtime from `seed` instead of the built-in one.
+    ///
+    /// The table and its masks are derived exactly as the build script does for `GEAR_SEED`,
+    /// so `with_seed(s)` matches a build with `GEAR_SEED=s`. Tables are cached per process,
+    /// making repeated calls with the same seed cheap. Different seeds produce unrelated
+    /// boundaries, so e.g. each tenant of a service can chunk with its own seed.
+    ///
+    /// ## Arguments
+    ///
+    /// * `seed`: The seed of the table generator.
+    ///
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        let table = GearTable::from_seed(seed);
+        self.masks = Masks::from_table(&table.masks, self.avg_size, self.normal)
+            .expect("normalization was validated on construction");
+        self.table = Some(table);
+        self.seed = Some(seed);
+        self
+    }
+
+    ///
+    /// Hashes with a caller-supplied Gear table instead of the built-in one.
+    ///
+    /// The masks are derived from `gear` the same way the build script derives them, so
+    /// passing the table another implementation uses (with the same sizes and rolling mode)
+    /// reproduces its boundaries byte for byte. Replaces any table set by [`FastCDC::with_seed`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `gear`: The 256-entry Gear table, indexed by byte value.
+    ///
+    pub fn with_gear_table(mut self, gear: &[u64; 256]) -> Self {
+        let table = GearTable::from_gear(*gear);
+        self.masks = Masks::from_table(&table.masks, self.avg_size, self.normal)
+            .expect("normalization was validated on construction");
+        self.table = Some(Arc::new(table));
+        self.seed = None;
+        self
+    }
+
+    ///
+    /// Overrides the masks judged before and after `avg_size`, e.g. with [`Masks::custom`].
+    ///
+    /// Together with [`FastCDC::with_gear_table`], this reproduces implementations that use
+    /// fixed published constants instead of masks derived from the table, as
+    /// [`FastCDC::paper`] does for the FastCDC 2020 paper's. Call it after any table
+    /// override, which re-derives the masks. The two-byte scan matches such implementations
+    /// directly; enable [`FastCDC::with_one_byte_rolling`] for ones that roll a single byte
+    /// per iteration.
+    ///
+    /// ## Arguments
+    ///
+    /// * `masks`: The masks to judge the rolling hash with.
+    ///
+    pub fn with_masks(mut self, masks: Masks) -> Self {
+        self.masks = masks;
+        self
+    }
+
+    /// Returns the masks judged before and after `avg_size`.
+    pub fn masks(&self) -> Masks {
+        self.masks
+    }
+
+    /// Returns the Gear tables used for hashing.
+    #[inline]
+    pub(crate) fn table(&self) -> &GearTable {
+        self.table.as_deref().unwrap_or(&BUILTIN)
+    }
+
+    /// Returns how the Gear table was chosen, as recorded in manifests.
+    pub(crate) fn table_id(&self) -> TableId {
+        match (&self.table, self.seed) {
+            (None, _) => TableId::builtin(),
+            (Some(_), Some(seed)) => TableId::Seed(seed),
+            (Some(table), None) => TableId::Custom(table.fingerprint()),
+        }
+    }
+
+    /// Returns the masks set by [`FastCDC::with_masks`], or `None` if they are derived from the table.
+    pub(crate) fn custom_masks(&self) -> Option<Masks> {
+        let derived = Masks::from_table(&self.table().masks, self.avg_size, self.normal).ok();
+        (derived != Some(self.masks)).then_some(self.masks)
+    }
+
+    /// Returns the on/off options that affect boundaries, as recorded in manifests.
+    pub(crate) fn option_bits(&self) -> u8 {
+        let mut bits = 0;
+        if self.one_byte {
+            bits |= OPTION_ONE_BYTE;
+        }
+        if !self.min_skip {
+            bits |= OPTION_NO_MIN_SKIP;
+        }
+        if self.utf8 {
+            bits |= OPTION_UTF8;
+        }
+        #[cfg(feature = "four-byte-rolling")]
+        if self.four_byte {
+            bits |= OPTION_FOUR_BYTE;
+        }
+        bits
+    }
+
+    /// Applies options recorded by [`FastCDC::option_bits`], or `None` if any bit is unknown.
+    pub(crate) fn with_option_bits(self, bits: u8) -> Option<Self> {
+        let known = OPTION_ONE_BYTE | OPTION_NO_MIN_SKIP | OPTION_UTF8;
+        #[cfg(feature = "four-byte-rolling")]
+        let known = known | OPTION_FOUR_BYTE;
+
+        if bits & !known != 0 {
+            return None;
+        }
+        let chunker = self
+            .with_one_byte_rolling(bits & OPTION_ONE_BYTE != 0)
+            .with_min_size_skip(bits & OPTION_NO_MIN_SKIP == 0)
+            .with_utf8_boundaries(bits & OPTION_UTF8 != 0);
+        #[cfg(feature = "four-byte-rolling")]
+        let chunker = chunker.with_four_byte_rolling(bits & OPTION_FOUR_BYTE != 0);
+
+        Some(chunker)
+    }
+
+    /// Returns the boundary alignment and tolerance, if set.
+    pub fn alignment(&self) -> Option<(usize, usize)> {
+        (self.alignment > 0).then_some((self.alignment, self.align_tolerance))
+    }
+
     ///
     /// Creates an iterator that yields chunks from the provided reader.
     ///
+    /// Each `read_vectored` call also fills a read-ahead buffer of at least 64 KB, so small
+    /// `max_size` values don't cost a syscall per chunk.
+    ///
     /// ## Arguments
     ///
     /// * `reader`: The source to read data from (must implement `Read`).
     ///
     pub fn chunks<R: Read>(&self, reader: R) -> FastCDCIter<'_, R> {
+        FastCDCIter::new(Cow::Borrowed(self), reader)
+    }
+
+    ///
+    /// Creates an iterator that owns the chunker, so it can be returned from a function or
+    /// moved to another thread.
+    ///
+    /// Yields the same chunks as [`FastCDC::chunks`]. Clone the chunker first to keep
+    /// using it; cloning is cheap, as custom Gear tables are shared.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn into_chunks<R: Read>(self, reader: R) -> FastCDCIter<'static, R> {
+        FastCDCIter::new(Cow::Owned(self), reader)
+    }
+
+    ///
+    /// Creates an iterator that resumes chunking at a chunk boundary of a larger stream.
+    ///
+    /// Chunking restarts from scratch at every boundary, so resuming at an offset the
+    /// stream was previously cut at yields exactly the chunks a full pass would have.
+    /// Chunk offsets continue from `offset`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source, positioned at `offset` (must implement `Read`).
+    /// * `offset`: The stream offset of a previously produced chunk boundary.
+    ///
+    pub fn chunks_at<R: Read>(&self, reader: R, offset: u64) -> FastCDCIter<'_, R> {
         FastCDCIter {
-            chunker: self,
-            reader,
-            buf: BytesMut::with_capacity(self.max_size),
-            processed: 0,
-            eof: false,
+            processed: offset,
+            ..self.chunks(reader)
         }
     }
 
+    ///
+    /// Creates an iterator that yields chunks from the provided reader,
+    /// reading ahead on a dedicated thread so I/O overlaps with cut-point scanning.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read` and `Send`).
+    /// * `depth`: The number of `max_size` buffers that may be read ahead of the scan.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `depth` is zero.
+    ///
+    pub fn chunks_read_ahead<R>(&self, reader: R, depth: usize) -> FastCDCIter<'_, ReadAhead>
+    where
+        R: Read + Send + 'static,
+    {
+        self.chunks(ReadAhead::new(reader, depth, self.max_size))
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from a file read with `O_DIRECT`,
+    /// bypassing the page cache.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The file to chunk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the file cannot be opened for direct I/O.
+    ///
+    #[cfg(all(feature = "direct-io", target_os = "linux"))]
+    pub fn chunks_direct<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+    ) -> io::Result<FastCDCIter<'_, crate::adapter::DirectReader>> {
+        Ok(self.chunks(crate::adapter::DirectReader::open(path)?))
+    }
+
+    ///
+    /// Creates an iterator that chunks the data regions of a sparse file,
+    /// reporting holes as records instead of reading them.
+    ///
+    /// ## Arguments
+    ///
+    /// * `file`: The file to chunk, read from its start.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the file's metadata cannot be read.
+    ///
+    #[cfg(all(feature = "sparse", target_os = "linux"))]
+    pub fn chunks_sparse(
+        &self,
+        file: std::fs::File,
+    ) -> io::Result<crate::adapter::SparseChunks<'_>> {
+        crate::adapter::SparseChunks::new(self, file)
+    }
+
+    ///
+    /// Creates an iterator that chunks a file read through io_uring, keeping `depth`
+    /// reads in flight. The iterator blocks while waiting for reads; see
+    /// [`UringChunks`](crate::adapter::UringChunks) for driving it from async code.
+    ///
+    /// ## Arguments
+    ///
+    /// * `file`: The file to chunk, read from its start.
+    /// * `depth`: The number of reads kept in flight.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the file's metadata cannot be read or the ring cannot be set up,
+    /// e.g. `ENOSYS` on kernels without io_uring or `EPERM` where it is disabled.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `depth` is zero.
+    ///
+    #[cfg(all(feature = "uring", target_os = "linux"))]
+    pub fn chunks_uring(
+        &self,
+        file: std::fs::File,
+        depth: usize,
+    ) -> io::Result<crate::adapter::UringChunks<'_>> {
+        crate::adapter::UringChunks::new(self, file, depth, false)
+    }
+
+    ///
+    /// Creates an iterator that chunks a file opened with `O_DIRECT` and read through
+    /// io_uring, so scans of huge files don't evict the page cache.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The file to chunk.
+    /// * `depth`: The number of reads kept in flight.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the file cannot be opened for direct I/O (e.g. `EINVAL` on
+    /// `tmpfs`) or the ring cannot be set up.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `depth` is zero.
+    ///
+    #[cfg(all(feature = "uring", feature = "direct-io", target_os = "linux"))]
+    pub fn chunks_uring_direct<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        depth: usize,
+    ) -> io::Result<crate::adapter::UringChunks<'_>> {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECT)
+            .open(path)?;
+        crate::adapter::UringChunks::new(self, file, depth, true)
+    }
+
+    ///
+    /// Applies delimiter snapping or boundary alignment to a cut point found in `window`,
+    /// the scanned bytes of a chunk starting at stream offset `offset`.
+    ///
+    /// Returns `None` if the adjustment needs bytes past the end of `window` and the
+    /// window is not `complete` (holding `max_size` bytes or the rest of the stream).
+    ///
+    #[inline]
+    pub(crate) fn adjust_cutpoint(
+        &self,
+        offset: u64,
+        window: &[u8],
+        cutpoint: usize,
+        complete: bool,
+    ) -> Option<usize> {
+        let cutpoint = match self.delimiter {
+            Some((delimiter, slack)) => {
+                self.snap_to_delimiter(window, cutpoint, delimiter, slack, complete)?
+            }
+            None => self.align_cutpoint(offset, cutpoint),
+        };
+
+        if self.utf8 {
+            self.snap_to_char(window, cutpoint, complete)
+        } else {
+            Some(cutpoint)
+        }
+    }
+
+    ///
+    /// Returns how many bytes at the start of the following data can affect where a chunk
+    /// ends, counted from its end.
+    ///
+    /// The scan judges the first byte after a cut together with the rest of its pair (or
+    /// quad), and the adjustments can move a cut away from where the scan found it.
+    ///
+    pub(crate) fn cut_lookahead(&self) -> u64 {
+        #[cfg(feature = "four-byte-rolling")]
+        let scan = if self.four_byte { 4 } else { 2 };
+        #[cfg(not(feature = "four-byte-rolling"))]
+        let scan = 2;
+
+        // Alignment pulls a cut back by up to `align_tolerance` bytes. Delimiter snapping
+        // moves it back by up to `slack` bytes after searching `slack` bytes past it.
+        let adjust = match self.delimiter {
+            Some((_, slack)) => 2 * slack,
+            None => self.align_tolerance,
+        };
+        // UTF-8 snapping moves the cut back by up to 3 bytes.
+        let utf8 = if self.utf8 { 3 } else { 0 };
+
+        (scan + adjust + utf8) as u64
+    }
+
+    fn snap_to_char(&self, window: &[u8], cutpoint: usize, complete: bool) -> Option<usize> {
+        // The last lead byte within 3 bytes of the cut; a longer run of continuation bytes
+        // is either a complete 4-byte sequence or not UTF-8 at all.
+        let back = window[cutpoint.saturating_sub(3)..cutpoint]
+            .iter()
+            .rposition(|&b| b & 0xC0 != 0x80);
+        let Some(i) = back else {
+            return Some(cutpoint);
+        };
+
+        let start = cutpoint.saturating_sub(3) + i;
+        let len = match window[start] {
+            b if b & 0xE0 == 0xC0 => 2,
+            b if b & 0xF0 == 0xE0 => 3,
+            b if b & 0xF8 == 0xF0 => 4,
+            _ => 1,
+        };
+        let end = start + len;
+
+        if end <= cutpoint {
+            return Some(cutpoint);
+        }
+        if start >= self.min_size {
+            return Some(start);
+        }
+
+        // Moving back would undercut `min_size`, so move past the sequence if possible.
+        if end > window.len() && !complete {
+            return None;
+        }
+        if end <= window.len() && end <= self.max_size {
+            Some(end)
+        } else {
+            Some(start)
+        }
+    }
+
+    fn snap_to_delimiter(
+        &self,
+        window: &[u8],
+        cutpoint: usize,
+        delimiter: u8,
+        slack: usize,
+        complete: bool,
+    ) -> Option<usize> {
+        // Index of the last byte of the chunk, and the furthest it may move forward.
+        let end = cutpoint - 1;
+        let last = (end + slack).min(self.max_size - 1);
+
+        if last >= window.len() && !complete {
+            return None;
+        }
+
+        let forward = &window[end..=last.min(window.len() - 1)];
+        if let Some(i) = forward.iter().position(|&b| b == delimiter) {
+            return Some(cutpoint + i);
+        }
+
+        let first = end.saturating_sub(slack).max(self.min_size - 1);
+        let backward = &window[first.min(end)..end];
+        Some(
+            backward
+                .iter()
+                .rposition(|&b| b == delimiter)
+                .map_or(cutpoint, |i| first + i + 1),
+        )
+    }
+
+    /// Applies the boundary alignment to a cut point found at stream offset `offset`.
     #[inline]
-    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+    fn align_cutpoint(&self, offset: u64, cutpoint: usize) -> usize {
+        if self.alignment == 0 {
+            return cutpoint;
+        }
+
+        let end = offset + cutpoint as u64;
+        let aligned = end - end % self.alignment as u64;
+        let shift = (end - aligned) as usize;
+
+        if shift <= self.align_tolerance && cutpoint - shift >= self.min_size {
+            cutpoint - shift
+        } else {
+            cutpoint
+        }
+    }
+
+    #[inline]
+    pub(crate) fn find_cutpoint_from(
+        &self,
+        source: &[u8],
+        offset: usize,
+        prev_hash: u64,
+    ) -> (u64, usize) {
+        let hash_start = if self.min_skip { self.min_size } else { 0 };
+
+        #[cfg(feature = "four-byte-rolling")]
+        if self.four_byte {
+            return find_cutpoint_quad(
+                self.table(),
+                source,
+                offset,
+                prev_hash,
+                hash_start,
+                self.min_size,
+                self.avg_size,
+                self.max_size,
+                self.masks.mask_s,
+                self.masks.mask_l,
+            );
+        }
+
+        if self.one_byte {
+            return find_cutpoint_single(
+                &mut TableGear::new(self.table(), prev_hash),
+                source,
+                offset,
+                hash_start,
+                self.min_size,
+                self.avg_size,
+                self.max_size,
+                self.masks.mask_s,
+                self.masks.mask_l,
+            );
+        }
+
         find_cutpoint_inner(
+            self.table(),
             source,
-            0,
-            0,
+            offset,
+            prev_hash,
+            hash_start,
             self.min_size,
             self.avg_size,
             self.max_size,
@@ -160,47 +1043,156 @@ impl FastCDC {
             self.masks.mask_l_ls,
         )
     }
+
+    /// Returns the offset to resume scanning at after `scan_len` bytes found no cut point.
+    #[inline]
+    pub(super) fn resume_offset(&self, scan_len: usize) -> usize {
+        // The two-byte scan only consumes whole pairs, the four-byte one whole quads.
+        #[cfg(feature = "four-byte-rolling")]
+        if self.four_byte {
+            return ((scan_len / 4) * 4).max(self.min_size);
+        }
+
+        let scanned = if self.one_byte {
+            scan_len
+        } else {
+            (scan_len / 2) * 2
+        };
+        scanned.max(self.min_size)
+    }
 }
 
 pub struct FastCDCIter<'a, R: Read> {
-    chunker: &'a FastCDC,
+    chunker: Cow<'a, FastCDC>,
     reader: R,
     buf: BytesMut,
+    buf_init: SpareInit,
+    spare: Box<[u8]>,
+    spare_pos: usize,
+    spare_len: usize,
     processed: u64,
     eof: bool,
+    peak: usize,
+    pool: Option<BufferPool>,
+    #[cfg(feature = "stats")]
+    stats: crate::fastcdc::ChunkerStats,
+}
+
+impl<'a, R: Read> FastCDCIter<'a, R> {
+    fn new(chunker: Cow<'a, FastCDC>, reader: R) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(chunker.max_size),
+            buf_init: SpareInit::default(),
+            spare: vec![
+                0u8;
+                chunker
+                    .read_size
+                    .unwrap_or(chunker.max_size.max(SPARE_SIZE_MIN))
+            ]
+            .into_boxed_slice(),
+            spare_pos: 0,
+            spare_len: 0,
+            chunker,
+            reader,
+            processed: 0,
+            eof: false,
+            peak: 0,
+            pool: None,
+            #[cfg(feature = "stats")]
+            stats: Default::default(),
+        }
+    }
+
+    ///
+    /// Copies every chunk into a buffer drawn from `pool`, recycled when the chunk is dropped.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to draw chunk buffers from.
+    ///
+    pub fn with_pool(mut self, pool: BufferPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Returns the bytes currently buffered, including those read ahead, and the peak
+    /// reached so far.
+    pub fn buffer_usage(&self) -> BufferUsage {
+        BufferUsage {
+            buffered: self.buffered(),
+            peak: self.peak,
+        }
+    }
+
+    fn buffered(&self) -> usize {
+        self.buf.len() + self.spare_len - self.spare_pos
+    }
+
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        &self.stats
+    }
 }
 
 impl<'a, R: Read> Iterator for FastCDCIter<'a, R> {
     type Item = io::Result<Chunk>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.eof && self.buf.is_empty() {
+        if self.eof && self.buffered() == 0 {
             return None;
         }
 
-        while !self.eof && self.buf.len() < self.chunker.max_size {
+        while self.buf.len() < self.chunker.max_size {
             let buf_len = self.buf.len();
             let needed = self.chunker.max_size - buf_len;
 
-            self.buf.reserve(needed);
-
-            // SAFETY: Capacity is guaranteed via `reserve`. We only increase length
-            // after writing to the uninitialized region to prevent undefined behavior.
-            unsafe {
-                let ptr = self.buf.as_mut_ptr().add(buf_len);
-                let dst = std::slice::from_raw_parts_mut(ptr, needed);
-
-                match self.reader.read(dst) {
-                    Ok(0) => {
-                        self.eof = true;
-                        break;
-                    }
-                    Ok(n) => {
-                        self.buf.set_len(buf_len + n);
-                    }
-                    Err(e) => {
-                        return Some(Err(e));
-                    }
+            // Bytes read ahead by the previous call complete the window without a syscall.
+            if self.spare_pos < self.spare_len {
+                let take = needed.min(self.spare_len - self.spare_pos);
+                self.buf_init
+                    .spare(&mut self.buf, take)
+                    .copy_from_slice(&self.spare[self.spare_pos..self.spare_pos + take]);
+                // SAFETY: The `take` bytes of spare capacity were just written.
+                unsafe { self.buf.set_len(buf_len + take) };
+                self.buf_init.filled(take);
+                self.spare_pos += take;
+                continue;
+            }
+            if self.eof {
+                break;
+            }
+
+            // A configured read size is split between the window and the read-ahead.
+            let (window, ahead) = match self.chunker.read_size {
+                Some(read_size) => (needed.min(read_size), read_size.saturating_sub(needed)),
+                None => (needed, self.spare.len()),
+            };
+
+            // One call fills the window and reads ahead into the spare buffer, so small
+            // windows don't cost a syscall each. Readers without vectored reads fill the
+            // window only.
+            let dst = self.buf_init.spare(&mut self.buf, window);
+            let mut bufs = [
+                IoSliceMut::new(dst),
+                IoSliceMut::new(&mut self.spare[..ahead]),
+            ];
+            match self.reader.read_vectored(&mut bufs) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(n) => {
+                    let filled = n.min(window);
+                    // SAFETY: The spare capacity read into is initialized.
+                    unsafe { self.buf.set_len(buf_len + filled) };
+                    self.buf_init.filled(filled);
+                    self.spare_pos = 0;
+                    self.spare_len = n.saturating_sub(window);
+                    self.peak = self.peak.max(self.buffered());
+                }
+                Err(e) => {
+                    return Some(Err(e));
                 }
             }
         }
@@ -210,9 +1202,29 @@ impl<'a, R: Read> Iterator for FastCDCIter<'a, R> {
         }
 
         let scan_len = self.buf.len().min(self.chunker.max_size);
-        let (fp_hash, cutpoint) = self.chunker.find_cutpoint(&self.buf[..scan_len]);
+        let (fp_hash, mut cutpoint) = self.chunker.find_cutpoint_from(&self.buf[..scan_len], 0, 0);
+
+        #[cfg(feature = "stats")]
+        self.stats.record(&self.chunker, cutpoint, scan_len);
+
+        // The tail of the stream is never moved, as no data follows it.
+        if !(self.eof && cutpoint == self.buf.len()) {
+            // The window holds `max_size` bytes or the rest of the stream, so it is complete.
+            cutpoint = self
+                .chunker
+                .adjust_cutpoint(self.processed, &self.buf[..scan_len], cutpoint, true)
+                .unwrap_or(cutpoint);
+        }
 
-        let data = self.buf.split_to(cutpoint).freeze();
+        // A pooled copy leaves the read buffer unshared, so it is reused in place.
+        let data = match &self.pool {
+            Some(pool) => {
+                let data = pool.copy_from_slice(&self.buf[..cutpoint]);
+                self.buf.advance(cutpoint);
+                data
+            }
+            None => self.buf.split_to(cutpoint).freeze(),
+        };
 
         let chunk = Chunk {
             fp_hash,
@@ -1,20 +1,32 @@
 include!(concat!(env!("OUT_DIR"), "/gear_table.rs"));
 
+use crate::fastcdc::table::GearTable;
+use crate::hash::RollingHash;
+use std::ops::ControlFlow;
+
+#[cfg(feature = "simd")]
+use crate::fastcdc::simd::scan_pairs;
+#[cfg(not(feature = "simd"))]
+use scan_pairs_scalar as scan_pairs;
+
 ///
 /// Identifies the cut point (chunk boundary) within the buffer using the FastCDC algorithm,
 /// with support for incremental scanning.
 ///
 /// This function enables resuming the scanning process from a specific offset using a previously
 /// computed hash value. This avoids re-scanning processed bytes, optimizing performance for
-/// streaming or buffered data.
+/// streaming or buffered data. With the `simd` feature, the judged pairs are scanned by
+/// lane-parallel kernels chosen at runtime, yielding the same results.
 ///
 /// Returns a tuple containing the current rolling hash and the cut point offset.
 ///
 /// ## Arguments
 ///
+/// * `table`: The Gear tables to hash with.
 /// * `source`: The input data buffer to scan.
 /// * `offset`: The byte offset to resume scanning from. Should be aligned to a 2-byte boundary.
 /// * `prev_hash`: The rolling hash state at the given `offset`.
+/// * `hash_start`: Where hashing starts when scanning from scratch (`min_size` to skip the prefix).
 /// * `min_size`: The minimum allowed chunk size.
 /// * `avg_size`: The target average chunk size.
 /// * `max_size`: The maximum allowed chunk size.
@@ -26,9 +38,11 @@ include!(concat!(env!("OUT_DIR"), "/gear_table.rs"));
 #[allow(clippy::too_many_arguments)]
 #[inline]
 pub(super) fn find_cutpoint_inner(
+    table: &GearTable,
     source: &[u8],
     offset: usize,
     prev_hash: u64,
+    hash_start: usize,
     min_size: usize,
     avg_size: usize,
     max_size: usize,
@@ -44,7 +58,14 @@ pub(super) fn find_cutpoint_inner(
     }
 
     let (mut start_idx, mut fp_hash) = if offset < min_size {
-        ((min_size / 2), 0u64)
+        // Warm the hash up over the prefix without judging it (empty when skipping).
+        let mut fp_hash = 0u64;
+        for pair_idx in (hash_start / 2)..(min_size / 2) {
+            let byte_idx = pair_idx * 2;
+            fp_hash = (fp_hash << 2).wrapping_add(table.gear_ls[source[byte_idx] as usize]);
+            fp_hash = fp_hash.wrapping_add(table.gear[source[byte_idx + 1] as usize]);
+        }
+        ((min_size / 2), fp_hash)
     } else {
         let aligned_offset = (offset / 2) * 2;
         ((aligned_offset / 2), prev_hash)
@@ -54,49 +75,310 @@ pub(super) fn find_cutpoint_inner(
     let end_idx = scan_len / 2;
 
     if start_idx < center_idx {
-        for pair_idx in start_idx..center_idx {
-            let byte_idx = pair_idx * 2;
+        match scan_pairs(
+            table, source, start_idx, center_idx, fp_hash, mask_s_ls, mask_s,
+        ) {
+            ControlFlow::Break(cut) => return cut,
+            ControlFlow::Continue(hash) => fp_hash = hash,
+        }
+        start_idx = center_idx;
+    }
 
-            if byte_idx + 1 >= source.len() {
-                break;
-            }
+    start_idx = start_idx.max(center_idx);
 
-            fp_hash = (fp_hash << 2).wrapping_add(GEAR_LS[source[byte_idx] as usize]);
+    match scan_pairs(
+        table, source, start_idx, end_idx, fp_hash, mask_l_ls, mask_l,
+    ) {
+        ControlFlow::Break(cut) => cut,
+        ControlFlow::Continue(hash) => (hash, scan_len),
+    }
+}
 
-            if (fp_hash & mask_s_ls) == 0 {
-                return (fp_hash, byte_idx);
-            }
+///
+/// Rolls the two-byte gear hash over the pairs `start_idx..end_idx`, judging every byte
+/// against one pair of masks.
+///
+/// Breaks with the fingerprint and offset of the first cut point, or continues with the
+/// hash after the last pair.
+///
+#[allow(clippy::too_many_arguments)]
+#[inline]
+pub(super) fn scan_pairs_scalar(
+    table: &GearTable,
+    source: &[u8],
+    start_idx: usize,
+    end_idx: usize,
+    mut fp_hash: u64,
+    mask_ls: u64,
+    mask: u64,
+) -> ControlFlow<(u64, usize), u64> {
+    for pair_idx in start_idx..end_idx {
+        let byte_idx = pair_idx * 2;
 
-            fp_hash = fp_hash.wrapping_add(GEAR[source[byte_idx + 1] as usize]);
+        if byte_idx + 1 >= source.len() {
+            break;
+        }
 
-            if (fp_hash & mask_s) == 0 {
-                return (fp_hash, byte_idx + 1);
-            }
+        fp_hash = (fp_hash << 2).wrapping_add(table.gear_ls[source[byte_idx] as usize]);
+
+        if (fp_hash & mask_ls) == 0 {
+            return ControlFlow::Break((fp_hash, byte_idx));
+        }
+
+        fp_hash = fp_hash.wrapping_add(table.gear[source[byte_idx + 1] as usize]);
+
+        if (fp_hash & mask) == 0 {
+            return ControlFlow::Break((fp_hash, byte_idx + 1));
+        }
+    }
+
+    ControlFlow::Continue(fp_hash)
+}
+
+///
+/// Identifies the cut point with the gear hash unrolled to four bytes per iteration.
+///
+/// Produces the same hash sequence as [`find_cutpoint_single`], judged in whole quads: the
+/// hash before the `k`-th byte of a quad is shifted left by `3 - k` bits and judged against
+/// the masks shifted alike. Resuming works at offsets that are multiples of 4.
+///
+/// Returns a tuple containing the current rolling hash and the cut point offset.
+///
+/// ## Arguments
+///
+/// * `table`: The Gear tables to hash with.
+/// * `source`: The input data buffer to scan.
+/// * `offset`: The byte offset to resume scanning from. Should be aligned to a 4-byte boundary.
+/// * `prev_hash`: The rolling hash state at the given `offset`.
+/// * `hash_start`: Where hashing starts when scanning from scratch (`min_size` to skip the prefix).
+/// * `min_size`: The minimum allowed chunk size.
+/// * `avg_size`: The target average chunk size.
+/// * `max_size`: The maximum allowed chunk size.
+/// * `mask_s`: Bitmask for the region smaller than the average size.
+/// * `mask_l`: Bitmask for the region larger than the average size.
+///
+#[cfg(feature = "four-byte-rolling")]
+#[allow(clippy::too_many_arguments)]
+#[inline]
+pub(super) fn find_cutpoint_quad(
+    table: &GearTable,
+    source: &[u8],
+    offset: usize,
+    prev_hash: u64,
+    hash_start: usize,
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    mask_s: u64,
+    mask_l: u64,
+) -> (u64, usize) {
+    let scan_len = source.len().min(max_size);
+
+    if scan_len <= min_size {
+        return (prev_hash, scan_len);
+    }
+
+    let (mut start_idx, mut fp_hash) = if offset < min_size {
+        // Warm the hash up over the prefix without judging it (empty when skipping).
+        let mut fp_hash = 0u64;
+        for quad_idx in (hash_start / 4)..(min_size / 4) {
+            let quad = &source[quad_idx * 4..quad_idx * 4 + 4];
+            fp_hash = (fp_hash << 4)
+                .wrapping_add(table.gear_ls3[quad[0] as usize])
+                .wrapping_add(table.gear_ls2[quad[1] as usize])
+                .wrapping_add(table.gear_ls[quad[2] as usize])
+                .wrapping_add(table.gear[quad[3] as usize]);
+        }
+        ((min_size / 4), fp_hash)
+    } else {
+        ((offset / 4), prev_hash)
+    };
+
+    let center_idx = avg_size.min(scan_len) / 4;
+    let end_idx = scan_len / 4;
+
+    if start_idx < center_idx {
+        match scan_quads(table, source, start_idx, center_idx, fp_hash, mask_s) {
+            ControlFlow::Break(cut) => return cut,
+            ControlFlow::Continue(hash) => fp_hash = hash,
         }
         start_idx = center_idx;
     }
 
     start_idx = start_idx.max(center_idx);
 
-    for pair_idx in start_idx..end_idx {
-        let byte_idx = pair_idx * 2;
+    match scan_quads(table, source, start_idx, end_idx, fp_hash, mask_l) {
+        ControlFlow::Break(cut) => cut,
+        ControlFlow::Continue(hash) => (hash, scan_len),
+    }
+}
 
-        if byte_idx + 1 >= source.len() {
-            break;
+/// Rolls the four-byte gear hash over the quads `start_idx..end_idx`, judging every byte.
+#[cfg(feature = "four-byte-rolling")]
+#[inline]
+fn scan_quads(
+    table: &GearTable,
+    source: &[u8],
+    start_idx: usize,
+    end_idx: usize,
+    mut fp_hash: u64,
+    mask: u64,
+) -> ControlFlow<(u64, usize), u64> {
+    let (mask_ls3, mask_ls2, mask_ls) = (mask << 3, mask << 2, mask << 1);
+
+    for quad_idx in start_idx..end_idx {
+        let byte_idx = quad_idx * 4;
+        let quad = &source[byte_idx..byte_idx + 4];
+
+        fp_hash = (fp_hash << 4).wrapping_add(table.gear_ls3[quad[0] as usize]);
+        if (fp_hash & mask_ls3) == 0 {
+            return ControlFlow::Break((fp_hash, byte_idx));
         }
 
-        fp_hash = (fp_hash << 2).wrapping_add(GEAR_LS[source[byte_idx] as usize]);
+        fp_hash = fp_hash.wrapping_add(table.gear_ls2[quad[1] as usize]);
+        if (fp_hash & mask_ls2) == 0 {
+            return ControlFlow::Break((fp_hash, byte_idx + 1));
+        }
+
+        fp_hash = fp_hash.wrapping_add(table.gear_ls[quad[2] as usize]);
+        if (fp_hash & mask_ls) == 0 {
+            return ControlFlow::Break((fp_hash, byte_idx + 2));
+        }
+
+        fp_hash = fp_hash.wrapping_add(table.gear[quad[3] as usize]);
+        if (fp_hash & mask) == 0 {
+            return ControlFlow::Break((fp_hash, byte_idx + 3));
+        }
+    }
+
+    ControlFlow::Continue(fp_hash)
+}
+
+///
+/// The gear hash over one of the tables of a [`GearTable`], rolled by [`find_cutpoint_single`].
+///
+pub(super) struct TableGear<'a> {
+    gear: &'a [u64; 256],
+    hash: u64,
+}
 
-        if (fp_hash & mask_l_ls) == 0 {
-            return (fp_hash, byte_idx);
+impl<'a> TableGear<'a> {
+    /// Creates the hash over `table` with the state `hash`.
+    #[inline]
+    pub(super) fn new(table: &'a GearTable, hash: u64) -> Self {
+        Self {
+            gear: &table.gear,
+            hash,
         }
+    }
+}
+
+impl RollingHash for TableGear<'_> {
+    fn window(&self) -> usize {
+        64
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.hash = 0;
+    }
+
+    #[inline]
+    fn push(&mut self, byte: u8) {
+        self.hash = (self.hash << 1).wrapping_add(self.gear[byte as usize]);
+    }
+
+    #[inline]
+    fn roll(&mut self, _out: u8, inp: u8) {
+        self.push(inp);
+    }
+
+    #[inline]
+    fn digest(&self) -> u64 {
+        self.hash
+    }
+}
+
+///
+/// Identifies the cut point by rolling `hash` one byte per iteration.
+///
+/// With a [`TableGear`], this produces the same hash sequence as the original FastCDC
+/// formulation, at roughly half the speed of [`find_cutpoint_inner`]. Resuming works at any
+/// byte `offset`.
+///
+/// Returns a tuple containing the current rolling hash and the cut point offset.
+///
+/// ## Arguments
+///
+/// * `hash`: The rolling hash, holding its state at `offset` when resuming; reset otherwise.
+/// * `source`: The input data buffer to scan.
+/// * `offset`: The byte offset to resume scanning from.
+/// * `hash_start`: Where hashing starts when scanning from scratch (`min_size` to skip the prefix).
+/// * `min_size`: The minimum allowed chunk size.
+/// * `avg_size`: The target average chunk size.
+/// * `max_size`: The maximum allowed chunk size.
+/// * `mask_s`: Bitmask for the region smaller than the average size.
+/// * `mask_l`: Bitmask for the region larger than the average size.
+///
+#[allow(clippy::too_many_arguments)]
+#[inline]
+pub(super) fn find_cutpoint_single<H: RollingHash>(
+    hash: &mut H,
+    source: &[u8],
+    offset: usize,
+    hash_start: usize,
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    mask_s: u64,
+    mask_l: u64,
+) -> (u64, usize) {
+    let scan_len = source.len().min(max_size);
+
+    if scan_len <= min_size {
+        return (hash.digest(), scan_len);
+    }
+
+    // Pushes the byte at `idx` while the window fills, then slides the window over it.
+    let window = hash.window();
+    let feed = |hash: &mut H, idx: usize| {
+        if idx - hash_start < window {
+            hash.push(source[idx]);
+        } else {
+            hash.roll(source[idx - window], source[idx]);
+        }
+    };
+
+    let mut idx = if offset < min_size {
+        // Warm the hash up over the prefix without judging it (empty when skipping).
+        hash.reset();
+        for idx in hash_start..min_size {
+            feed(hash, idx);
+        }
+        min_size
+    } else {
+        offset
+    };
+
+    let center = avg_size.min(scan_len);
+
+    while idx < center {
+        feed(hash, idx);
+
+        if (hash.digest() & mask_s) == 0 {
+            return (hash.digest(), idx);
+        }
+        idx += 1;
+    }
 
-        fp_hash = fp_hash.wrapping_add(GEAR[source[byte_idx + 1] as usize]);
+    while idx < scan_len {
+        feed(hash, idx);
 
-        if (fp_hash & mask_l) == 0 {
-            return (fp_hash, byte_idx + 1);
+        if (hash.digest() & mask_l) == 0 {
+            return (hash.digest(), idx);
         }
+        idx += 1;
     }
 
-    (fp_hash, scan_len)
+    (hash.digest(), scan_len)
 }
@@ -0,0 +1,180 @@
+use crate::fastcdc::{BufferPool, Chunk, FastCDC};
+use bytes::{Buf, Bytes, BytesMut};
+use std::{borrow::Cow, io};
+
+impl FastCDC {
+    /// Creates a decoder that cuts chunks from a caller-managed buffer.
+    pub fn decoder(&self) -> FastCDCDecoder<'_> {
+        FastCDCDecoder::new(Cow::Borrowed(self))
+    }
+
+    /// Creates a decoder that owns the chunker.
+    pub fn into_decoder(self) -> FastCDCDecoder<'static> {
+        FastCDCDecoder::new(Cow::Owned(self))
+    }
+}
+
+///
+/// Cuts chunks out of a buffer the caller fills, in the shape of a framing codec.
+///
+/// [`decode`](FastCDCDecoder::decode) and [`decode_eof`](FastCDCDecoder::decode_eof) have
+/// the signatures of `tokio_util::codec::Decoder`, so the decoder can back a `FramedRead`
+/// by forwarding to them. The scan resumes where the previous call stopped, so feeding the
+/// buffer a few bytes at a time stays O(N), and the chunks match [`FastCDC::chunks`].
+///
+pub struct FastCDCDecoder<'a> {
+    pub(super) chunker: Cow<'a, FastCDC>,
+    pub(super) processed: u64,
+    scanned: usize,
+    fp_hash: u64,
+    pub(super) pool: Option<BufferPool>,
+    #[cfg(feature = "stats")]
+    stats: crate::fastcdc::ChunkerStats,
+}
+
+impl<'a> FastCDCDecoder<'a> {
+    pub(super) fn new(chunker: Cow<'a, FastCDC>) -> Self {
+        Self {
+            chunker,
+            processed: 0,
+            scanned: 0,
+            fp_hash: 0,
+            pool: None,
+            #[cfg(feature = "stats")]
+            stats: Default::default(),
+        }
+    }
+
+    /// Returns the number of bytes emitted as chunks so far.
+    pub fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        &self.stats
+    }
+
+    ///
+    /// Splits the next chunk off the front of `src`, if its cut point is already known.
+    ///
+    /// Returns `Ok(None)` when more data is needed; the bytes are left in `src`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `src`: The buffered input. Only ever appended to between calls.
+    ///
+    pub fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Chunk>> {
+        Ok(self.next_chunk(src, false))
+    }
+
+    ///
+    /// Splits the next chunk off the front of `src` once the input has ended, flushing
+    /// the remaining bytes as the last chunk.
+    ///
+    /// Returns `Ok(None)` once `src` is empty.
+    ///
+    /// ## Arguments
+    ///
+    /// * `src`: The buffered input.
+    ///
+    pub fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<Chunk>> {
+        Ok(self.next_chunk(src, true))
+    }
+
+    pub(super) fn next_chunk(&mut self, buf: &mut BytesMut, eof: bool) -> Option<Chunk> {
+        let (cutpoint, fp_hash) = self.next_cutpoint(buf, eof)?;
+        let data = match &self.pool {
+            Some(pool) => {
+                let data = pool.copy_from_slice(&buf[..cutpoint]);
+                buf.advance(cutpoint);
+                data
+            }
+            None => buf.split_to(cutpoint).freeze(),
+        };
+        Some(self.emit(data, fp_hash))
+    }
+
+    /// Finds the end of the chunk starting at `buf[0]`, returning its length and fingerprint.
+    pub(super) fn next_cutpoint(&mut self, buf: &[u8], eof: bool) -> Option<(usize, u64)> {
+        if buf.is_empty() || (buf.len() < self.chunker.min_size && !eof) {
+            return None;
+        }
+
+        let scan_len = buf.len().min(self.chunker.max_size);
+
+        // Resume search from `scanned` offset using saved `fp_hash` to ensure O(N) complexity.
+        let (new_fp_hash, found_cutpoint) =
+            self.chunker
+                .find_cutpoint_from(&buf[..scan_len], self.scanned, self.fp_hash);
+
+        let cutpoint = match found_cutpoint {
+            // A valid cutpoint found by the rolling hash.
+            cp if cp < scan_len => Some(cp),
+
+            // Force a cut if the buffer exceeds the maximum chunk size to prevent memory issues.
+            _ if buf.len() >= self.chunker.max_size => Some(self.chunker.max_size),
+
+            // Flush the remaining bytes as the last chunk if the stream has ended.
+            _ if eof => Some(scan_len),
+
+            // Return `None` to wait for more data if no conditions are met.
+            _ => None,
+        };
+
+        let complete = eof || buf.len() >= self.chunker.max_size;
+        let adjusted = match cutpoint {
+            // The tail of the stream is never moved, as no data follows it.
+            Some(cp) if eof && cp == buf.len() => Some(cp),
+            Some(cp) => {
+                self.chunker
+                    .adjust_cutpoint(self.processed, &buf[..scan_len], cp, complete)
+            }
+            None => None,
+        };
+
+        match adjusted {
+            Some(cp) => {
+                #[cfg(feature = "stats")]
+                self.stats.record(&self.chunker, found_cutpoint, scan_len);
+
+                Some((cp, new_fp_hash))
+            }
+            // The cut point is known, but adjusting it needs more data; the scan is
+            // repeated from the same state once it arrives.
+            None if cutpoint.is_some() => None,
+            None => {
+                // Skip already checked bytes (whole pairs in the two-byte mode).
+                self.scanned = self.chunker.resume_offset(scan_len);
+                self.fp_hash = new_fp_hash;
+                None
+            }
+        }
+    }
+
+    /// Emits the chunk found by [`next_cutpoint`](Self::next_cutpoint) and resets the scan.
+    pub(super) fn emit(&mut self, data: Bytes, fp_hash: u64) -> Chunk {
+        let length = data.len();
+        let chunk = Chunk {
+            fp_hash,
+            data,
+            offset: self.processed,
+            length,
+        };
+        self.advance(length);
+
+        chunk
+    }
+
+    /// Moves past a chunk of `length` bytes and resets the scan.
+    pub(super) fn advance(&mut self, length: usize) {
+        self.processed += length as u64;
+        self.scanned = 0;
+        self.fp_hash = 0;
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/decoder_tests.rs"]
+mod tests;
@@ -0,0 +1,131 @@
+use crate::fastcdc::{BufferUsage, Chunk, FastCDC, FastCDCDecoder};
+use bytes::BytesMut;
+use futures::{Stream, stream::FusedStream};
+use std::{
+    borrow::Cow,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+impl FastCDC {
+    ///
+    /// Creates a stream that yields chunks from a stream of byte frames, such as an HTTP
+    /// body or a sequence of messages.
+    ///
+    /// The rolling hash carries across frame boundaries, so the chunks match
+    /// [`FastCDC::chunks`] over the concatenated frames.
+    ///
+    /// ## Arguments
+    ///
+    /// * `frames`: The source of frames (must implement `Stream<Item = Result<B, E>>`).
+    ///
+    pub fn chunks_frames<S, B, E>(&self, frames: S) -> FastCDCFrames<'_, S>
+    where
+        S: Stream<Item = Result<B, E>> + Unpin,
+        B: AsRef<[u8]>,
+    {
+        FastCDCFrames::new(Cow::Borrowed(self), frames)
+    }
+
+    ///
+    /// Creates a stream over byte frames that owns the chunker.
+    ///
+    /// Yields the same chunks as [`FastCDC::chunks_frames`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `frames`: The source of frames (must implement `Stream<Item = Result<B, E>>`).
+    ///
+    pub fn into_frame_stream<S, B, E>(self, frames: S) -> FastCDCFrames<'static, S>
+    where
+        S: Stream<Item = Result<B, E>> + Unpin,
+        B: AsRef<[u8]>,
+    {
+        FastCDCFrames::new(Cow::Owned(self), frames)
+    }
+}
+
+///
+/// A stream of chunks cut from a stream of byte frames.
+///
+/// Created by [`FastCDC::chunks_frames`]. An error from the source is passed through
+/// unchanged, and the bytes buffered so far are kept.
+///
+pub struct FastCDCFrames<'a, S> {
+    decoder: FastCDCDecoder<'a>,
+    frames: S,
+    buf: BytesMut,
+    eof: bool,
+    peak: usize,
+}
+
+impl<'a, S> FastCDCFrames<'a, S> {
+    fn new(chunker: Cow<'a, FastCDC>, frames: S) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(chunker.max_size),
+            decoder: FastCDCDecoder::new(chunker),
+            frames,
+            eof: false,
+            peak: 0,
+        }
+    }
+
+    /// Returns the bytes currently buffered and the peak reached so far.
+    pub fn buffer_usage(&self) -> BufferUsage {
+        BufferUsage {
+            buffered: self.buf.len(),
+            peak: self.peak,
+        }
+    }
+
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        self.decoder.stats()
+    }
+}
+
+impl<'a, S, B, E> Stream for FastCDCFrames<'a, S>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    type Item = Result<Chunk, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(chunk) = this.decoder.next_chunk(&mut this.buf, this.eof) {
+                return Poll::Ready(Some(Ok(chunk)));
+            }
+            if this.eof {
+                return Poll::Ready(None);
+            }
+
+            match Pin::new(&mut this.frames).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(Some(Ok(frame))) => {
+                    this.buf.extend_from_slice(frame.as_ref());
+                    this.peak = this.peak.max(this.buf.len());
+                }
+                Poll::Ready(None) => this.eof = true,
+            }
+        }
+    }
+}
+
+impl<'a, S, B, E> FusedStream for FastCDCFrames<'a, S>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    fn is_terminated(&self) -> bool {
+        self.eof && self.buf.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/frames_tests.rs"]
+mod tests;
@@ -0,0 +1,120 @@
+use crate::chunker::{ChunkIter, CutPoint};
+use crate::fastcdc::FastCDC;
+use crate::fastcdc::cut::find_cutpoint_single;
+use crate::hash::RollingHash;
+use std::io::Read;
+
+impl FastCDC {
+    ///
+    /// Replaces the Gear hash with a custom rolling hash, keeping normalized chunking and
+    /// sub-minimum skipping.
+    ///
+    /// Cut points are judged against the same masks as the Gear hash, which test bits spread
+    /// across all 64 bits of the digest, so narrower hashes should spread their output (e.g. by
+    /// multiplying by an odd constant). Each byte is rolled individually, as with
+    /// [`FastCDC::with_one_byte_rolling`]; alignment, delimiter, and UTF-8 snapping apply as
+    /// they do to `FastCDC`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `hash`: The boundary hash, cloned and reset at the start of every chunk.
+    ///
+    pub fn with_rolling_hash<H: RollingHash + Clone>(self, hash: H) -> HashedCDC<H> {
+        HashedCDC {
+            chunker: self,
+            hash,
+        }
+    }
+}
+
+/// A FastCDC chunker driven by a custom [`RollingHash`], created by [`FastCDC::with_rolling_hash`].
+#[derive(Debug, Clone)]
+pub struct HashedCDC<H> {
+    chunker: FastCDC,
+    hash: H,
+}
+
+impl<H: RollingHash + Clone> HashedCDC<H> {
+    /// Returns the underlying chunker parameters.
+    pub fn chunker(&self) -> &FastCDC {
+        &self.chunker
+    }
+
+    /// Returns the boundary hash.
+    pub fn hash(&self) -> &H {
+        &self.hash
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+}
+
+impl<H: RollingHash + Clone> CutPoint for HashedCDC<H> {
+    fn max_size(&self) -> usize {
+        self.chunker.max_size
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        self.find_cutpoint_at(0, source)
+    }
+
+    fn find_cutpoint_at(&self, offset: u64, source: &[u8]) -> (u64, usize) {
+        let chunker = &self.chunker;
+        let scan_len = source.len().min(chunker.max_size);
+        let hash_start = if chunker.min_skip {
+            chunker.min_size
+        } else {
+            0
+        };
+
+        let (fp_hash, cutpoint) = find_cutpoint_single(
+            &mut self.hash.clone(),
+            &source[..scan_len],
+            0,
+            hash_start,
+            chunker.min_size,
+            chunker.avg_size,
+            chunker.max_size,
+            chunker.masks.mask_s,
+            chunker.masks.mask_l,
+        );
+
+        // The tail of the stream is never moved, as no data follows it.
+        if cutpoint == source.len() && source.len() < chunker.max_size {
+            return (fp_hash, cutpoint);
+        }
+
+        // `source` holds `max_size` bytes or the rest of the stream, so it is complete.
+        let cutpoint = chunker
+            .adjust_cutpoint(offset, &source[..scan_len], cutpoint, true)
+            .unwrap_or(cutpoint);
+        (fp_hash, cutpoint)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/hashed_tests.rs"]
+mod tests;
@@ -1,7 +1,10 @@
 include!(concat!(env!("OUT_DIR"), "/mask_table.rs"));
 
+use crate::error::ClastError;
+use crate::fastcdc::table::MASK_PADDING_SLOTS;
+
 /// Levels of chunk size normalization.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Normal {
     /// No normalization.
     None,
@@ -11,43 +14,157 @@ pub enum Normal {
     Level2,
     /// Level 3 normalization.
     Level3,
+    /// A custom normalization offset, for experiments beyond `Level3`.
+    ///
+    /// The mask bit counts are `log2(avg_size)` plus and minus the offset, so the usable
+    /// range depends on `avg_size` and is checked when a chunker is constructed.
+    /// Offsets 0 ~ 3 behave like the named levels.
+    Custom(u32),
 }
 
 impl Normal {
-    fn offset(&self) -> u32 {
+    pub(crate) fn offset(&self) -> u32 {
         match self {
             Normal::None => 0,
             Normal::Level1 => 1,
             Normal::Level2 => 2,
             Normal::Level3 => 3,
+            Normal::Custom(offset) => *offset,
+        }
+    }
+
+    pub(crate) fn from_offset(offset: u32) -> Self {
+        match offset {
+            0 => Normal::None,
+            1 => Normal::Level1,
+            2 => Normal::Level2,
+            3 => Normal::Level3,
+            _ => Normal::Custom(offset),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+///
+/// The pair of masks FastCDC judges the rolling hash with.
+///
+/// A cut point is found where the hash has zeros in every bit of the mask: the stricter
+/// `mask_s` applies below `avg_size` and the looser `mask_l` from `avg_size` on.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Masks {
-    pub mask_s: u64,
-    pub mask_s_ls: u64,
-    pub mask_l: u64,
-    pub mask_l_ls: u64,
+    pub(crate) mask_s: u64,
+    pub(crate) mask_s_ls: u64,
+    pub(crate) mask_l: u64,
+    pub(crate) mask_l_ls: u64,
 }
 
 impl Masks {
-    pub fn new(avg_size: usize, normal: Normal) -> Self {
-        let bits = avg_size.ilog2();
-        let offset = normal.offset();
+    ///
+    /// Selects the build-time masks for `avg_size` and `normal`, as `FastCDC::new` does.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `ClastError::InvalidNormalization` if the normalization offset has no masks
+    /// for `avg_size`.
+    ///
+    pub fn new(avg_size: usize, normal: Normal) -> Result<Self, ClastError> {
+        Self::from_table(&MASK_TABLE, avg_size, normal)
+    }
+
+    ///
+    /// Constructs hand-picked masks, e.g. contiguous low bits (`(1 << n) - 1`).
+    ///
+    /// The number of bits set controls the cut probability: each mask with `n` bits cuts
+    /// with probability `2^-n` per byte on random data.
+    ///
+    /// ## Arguments
+    ///
+    /// * `mask_s`: The stricter mask used below `avg_size`.
+    /// * `mask_l`: The looser mask used from `avg_size` on.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if either mask uses bit 63, which the two-byte scan cannot judge.
+    ///
+    pub fn custom(mask_s: u64, mask_l: u64) -> Self {
+        assert!((mask_s | mask_l) >> 63 == 0, "masks must not use bit 63");
+
+        Self {
+            mask_s,
+            mask_s_ls: mask_s << 1,
+            mask_l,
+            mask_l_ls: mask_l << 1,
+        }
+    }
+
+    /// Rebuilds recorded masks without checking them, as [`Masks::custom`] would construct them.
+    pub(crate) fn from_recorded(mask_s: u64, mask_l: u64) -> Self {
+        Self {
+            mask_s,
+            mask_s_ls: mask_s << 1,
+            mask_l,
+            mask_l_ls: mask_l << 1,
+        }
+    }
+
+    /// Returns the mask used below `avg_size`.
+    pub fn mask_s(&self) -> u64 {
+        self.mask_s
+    }
+
+    /// Returns the mask used from `avg_size` on.
+    pub fn mask_l(&self) -> u64 {
+        self.mask_l
+    }
+
+    /// Selects the masks for `avg_size` and `normal` from a mask table.
+    pub(crate) fn from_table(
+        table: &[u64],
+        avg_size: usize,
+        normal: Normal,
+    ) -> Result<Self, ClastError> {
+        let (small, large) = mask_indices(avg_size, normal, table.len())?;
 
-        let mask_s = MASK_TABLE[(bits + offset) as usize];
+        let mask_s = table[small];
         let mask_s_ls = mask_s << 1;
 
-        let mask_l = MASK_TABLE[(bits - offset) as usize];
+        let mask_l = table[large];
         let mask_l_ls = mask_l << 1;
 
-        Self {
+        Ok(Self {
             mask_s,
             mask_s_ls,
             mask_l,
             mask_l_ls,
-        }
+        })
     }
 }
+
+///
+/// Returns the mask table indices used before and after `avg_size`.
+///
+/// ## Errors
+///
+/// Returns `ClastError::InvalidNormalization` if either index would fall outside the used
+/// slots of a table with `table_len` entries.
+///
+pub(crate) fn mask_indices(
+    avg_size: usize,
+    normal: Normal,
+    table_len: usize,
+) -> Result<(usize, usize), ClastError> {
+    let bits = avg_size.ilog2() as usize;
+    let offset = normal.offset() as usize;
+
+    let max_offset = bits
+        .saturating_sub(MASK_PADDING_SLOTS)
+        .min((table_len - 1).saturating_sub(bits));
+    if offset > max_offset {
+        return Err(ClastError::InvalidNormalization {
+            given: offset,
+            bounds: 0..=max_offset,
+        });
+    }
+
+    Ok((bits + offset, bits - offset))
+}
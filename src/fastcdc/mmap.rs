@@ -0,0 +1,177 @@
+use crate::fastcdc::{Chunk, FastCDC, FastCDCDecoder};
+use bytes::Bytes;
+use std::{borrow::Cow, fs::File, io, os::unix::io::AsRawFd, path::Path, ptr, slice};
+
+/// Default size of each mapped window of a file.
+pub const MMAP_WINDOW_SIZE: usize = 1 << 30; // 1 GB
+
+impl FastCDC {
+    ///
+    /// Memory-maps a file and chunks it without copying.
+    ///
+    /// Every chunk's data is a slice of the mapping. The file is mapped in windows of
+    /// `MMAP_WINDOW_SIZE` bytes, remapped as chunking advances, so files larger than the
+    /// address space or RAM are fine; a window stays mapped while any of its chunks is alive.
+    /// Yields the same chunks as [`FastCDC::chunks`].
+    ///
+    /// The file must not be modified or truncated while it is mapped; truncation raises
+    /// `SIGBUS` when the missing pages are touched.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The file to chunk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the error from `open(2)` or `fstat(2)`. Mapping errors are returned by the iterator.
+    ///
+    pub fn chunk_file<P: AsRef<Path>>(&self, path: P) -> io::Result<MmapChunks<'_>> {
+        MmapChunks::new(self, File::open(path)?, MMAP_WINDOW_SIZE)
+    }
+}
+
+/// A read-only mapping of part of a file, unmapped when dropped.
+struct Mapping {
+    ptr: *mut libc::c_void,
+    len: usize,
+    skip: usize,
+}
+
+// SAFETY: The mapping is read-only and owned; nothing else unmaps it.
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    fn new(file: &File, offset: u64, len: usize, page: u64) -> io::Result<Self> {
+        // `mmap(2)` offsets must be page-aligned; the leading bytes are skipped.
+        let aligned = offset - offset % page;
+        let skip = (offset - aligned) as usize;
+        let map_len = len + skip;
+
+        // SAFETY: A fresh read-only private mapping of an open file; the result is checked.
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                map_len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                aligned as libc::off_t,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            ptr,
+            len: map_len,
+            skip,
+        })
+    }
+}
+
+impl AsRef<[u8]> for Mapping {
+    fn as_ref(&self) -> &[u8] {
+        // SAFETY: `ptr` maps `len` readable bytes for the lifetime of `self`.
+        unsafe { &slice::from_raw_parts(self.ptr as *const u8, self.len)[self.skip..] }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` and `len` describe a mapping created by `Mapping::new`.
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+///
+/// An iterator over the chunks of a memory-mapped file.
+///
+/// Created by [`FastCDC::chunk_file`].
+///
+pub struct MmapChunks<'a> {
+    decoder: FastCDCDecoder<'a>,
+    file: File,
+    file_len: u64,
+    page: u64,
+    window_size: usize,
+    window: Bytes,
+    window_offset: u64,
+}
+
+impl<'a> MmapChunks<'a> {
+    pub(crate) fn new(chunker: &'a FastCDC, file: File, window_size: usize) -> io::Result<Self> {
+        let file_len = file.metadata()?.len();
+        // SAFETY: `sysconf` has no preconditions.
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64;
+
+        Ok(Self {
+            // A window must hold a whole chunk past any point in it.
+            window_size: window_size.max(chunker.max_size * 2),
+            decoder: FastCDCDecoder::new(Cow::Borrowed(chunker)),
+            file,
+            file_len,
+            page,
+            window: Bytes::new(),
+            window_offset: 0,
+        })
+    }
+
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        self.decoder.stats()
+    }
+
+    fn remap(&mut self, offset: u64) -> io::Result<()> {
+        let len = (self.file_len - offset).min(self.window_size as u64) as usize;
+        let mapping = Mapping::new(&self.file, offset, len, self.page)?;
+
+        self.window = Bytes::from_owner(mapping);
+        self.window_offset = offset;
+
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for MmapChunks<'a> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.decoder.processed;
+        if offset >= self.file_len {
+            return None;
+        }
+
+        let max_size = self.decoder.chunker.max_size;
+        let window_end = self.window_offset + self.window.len() as u64;
+        let at_end = window_end == self.file_len;
+
+        // Remap once the rest of the window could cut a chunk short.
+        if window_end - offset < max_size as u64
+            && !at_end
+            && let Err(e) = self.remap(offset)
+        {
+            return Some(Err(e));
+        }
+
+        let pos = (offset - self.window_offset) as usize;
+        let eof = self.window_offset + self.window.len() as u64 == self.file_len;
+
+        // The window holds `max_size` bytes or the rest of the file, so a cut is always found.
+        let (cutpoint, fp_hash) = self
+            .decoder
+            .next_cutpoint(&self.window[pos..], eof)
+            .expect("a complete window always yields a cut point");
+        let data = self.window.slice(pos..pos + cutpoint);
+
+        Some(Ok(self.decoder.emit(data, fp_hash)))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/mmap_tests.rs"]
+mod tests;
@@ -17,14 +17,83 @@
 //! 5. **Rolling Two Bytes each time**: Processes two bytes per iteration to further minimize CPU overhead.
 //!
 
+mod boundaries;
+mod buffered;
+mod builder;
 mod chunk;
 mod core;
 mod cut;
+mod decoder;
+mod hashed;
 mod mask;
+mod narrow;
+pub mod parallel;
+mod pool;
+mod preset;
+mod reference;
+mod sketch;
+mod spare;
+mod state;
+mod sweep;
+mod table;
+mod throughput;
+mod usage;
+mod zero_copy;
 
-pub use chunk::Chunk;
+pub use boundaries::FastCDCBoundaries;
+pub use buffered::FastCDCBufIter;
+pub use builder::FastCDCBuilder;
+pub use chunk::{Boundary, Chunk, ChunkRef};
 pub use core::FastCDC;
-pub use mask::Normal;
+#[cfg(all(feature = "sparse", target_os = "linux"))]
+pub(crate) use core::FastCDCIter;
+pub(crate) use core::{MAX_CHUNK_SIZE_MAX, validate_sizes};
+pub(crate) use cut::GEAR;
+pub use decoder::FastCDCDecoder;
+pub use hashed::HashedCDC;
+pub use mask::{Masks, Normal};
+pub use narrow::FastCDC32;
+pub use pool::BufferPool;
+pub use reference::{PAPER_GEAR, PAPER_MASKS};
+pub use sketch::Sketch;
+pub(crate) use spare::SpareInit;
+pub use state::ChunkerState;
+pub use sweep::{Sweep, SweepReport};
+pub(crate) use table::{TableId, published_gear};
+pub use throughput::Throughput;
+pub use usage::BufferUsage;
+pub use zero_copy::{BytesChunks, SliceChunks};
 
 #[cfg(feature = "async")]
+mod blocking;
+#[cfg(any(feature = "async", feature = "futures-io"))]
+mod frames;
+#[cfg(feature = "async")]
+mod pipeline;
+#[cfg(any(feature = "async", feature = "futures-io"))]
+mod sink;
+#[cfg(any(feature = "async", feature = "futures-io"))]
 pub mod stream;
+
+#[cfg(feature = "async")]
+pub use blocking::BlockingChunks;
+#[cfg(any(feature = "async", feature = "futures-io"))]
+pub use frames::FastCDCFrames;
+#[cfg(feature = "async")]
+pub use pipeline::{DigestedChunks, PIPELINE_CAPACITY, Pipeline};
+#[cfg(any(feature = "async", feature = "futures-io"))]
+pub use sink::FastCDCSink;
+
+#[cfg(all(feature = "mmap", unix))]
+mod mmap;
+#[cfg(all(feature = "mmap", unix))]
+pub use mmap::{MMAP_WINDOW_SIZE, MmapChunks};
+
+#[cfg(feature = "simd")]
+mod simd;
+
+#[cfg(feature = "stats")]
+mod stats;
+
+#[cfg(feature = "stats")]
+pub use stats::ChunkerStats;
@@ -0,0 +1,184 @@
+include!(concat!(env!("OUT_DIR"), "/mask_table32.rs"));
+
+use crate::chunker::{ChunkIter, CutPoint, Sizes};
+use crate::error::ClastError;
+use crate::fastcdc::Normal;
+use crate::fastcdc::cut::GEAR32;
+use crate::fastcdc::mask::mask_indices;
+use std::io::Read;
+
+///
+/// A FastCDC chunker computing a 32-bit Gear hash, for targets without fast 64-bit arithmetic.
+///
+/// The Gear table holds the high 32 bits of each entry of the 64-bit table, and the masks are
+/// selected from the 32 available bits the same way. Bytes are rolled one at a time, so the
+/// hash depends only on the last 32 bytes. Boundaries are deterministic for a given
+/// `GEAR_SEED` but differ from those of [`FastCDC`](crate::fastcdc::FastCDC); chunks carry
+/// the zero-extended 32-bit hash as `fp_hash`.
+///
+/// ## Test Vectors
+///
+/// With the default `GEAR_SEED`, sizes of `4_069 / 8_192 / 16_384`, and [`Normal::Level2`],
+/// the first chunks of the bytes `(i * 2_654_435_761 >> 13) as u8` for `i` in `0..100_000`
+/// (with wrapping 32-bit multiplication) are:
+///
+/// | Offset | Length | `fp_hash`    |
+/// |--------|--------|--------------|
+/// | 0      | 8658   | `0xd9584814` |
+/// | 8658   | 9820   | `0xd9584814` |
+/// | 18478  | 10064  | `0xd9584814` |
+/// | 28542  | 9820   | `0xd9584814` |
+/// | 38362  | 10041  | `0xa87c0814` |
+///
+#[derive(Debug, Clone)]
+pub struct FastCDC32 {
+    sizes: Sizes,
+    normal: Normal,
+    mask_s: u32,
+    mask_l: u32,
+}
+
+impl FastCDC32 {
+    ///
+    /// Constructs a new `FastCDC32` instance.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    /// * `normal`: The normalization level for chunk size distribution.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`FastCDC::new`](crate::fastcdc::FastCDC::new).
+    ///
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize, normal: Normal) -> Self {
+        match Self::try_new(min_size, avg_size, max_size, normal) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a new `FastCDC32` instance.
+    /// Unlike `new`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    /// * `normal`: The normalization level for chunk size distribution.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as
+    /// [`FastCDC::try_new`](crate::fastcdc::FastCDC::try_new).
+    ///
+    pub fn try_new(
+        min_size: usize,
+        avg_size: usize,
+        max_size: usize,
+        normal: Normal,
+    ) -> Result<Self, ClastError> {
+        let sizes = Sizes::new(min_size, avg_size, max_size)?;
+
+        let (small, large) = mask_indices(avg_size, normal, MASK_TABLE32.len())?;
+
+        Ok(Self {
+            sizes,
+            normal,
+            mask_s: MASK_TABLE32[small],
+            mask_l: MASK_TABLE32[large],
+        })
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.sizes.min
+    }
+
+    /// Returns the target average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.sizes.avg
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    /// Returns the normalization level.
+    pub fn normal(&self) -> Normal {
+        self.normal
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+}
+
+impl CutPoint for FastCDC32 {
+    fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        let scan_len = source.len().min(self.sizes.max);
+
+        if scan_len <= self.sizes.min {
+            return (0, scan_len);
+        }
+
+        let center = self.sizes.avg.min(scan_len);
+        let mut fp_hash = 0u32;
+        let mut idx = self.sizes.min;
+
+        while idx < center {
+            fp_hash = (fp_hash << 1).wrapping_add(GEAR32[source[idx] as usize]);
+
+            if (fp_hash & self.mask_s) == 0 {
+                return (fp_hash as u64, idx);
+            }
+            idx += 1;
+        }
+
+        while idx < scan_len {
+            fp_hash = (fp_hash << 1).wrapping_add(GEAR32[source[idx] as usize]);
+
+            if (fp_hash & self.mask_l) == 0 {
+                return (fp_hash as u64, idx);
+            }
+            idx += 1;
+        }
+
+        (fp_hash as u64, scan_len)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/narrow_tests.rs"]
+mod tests;
@@ -0,0 +1,279 @@
+use crate::fastcdc::{BytesChunks, Chunk, ChunkRef, FastCDC, SliceChunks};
+use bytes::Bytes;
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    num::NonZeroUsize,
+    panic,
+    path::Path,
+    thread,
+};
+
+/// Segments shorter than this many `max_size` chunks are not worth a thread.
+const MIN_SEGMENT_CHUNKS: usize = 64;
+
+impl FastCDC {
+    ///
+    /// Chunks an in-memory buffer on all available cores.
+    ///
+    /// The input is split into one segment per core, and each segment is chunked from its
+    /// own start on a scoped thread. The segments are then stitched in order: chunking
+    /// continues sequentially past each join until it reaches a boundary the next segment
+    /// also found, from where both agree. The chunks are therefore identical to those of
+    /// [`FastCDC::chunk_slice`]; boundaries usually resynchronize within a few chunks.
+    ///
+    /// ## Arguments
+    ///
+    /// * `data`: The whole input.
+    ///
+    pub fn par_chunks<'a>(&self, data: &'a [u8]) -> Vec<ChunkRef<'a>> {
+        self.par_chunks_placed(data, |_| {})
+    }
+
+    ///
+    /// Chunks an in-memory buffer on all available cores, like [`FastCDC::par_chunks`],
+    /// calling `place` on each spawned thread before it chunks its segment.
+    ///
+    /// `place` receives the index of the segment, which covers the `index`-th of the equal
+    /// parts of `data`, and can pin the thread to a core or NUMA node near that memory
+    /// (e.g. with `sched_setaffinity`). The first segment is chunked on the calling thread,
+    /// so `place` is called with indices from 1.
+    ///
+    /// ## Arguments
+    ///
+    /// * `data`: The whole input.
+    /// * `place`: The placement hook, called once on each spawned thread.
+    ///
+    pub fn par_chunks_placed<'a, F>(&self, data: &'a [u8], place: F) -> Vec<ChunkRef<'a>>
+    where
+        F: Fn(usize) + Sync,
+    {
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let segment_len = data
+            .len()
+            .div_ceil(threads)
+            .max(self.max_size * MIN_SEGMENT_CHUNKS);
+
+        self.par_chunks_segmented(data, segment_len, &place)
+    }
+
+    fn par_chunks_segmented<'a, F>(
+        &self,
+        data: &'a [u8],
+        segment_len: usize,
+        place: &F,
+    ) -> Vec<ChunkRef<'a>>
+    where
+        F: Fn(usize) + Sync,
+    {
+        let starts: Vec<usize> = (0..data.len()).step_by(segment_len.max(1)).collect();
+
+        // Each segment's chain covers every chunk starting before the next segment.
+        let chain = |start: usize| -> Vec<ChunkRef<'a>> {
+            let stop = start + segment_len;
+            SliceChunks::at(self, data, start)
+                .take_while(|chunk| (chunk.offset as usize) < stop)
+                .collect()
+        };
+
+        let mut chains = thread::scope(|scope| {
+            let handles: Vec<_> = starts
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(index, &start)| {
+                    scope.spawn(move || {
+                        place(index);
+                        chain(start)
+                    })
+                })
+                .collect();
+
+            let mut chains = vec![chain(0)];
+            chains.extend(
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e))),
+            );
+            chains
+        })
+        .into_iter();
+
+        let mut out = chains.next().unwrap_or_default();
+        let mut pos = out.last().map_or(0, chunk_end);
+
+        for chain in chains {
+            let cover = chain.last().map_or(pos, chunk_end);
+            let mut sequential = SliceChunks::at(self, data, pos);
+
+            while pos < cover {
+                // Once the sequential boundary is one the segment found, the rest agrees.
+                if let Ok(idx) = chain.binary_search_by_key(&(pos as u64), |chunk| chunk.offset) {
+                    out.extend_from_slice(&chain[idx..]);
+                    pos = cover;
+                    break;
+                }
+
+                let chunk = sequential
+                    .next()
+                    .expect("data remains before the end of the segment");
+                pos = chunk_end(&chunk);
+                out.push(chunk);
+            }
+        }
+
+        out
+    }
+}
+
+fn chunk_end(chunk: &ChunkRef<'_>) -> usize {
+    chunk.offset as usize + chunk.length
+}
+
+///
+/// Chunks a file by reading and chunking `threads` ranges of it concurrently.
+///
+/// Each thread reads its range, plus `max_size` bytes of the next so that the chunks
+/// starting in the range are complete, and chunks it from the range start. The ranges are
+/// then stitched in order as in [`FastCDC::par_chunks`], so the chunks, returned in offset
+/// order, are identical to those of a serial pass such as [`FastCDC::chunks`].
+///
+/// Every chunk's data is a slice of its range's buffer, so the whole file stays in memory
+/// while its chunks are alive.
+///
+/// ## Arguments
+///
+/// * `chunker`: The chunker configuration.
+/// * `path`: The file to chunk.
+/// * `threads`: The number of ranges read and chunked at once.
+///
+/// ## Errors
+///
+/// Returns the first error from opening or reading any range.
+///
+/// ## Panics
+///
+/// Panics if `threads` is zero.
+///
+pub fn chunk_file<P: AsRef<Path>>(
+    chunker: &FastCDC,
+    path: P,
+    threads: usize,
+) -> io::Result<Vec<Chunk>> {
+    chunk_file_placed(chunker, path, threads, |_| {})
+}
+
+///
+/// Chunks a file on `threads` threads, like [`chunk_file`], calling `place` on each thread
+/// before it reads its range.
+///
+/// `place` receives the index of the range, and can pin the thread to a core or NUMA node
+/// (e.g. with `sched_setaffinity`), so that the range's buffer is allocated and chunked
+/// locally.
+///
+/// ## Arguments
+///
+/// * `chunker`: The chunker configuration.
+/// * `path`: The file to chunk.
+/// * `threads`: The number of ranges read and chunked at once.
+/// * `place`: The placement hook, called once on each thread.
+///
+/// ## Errors
+///
+/// Returns the first error from opening or reading any range.
+///
+/// ## Panics
+///
+/// Panics if `threads` is zero.
+///
+pub fn chunk_file_placed<P, F>(
+    chunker: &FastCDC,
+    path: P,
+    threads: usize,
+    place: F,
+) -> io::Result<Vec<Chunk>>
+where
+    P: AsRef<Path>,
+    F: Fn(usize) + Sync,
+{
+    assert!(threads > 0, "threads must be greater than zero");
+
+    let path = path.as_ref();
+    let file_len = std::fs::metadata(path)?.len();
+    let range_len = file_len.div_ceil(threads as u64).max(1);
+    let overlap = chunker.max_size as u64;
+
+    let ranges = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads as u64)
+            .map(|i| (i as usize, i * range_len))
+            .take_while(|&(_, start)| start < file_len)
+            .map(|(index, start)| {
+                let place = &place;
+                scope.spawn(move || {
+                    place(index);
+                    let end = (start + range_len + overlap).min(file_len);
+                    let data = read_range(path, start, end)?;
+                    let stop = start + range_len;
+                    let chain = BytesChunks::at(chunker, data.clone(), start)
+                        .take_while(|chunk| chunk.offset < stop)
+                        .collect();
+
+                    Ok(Range { start, data, chain })
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect::<io::Result<Vec<_>>>()
+    })?;
+
+    let mut out = Vec::new();
+    let mut pos = 0;
+
+    for mut range in ranges {
+        let stop = (range.start + range_len).min(file_len);
+        let skip = (pos - range.start) as usize;
+        let mut sequential = BytesChunks::at(chunker, range.data.slice(skip..), pos);
+
+        // Chunks starting in the range end within its buffer, past which the next takes over.
+        while pos < stop {
+            if let Ok(idx) = range.chain.binary_search_by_key(&pos, |chunk| chunk.offset) {
+                let adopted = range.chain.split_off(idx);
+                pos = adopted.last().map_or(pos, |c| c.offset + c.length as u64);
+                out.extend(adopted);
+                break;
+            }
+
+            let chunk = sequential
+                .next()
+                .expect("data remains before the end of the range");
+            pos += chunk.length as u64;
+            out.push(chunk);
+        }
+    }
+
+    Ok(out)
+}
+
+/// A range of a file with the chunks found from its start.
+struct Range {
+    start: u64,
+    data: Bytes,
+    chain: Vec<Chunk>,
+}
+
+fn read_range(path: &Path, start: u64, end: u64) -> io::Result<Bytes> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+
+    let mut data = vec![0; (end - start) as usize];
+    file.read_exact(&mut data)?;
+
+    Ok(Bytes::from(data))
+}
+
+#[cfg(test)]
+#[path = "tests/parallel_tests.rs"]
+mod tests;
@@ -0,0 +1,135 @@
+use crate::digest::Digest;
+use crate::fastcdc::{Chunk, FastCDC};
+use futures::{SinkExt, Stream, StreamExt, channel::mpsc};
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::AsyncRead;
+
+/// Default number of chunks queued between pipeline stages.
+pub const PIPELINE_CAPACITY: usize = 16;
+
+impl FastCDC {
+    ///
+    /// Creates a pipeline that chunks the provided async reader and digests every chunk on
+    /// separate tasks.
+    ///
+    /// Nothing runs until a digest is chosen with [`Pipeline::hash`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead` and `Send`).
+    ///
+    pub fn pipeline<R>(&self, reader: R) -> Pipeline<R>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        Pipeline {
+            chunker: self.clone(),
+            reader,
+            capacity: PIPELINE_CAPACITY,
+        }
+    }
+}
+
+///
+/// A chunking pipeline awaiting its digest stage.
+///
+/// Created by [`FastCDC::pipeline`].
+///
+pub struct Pipeline<R> {
+    chunker: FastCDC,
+    reader: R,
+    capacity: usize,
+}
+
+impl<R> Pipeline<R>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    ///
+    /// Sets the number of chunks queued between stages, bounding the memory held by a
+    /// consumer that falls behind.
+    ///
+    /// ## Arguments
+    ///
+    /// * `capacity`: The number of chunks each channel holds.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+
+        self.capacity = capacity;
+        self
+    }
+
+    ///
+    /// Starts the pipeline, digesting every chunk with `D`.
+    ///
+    /// Boundary detection and digesting run on two tasks connected by bounded channels, so
+    /// the cut-point scan of the next chunks proceeds while earlier ones are digested; on a
+    /// multi-threaded runtime the stages run in parallel. Chunks are yielded in order with
+    /// their digests. Dropping the stream stops both tasks.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if called outside a Tokio runtime.
+    ///
+    pub fn hash<D: Digest>(self) -> DigestedChunks<D::Output> {
+        let (mut chunk_tx, mut chunk_rx) = mpsc::channel(self.capacity);
+        let (mut digest_tx, digest_rx) = mpsc::channel(self.capacity);
+        let mut chunks = self.chunker.into_stream(self.reader);
+
+        tokio::spawn(async move {
+            while let Some(chunk) = chunks.next().await {
+                let failed = chunk.is_err();
+
+                // The digest stage has stopped; stop reading.
+                if chunk_tx.send(chunk).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        tokio::spawn(async move {
+            while let Some(chunk) = chunk_rx.next().await {
+                let item = chunk.map(|chunk: Chunk| {
+                    let digest = D::digest(&chunk.data);
+                    (chunk, digest)
+                });
+
+                if digest_tx.send(item).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        DigestedChunks { rx: digest_rx }
+    }
+}
+
+///
+/// A stream of chunks paired with their digests, produced by a running pipeline.
+///
+/// Created by [`Pipeline::hash`]. The stream ends after the first error.
+///
+pub struct DigestedChunks<T> {
+    rx: mpsc::Receiver<io::Result<(Chunk, T)>>,
+}
+
+impl<T> Stream for DigestedChunks<T> {
+    type Item = io::Result<(Chunk, T)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().rx).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/pipeline_tests.rs"]
+mod tests;
@@ -0,0 +1,90 @@
+use bytes::Bytes;
+use std::sync::{Arc, Mutex};
+
+///
+/// A pool of chunk buffers, recycled once the chunks using them are dropped.
+///
+/// An iterator or stream given a pool copies each chunk out of its read buffer into a
+/// buffer drawn from the pool, instead of splitting the read buffer. The read buffer is
+/// then reused in place, and dropping a chunk hands its buffer back, so a long-running
+/// service chunking many streams stops allocating once the pool has warmed up.
+///
+/// Cloning is cheap and shares the pool, which is safe to use from many threads.
+///
+#[derive(Debug, Clone)]
+pub struct BufferPool {
+    inner: Arc<PoolInner>,
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    idle: Mutex<Vec<Vec<u8>>>,
+    max_idle: usize,
+}
+
+impl BufferPool {
+    ///
+    /// Creates an empty pool.
+    ///
+    /// ## Arguments
+    ///
+    /// * `max_idle`: The number of returned buffers kept for reuse; further ones are freed.
+    ///
+    pub fn new(max_idle: usize) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                idle: Mutex::new(Vec::new()),
+                max_idle,
+            }),
+        }
+    }
+
+    /// Returns the number of buffers waiting to be reused.
+    pub fn idle(&self) -> usize {
+        self.inner.lock().len()
+    }
+
+    /// Copies `data` into a pooled buffer that returns to the pool when the last clone of
+    /// the `Bytes` is dropped.
+    pub(crate) fn copy_from_slice(&self, data: &[u8]) -> Bytes {
+        let mut buf = self.inner.lock().pop().unwrap_or_default();
+        buf.clear();
+        buf.extend_from_slice(data);
+
+        Bytes::from_owner(Pooled {
+            buf,
+            pool: self.inner.clone(),
+        })
+    }
+}
+
+impl PoolInner {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Vec<u8>>> {
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A buffer on loan from a pool, returned when dropped.
+struct Pooled {
+    buf: Vec<u8>,
+    pool: Arc<PoolInner>,
+}
+
+impl AsRef<[u8]> for Pooled {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl Drop for Pooled {
+    fn drop(&mut self) {
+        let mut idle = self.pool.lock();
+        if idle.len() < self.pool.max_idle {
+            idle.push(std::mem::take(&mut self.buf));
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/pool_tests.rs"]
+mod tests;
@@ -0,0 +1,47 @@
+use crate::fastcdc::{FastCDC, Normal};
+
+///
+/// Parameter presets for common workloads.
+///
+/// | Preset                 | Min     | Avg     | Max      | Normal   |
+/// |------------------------|---------|---------|----------|----------|
+/// | `preset_backup`        | 1 MiB   | 4 MiB   | 16 MiB   | `Level2` |
+/// | `preset_small_files`   | 2 KiB   | 8 KiB   | 64 KiB   | `Level2` |
+/// | `preset_network_sync`  | 512 B   | 2 KiB   | 8 KiB    | `Level1` |
+///
+impl FastCDC {
+    ///
+    /// Constructs a chunker for large backup and archive streams (1 / 4 / 16 MiB).
+    ///
+    /// Large chunks keep the index small for terabyte-scale data, at the cost of
+    /// coarser deduplication of small edits. These are the largest sizes the crate accepts.
+    ///
+    pub fn preset_backup() -> Self {
+        Self::new(1_048_576, 4_194_304, 16_777_216, Normal::Level2)
+    }
+
+    ///
+    /// Constructs a chunker for collections of small files such as source trees (2 / 8 / 64 KiB).
+    ///
+    /// An 8 KiB average, the size commonly evaluated in the FastCDC paper, balances
+    /// deduplication ratio against per-chunk metadata. The same sizes are the CLI defaults.
+    ///
+    pub fn preset_small_files() -> Self {
+        Self::new(2_048, 8_192, 65_536, Normal::Level2)
+    }
+
+    ///
+    /// Constructs a chunker for delta transfer over the network (512 B / 2 KiB / 8 KiB).
+    ///
+    /// Small chunks keep the bytes resent after an edit low, which matters more than
+    /// metadata overhead when syncing between peers. Lighter normalization lets boundaries
+    /// follow content more closely.
+    ///
+    pub fn preset_network_sync() -> Self {
+        Self::new(512, 2_048, 8_192, Normal::Level1)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/preset_tests.rs"]
+mod tests;
@@ -0,0 +1,432 @@
+//!
+//! Lane-parallel versions of the two-byte gear scan, selected at runtime.
+//!
+//! The gear hash shifts left by one bit per byte, so after 64 bytes nothing of the earlier
+//! state is left: the hash at any position depends only on the 64 bytes before it. A block
+//! of pairs is therefore split into one run per SIMD lane, each lane warmed up over the 32
+//! pairs before its run, and all lanes roll in lockstep. The first lane that finds a cut
+//! point holds the earliest one, as the lanes cover the block in order. The resulting cut
+//! points and fingerprints are exactly those of [`scan_pairs_scalar`].
+//!
+
+use crate::fastcdc::cut::scan_pairs_scalar;
+use crate::fastcdc::table::GearTable;
+use std::ops::ControlFlow;
+
+/// Pairs (64 bytes) that fully determine the rolling hash.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const WARMUP_PAIRS: usize = 32;
+
+/// Upper bound on the pairs each lane scans per block.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const BLOCK_PAIRS: usize = 256;
+
+/// Masks with fewer bits cut every 2 KB or less on average, so the lanes rarely get far.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const MIN_MASK_BITS: u32 = 11;
+
+/// Below this many pairs per lane, warming the lanes up costs more than it saves.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const MIN_BLOCK_PAIRS: usize = 64;
+
+///
+/// Rolls the two-byte gear hash over the pairs `start_idx..end_idx` with the widest SIMD
+/// extension the CPU supports, falling back to [`scan_pairs_scalar`].
+///
+#[allow(clippy::too_many_arguments)]
+#[inline]
+pub(super) fn scan_pairs(
+    table: &GearTable,
+    source: &[u8],
+    start_idx: usize,
+    end_idx: usize,
+    fp_hash: u64,
+    mask_ls: u64,
+    mask: u64,
+) -> ControlFlow<(u64, usize), u64> {
+    // The lanes never read past `end_idx`, which the scalar scan also bounds by the source.
+    let end_idx = end_idx.min(source.len() / 2);
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") {
+            return scan_lanes(
+                table,
+                source,
+                start_idx,
+                end_idx,
+                fp_hash,
+                mask_ls,
+                mask,
+                x86::block_avx2,
+            );
+        }
+        if std::arch::is_x86_feature_detected!("sse4.2") {
+            return scan_lanes(
+                table,
+                source,
+                start_idx,
+                end_idx,
+                fp_hash,
+                mask_ls,
+                mask,
+                x86::block_sse42,
+            );
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return scan_lanes(
+                table,
+                source,
+                start_idx,
+                end_idx,
+                fp_hash,
+                mask_ls,
+                mask,
+                neon::block_neon,
+            );
+        }
+    }
+
+    scan_pairs_scalar(table, source, start_idx, end_idx, fp_hash, mask_ls, mask)
+}
+
+/// The earliest cut point of a block.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+struct Hit {
+    lane: usize,
+    step: usize,
+    second: bool,
+    fp_hash: u64,
+}
+
+/// Tracks the lowest lane that has found a cut point so far.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+struct Tracker {
+    live: u32,
+    best: Option<Hit>,
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+impl Tracker {
+    fn new(lanes: usize) -> Self {
+        Self {
+            live: (1 << lanes) - 1,
+            best: None,
+        }
+    }
+
+    /// Records the lanes in `hits` (of which `first` hit on the first byte of the pair),
+    /// returning `true` once the first lane has hit, as no later result can be earlier.
+    #[cold]
+    fn record(&mut self, step: usize, hits: u32, first: u32, x: &[u64], h: &[u64]) -> bool {
+        let hits = hits & self.live;
+        if hits == 0 {
+            return false;
+        }
+
+        let lane = hits.trailing_zeros() as usize;
+        let second = first >> lane & 1 == 0;
+        self.best = Some(Hit {
+            lane,
+            step,
+            second,
+            fp_hash: if second { h[lane] } else { x[lane] },
+        });
+        // Only lower lanes can still find an earlier cut point.
+        self.live = (1 << lane) - 1;
+
+        lane == 0
+    }
+
+    fn finish<const N: usize>(self, h: [u64; N]) -> Result<[u64; N], Hit> {
+        match self.best {
+            Some(hit) => Err(hit),
+            None => Ok(h),
+        }
+    }
+}
+
+///
+/// Rolls `N` lanes in lockstep for `steps` pairs, lane `i` starting at pair `starts[i]`
+/// with hash `init[i]`. Returns the final hashes, or the earliest cut point when `judge`.
+///
+/// ## Safety
+///
+/// The CPU must support the kernel's target features, and every pair scanned must lie
+/// within `source`.
+///
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+type Kernel<const N: usize> = unsafe fn(
+    &GearTable,
+    &[u8],
+    [usize; N],
+    usize,
+    [u64; N],
+    u64,
+    u64,
+    bool,
+) -> Result<[u64; N], Hit>;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+#[allow(clippy::too_many_arguments)]
+fn scan_lanes<const N: usize>(
+    table: &GearTable,
+    source: &[u8],
+    mut start_idx: usize,
+    end_idx: usize,
+    mut fp_hash: u64,
+    mask_ls: u64,
+    mask: u64,
+    kernel: Kernel<N>,
+) -> ControlFlow<(u64, usize), u64> {
+    // Dense masks cut within a few pairs, too soon to repay the warm-up.
+    if mask.count_ones() < MIN_MASK_BITS {
+        return scan_pairs_scalar(table, source, start_idx, end_idx, fp_hash, mask_ls, mask);
+    }
+
+    loop {
+        let lane_pairs = (end_idx.saturating_sub(start_idx) / N).min(BLOCK_PAIRS);
+        if lane_pairs < MIN_BLOCK_PAIRS {
+            return scan_pairs_scalar(table, source, start_idx, end_idx, fp_hash, mask_ls, mask);
+        }
+
+        let starts: [usize; N] = std::array::from_fn(|lane| start_idx + lane * lane_pairs);
+
+        // Every lane but the first is warmed up over the pairs before its run, which lie in
+        // the block; the first continues the serial hash, so its warm-up is discarded.
+        let warm_starts = std::array::from_fn(|lane| starts[lane.max(1)] - WARMUP_PAIRS);
+        // SAFETY: The kernel was selected by feature detection, and every pair lies before
+        // `end_idx`, which is within `source`.
+        let Ok(mut hashes) = (unsafe {
+            kernel(
+                table,
+                source,
+                warm_starts,
+                WARMUP_PAIRS,
+                [0; N],
+                0,
+                0,
+                false,
+            )
+        }) else {
+            unreachable!("warm-up does not judge cut points");
+        };
+        hashes[0] = fp_hash;
+
+        // SAFETY: As above; the last lane ends at `start_idx + N * lane_pairs <= end_idx`.
+        match unsafe {
+            kernel(
+                table, source, starts, lane_pairs, hashes, mask_ls, mask, true,
+            )
+        } {
+            Ok(hashes) => {
+                fp_hash = hashes[N - 1];
+                start_idx += N * lane_pairs;
+            }
+            Err(hit) => {
+                let byte_idx = (starts[hit.lane] + hit.step) * 2 + hit.second as usize;
+                return ControlFlow::Break((hit.fp_hash, byte_idx));
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::{Hit, Tracker};
+    use crate::fastcdc::table::GearTable;
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2")]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) unsafe fn block_avx2(
+        table: &GearTable,
+        source: &[u8],
+        starts: [usize; 4],
+        steps: usize,
+        init: [u64; 4],
+        mask_ls: u64,
+        mask: u64,
+        judge: bool,
+    ) -> Result<[u64; 4], Hit> {
+        let gear_ls = |b: usize| unsafe { table.gear_ls[*source.get_unchecked(b) as usize] } as i64;
+        let gear = |b: usize| unsafe { table.gear[*source.get_unchecked(b) as usize] } as i64;
+
+        // SAFETY: AVX2 is enabled, and the caller keeps every byte read within `source`.
+        unsafe {
+            let mut h = _mm256_loadu_si256(init.as_ptr().cast());
+            let mls = _mm256_set1_epi64x(mask_ls as i64);
+            let ms = _mm256_set1_epi64x(mask as i64);
+            let zero = _mm256_setzero_si256();
+            let mut tracker = Tracker::new(4);
+
+            for step in 0..steps {
+                let b = starts.map(|start| (start + step) * 2);
+
+                let x = _mm256_add_epi64(
+                    _mm256_slli_epi64::<2>(h),
+                    _mm256_set_epi64x(gear_ls(b[3]), gear_ls(b[2]), gear_ls(b[1]), gear_ls(b[0])),
+                );
+                h = _mm256_add_epi64(
+                    x,
+                    _mm256_set_epi64x(
+                        gear(b[3] + 1),
+                        gear(b[2] + 1),
+                        gear(b[1] + 1),
+                        gear(b[0] + 1),
+                    ),
+                );
+
+                if judge {
+                    let hit_x = _mm256_cmpeq_epi64(_mm256_and_si256(x, mls), zero);
+                    let hit_h = _mm256_cmpeq_epi64(_mm256_and_si256(h, ms), zero);
+                    let hits =
+                        _mm256_movemask_pd(_mm256_castsi256_pd(_mm256_or_si256(hit_x, hit_h)));
+
+                    if hits != 0 {
+                        let (mut xs, mut hs) = ([0u64; 4], [0u64; 4]);
+                        _mm256_storeu_si256(xs.as_mut_ptr().cast(), x);
+                        _mm256_storeu_si256(hs.as_mut_ptr().cast(), h);
+                        let first = _mm256_movemask_pd(_mm256_castsi256_pd(hit_x));
+
+                        if tracker.record(step, hits as u32, first as u32, &xs, &hs) {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            let mut hs = [0u64; 4];
+            _mm256_storeu_si256(hs.as_mut_ptr().cast(), h);
+            tracker.finish(hs)
+        }
+    }
+
+    #[target_feature(enable = "sse4.2")]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) unsafe fn block_sse42(
+        table: &GearTable,
+        source: &[u8],
+        starts: [usize; 2],
+        steps: usize,
+        init: [u64; 2],
+        mask_ls: u64,
+        mask: u64,
+        judge: bool,
+    ) -> Result<[u64; 2], Hit> {
+        let gear_ls = |b: usize| unsafe { table.gear_ls[*source.get_unchecked(b) as usize] } as i64;
+        let gear = |b: usize| unsafe { table.gear[*source.get_unchecked(b) as usize] } as i64;
+
+        // SAFETY: SSE4.2 is enabled, and the caller keeps every byte read within `source`.
+        unsafe {
+            let mut h = _mm_loadu_si128(init.as_ptr().cast());
+            let mls = _mm_set1_epi64x(mask_ls as i64);
+            let ms = _mm_set1_epi64x(mask as i64);
+            let zero = _mm_setzero_si128();
+            let mut tracker = Tracker::new(2);
+
+            for step in 0..steps {
+                let b = starts.map(|start| (start + step) * 2);
+
+                let x = _mm_add_epi64(
+                    _mm_slli_epi64::<2>(h),
+                    _mm_set_epi64x(gear_ls(b[1]), gear_ls(b[0])),
+                );
+                h = _mm_add_epi64(x, _mm_set_epi64x(gear(b[1] + 1), gear(b[0] + 1)));
+
+                if judge {
+                    let hit_x = _mm_cmpeq_epi64(_mm_and_si128(x, mls), zero);
+                    let hit_h = _mm_cmpeq_epi64(_mm_and_si128(h, ms), zero);
+                    let hits = _mm_movemask_pd(_mm_castsi128_pd(_mm_or_si128(hit_x, hit_h)));
+
+                    if hits != 0 {
+                        let (mut xs, mut hs) = ([0u64; 2], [0u64; 2]);
+                        _mm_storeu_si128(xs.as_mut_ptr().cast(), x);
+                        _mm_storeu_si128(hs.as_mut_ptr().cast(), h);
+                        let first = _mm_movemask_pd(_mm_castsi128_pd(hit_x));
+
+                        if tracker.record(step, hits as u32, first as u32, &xs, &hs) {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            let mut hs = [0u64; 2];
+            _mm_storeu_si128(hs.as_mut_ptr().cast(), h);
+            tracker.finish(hs)
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use super::{Hit, Tracker};
+    use crate::fastcdc::table::GearTable;
+    use std::arch::aarch64::*;
+
+    #[target_feature(enable = "neon")]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) unsafe fn block_neon(
+        table: &GearTable,
+        source: &[u8],
+        starts: [usize; 2],
+        steps: usize,
+        init: [u64; 2],
+        mask_ls: u64,
+        mask: u64,
+        judge: bool,
+    ) -> Result<[u64; 2], Hit> {
+        let gear_ls = |b: usize| unsafe { table.gear_ls[*source.get_unchecked(b) as usize] };
+        let gear = |b: usize| unsafe { table.gear[*source.get_unchecked(b) as usize] };
+
+        // SAFETY: NEON is enabled, and the caller keeps every byte read within `source`.
+        unsafe {
+            let mut h = vld1q_u64(init.as_ptr());
+            let mls = vdupq_n_u64(mask_ls);
+            let ms = vdupq_n_u64(mask);
+            let mut tracker = Tracker::new(2);
+
+            for step in 0..steps {
+                let b = starts.map(|start| (start + step) * 2);
+
+                let x = vaddq_u64(
+                    vshlq_n_u64::<2>(h),
+                    vld1q_u64([gear_ls(b[0]), gear_ls(b[1])].as_ptr()),
+                );
+                h = vaddq_u64(x, vld1q_u64([gear(b[0] + 1), gear(b[1] + 1)].as_ptr()));
+
+                if judge {
+                    let hit_x = vceqzq_u64(vandq_u64(x, mls));
+                    let hit_h = vceqzq_u64(vandq_u64(h, ms));
+                    let any = vorrq_u64(hit_x, hit_h);
+                    let hits = (vgetq_lane_u64::<0>(any) & 1) | (vgetq_lane_u64::<1>(any) & 2);
+
+                    if hits != 0 {
+                        let (mut xs, mut hs) = ([0u64; 2], [0u64; 2]);
+                        vst1q_u64(xs.as_mut_ptr(), x);
+                        vst1q_u64(hs.as_mut_ptr(), h);
+                        let first =
+                            (vgetq_lane_u64::<0>(hit_x) & 1) | (vgetq_lane_u64::<1>(hit_x) & 2);
+
+                        if tracker.record(step, hits as u32, first as u32, &xs, &hs) {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            let mut hs = [0u64; 2];
+            vst1q_u64(hs.as_mut_ptr(), h);
+            tracker.finish(hs)
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/simd_tests.rs"]
+mod tests;
@@ -0,0 +1,130 @@
+use crate::fastcdc::{Chunk, ChunkerState, FastCDC};
+use bytes::Bytes;
+use futures::{Sink, ready};
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+impl FastCDC {
+    ///
+    /// Creates a sink that chunks the byte frames sent to it and forwards every chunk
+    /// to `inner`.
+    ///
+    /// The chunks match [`FastCDC::chunks`] over the concatenated frames.
+    ///
+    /// ## Arguments
+    ///
+    /// * `inner`: The destination of the chunks (must implement `Sink<Chunk>`).
+    ///
+    pub fn chunks_sink<Si>(&self, inner: Si) -> FastCDCSink<'_, Si>
+    where
+        Si: Sink<Chunk> + Unpin,
+    {
+        FastCDCSink::new(self.state(), inner)
+    }
+
+    ///
+    /// Creates a chunking sink that owns the chunker.
+    ///
+    /// Forwards the same chunks as [`FastCDC::chunks_sink`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `inner`: The destination of the chunks (must implement `Sink<Chunk>`).
+    ///
+    pub fn into_sink<Si>(self, inner: Si) -> FastCDCSink<'static, Si>
+    where
+        Si: Sink<Chunk> + Unpin,
+    {
+        FastCDCSink::new(self.into_state(), inner)
+    }
+}
+
+///
+/// A sink of `Bytes` frames that forwards completed chunks to an inner sink.
+///
+/// Created by [`FastCDC::chunks_sink`]. Flushing forwards every chunk whose cut point is
+/// known; the bytes after the last cut point stay buffered until the sink is closed, as
+/// cutting them early would change the chunks.
+///
+pub struct FastCDCSink<'a, Si> {
+    state: ChunkerState<'a>,
+    inner: Si,
+    pending: VecDeque<Chunk>,
+    finished: bool,
+}
+
+impl<'a, Si> FastCDCSink<'a, Si>
+where
+    Si: Sink<Chunk> + Unpin,
+{
+    fn new(state: ChunkerState<'a>, inner: Si) -> Self {
+        Self {
+            state,
+            inner,
+            pending: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Returns the number of bytes received but not yet forwarded as chunks.
+    pub fn buffered(&self) -> usize {
+        self.state.buffered() + self.pending.iter().map(|c| c.length).sum::<usize>()
+    }
+
+    /// Consumes the sink, returning the inner sink.
+    pub fn into_inner(self) -> Si {
+        self.inner
+    }
+
+    fn poll_forward(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Si::Error>> {
+        while !self.pending.is_empty() {
+            ready!(Pin::new(&mut self.inner).poll_ready(cx))?;
+            let chunk = self.pending.pop_front().unwrap();
+            Pin::new(&mut self.inner).start_send(chunk)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a, Si> Sink<Bytes> for FastCDCSink<'a, Si>
+where
+    Si: Sink<Chunk> + Unpin,
+{
+    type Error = Si::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().poll_forward(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Bytes) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        let chunks = this.state.push(&item);
+        this.pending.extend(chunks);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        ready!(this.poll_forward(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        if !this.finished {
+            while let Some(chunk) = this.state.finish() {
+                this.pending.push_back(chunk);
+            }
+            this.finished = true;
+        }
+        ready!(this.poll_forward(cx))?;
+        Pin::new(&mut this.inner).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sink_tests.rs"]
+mod tests;
@@ -0,0 +1,99 @@
+use crate::fastcdc::cut::GEAR;
+
+///
+/// A MinHash sketch of a chunk's content, for finding near-duplicate chunks.
+///
+/// The gear hash rolled over the chunk (one byte at a time, as the chunker does) fingerprints
+/// the 64 bytes ending at each position. Each fingerprint is mixed and assigned to one of
+/// `bins` bins by its top bits, and every bin keeps its minimum (one-permutation hashing),
+/// so a sketch costs one pass over the data regardless of its size. The fraction of
+/// matching bins estimates the Jaccard similarity of two chunks' 64-byte shingles.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sketch {
+    mins: Vec<u64>,
+}
+
+/// Marks a bin no fingerprint was assigned to.
+const EMPTY: u64 = u64::MAX;
+
+impl Sketch {
+    ///
+    /// Computes the sketch of `data`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `data`: The bytes to sketch.
+    /// * `bins`: The number of bins; more bins give a finer similarity estimate.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `bins` is zero.
+    ///
+    pub fn from_data(data: &[u8], bins: usize) -> Self {
+        assert!(bins > 0, "bins must be greater than zero");
+
+        let mut mins = vec![EMPTY; bins];
+        let mut fp_hash = 0u64;
+
+        for &byte in data {
+            fp_hash = (fp_hash << 1).wrapping_add(GEAR[byte as usize]);
+
+            let mixed = mix(fp_hash);
+            let bin = ((mixed as u128 * bins as u128) >> 64) as usize;
+            mins[bin] = mins[bin].min(mixed);
+        }
+
+        Self { mins }
+    }
+
+    /// Returns the minimum of each bin, with `u64::MAX` for empty bins.
+    pub fn mins(&self) -> &[u64] {
+        &self.mins
+    }
+
+    ///
+    /// Estimates the similarity of two sketches, from `0.0` (disjoint) to `1.0` (identical).
+    ///
+    /// Only bins that are non-empty in at least one sketch are compared.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the sketches have different numbers of bins.
+    ///
+    pub fn similarity(&self, other: &Sketch) -> f64 {
+        assert_eq!(
+            self.mins.len(),
+            other.mins.len(),
+            "sketches must have the same number of bins"
+        );
+
+        let (mut matched, mut compared) = (0usize, 0usize);
+        for (&a, &b) in self.mins.iter().zip(&other.mins) {
+            if a == EMPTY && b == EMPTY {
+                continue;
+            }
+            compared += 1;
+            if a == b {
+                matched += 1;
+            }
+        }
+
+        if compared == 0 {
+            return 1.0;
+        }
+        matched as f64 / compared as f64
+    }
+}
+
+/// The SplitMix64 finalizer, spreading every bit of the gear hash over the whole word.
+#[inline]
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+#[path = "tests/sketch_tests.rs"]
+mod tests;
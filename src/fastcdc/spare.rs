@@ -0,0 +1,60 @@
+use bytes::BytesMut;
+use std::mem::MaybeUninit;
+
+///
+/// Tracks how much of a buffer's spare capacity is initialized, so that reading into it
+/// zeroes each byte of capacity at most once.
+///
+/// `Read` implementations may read the slice they are given, so it must be initialized;
+/// zeroing the whole spare capacity before every read would cost as much as the read.
+/// Like `std::io::BorrowedBuf`, only capacity that has never been initialized is zeroed.
+///
+/// The count stays valid while the bytes after the buffer's length are untouched, which
+/// holds across `split_to` and `advance`. Every append to the buffer must be reported with
+/// [`SpareInit::filled`]; any other growth must go through [`SpareInit::reserve`].
+///
+#[derive(Debug, Default)]
+pub struct SpareInit {
+    init: usize,
+}
+
+impl SpareInit {
+    /// Returns the first `len` bytes of `buf`'s spare capacity, reserving and zeroing them
+    /// as needed.
+    pub(crate) fn spare<'b>(&mut self, buf: &'b mut BytesMut, len: usize) -> &'b mut [u8] {
+        let spare = self.reserve(buf, len);
+        if self.init < len {
+            spare[self.init..].fill(MaybeUninit::new(0));
+            self.init = len;
+        }
+
+        // SAFETY: The first `init` bytes of the spare capacity, and so all of `spare`, have
+        // been initialized, either zeroed above or written since.
+        unsafe { &mut *(spare as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// Returns the first `len` bytes of `buf`'s spare capacity, reserving them as needed but
+    /// leaving them possibly uninitialized.
+    pub(crate) fn reserve<'b>(
+        &mut self,
+        buf: &'b mut BytesMut,
+        len: usize,
+    ) -> &'b mut [MaybeUninit<u8>] {
+        if buf.capacity() - buf.len() < len {
+            // The buffer may move or be reallocated, so nothing past its length is known.
+            buf.reserve(len);
+            self.init = 0;
+        }
+
+        &mut buf.spare_capacity_mut()[..len]
+    }
+
+    /// Records that `n` bytes were appended to the buffer from the start of its spare capacity.
+    pub(crate) fn filled(&mut self, n: usize) {
+        self.init = self.init.saturating_sub(n);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/spare_tests.rs"]
+mod tests;
@@ -0,0 +1,79 @@
+use crate::fastcdc::{Chunk, FastCDC, FastCDCDecoder};
+use bytes::BytesMut;
+use std::borrow::Cow;
+
+impl FastCDC {
+    /// Creates a push-based chunker for callers that deliver the input themselves.
+    pub fn state(&self) -> ChunkerState<'_> {
+        ChunkerState::new(Cow::Borrowed(self))
+    }
+
+    /// Creates a push-based chunker that owns the chunker.
+    pub fn into_state(self) -> ChunkerState<'static> {
+        ChunkerState::new(Cow::Owned(self))
+    }
+}
+
+///
+/// A sans-IO chunker: the caller pushes bytes as they arrive and collects the chunks.
+///
+/// Suits event loops, completion-based I/O, and FFI callers where neither `Read` nor
+/// `AsyncRead` fits. The rolling state persists between pushes, so the chunks match
+/// [`FastCDC::chunks`] over the concatenated input however it is split.
+///
+pub struct ChunkerState<'a> {
+    decoder: FastCDCDecoder<'a>,
+    buf: BytesMut,
+}
+
+impl<'a> ChunkerState<'a> {
+    fn new(chunker: Cow<'a, FastCDC>) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(chunker.max_size),
+            decoder: FastCDCDecoder::new(chunker),
+        }
+    }
+
+    ///
+    /// Appends `data` to the input and returns every chunk whose cut point is now known.
+    ///
+    /// At most `max_size` bytes (plus the last push) stay buffered.
+    ///
+    /// ## Arguments
+    ///
+    /// * `data`: The next bytes of the input.
+    ///
+    pub fn push(&mut self, data: &[u8]) -> Vec<Chunk> {
+        self.buf.extend_from_slice(data);
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = self.decoder.next_chunk(&mut self.buf, false) {
+            chunks.push(chunk);
+        }
+        chunks
+    }
+
+    ///
+    /// Ends the input and returns the next of the remaining chunks.
+    ///
+    /// Call it until it returns `None` to flush everything buffered; usually a single
+    /// chunk remains, but an adjusted cut point may leave more.
+    ///
+    pub fn finish(&mut self) -> Option<Chunk> {
+        self.decoder.next_chunk(&mut self.buf, true)
+    }
+
+    /// Returns the number of bytes pushed but not yet emitted as chunks.
+    pub fn buffered(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns the number of bytes emitted as chunks so far.
+    pub fn processed(&self) -> u64 {
+        self.decoder.processed()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/state_tests.rs"]
+mod tests;
@@ -0,0 +1,77 @@
+use crate::fastcdc::FastCDC;
+
+///
+/// Counters describing how a chunker found its cut points.
+///
+/// Collected per iterator or stream when the `stats` feature is enabled, so the
+/// hash-judgment analyses of the FastCDC paper can be reproduced on real datasets.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkerStats {
+    /// The number of chunks produced.
+    pub chunks: u64,
+    /// The number of bytes fed through the gear hash.
+    pub bytes_hashed: u64,
+    /// The number of bytes passed over by sub-minimum cut-point skipping.
+    pub bytes_skipped: u64,
+    /// Cut points matched with the small (stricter) mask, below `avg_size`.
+    pub small_mask_cuts: u64,
+    /// Cut points matched with the large (looser) mask, from `avg_size` on.
+    pub large_mask_cuts: u64,
+    /// Cuts forced at `max_size` because no cut point matched.
+    pub forced_cuts: u64,
+    /// Final chunks ended by the end of the data.
+    pub tail_cuts: u64,
+}
+
+impl ChunkerStats {
+    /// Returns the average number of bytes hashed per chunk.
+    pub fn avg_scan_len(&self) -> f64 {
+        if self.chunks == 0 {
+            return 0.0;
+        }
+        self.bytes_hashed as f64 / self.chunks as f64
+    }
+
+    ///
+    /// Records a cut point returned by the scan.
+    ///
+    /// `cutpoint` is the scan result before alignment, and `scan_len` the number of bytes
+    /// that were available to it.
+    ///
+    pub(super) fn record(&mut self, chunker: &FastCDC, cutpoint: usize, scan_len: usize) {
+        self.chunks += 1;
+
+        let hash_start = if chunker.min_skip {
+            chunker.min_size
+        } else {
+            0
+        };
+        let skipped = hash_start.min(cutpoint);
+        self.bytes_skipped += skipped as u64;
+
+        if scan_len <= chunker.min_size {
+            // Too short to scan at all.
+            self.bytes_skipped += (cutpoint - skipped) as u64;
+            self.tail_cuts += 1;
+            return;
+        }
+
+        if cutpoint < scan_len {
+            // The byte that matched is hashed but belongs to the next chunk.
+            self.bytes_hashed += (cutpoint + 1 - hash_start) as u64;
+            if cutpoint < chunker.avg_size {
+                self.small_mask_cuts += 1;
+            } else {
+                self.large_mask_cuts += 1;
+            }
+        } else {
+            self.bytes_hashed += (scan_len - hash_start) as u64;
+            if scan_len == chunker.max_size {
+                self.forced_cuts += 1;
+            } else {
+                self.tail_cuts += 1;
+            }
+        }
+    }
+}
@@ -1,13 +1,115 @@
-use crate::fastcdc::{Chunk, FastCDC, cut::find_cutpoint_inner};
-use bytes::BytesMut;
-use futures::Stream;
+use crate::fastcdc::{BufferPool, BufferUsage, Chunk, FastCDC, FastCDCDecoder, SpareInit};
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, stream::FusedStream, task::AtomicWaker};
 use std::{
+    borrow::Cow,
     io,
     pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
     task::{Context, Poll},
 };
+#[cfg(feature = "async")]
 use tokio::io::{AsyncRead, ReadBuf};
 
+/// Default number of chunks a stream yields in a row before returning to the executor.
+pub const YIELD_BUDGET: usize = 128;
+
+mod sealed {
+    use super::*;
+
+    pub trait Sealed {
+        /// Reads at most `limit` bytes into the spare capacity of `buf`, returning the number
+        /// of bytes appended. `init` tracks the spare capacity already initialized.
+        fn poll_fill(
+            &mut self,
+            cx: &mut Context<'_>,
+            buf: &mut BytesMut,
+            init: &mut SpareInit,
+            limit: usize,
+        ) -> Poll<io::Result<usize>>;
+    }
+}
+
+///
+/// An async reader that [`FastCDCStream`] can pull data from.
+///
+/// Implemented for every `tokio::io::AsyncRead` (with the `async` feature) and for
+/// [`FuturesRead`], which adapts a `futures::io::AsyncRead` (with the `futures-io` feature).
+///
+pub trait AsyncSource: Unpin + sealed::Sealed {}
+
+#[cfg(feature = "async")]
+impl<R: AsyncRead + Unpin> AsyncSource for R {}
+
+#[cfg(feature = "async")]
+impl<R: AsyncRead + Unpin> sealed::Sealed for R {
+    fn poll_fill(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut BytesMut,
+        init: &mut SpareInit,
+        limit: usize,
+    ) -> Poll<io::Result<usize>> {
+        // `ReadBuf` tracks initialization itself, so nothing is zeroed.
+        let mut read_buf = ReadBuf::uninit(init.reserve(buf, limit));
+
+        match Pin::new(self).poll_read(cx, &mut read_buf) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Ready(Ok(())) => {
+                let n = read_buf.filled().len();
+                // SAFETY: `read_buf` ensures `n` bytes were initialized/written.
+                unsafe {
+                    let new_len = buf.len() + n;
+                    buf.set_len(new_len);
+                }
+                init.filled(n);
+                Poll::Ready(Ok(n))
+            }
+        }
+    }
+}
+
+///
+/// Adapts a `futures::io::AsyncRead` for [`FastCDCStream`], so the stream can be driven
+/// by async-std, smol, or any other executor without depending on tokio.
+///
+/// Created by [`FastCDC::chunks_futures`] and [`FastCDC::into_futures_stream`].
+///
+#[cfg(feature = "futures-io")]
+#[derive(Debug)]
+pub struct FuturesRead<R>(pub R);
+
+#[cfg(feature = "futures-io")]
+impl<R: futures::io::AsyncRead + Unpin> AsyncSource for FuturesRead<R> {}
+
+#[cfg(feature = "futures-io")]
+impl<R: futures::io::AsyncRead + Unpin> sealed::Sealed for FuturesRead<R> {
+    fn poll_fill(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut BytesMut,
+        init: &mut SpareInit,
+        limit: usize,
+    ) -> Poll<io::Result<usize>> {
+        // `futures::io::AsyncRead` takes an initialized slice; capacity is zeroed only once.
+        let result = Pin::new(&mut self.0).poll_read(cx, init.spare(buf, limit));
+        if let Poll::Ready(Ok(n)) = result {
+            // SAFETY: The spare capacity read into is initialized.
+            unsafe {
+                let new_len = buf.len() + n;
+                buf.set_len(new_len);
+            }
+            init.filled(n);
+        }
+
+        result
+    }
+}
+
 impl FastCDC {
     ///
     /// Creates a stream that yields chunks from the provided async reader.
@@ -16,158 +118,366 @@ impl FastCDC {
     ///
     /// * `reader`: The source to read data from (must implement `AsyncRead`).
     ///
+    #[cfg(feature = "async")]
     pub fn chunks_async<R>(&self, reader: R) -> FastCDCStream<'_, R>
     where
         R: AsyncRead + Unpin,
     {
-        FastCDCStream {
-            chunker: self,
-            reader,
-            buf: BytesMut::with_capacity(self.max_size),
-            processed: 0,
-            eof: false,
-            scanned: 0,
-            fp_hash: 0,
-        }
+        FastCDCStream::new(Cow::Borrowed(self), reader)
     }
 
-    #[inline]
-    fn find_cutpoint_from(&self, source: &[u8], offset: usize, prev_hash: u64) -> (u64, usize) {
-        find_cutpoint_inner(
-            source,
-            offset,
-            prev_hash,
-            self.min_size,
-            self.avg_size,
-            self.max_size,
-            self.masks.mask_s,
-            self.masks.mask_s_ls,
-            self.masks.mask_l,
-            self.masks.mask_l_ls,
-        )
+    ///
+    /// Creates a stream that owns the chunker, so it can be returned from a function or
+    /// spawned onto a runtime.
+    ///
+    /// Yields the same chunks as [`FastCDC::chunks_async`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn into_stream<R>(self, reader: R) -> FastCDCStream<'static, R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        FastCDCStream::new(Cow::Owned(self), reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided `futures` async reader.
+    ///
+    /// Yields the same chunks as [`FastCDC::chunks_async`], without requiring tokio.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `futures::io::AsyncRead`).
+    ///
+    #[cfg(feature = "futures-io")]
+    pub fn chunks_futures<R>(&self, reader: R) -> FastCDCStream<'_, FuturesRead<R>>
+    where
+        R: futures::io::AsyncRead + Unpin,
+    {
+        FastCDCStream::new(Cow::Borrowed(self), FuturesRead(reader))
+    }
+
+    ///
+    /// Creates a stream over a `futures` async reader that owns the chunker.
+    ///
+    /// Yields the same chunks as [`FastCDC::chunks_futures`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `futures::io::AsyncRead`).
+    ///
+    #[cfg(feature = "futures-io")]
+    pub fn into_futures_stream<R>(self, reader: R) -> FastCDCStream<'static, FuturesRead<R>>
+    where
+        R: futures::io::AsyncRead + Unpin,
+    {
+        FastCDCStream::new(Cow::Owned(self), FuturesRead(reader))
     }
 }
 
 pub struct FastCDCStream<'a, R>
 where
-    R: AsyncRead + Unpin,
+    R: AsyncSource,
 {
-    chunker: &'a FastCDC,
+    decoder: FastCDCDecoder<'a>,
     reader: R,
     buf: BytesMut,
-    processed: u64,
+    buf_init: SpareInit,
     eof: bool,
-    scanned: usize,
-    fp_hash: u64,
+    peak: usize,
+    total_len: Option<u64>,
+    limit: Option<MemoryLimit>,
+    yield_budget: usize,
+    budget: usize,
+}
+
+/// A cap on the bytes buffered plus those of yielded chunks still alive.
+struct MemoryLimit {
+    limit: usize,
+    in_flight: Arc<InFlight>,
+}
+
+/// The bytes of yielded chunks not yet dropped, and the stream waiting for them.
+#[derive(Default)]
+struct InFlight {
+    bytes: AtomicUsize,
+    waker: AtomicWaker,
+}
+
+/// A chunk's data, released from the in-flight total when the last clone is dropped.
+struct Tracked {
+    data: Bytes,
+    in_flight: Arc<InFlight>,
+}
+
+impl AsRef<[u8]> for Tracked {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.in_flight
+            .bytes
+            .fetch_sub(self.data.len(), Ordering::AcqRel);
+        self.in_flight.waker.wake();
+    }
 }
 
 impl<'a, R> FastCDCStream<'a, R>
 where
-    R: AsyncRead + Unpin,
+    R: AsyncSource,
 {
-    fn yield_chunk(&mut self, cutpoint: usize, fp_hash: u64) -> Chunk {
-        let data = self.buf.split_to(cutpoint).freeze();
-        let chunk = Chunk {
-            fp_hash,
-            data,
-            offset: self.processed,
-            length: cutpoint,
-        };
+    fn new(chunker: Cow<'a, FastCDC>, reader: R) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(chunker.max_size),
+            buf_init: SpareInit::default(),
+            decoder: FastCDCDecoder::new(chunker),
+            reader,
+            eof: false,
+            peak: 0,
+            total_len: None,
+            limit: None,
+            yield_budget: YIELD_BUDGET,
+            budget: YIELD_BUDGET,
+        }
+    }
+
+    ///
+    /// Declares the total length of the input, enabling [`Stream::size_hint`].
+    ///
+    /// The hint bounds the number of remaining chunks by the remaining bytes divided by
+    /// `max_size` and `min_size`. It is only as accurate as `len`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `len`: The number of bytes the reader will yield in total.
+    ///
+    pub fn with_total_len(mut self, len: u64) -> Self {
+        self.total_len = Some(len);
+        self
+    }
+
+    ///
+    /// Copies every chunk into a buffer drawn from `pool`, recycled when the chunk is dropped.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to draw chunk buffers from; it may be shared by many streams.
+    ///
+    pub fn with_pool(mut self, pool: BufferPool) -> Self {
+        self.decoder.pool = Some(pool);
+        self
+    }
+
+    ///
+    /// Caps the bytes held by the stream: those buffered plus those of yielded chunks that
+    /// are still alive, wherever they are queued downstream.
+    ///
+    /// Once the cap is reached the stream stops reading and returns `Pending` until enough
+    /// chunks are dropped, so a lagging consumer applies backpressure to the reader instead
+    /// of letting chunks pile up.
+    ///
+    /// ## Arguments
+    ///
+    /// * `limit`: The maximum number of bytes held.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `limit` is less than `max_size`, which a single chunk may need.
+    ///
+    pub fn with_memory_limit(mut self, limit: usize) -> Self {
+        assert!(
+            limit >= self.decoder.chunker.max_size,
+            "memory limit must be at least max_size"
+        );
 
-        self.processed += cutpoint as u64;
-        self.scanned = 0;
-        self.fp_hash = 0;
+        self.limit = Some(MemoryLimit {
+            limit,
+            in_flight: Arc::default(),
+        });
+        self
+    }
 
-        chunk
+    ///
+    /// Sets how many chunks the stream yields in a row before returning `Pending` once, so
+    /// a consumer draining an always-ready reader (in memory, or a fast file) periodically
+    /// returns to the executor instead of starving other tasks.
+    ///
+    /// The budget is refilled whenever the reader itself returns `Pending`. The default is
+    /// [`YIELD_BUDGET`]; `usize::MAX` effectively disables yielding.
+    ///
+    /// ## Arguments
+    ///
+    /// * `budget`: The number of chunks yielded between forced yields.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `budget` is zero.
+    ///
+    pub fn with_yield_budget(mut self, budget: usize) -> Self {
+        assert!(budget > 0, "yield budget must be greater than zero");
+
+        self.yield_budget = budget;
+        self.budget = budget;
+        self
+    }
+
+    /// Returns the bytes of yielded chunks still alive, if a memory limit is set.
+    pub fn in_flight(&self) -> Option<usize> {
+        self.limit
+            .as_ref()
+            .map(|limit| limit.in_flight.bytes.load(Ordering::Acquire))
+    }
+
+    /// Returns the bytes currently buffered and the peak reached so far.
+    pub fn buffer_usage(&self) -> BufferUsage {
+        BufferUsage {
+            buffered: self.buf.len(),
+            peak: self.peak,
+        }
+    }
+
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        self.decoder.stats()
+    }
+}
+
+impl MemoryLimit {
+    /// Returns how many more bytes may be read, registering to be woken if none may.
+    fn headroom(&self, buffered: usize, cx: &mut Context<'_>) -> usize {
+        let headroom = || {
+            let held = buffered + self.in_flight.bytes.load(Ordering::Acquire);
+            self.limit.saturating_sub(held)
+        };
+
+        match headroom() {
+            0 => {
+                // Check again after registering, in case the last chunk dropped meanwhile.
+                self.in_flight.waker.register(cx.waker());
+                headroom()
+            }
+            headroom => headroom,
+        }
     }
 }
 
 impl<'a, R> Stream for FastCDCStream<'a, R>
 where
-    R: AsyncRead + Unpin,
+    R: AsyncSource,
 {
     type Item = io::Result<Chunk>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
 
+        if this.budget == 0 {
+            // Give other tasks a turn; the stream is ready to continue as soon as polled.
+            this.budget = this.yield_budget;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
         loop {
             if this.eof && this.buf.is_empty() {
                 return Poll::Ready(None);
             }
 
-            if this.buf.len() >= this.chunker.min_size || (this.eof && !this.buf.is_empty()) {
-                let scan_len = this.buf.len().min(this.chunker.max_size);
-
-                // Resume search from `scanned` offset using saved `fp_hash` to ensure O(N) complexity.
-                let (new_fp_hash, found_cutpoint) = this.chunker.find_cutpoint_from(
-                    &this.buf[..scan_len],
-                    this.scanned,
-                    this.fp_hash,
-                );
-
-                let cutpoint = match found_cutpoint {
-                    // A valid cutpoint found by the rolling hash.
-                    cp if cp < scan_len => Some(cp),
-
-                    // Force a cut if the buffer exceeds the maximum chunk size to prevent memory issues.
-                    _ if this.buf.len() >= this.chunker.max_size => Some(this.chunker.max_size),
-
-                    // Flush the remaining bytes as the last chunk if the stream has ended.
-                    _ if this.eof => Some(scan_len),
-
-                    // Return `None` to wait for more data if no conditions are met.
-                    _ => None,
-                };
-
-                match cutpoint {
-                    Some(cp) => {
-                        let chunk = this.yield_chunk(cp, new_fp_hash);
-                        return Poll::Ready(Some(Ok(chunk)));
-                    }
-                    None => {
-                        // Align cursor to 2-byte boundary and skip already checked bytes.
-                        this.scanned = ((scan_len / 2) * 2).max(this.chunker.min_size);
-                        this.fp_hash = new_fp_hash;
-                    }
+            if let Some(mut chunk) = this.decoder.next_chunk(&mut this.buf, this.eof) {
+                if let Some(limit) = &this.limit {
+                    limit
+                        .in_flight
+                        .bytes
+                        .fetch_add(chunk.length, Ordering::AcqRel);
+                    chunk.data = Bytes::from_owner(Tracked {
+                        data: chunk.data,
+                        in_flight: limit.in_flight.clone(),
+                    });
                 }
+                this.budget -= 1;
+                return Poll::Ready(Some(Ok(chunk)));
             }
 
-            if this.buf.len() < this.chunker.max_size && !this.eof {
-                // Reserve space incrementally (4KB ~ remaining) to avoid large upfront allocation.
-                let read_size = (4096)
-                    .max(this.chunker.min_size)
-                    .min(this.chunker.max_size.saturating_sub(this.buf.len()));
-                if read_size > 0 {
-                    this.buf.reserve(read_size);
+            let (min_size, max_size) =
+                (this.decoder.chunker.min_size, this.decoder.chunker.max_size);
+            if this.buf.len() < max_size && !this.eof {
+                // Read incrementally (4KB ~ remaining) to avoid large upfront allocation.
+                let mut read_size = this.decoder.chunker.read_size.unwrap_or_else(|| {
+                    (4096)
+                        .max(min_size)
+                        .min(max_size.saturating_sub(this.buf.len()))
+                });
+                if let Some(limit) = &this.limit {
+                    match limit.headroom(this.buf.len(), cx) {
+                        0 => {
+                            this.budget = this.yield_budget;
+                            return Poll::Pending;
+                        }
+                        headroom => read_size = read_size.min(headroom),
+                    }
                 }
 
-                let dst = this.buf.spare_capacity_mut();
-                let mut read_buf = ReadBuf::uninit(dst);
-
-                match Pin::new(&mut this.reader).poll_read(cx, &mut read_buf) {
-                    Poll::Pending => return Poll::Pending,
-                    Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
-                    Poll::Ready(Ok(())) => {
-                        let n = read_buf.filled().len();
-                        if n == 0 {
-                            this.eof = true;
-                        } else {
-                            // SAFETY: `read_buf` ensures `n` bytes were initialized/written.
-                            unsafe {
-                                let new_len = this.buf.len() + n;
-                                this.buf.set_len(new_len);
-                            }
-                        }
+                match this
+                    .reader
+                    .poll_fill(cx, &mut this.buf, &mut this.buf_init, read_size)
+                {
+                    Poll::Pending => {
+                        this.budget = this.yield_budget;
+                        return Poll::Pending;
                     }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                    Poll::Ready(Ok(0)) => this.eof = true,
+                    Poll::Ready(Ok(_)) => this.peak = this.peak.max(this.buf.len()),
                 }
             } else {
                 return Poll::Pending;
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let Some(total_len) = self.total_len else {
+            return (0, None);
+        };
+        let chunker = &self.decoder.chunker;
+        let remaining = total_len.saturating_sub(self.decoder.processed);
+        if remaining == 0 {
+            return (0, Some(0));
+        }
+
+        // Every chunk but the last is at least `min_size`, less up to 3 bytes when
+        // cut points are moved off UTF-8 sequences.
+        let shortest = if chunker.utf8 {
+            chunker.min_size - 3
+        } else {
+            chunker.min_size
+        };
+        let lower = remaining.div_ceil(chunker.max_size as u64);
+        let upper = remaining / shortest as u64 + 1;
+
+        (
+            usize::try_from(lower).unwrap_or(usize::MAX),
+            usize::try_from(upper).ok(),
+        )
+    }
+}
+
+impl<'a, R> FusedStream for FastCDCStream<'a, R>
+where
+    R: AsyncSource,
+{
+    fn is_terminated(&self) -> bool {
+        self.eof && self.buf.is_empty()
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "async"))]
 #[path = "tests/stream_tests.rs"]
 mod tests;
@@ -0,0 +1,162 @@
+use crate::digest::Sha256;
+use crate::fastcdc::FastCDC;
+use std::collections::HashSet;
+use std::io::{self, Read};
+
+/// Chunk counts and dedup metrics of one configuration, produced by [`Sweep::run`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SweepReport {
+    /// The number of chunks produced.
+    pub chunks: u64,
+    /// The number of distinct chunks by SHA-256 digest.
+    pub unique_chunks: u64,
+    /// The total length of the input.
+    pub total_bytes: u64,
+    /// The total length of the distinct chunks.
+    pub unique_bytes: u64,
+}
+
+impl SweepReport {
+    /// Returns the average chunk size.
+    pub fn avg_chunk_size(&self) -> f64 {
+        if self.chunks == 0 {
+            return 0.0;
+        }
+        self.total_bytes as f64 / self.chunks as f64
+    }
+
+    /// Returns the dedup ratio, the total length divided by the length of distinct chunks.
+    pub fn dedup_ratio(&self) -> f64 {
+        if self.unique_bytes == 0 {
+            return 1.0;
+        }
+        self.total_bytes as f64 / self.unique_bytes as f64
+    }
+}
+
+/// Per-configuration progress through the shared buffer.
+struct Lane {
+    /// The stream offset of the next chunk.
+    next: u64,
+    seen: HashSet<[u8; 32]>,
+    report: SweepReport,
+}
+
+///
+/// Compares several chunker configurations over the same input in a single read pass.
+///
+/// The input is read once into a window shared by all configurations, and each one cuts
+/// chunks from it exactly as [`FastCDC::chunks`] would. Gear hashing itself cannot be
+/// shared, as every cut point depends on where its chunk starts, but the dataset is only
+/// read from storage once however many configurations are compared.
+///
+#[derive(Debug, Clone)]
+pub struct Sweep {
+    chunkers: Vec<FastCDC>,
+}
+
+impl Sweep {
+    ///
+    /// Constructs a sweep over the given configurations.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunkers`: The configurations to compare.
+    ///
+    pub fn new(chunkers: Vec<FastCDC>) -> Self {
+        Self { chunkers }
+    }
+
+    /// Returns the configurations being compared.
+    pub fn chunkers(&self) -> &[FastCDC] {
+        &self.chunkers
+    }
+
+    ///
+    /// Chunks `reader` with every configuration, returning one report per configuration
+    /// in the order they were given.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns the first error raised by `reader`, other than `ErrorKind::Interrupted`.
+    ///
+    pub fn run<R: Read>(&self, mut reader: R) -> io::Result<Vec<SweepReport>> {
+        let block = self
+            .chunkers
+            .iter()
+            .map(|c| c.max_size())
+            .max()
+            .unwrap_or(0);
+        let mut lanes: Vec<Lane> = self
+            .chunkers
+            .iter()
+            .map(|_| Lane {
+                next: 0,
+                seen: HashSet::new(),
+                report: SweepReport::default(),
+            })
+            .collect();
+
+        // `window[0]` is at stream offset `base`.
+        let mut window = Vec::with_capacity(block * 2);
+        let mut base = 0u64;
+        let mut eof = self.chunkers.is_empty();
+
+        while !eof {
+            let len = window.len();
+            window.resize(len + block, 0);
+            let n = loop {
+                match reader.read(&mut window[len..]) {
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    other => break other?,
+                }
+            };
+            window.truncate(len + n);
+            eof = n == 0;
+
+            for (chunker, lane) in self.chunkers.iter().zip(&mut lanes) {
+                loop {
+                    let start = (lane.next - base) as usize;
+                    let available = window.len() - start;
+                    if available == 0 || (!eof && available < chunker.max_size()) {
+                        break;
+                    }
+
+                    let source = &window[start..];
+                    let scan_len = available.min(chunker.max_size());
+                    let (_, mut cutpoint) = chunker.find_cutpoint_from(&source[..scan_len], 0, 0);
+
+                    // Mirrors the iterator: the tail of the stream is never moved.
+                    if !(eof && cutpoint == available) {
+                        cutpoint = chunker
+                            .adjust_cutpoint(lane.next, &source[..scan_len], cutpoint, true)
+                            .unwrap_or(cutpoint);
+                    }
+
+                    lane.report.chunks += 1;
+                    lane.report.total_bytes += cutpoint as u64;
+                    if lane.seen.insert(Sha256::digest(&source[..cutpoint])) {
+                        lane.report.unique_chunks += 1;
+                        lane.report.unique_bytes += cutpoint as u64;
+                    }
+                    lane.next += cutpoint as u64;
+                }
+            }
+
+            // Drop the data every configuration has moved past.
+            let consumed = lanes.iter().map(|l| l.next).min().unwrap_or(base);
+            window.drain(..(consumed - base) as usize);
+            base = consumed;
+        }
+
+        Ok(lanes.into_iter().map(|lane| lane.report).collect())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sweep_tests.rs"]
+mod tests;
@@ -0,0 +1,308 @@
+use crate::digest::Sha256;
+use crate::fastcdc::PAPER_GEAR;
+use crate::fastcdc::cut::{GEAR, GEAR_LS};
+#[cfg(feature = "four-byte-rolling")]
+use crate::fastcdc::cut::{GEAR_LS2, GEAR_LS3};
+use crate::fastcdc::mask::MASK_TABLE;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Number of entries in the mask table (one per mask bit count).
+pub(crate) const MASK_TABLE_SIZE: usize = 32;
+/// Bit counts below this are never used and left as zero.
+pub(crate) const MASK_PADDING_SLOTS: usize = 5;
+/// Bits at or above this position are penalized, since high bits carry little entropy.
+const HIGH_BIT_RISK_START: usize = 60;
+
+/// Number of seeded tables kept by [`GearTable::from_seed`].
+const SEED_CACHE_SIZE: usize = 64;
+
+/// The Gear table, its left-shifted copies, and the masks derived from it.
+#[derive(Clone)]
+pub(crate) struct GearTable {
+    pub(crate) gear: [u64; 256],
+    pub(crate) gear_ls: [u64; 256],
+    #[cfg(feature = "four-byte-rolling")]
+    pub(crate) gear_ls2: [u64; 256],
+    #[cfg(feature = "four-byte-rolling")]
+    pub(crate) gear_ls3: [u64; 256],
+    pub(crate) masks: [u64; MASK_TABLE_SIZE],
+}
+
+/// The table generated at build time (from `GEAR_SEED`).
+pub(crate) static BUILTIN: GearTable = GearTable {
+    gear: GEAR,
+    gear_ls: GEAR_LS,
+    #[cfg(feature = "four-byte-rolling")]
+    gear_ls2: GEAR_LS2,
+    #[cfg(feature = "four-byte-rolling")]
+    gear_ls3: GEAR_LS3,
+    masks: MASK_TABLE,
+};
+
+static SEED_CACHE: Mutex<Vec<(u64, Arc<GearTable>)>> = Mutex::new(Vec::new());
+
+/// How a chunker's Gear table was chosen, as recorded in manifests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TableId {
+    /// The built-in table, identified by its fingerprint.
+    Builtin(u64),
+    /// A table generated from this seed.
+    Seed(u64),
+    /// A keyed or caller-supplied table, identified by its fingerprint.
+    Custom(u64),
+}
+
+impl TableId {
+    /// Returns the identity of the built-in table.
+    pub(crate) fn builtin() -> Self {
+        TableId::Builtin(BUILTIN.fingerprint())
+    }
+}
+
+impl fmt::Debug for GearTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GearTable")
+            .field("gear[0]", &self.gear[0])
+            .finish_non_exhaustive()
+    }
+}
+
+impl GearTable {
+    /// Builds the tables for `gear`, deriving the masks the same way as the build script.
+    pub(crate) fn from_gear(gear: [u64; 256]) -> Self {
+        Self {
+            gear,
+            gear_ls: gear.map(|val| val << 1),
+            #[cfg(feature = "four-byte-rolling")]
+            gear_ls2: gear.map(|val| val << 2),
+            #[cfg(feature = "four-byte-rolling")]
+            gear_ls3: gear.map(|val| val << 3),
+            masks: derive_masks(&gear),
+        }
+    }
+
+    /// Returns the tables the build script generates for `seed`, from a process-wide cache.
+    pub(crate) fn from_seed(seed: u64) -> Arc<Self> {
+        let mut cache = SEED_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some((_, table)) = cache.iter().find(|(s, _)| *s == seed) {
+            return Arc::clone(table);
+        }
+
+        let mut rng = ChaCha20::seed_from_u64(seed);
+        let table = Arc::new(Self::from_gear([(); 256].map(|_| rng.next_u64())));
+
+        if cache.len() == SEED_CACHE_SIZE {
+            cache.remove(0);
+        }
+        cache.push((seed, Arc::clone(&table)));
+
+        table
+    }
+
+    /// Returns the fingerprint of the table (see [`fingerprint`]).
+    pub(crate) fn fingerprint(&self) -> u64 {
+        fingerprint(&self.gear)
+    }
+}
+
+/// Returns the first eight bytes of the SHA-256 of `gear`, identifying it without revealing it.
+pub(crate) fn fingerprint(gear: &[u64; 256]) -> u64 {
+    let mut bytes = [0u8; 256 * 8];
+    for (chunk, val) in bytes.chunks_exact_mut(8).zip(gear) {
+        chunk.copy_from_slice(&val.to_le_bytes());
+    }
+    u64::from_le_bytes(Sha256::digest(&bytes)[..8].try_into().unwrap())
+}
+
+/// Returns the published table with the given fingerprint, so manifests can rebuild it.
+pub(crate) fn published_gear(id: u64) -> Option<&'static [u64; 256]> {
+    [&PAPER_GEAR]
+        .into_iter()
+        .find(|gear| fingerprint(gear) == id)
+}
+
+// --- ChaCha20 ---
+
+/// The ChaCha20 generator of `rand_chacha`, so seeds reproduce build-time `GEAR_SEED` tables.
+struct ChaCha20 {
+    key: [u32; 8],
+    counter: u64,
+    block: [u32; 16],
+    index: usize,
+}
+
+impl ChaCha20 {
+    /// Expands `state` into a key with PCG32, as `rand_core`'s `seed_from_u64` does.
+    fn seed_from_u64(mut state: u64) -> Self {
+        const MUL: u64 = 6_364_136_223_846_793_005;
+        const INC: u64 = 11_634_580_027_462_260_723;
+
+        let mut key = [0u32; 8];
+        for word in key.iter_mut() {
+            state = state.wrapping_mul(MUL).wrapping_add(INC);
+            let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+            *word = xorshifted.rotate_right((state >> 59) as u32);
+        }
+
+        Self {
+            key,
+            counter: 0,
+            block: [0; 16],
+            index: 16,
+        }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        if self.index == 16 {
+            self.refill();
+        }
+        self.index += 1;
+        self.block[self.index - 1]
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    fn refill(&mut self) {
+        const SIGMA: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+        let mut input = [0u32; 16];
+        input[..4].copy_from_slice(&SIGMA);
+        input[4..12].copy_from_slice(&self.key);
+        input[12] = self.counter as u32;
+        input[13] = (self.counter >> 32) as u32;
+
+        let mut x = input;
+        for _ in 0..10 {
+            for (a, b, c, d) in [
+                (0, 4, 8, 12),
+                (1, 5, 9, 13),
+                (2, 6, 10, 14),
+                (3, 7, 11, 15),
+                (0, 5, 10, 15),
+                (1, 6, 11, 12),
+                (2, 7, 8, 13),
+                (3, 4, 9, 14),
+            ] {
+                x[a] = x[a].wrapping_add(x[b]);
+                x[d] = (x[d] ^ x[a]).rotate_left(16);
+                x[c] = x[c].wrapping_add(x[d]);
+                x[b] = (x[b] ^ x[c]).rotate_left(12);
+                x[a] = x[a].wrapping_add(x[b]);
+                x[d] = (x[d] ^ x[a]).rotate_left(8);
+                x[c] = x[c].wrapping_add(x[d]);
+                x[b] = (x[b] ^ x[c]).rotate_left(7);
+            }
+        }
+
+        for (out, (mixed, initial)) in self.block.iter_mut().zip(x.iter().zip(input)) {
+            *out = mixed.wrapping_add(initial);
+        }
+        self.counter += 1;
+        self.index = 0;
+    }
+}
+
+// --- Mask Derivation ---
+
+struct BitStat {
+    position: usize,
+    bias: f64,
+    column: Vec<f64>,
+}
+
+/// Picks, for every bit count, low-bias bits that are least correlated with each other.
+fn derive_masks(gear: &[u64; 256]) -> [u64; MASK_TABLE_SIZE] {
+    let stats: Vec<BitStat> = (0..64)
+        .map(|position| {
+            let column: Vec<f64> = gear
+                .iter()
+                .map(|val| ((val >> position) & 1) as f64)
+                .collect();
+            let ones = column.iter().sum::<f64>();
+            let risk = if position >= HIGH_BIT_RISK_START {
+                500.0
+            } else {
+                0.0
+            };
+
+            BitStat {
+                position,
+                bias: (ones - 128.0).abs() + risk,
+                column,
+            }
+        })
+        .collect();
+
+    let mut masks = [0u64; MASK_TABLE_SIZE];
+    for (bits, mask) in masks.iter_mut().enumerate().skip(MASK_PADDING_SLOTS) {
+        *mask = optimal_mask(&stats, bits);
+    }
+    masks
+}
+
+fn optimal_mask(stats: &[BitStat], bits_needed: usize) -> u64 {
+    let mut candidates: Vec<&BitStat> = stats.iter().collect();
+    candidates.sort_by(|a, b| a.bias.partial_cmp(&b.bias).unwrap());
+
+    let mut selected: Vec<&BitStat> = vec![candidates[0]];
+
+    while selected.len() < bits_needed {
+        let mut best = None;
+        let mut min_score = f64::MAX;
+
+        for &candidate in &candidates {
+            if selected.iter().any(|s| s.position == candidate.position) {
+                continue;
+            }
+
+            let correlation_sum: f64 = selected
+                .iter()
+                .map(|s| pearson_correlation(&candidate.column, &s.column).abs())
+                .sum();
+            let score = correlation_sum + (candidate.bias * 0.02);
+
+            if score < min_score {
+                min_score = score;
+                best = Some(candidate);
+            }
+        }
+
+        match best {
+            Some(candidate) => selected.push(candidate),
+            None => break,
+        }
+    }
+
+    selected.iter().fold(0, |mask, s| mask | 1 << s.position)
+}
+
+fn pearson_correlation(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let (mut sum_x, mut sum_y, mut sum_xy, mut sum_sq_x, mut sum_sq_y) = (0.0, 0.0, 0.0, 0.0, 0.0);
+
+    for (&a, &b) in x.iter().zip(y) {
+        sum_x += a;
+        sum_y += b;
+        sum_xy += a * b;
+        sum_sq_x += a * a;
+        sum_sq_y += b * b;
+    }
+
+    let denominator = ((n * sum_sq_x - sum_x * sum_x) * (n * sum_sq_y - sum_y * sum_y)).sqrt();
+
+    if denominator == 0.0 {
+        0.0
+    } else {
+        (n * sum_xy - sum_x * sum_y) / denominator
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/table_tests.rs"]
+mod tests;
@@ -0,0 +1,75 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+use futures::StreamExt;
+use std::io::Cursor;
+
+// --- Chunking Tests ---
+
+#[tokio::test]
+async fn test_matches_sync_chunking() {
+    let data = generate_patterned_data(200_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let expected = chunker
+        .chunks(&data[..])
+        .map(|c| c.map(|c| (c.offset, c.length, c.fp_hash)))
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk synchronously");
+
+    let actual = chunker
+        .chunks_blocking(Cursor::new(data.clone()), 1)
+        .map(|c| c.map(|c| (c.offset, c.length, c.fp_hash)))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk on the blocking thread");
+
+    // The bridged stream must yield the same chunks as the iterator
+    assert_eq!(actual, expected, "Bridged chunks do not match");
+}
+
+#[tokio::test]
+async fn test_early_drop() {
+    let data = generate_patterned_data(200_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut stream = chunker.chunks_blocking(Cursor::new(data), 1);
+    let first = stream
+        .next()
+        .await
+        .expect("Stream expected to yield a chunk");
+
+    // Dropping the stream mid-way must not block or panic
+    assert!(first.is_ok());
+    drop(stream);
+}
+
+// --- Error Test ---
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("simulated read error"))
+    }
+}
+
+#[tokio::test]
+async fn test_reader_error() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut stream = chunker.chunks_blocking(FailingReader, 4);
+    let result = stream
+        .next()
+        .await
+        .expect("Stream expected to yield a result");
+
+    // The error must be forwarded and end the stream
+    assert!(result.is_err(), "Stream failed to propagate the read error");
+    assert!(
+        stream.next().await.is_none(),
+        "Stream must end after an error"
+    );
+}
@@ -0,0 +1,71 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+/// A reader that returns at most `step` bytes per read.
+struct Trickle<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.step).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+// --- Boundary Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<Boundary> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| Boundary {
+            offset: c.offset,
+            length: c.length,
+            fp_hash: c.fp_hash,
+        })
+        .collect();
+
+    // The boundaries match the chunks, however the reader splits its reads
+    let actual: Vec<Boundary> = chunker.boundaries(&data[..]).map(|b| b.unwrap()).collect();
+    assert_eq!(actual, expected);
+
+    let reader = Trickle {
+        data: &data,
+        step: 1_000,
+    };
+    let actual: Vec<Boundary> = chunker.boundaries(reader).map(|b| b.unwrap()).collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_boundaries_cover_input() {
+    let data = generate_noise(123_457);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // The boundaries are contiguous and cover every byte
+    let mut offset = 0;
+    for boundary in chunker.boundaries(&data[..]) {
+        let boundary = boundary.unwrap();
+        assert_eq!(boundary.offset, offset);
+        assert!(boundary.length <= MAX_SIZE);
+        offset += boundary.length as u64;
+    }
+    assert_eq!(offset, data.len() as u64);
+
+    // An empty reader has no boundaries
+    assert!(chunker.boundaries(&[][..]).next().is_none());
+}
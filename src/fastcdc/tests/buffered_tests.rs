@@ -0,0 +1,103 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::io::{BufReader, Read};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn summarize<I: Iterator<Item = io::Result<Chunk>>>(chunks: I) -> Vec<(u64, usize, u64)> {
+    chunks
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect()
+}
+
+/// A reader that is interrupted before every successful read.
+struct Interrupting<R> {
+    inner: R,
+    interrupt: bool,
+}
+
+impl<R: Read> Read for Interrupting<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        }
+        self.inner.read(buf)
+    }
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected = summarize(chunker.chunks(&data[..]));
+
+    // A slice is scanned in place
+    assert_eq!(summarize(chunker.chunks_buffered(&data[..])), expected);
+
+    // Reader buffers smaller and larger than `min_size` and `max_size` yield the same chunks
+    for capacity in [1_000, 8_192, 65_536] {
+        let reader = BufReader::with_capacity(capacity, &data[..]);
+        let actual = summarize(chunker.chunks_buffered(reader));
+        assert_eq!(actual, expected, "capacity {}", capacity);
+    }
+}
+
+#[test]
+fn test_adjusted_cutpoints_match_iterator() {
+    let data: Vec<u8> = generate_noise(200_000)
+        .into_iter()
+        .map(|b| if b % 61 == 0 { b'\n' } else { b })
+        .collect();
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_delimiter(b'\n', 1_024);
+    let expected = summarize(chunker.chunks(&data[..]));
+
+    // Cut points moved to a delimiter match, even across refills
+    let reader = BufReader::with_capacity(10_000, &data[..]);
+    assert_eq!(summarize(chunker.chunks_buffered(reader)), expected);
+}
+
+#[test]
+fn test_carry_is_bounded() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // In-place scans only carry the tail, as the end of the input is not yet known
+    let mut iter = chunker.chunks_buffered(&data[..]);
+    let last = iter.by_ref().last().unwrap().unwrap();
+    assert_eq!(iter.buffer_usage().peak, last.length);
+
+    // Carried bytes never exceed `max_size`
+    let mut iter = chunker.chunks_buffered(BufReader::with_capacity(1_000, &data[..]));
+    iter.by_ref().for_each(drop);
+    assert!(iter.buffer_usage().peak <= MAX_SIZE);
+    assert_eq!(iter.buffer_usage().buffered, 0);
+}
+
+// --- Error Test ---
+
+#[test]
+fn test_interrupted_reads_are_retried() {
+    let data = generate_noise(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected = summarize(chunker.chunks(&data[..]));
+
+    // `Interrupted` is retried rather than returned
+    let reader = BufReader::with_capacity(
+        4_096,
+        Interrupting {
+            inner: &data[..],
+            interrupt: false,
+        },
+    );
+    assert_eq!(summarize(chunker.chunks_buffered(reader)), expected);
+}
@@ -0,0 +1,75 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_noise_data};
+
+fn lengths(chunker: &FastCDC, data: &[u8]) -> Vec<usize> {
+    chunker.chunks(data).map(|c| c.unwrap().length).collect()
+}
+
+// --- Builder Tests ---
+
+#[test]
+fn test_builder_defaults() {
+    let chunker = FastCDC::builder().build().unwrap();
+
+    // Defaults match the CLI sizes and the FastCDC option defaults
+    assert_eq!(
+        (chunker.min_size(), chunker.avg_size(), chunker.max_size()),
+        (2_048, 8_192, 65_536)
+    );
+    assert_eq!(chunker.normal(), Normal::Level2);
+    assert!(chunker.min_size_skip() && !chunker.one_byte_rolling());
+    assert_eq!(chunker.alignment(), None);
+}
+
+#[test]
+fn test_builder_matches_methods() {
+    let data = generate_noise_data(300_000);
+
+    let built = FastCDC::builder()
+        .min(MIN_SIZE)
+        .avg(AVG_SIZE)
+        .max(MAX_SIZE)
+        .normal(Normal::Level1)
+        .seed(7)
+        .one_byte_rolling(true)
+        .alignment(4_096, 512)
+        .read_size(1_048_576)
+        .build()
+        .unwrap();
+    let chained = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level1)
+        .with_seed(7)
+        .with_one_byte_rolling(true)
+        .with_alignment(4_096, 512)
+        .with_read_size(1_048_576);
+
+    // The builder configures the same chunker as the chained methods
+    assert_eq!(lengths(&built, &data), lengths(&chained, &data));
+    assert_eq!(built.masks(), chained.masks());
+    assert_eq!(built.read_size(), chained.read_size());
+
+    // Keyed tables are built the same way as the keyed constructor
+    let keyed = FastCDC::builder()
+        .min(MIN_SIZE)
+        .avg(AVG_SIZE)
+        .max(MAX_SIZE)
+        .key(b"secret")
+        .build()
+        .unwrap();
+    let expected = FastCDC::keyed(b"secret", MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    assert_eq!(lengths(&keyed, &data), lengths(&expected, &data));
+}
+
+#[test]
+fn test_builder_errors() {
+    // Invalid options are reported instead of panicking
+    let errors = [
+        FastCDC::builder().min(AVG_SIZE).avg(MIN_SIZE).build(),
+        FastCDC::builder().alignment(0, 0).build(),
+        FastCDC::builder().read_size(0).build(),
+        FastCDC::builder().masks(1 << 63, 0).build(),
+    ];
+
+    for result in errors {
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
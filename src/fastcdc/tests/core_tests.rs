@@ -1,25 +1,9 @@
 use super::*;
+use crate::test_util::{
+    AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_noise_data, generate_patterned_data,
+};
 use std::{env, fs, io, path::PathBuf};
 
-const MIN_SIZE: usize = 4_069;
-const AVG_SIZE: usize = 8_192;
-const MAX_SIZE: usize = 16_384;
-
-fn generate_patterned_data(len: usize) -> Vec<u8> {
-    const BLOCKS: [&[u8]; 3] = [b"LOREM", b"IPSUM", b"DOLOR"];
-
-    let mut data = Vec::with_capacity(len);
-    let mut idx = 0;
-
-    while data.len() < len {
-        data.extend_from_slice(BLOCKS[idx % BLOCKS.len()]);
-        idx += 1;
-    }
-
-    data.truncate(len);
-    data
-}
-
 // --- Input Tests ---
 
 #[test]
@@ -59,6 +43,129 @@ fn test_small_input() {
     assert_eq!(chunks[0].data.as_ref(), &data[..]);
 }
 
+#[test]
+fn test_invalid_sizes() {
+    // Each bound is reported with the offending value
+    assert!(matches!(
+        FastCDC::try_new(32, AVG_SIZE, MAX_SIZE, Normal::Level2),
+        Err(ClastError::InvalidMinSize { given: 32, .. })
+    ));
+    assert!(matches!(
+        FastCDC::try_new(MIN_SIZE, 128, MAX_SIZE, Normal::Level2),
+        Err(ClastError::InvalidAvgSize { given: 128, .. })
+    ));
+    assert!(matches!(
+        FastCDC::try_new(MIN_SIZE, AVG_SIZE, 1 << 25, Normal::Level2),
+        Err(ClastError::InvalidMaxSize {
+            given: 33_554_432,
+            ..
+        })
+    ));
+
+    // Out-of-order sizes are rejected, and callers using io::Result still see InvalidInput
+    let err = FastCDC::try_new(AVG_SIZE, MIN_SIZE, MAX_SIZE, Normal::Level2).unwrap_err();
+    assert!(matches!(err, ClastError::InvalidOrdering { .. }));
+    assert_eq!(io::Error::from(err).kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_unchecked_bounds() {
+    const MIB: usize = 1_048_576;
+
+    // The default limits reject 64 MB chunks, the extended ones accept them
+    assert!(matches!(
+        FastCDC::try_new(4 * MIB, 16 * MIB, 64 * MIB, Normal::Level3),
+        Err(ClastError::InvalidMinSize { .. })
+    ));
+    let chunker = FastCDC::try_new_unchecked_bounds(4 * MIB, 16 * MIB, 64 * MIB, Normal::Level3)
+        .expect("Extended bounds should accept 64 MB chunks");
+
+    // The mask table covers 16 MB averages with Level 3 normalization
+    assert_eq!(chunker.masks().mask_s().count_ones(), 27);
+    assert_eq!(chunker.masks().mask_l().count_ones(), 21);
+
+    // Lower limits, ordering, and the extended upper limits are still enforced
+    assert!(matches!(
+        FastCDC::try_new_unchecked_bounds(32, 16 * MIB, 64 * MIB, Normal::Level2),
+        Err(ClastError::InvalidMinSize { given: 32, .. })
+    ));
+    assert!(matches!(
+        FastCDC::try_new_unchecked_bounds(4 * MIB, 64 * MIB, 16 * MIB, Normal::Level2),
+        Err(ClastError::InvalidOrdering { .. })
+    ));
+    assert!(matches!(
+        FastCDC::try_new_unchecked_bounds(4 * MIB, 16 * MIB, 2_048 * MIB, Normal::Level2),
+        Err(ClastError::InvalidMaxSize { .. })
+    ));
+}
+
+#[test]
+fn test_mask_coverage() {
+    // Every power-of-two average up to the extended limit gets full-strength masks
+    for bits in AVG_CHUNK_SIZE_MIN.ilog2()..=UNCHECKED_AVG_CHUNK_SIZE_MAX.ilog2() {
+        for normal in [Normal::None, Normal::Level1, Normal::Level2, Normal::Level3] {
+            let masks = Masks::new(1 << bits, normal).unwrap();
+            let offset = normal.offset();
+
+            assert_eq!(masks.mask_s().count_ones(), bits + offset);
+            assert_eq!(masks.mask_l().count_ones(), bits - offset);
+        }
+    }
+}
+
+#[test]
+fn test_large_chunks() {
+    const MIB: usize = 1_048_576;
+
+    let data = generate_noise_data(60 * MIB);
+    let chunker =
+        FastCDC::try_new_unchecked_bounds(17 * MIB, 18 * MIB, 24 * MIB, Normal::Level1).unwrap();
+    let lengths: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // Chunks beyond the default 16 MB limit are produced and bounded by the sizes
+    assert_eq!(lengths.iter().sum::<usize>(), data.len());
+    assert!(lengths.len() >= 3);
+    assert!(
+        lengths[..lengths.len() - 1]
+            .iter()
+            .all(|&len| (17 * MIB..=24 * MIB).contains(&len))
+    );
+}
+
+#[test]
+fn test_custom_normalization() {
+    let data = generate_noise_data(300_000);
+    let lengths = |chunker: &FastCDC| -> Vec<usize> {
+        chunker
+            .chunks(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect()
+    };
+
+    // Custom offsets matching a named level behave like it
+    let named = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let custom = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Custom(2));
+    assert_eq!(lengths(&custom), lengths(&named));
+
+    // Offsets beyond Level3 widen the gap between the two masks
+    let wide = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Custom(6));
+    assert_eq!(wide.masks().mask_s().count_ones(), 19);
+    assert_eq!(wide.masks().mask_l().count_ones(), 7);
+
+    // Offsets without masks are rejected instead of indexing past the table
+    assert!(matches!(
+        FastCDC::try_new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Custom(9)),
+        Err(ClastError::InvalidNormalization { given: 9, .. })
+    ));
+    assert!(matches!(
+        Masks::new(AVG_SIZE, Normal::Custom(u32::MAX)),
+        Err(ClastError::InvalidNormalization { .. })
+    ));
+}
+
 // --- Chunking Tests ---
 
 #[test]
@@ -143,6 +250,719 @@ fn test_image_chunking() {
     );
 }
 
+#[test]
+fn test_chunks_at_boundary() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let full: Vec<(u64, usize)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.map(|c| (c.offset, c.length)))
+        .collect::<io::Result<_>>()
+        .expect("Failed to chunk");
+
+    let (offset, _) = full[2];
+    let resumed: Vec<(u64, usize)> = chunker
+        .chunks_at(&data[offset as usize..], offset)
+        .map(|c| c.map(|c| (c.offset, c.length)))
+        .collect::<io::Result<_>>()
+        .expect("Failed to resume chunking");
+
+    // Resuming at a boundary reproduces the remaining chunks and their offsets
+    assert_eq!(resumed, full[2..]);
+}
+
+#[test]
+fn test_buffer_usage() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut iter = chunker.chunks(&data[..]);
+
+    // Nothing is buffered before the first read
+    assert_eq!(iter.buffer_usage(), BufferUsage::default());
+
+    let chunk = iter.next().unwrap().unwrap();
+    let usage = iter.buffer_usage();
+
+    // The first fill reads one max_size window and the read-ahead, of which the chunk
+    // has been emitted
+    let window = MAX_SIZE + SPARE_SIZE_MIN;
+    assert_eq!(usage.peak, window);
+    assert_eq!(usage.buffered, window - chunk.length);
+
+    for chunk in iter.by_ref() {
+        chunk.unwrap();
+    }
+
+    // The buffer drains completely, and the peak never exceeds the first fill
+    assert_eq!(iter.buffer_usage().buffered, 0);
+    assert_eq!(iter.buffer_usage().peak, window);
+    assert_eq!(
+        [iter.buffer_usage(), iter.buffer_usage()]
+            .into_iter()
+            .sum::<BufferUsage>()
+            .peak,
+        2 * window
+    );
+}
+
+struct CountingReader<'a> {
+    data: &'a [u8],
+    calls: usize,
+    largest: usize,
+}
+
+impl<'a> CountingReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            calls: 0,
+            largest: 0,
+        }
+    }
+}
+
+impl Read for CountingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.calls += 1;
+        self.largest = self.largest.max(buf.len());
+        self.data.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        self.calls += 1;
+        self.largest = self.largest.max(bufs.iter().map(|b| b.len()).sum());
+        self.data.read_vectored(bufs)
+    }
+}
+
+/// Reads the window it is handed before writing to it, as `Read` implementations may.
+struct PeekingReader<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl Read for PeekingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        assert!(buf.iter().all(|&b| b == 0), "spare capacity must be zeroed");
+        let n = buf.len().min(self.step);
+        self.data.read(&mut buf[..n])
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        // The read-ahead buffer holds earlier data; the window is fresh capacity
+        match bufs.first_mut() {
+            Some(window) if !window.is_empty() => self.read(window),
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn test_reader_sees_initialized_buffer() {
+    let data = generate_noise_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize)> = chunker
+        .chunk_slice(&data)
+        .map(|c| (c.offset, c.length))
+        .collect();
+
+    // Every window handed to the reader is initialized, including after chunks are split off
+    let reader = PeekingReader {
+        data: &data,
+        step: 3_000,
+    };
+    let actual: Vec<(u64, usize)> = chunker
+        .chunks(reader)
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length))
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_vectored_read_ahead() {
+    let data = generate_noise_data(200_000);
+    let chunker = FastCDC::new(256, 512, 1_024, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunk_slice(&data)
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    let mut reader = CountingReader::new(&data);
+    let actual: Vec<(u64, usize, u64)> = chunker
+        .chunks(&mut reader)
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // Reading ahead yields the same chunks as scanning the whole input
+    assert_eq!(actual, expected);
+
+    // Each call fills the window and the read-ahead, so there are far fewer calls than chunks
+    let per_call = 1_024 + SPARE_SIZE_MIN;
+    assert!(reader.calls <= data.len().div_ceil(per_call) + 1);
+    assert!(reader.calls * 10 < actual.len());
+}
+
+#[test]
+fn test_read_size() {
+    let data = generate_noise_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunk_slice(&data)
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    for read_size in [1_000, 4_096, 1_048_576] {
+        let chunker = chunker.clone().with_read_size(read_size);
+        let mut reader = CountingReader::new(&data);
+        let actual: Vec<(u64, usize, u64)> = chunker
+            .chunks(&mut reader)
+            .map(|c| c.unwrap())
+            .map(|c| (c.offset, c.length, c.fp_hash))
+            .collect();
+
+        // The read size changes the calls made, never the chunks
+        assert_eq!(actual, expected);
+        assert_eq!(reader.largest, read_size);
+    }
+
+    // Large reads take the whole input at once
+    let mut reader = CountingReader::new(&data);
+    let chunker = chunker.with_read_size(1_048_576);
+    chunker.chunks(&mut reader).for_each(|c| drop(c.unwrap()));
+    assert_eq!(reader.calls, 2);
+}
+
+#[test]
+#[should_panic(expected = "read size must be greater than zero")]
+fn test_zero_read_size() {
+    FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_read_size(0);
+}
+
+#[test]
+fn test_owned_iterator() {
+    fn spawn_chunking(data: Vec<u8>) -> std::thread::JoinHandle<Vec<usize>> {
+        let chunks = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+            .with_seed(3)
+            .into_chunks(io::Cursor::new(data));
+
+        std::thread::spawn(move || chunks.map(|c| c.unwrap().length).collect())
+    }
+
+    let data = generate_noise_data(300_000);
+    let expected: Vec<usize> = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+        .with_seed(3)
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // An owned iterator outlives its chunker and yields the same chunks
+    assert_eq!(spawn_chunking(data).join().unwrap(), expected);
+}
+
+// --- Rolling Mode Tests ---
+
+/// The canonical single-byte FastCDC cut-point search.
+fn reference_cutpoint(source: &[u8], min: usize, avg: usize, max: usize, masks: Masks) -> usize {
+    reference_cutpoint_from(source, min, min, avg, max, masks)
+}
+
+/// The single-byte search, hashing from `start` and judging from `min`.
+fn reference_cutpoint_from(
+    source: &[u8],
+    start: usize,
+    min: usize,
+    avg: usize,
+    max: usize,
+    masks: Masks,
+) -> usize {
+    let len = source.len().min(max);
+    if len <= min {
+        return len;
+    }
+
+    let mut hash = 0u64;
+    for (idx, &byte) in source.iter().enumerate().take(len).skip(start) {
+        hash = (hash << 1).wrapping_add(crate::fastcdc::cut::GEAR[byte as usize]);
+        let mask = if idx < avg {
+            masks.mask_s
+        } else {
+            masks.mask_l
+        };
+        if idx >= min && hash & mask == 0 {
+            return idx;
+        }
+    }
+    len
+}
+
+#[test]
+fn test_one_byte_rolling_matches_reference() {
+    let data = generate_noise_data(300_000);
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_one_byte_rolling(true);
+
+    let mut expected = Vec::new();
+    let mut rest = &data[..];
+    while !rest.is_empty() {
+        let cut = reference_cutpoint(rest, MIN_SIZE, AVG_SIZE, MAX_SIZE, chunker.masks);
+        expected.push(cut);
+        rest = &rest[cut..];
+    }
+
+    let actual: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // The one-byte mode must follow the canonical hash sequence exactly
+    assert!(chunker.one_byte_rolling());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_rolling_modes_share_hash_sequence() {
+    let data = generate_noise_data(300_000);
+    let two_byte = FastCDC::new(4_096, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let one_byte = two_byte.clone().with_one_byte_rolling(true);
+
+    let chunks = |chunker: &FastCDC| {
+        chunker
+            .chunks(&data[..])
+            .map(|c| c.map(|c| (c.length, c.fp_hash)))
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap()
+    };
+
+    // With even sizes both modes cut at the same offsets
+    for ((len_2, fp_2), (len_1, fp_1)) in chunks(&two_byte).into_iter().zip(chunks(&one_byte)) {
+        assert_eq!(len_2, len_1);
+        // Cuts on the first byte of a pair report the shifted fingerprint
+        assert!(fp_2 == fp_1 || fp_2 == fp_1 << 1, "Unexpected fingerprint");
+    }
+}
+
+#[cfg(feature = "four-byte-rolling")]
+#[test]
+fn test_four_byte_rolling_shares_hash_sequence() {
+    let data = generate_noise_data(300_000);
+    let four_byte =
+        FastCDC::new(4_096, AVG_SIZE, MAX_SIZE, Normal::Level2).with_four_byte_rolling(true);
+    let one_byte = four_byte.clone().with_one_byte_rolling(true);
+
+    let chunks = |chunker: &FastCDC| {
+        chunker
+            .chunks(&data[..])
+            .map(|c| c.map(|c| (c.length, c.fp_hash)))
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap()
+    };
+    let (quads, singles) = (chunks(&four_byte), chunks(&one_byte));
+
+    // With sizes that are multiples of 4 both modes cut at the same offsets, bar the tail
+    assert_eq!(quads.len(), singles.len());
+    for ((len_4, fp_4), (len_1, fp_1)) in quads.iter().zip(&singles).take(quads.len() - 1) {
+        assert_eq!(len_4, len_1);
+        // Cuts before the last byte of a quad report the fingerprint shifted by up to 3
+        assert!(
+            (0..4).any(|shift| *fp_4 == fp_1 << shift),
+            "Unexpected fingerprint"
+        );
+    }
+
+    // The modes are exclusive and recorded in manifests
+    assert!(!one_byte.four_byte_rolling() && !four_byte.one_byte_rolling());
+    let restored = FastCDC::new(4_096, AVG_SIZE, MAX_SIZE, Normal::Level2)
+        .with_option_bits(four_byte.option_bits())
+        .unwrap();
+    assert!(restored.four_byte_rolling());
+}
+
+#[cfg(feature = "four-byte-rolling")]
+#[test]
+fn test_four_byte_rolling_resumes_scans() {
+    let data = generate_noise_data(300_000);
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_four_byte_rolling(true);
+    let expected: Vec<(usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.length, c.fp_hash))
+        .collect();
+
+    // Short reads resume the scan mid-chunk at quad boundaries with an odd min_size
+    let mut decoder = chunker.decoder();
+    let mut buf = bytes::BytesMut::new();
+    let mut actual = Vec::new();
+    for piece in data.chunks(1_001) {
+        buf.extend_from_slice(piece);
+        while let Some(chunk) = decoder.decode(&mut buf).unwrap() {
+            actual.push((chunk.length, chunk.fp_hash));
+        }
+    }
+    while let Some(chunk) = decoder.decode_eof(&mut buf).unwrap() {
+        actual.push((chunk.length, chunk.fp_hash));
+    }
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_disabled_min_skip_hashes_prefix() {
+    let data = generate_noise_data(300_000);
+    let skipping =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_one_byte_rolling(true);
+    let full = skipping.clone().with_min_size_skip(false);
+
+    // Hashing the whole chunk reproduces the reference scan with the fold starting at 0
+    let mut rest = &data[..];
+    let mut expected = Vec::new();
+    while !rest.is_empty() {
+        let cut = reference_cutpoint_from(rest, 0, MIN_SIZE, AVG_SIZE, MAX_SIZE, full.masks);
+        expected.push(cut);
+        rest = &rest[cut..];
+    }
+    let actual: Vec<usize> = full.chunks(&data[..]).map(|c| c.unwrap().length).collect();
+    assert_eq!(actual, expected);
+
+    // Boundaries are still never placed before min_size
+    assert!(
+        actual[..actual.len() - 1]
+            .iter()
+            .all(|&len| len >= MIN_SIZE)
+    );
+    assert!(!full.min_size_skip() && skipping.min_size_skip());
+}
+
+// --- Seed Tests ---
+
+#[test]
+fn test_seeded_gear_table() {
+    let data = generate_noise_data(300_000);
+    let builtin = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let lengths = |chunker: &FastCDC| -> Vec<usize> {
+        chunker
+            .chunks(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect()
+    };
+
+    // A seeded table moves the boundaries but keeps the size constraints
+    let seeded = lengths(&builtin.clone().with_seed(7));
+    assert_ne!(seeded, lengths(&builtin));
+    assert_eq!(seeded.iter().sum::<usize>(), data.len());
+    assert!(
+        seeded[..seeded.len() - 1]
+            .iter()
+            .all(|&len| (MIN_SIZE..=MAX_SIZE).contains(&len))
+    );
+
+    // The same seed always yields the same boundaries
+    assert_eq!(lengths(&builtin.clone().with_seed(7)), seeded);
+
+    // The one-byte scan uses the seeded table too
+    let one_byte = builtin.with_one_byte_rolling(true);
+    assert_ne!(lengths(&one_byte.clone().with_seed(7)), lengths(&one_byte));
+}
+
+#[test]
+fn test_supplied_gear_table() {
+    let data = generate_noise_data(300_000);
+    let builtin = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let lengths = |chunker: &FastCDC| -> Vec<usize> {
+        chunker
+            .chunks(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect()
+    };
+
+    // Supplying the built-in table reproduces the default boundaries and masks
+    let same = builtin.clone().with_gear_table(&crate::fastcdc::cut::GEAR);
+    assert_eq!(lengths(&same), lengths(&builtin));
+    assert_eq!(same.masks.mask_s, builtin.masks.mask_s);
+    assert_eq!(same.masks.mask_l, builtin.masks.mask_l);
+
+    // A table with constant low bits moves the boundaries and derives masks avoiding them
+    let gear = crate::fastcdc::cut::GEAR.map(|val| val & !0xff);
+    let biased = builtin.clone().with_gear_table(&gear);
+    assert_ne!(lengths(&biased), lengths(&builtin));
+    assert_eq!(biased.masks.mask_s & 0xff, 0);
+    assert_eq!(biased.masks.mask_l & 0xff, 0);
+}
+
+#[test]
+fn test_keyed_gear_table() {
+    let data = generate_noise_data(300_000);
+    let lengths = |chunker: &FastCDC| -> Vec<usize> {
+        chunker
+            .chunks(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect()
+    };
+    let keyed = |key: &[u8]| FastCDC::keyed(key, MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // The same key reproduces the boundaries, a different key moves them
+    let first = lengths(&keyed(b"tenant-1"));
+    assert_eq!(lengths(&keyed(b"tenant-1")), first);
+    assert_ne!(lengths(&keyed(b"tenant-2")), first);
+    assert_ne!(
+        lengths(&FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)),
+        first
+    );
+
+    // Size constraints still hold, and invalid sizes are rejected
+    assert!(
+        first[..first.len() - 1]
+            .iter()
+            .all(|&len| (MIN_SIZE..=MAX_SIZE).contains(&len))
+    );
+    let err = FastCDC::try_keyed(b"key", MAX_SIZE, AVG_SIZE, MIN_SIZE, Normal::Level2).unwrap_err();
+    assert!(matches!(err, ClastError::InvalidOrdering { .. }));
+}
+
+#[test]
+fn test_explicit_masks() {
+    let data = generate_noise_data(300_000);
+    let builtin = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let lengths = |chunker: &FastCDC| -> Vec<usize> {
+        chunker
+            .chunks(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect()
+    };
+
+    // Passing the derived masks back in changes nothing
+    let same = builtin.clone().with_masks(builtin.masks());
+    assert_eq!(lengths(&same), lengths(&builtin));
+    assert_eq!(
+        Masks::new(AVG_SIZE, Normal::Level2).unwrap(),
+        builtin.masks()
+    );
+
+    // Contiguous low-bit masks with fewer bits cut more often
+    let loose = builtin.clone().with_masks(Masks::custom(0x0f, 0x0f));
+    assert_eq!(loose.masks().mask_s(), 0x0f);
+    assert_eq!(loose.masks().mask_l(), 0x0f);
+    assert!(lengths(&loose).len() > lengths(&builtin).len());
+}
+
+#[test]
+#[should_panic(expected = "masks must not use bit 63")]
+fn test_explicit_masks_high_bit() {
+    Masks::custom(1 << 63, 0);
+}
+
+// --- Alignment Tests ---
+
+#[test]
+fn test_alignment_snaps_boundaries() {
+    const ALIGN: usize = 4_096;
+
+    let data = generate_noise_data(300_000);
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_alignment(ALIGN, ALIGN);
+    let chunks = chunker
+        .chunks(&data[..])
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk");
+
+    let (last, rest) = chunks.split_last().unwrap();
+
+    for chunk in rest {
+        let end = chunk.offset as usize + chunk.length;
+
+        // A boundary stays unaligned only if snapping it would undercut min_size
+        assert!(
+            end.is_multiple_of(ALIGN) || chunk.length - end % ALIGN < MIN_SIZE,
+            "Boundary at {} was not aligned",
+            end
+        );
+        assert!(chunk.length >= MIN_SIZE && chunk.length <= MAX_SIZE);
+    }
+
+    // The final chunk ends at the end of the data regardless of alignment
+    assert_eq!(last.offset as usize + last.length, data.len());
+}
+
+#[test]
+fn test_alignment_tolerance() {
+    let data = generate_noise_data(300_000);
+    let plain = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let aligned = plain.clone().with_alignment(4_096, 0);
+
+    let lengths = |chunker: &FastCDC| {
+        chunker
+            .chunks(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect::<Vec<_>>()
+    };
+
+    // With zero tolerance, only boundaries already aligned are accepted
+    assert_eq!(lengths(&plain), lengths(&aligned));
+    assert_eq!(aligned.alignment(), Some((4_096, 0)));
+    assert_eq!(plain.alignment(), None);
+}
+
+// --- Delimiter Tests ---
+
+fn generate_line_data(len: usize) -> Vec<u8> {
+    generate_noise_data(len)
+        .into_iter()
+        .map(|b| if b % 97 == 0 { b'\n' } else { b'a' + b % 26 })
+        .collect()
+}
+
+#[test]
+fn test_delimiter_snaps_boundaries() {
+    let data = generate_line_data(300_000);
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_delimiter(b'\n', 1_024);
+    let chunks = chunker
+        .chunks(&data[..])
+        .collect::<io::Result<Vec<_>>>()
+        .expect("Failed to chunk");
+
+    let (last, rest) = chunks.split_last().unwrap();
+
+    // Lines are far shorter than the slack, so every chunk but the last ends one
+    for chunk in rest {
+        assert_eq!(chunk.data.last(), Some(&b'\n'), "Chunk at {}", chunk.offset);
+        assert!(chunk.length >= MIN_SIZE && chunk.length <= MAX_SIZE);
+    }
+    assert_eq!(last.offset as usize + last.length, data.len());
+    assert_eq!(chunker.delimiter(), Some((b'\n', 1_024)));
+}
+
+#[test]
+fn test_delimiter_outside_slack() {
+    let data = generate_noise_data(300_000);
+    let plain = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let snapped = plain.clone().with_delimiter(b'\n', 1_024);
+    let unreachable = plain.clone().with_delimiter(b'\n', 0);
+
+    let lengths = |chunker: &FastCDC, data: &[u8]| {
+        chunker
+            .chunks(data)
+            .map(|c| c.unwrap().length)
+            .collect::<Vec<_>>()
+    };
+
+    // Without a delimiter in reach, cut points stay where they are
+    let no_lines: Vec<u8> = data.iter().map(|&b| b | 0x80).collect();
+    assert_eq!(lengths(&plain, &no_lines), lengths(&snapped, &no_lines));
+    assert_eq!(lengths(&plain, &data), lengths(&unreachable, &data));
+}
+
+// --- UTF-8 Tests ---
+
+fn generate_utf8_data(len: usize) -> Vec<u8> {
+    const CHARS: [char; 5] = ['a', 'é', '한', '𝄞', '\n'];
+
+    let mut text = String::with_capacity(len + 4);
+    for b in generate_noise_data(len) {
+        if text.len() >= len {
+            break;
+        }
+        text.push(CHARS[b as usize % CHARS.len()]);
+    }
+    text.into_bytes()
+}
+
+#[test]
+fn test_utf8_boundaries() {
+    let data = generate_utf8_data(300_000);
+    let plain = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let safe = plain.clone().with_utf8_boundaries(true);
+
+    // Plain chunking splits code points in mixed-width text
+    let split = plain
+        .chunks(&data[..])
+        .filter(|c| std::str::from_utf8(&c.as_ref().unwrap().data).is_err())
+        .count();
+    assert!(split > 0, "Expected plain chunking to split a code point");
+
+    // Every chunk is valid UTF-8 on its own, within the size bounds
+    let chunks: Vec<Chunk> = safe.chunks(&data[..]).map(|c| c.unwrap()).collect();
+    for chunk in &chunks {
+        assert!(
+            std::str::from_utf8(&chunk.data).is_ok(),
+            "Chunk at {}",
+            chunk.offset
+        );
+        assert!(chunk.length <= MAX_SIZE);
+    }
+    assert_eq!(chunks.iter().map(|c| c.length).sum::<usize>(), data.len());
+    assert!(safe.utf8_boundaries() && !plain.utf8_boundaries());
+}
+
+#[test]
+fn test_utf8_boundaries_with_alignment() {
+    let data = generate_utf8_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+        .with_alignment(4_096, 4_096)
+        .with_utf8_boundaries(true);
+
+    // Aligned cut points are still moved off code point interiors
+    for chunk in chunker.chunks(&data[..]) {
+        let chunk = chunk.unwrap();
+        assert!(
+            std::str::from_utf8(&chunk.data).is_ok(),
+            "Chunk at {}",
+            chunk.offset
+        );
+    }
+}
+
+// --- Stats Tests ---
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_stats_account_for_every_chunk() {
+    let data = generate_noise_data(500_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut iter = chunker.chunks(&data[..]);
+    let chunks: Vec<Chunk> = iter.by_ref().map(|c| c.unwrap()).collect();
+    let stats = *iter.stats();
+
+    // Every chunk is classified exactly once, and only the last one by the end of data
+    assert_eq!(stats.chunks, chunks.len() as u64);
+    assert_eq!(
+        stats.small_mask_cuts + stats.large_mask_cuts + stats.forced_cuts + stats.tail_cuts,
+        stats.chunks
+    );
+    assert_eq!(stats.tail_cuts, 1);
+    assert_eq!(
+        stats.forced_cuts,
+        chunks[..chunks.len() - 1]
+            .iter()
+            .filter(|c| c.length == MAX_SIZE)
+            .count() as u64
+    );
+
+    // Each chunk skips its first min_size bytes (or all of a shorter tail)
+    let skipped: usize = chunks.iter().map(|c| c.length.min(MIN_SIZE)).sum();
+    assert_eq!(stats.bytes_skipped, skipped as u64);
+    assert!(stats.avg_scan_len() > 0.0 && stats.avg_scan_len() < (MAX_SIZE - MIN_SIZE) as f64);
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_stats_without_min_skip() {
+    let data = generate_noise_data(200_000);
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_min_size_skip(false);
+
+    let mut iter = chunker.chunks(&data[..]);
+    iter.by_ref().for_each(|c| drop(c.unwrap()));
+
+    // Nothing is skipped when hashing starts at the first byte
+    assert_eq!(iter.stats().bytes_skipped, 0);
+    assert!(iter.stats().bytes_hashed >= data.len() as u64 - MAX_SIZE as u64);
+}
+
 // --- Error Test ---
 
 struct FailingReader;
@@ -0,0 +1,86 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+/// Feeds `data` to the decoder `step` bytes at a time, the way a `FramedRead` would.
+fn decode_all(decoder: &mut FastCDCDecoder<'_>, data: &[u8], step: usize) -> Vec<Chunk> {
+    let mut buf = BytesMut::new();
+    let mut chunks = Vec::new();
+
+    for piece in data.chunks(step) {
+        buf.extend_from_slice(piece);
+        while let Some(chunk) = decoder.decode(&mut buf).unwrap() {
+            chunks.push(chunk);
+        }
+    }
+    while let Some(chunk) = decoder.decode_eof(&mut buf).unwrap() {
+        chunks.push(chunk);
+    }
+
+    chunks
+}
+
+// --- Decoding Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // The chunks do not depend on how the buffer is filled
+    for step in [1_000, 4_096, 65_536] {
+        let actual: Vec<(u64, usize, u64)> = decode_all(&mut chunker.decoder(), &data, step)
+            .into_iter()
+            .map(|c| (c.offset, c.length, c.fp_hash))
+            .collect();
+        assert_eq!(actual, expected, "step {}", step);
+    }
+}
+
+#[test]
+fn test_waits_for_more_data() {
+    let data = generate_noise(MIN_SIZE - 1);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let mut decoder = chunker.decoder();
+    let mut buf = BytesMut::from(&data[..]);
+
+    // Less than `min_size` is kept until the input ends
+    assert!(decoder.decode(&mut buf).unwrap().is_none());
+    assert_eq!(buf.len(), data.len());
+
+    // The remainder is flushed as the last chunk
+    let chunk = decoder.decode_eof(&mut buf).unwrap().unwrap();
+    assert_eq!(&chunk.data[..], &data[..]);
+    assert!(buf.is_empty());
+    assert!(decoder.decode_eof(&mut buf).unwrap().is_none());
+    assert_eq!(decoder.processed(), data.len() as u64);
+}
+
+#[test]
+fn test_owned_decoder() {
+    let data = generate_noise(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // An owned decoder yields the same chunks
+    let mut decoder = chunker.into_decoder();
+    let actual: Vec<usize> = decode_all(&mut decoder, &data, 8_192)
+        .into_iter()
+        .map(|c| c.length)
+        .collect();
+    assert_eq!(actual, expected);
+}
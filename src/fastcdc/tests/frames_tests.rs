@@ -0,0 +1,88 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use bytes::Bytes;
+use futures::{StreamExt, executor::block_on, stream};
+use std::io;
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn frames(data: &[u8], size: usize) -> Vec<io::Result<Bytes>> {
+    data.chunks(size)
+        .map(|frame| Ok(Bytes::copy_from_slice(frame)))
+        .collect()
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // Frame boundaries do not affect the chunks, whether smaller or larger than `max_size`
+    for size in [1, 1_500, 65_536, 300_000] {
+        let stream = chunker.chunks_frames(stream::iter(frames(&data, size)));
+        let actual: Vec<(u64, usize, u64)> = block_on(
+            stream
+                .map(|c| c.unwrap())
+                .map(|c| (c.offset, c.length, c.fp_hash))
+                .collect(),
+        );
+        assert_eq!(actual, expected, "frame size {}", size);
+    }
+}
+
+#[test]
+fn test_empty_frames() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // Empty frames and an empty source yield no chunks
+    let source = stream::iter(vec![Ok::<_, io::Error>(Vec::new()), Ok(Vec::new())]);
+    let mut stream = chunker.chunks_frames(source);
+    assert!(block_on(stream.next()).is_none());
+    assert!(stream.is_terminated());
+}
+
+#[test]
+fn test_owned_frame_stream() {
+    let data = generate_noise(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // An owned stream yields the same chunks
+    let stream = chunker.into_frame_stream(stream::iter(frames(&data, 4_096)));
+    let actual: Vec<usize> = block_on(stream.map(|c| c.unwrap().length).collect());
+    assert_eq!(actual, expected);
+}
+
+// --- Error Test ---
+
+#[test]
+fn test_source_error() {
+    let data = generate_noise(MIN_SIZE / 2);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let source = stream::iter(vec![Ok(data.clone()), Err("connection reset")]);
+    let mut stream = chunker.chunks_frames(source);
+
+    // The source's error is passed through with its own type
+    assert_eq!(
+        block_on(stream.next()).unwrap().unwrap_err(),
+        "connection reset"
+    );
+
+    // The bytes buffered before the error are kept
+    assert_eq!(stream.buffer_usage().buffered, data.len());
+}
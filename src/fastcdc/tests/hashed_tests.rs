@@ -0,0 +1,106 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::hash::GearHash;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, assert_tiles, generate_noise_data};
+use std::io;
+
+/// A cyclic-polynomial hash over 32 bytes, spread to 64 bits.
+#[derive(Clone, Default)]
+struct Cyclic {
+    hash: u32,
+}
+
+fn table(byte: u8) -> u32 {
+    (byte as u32 + 1)
+        .wrapping_mul(0x9e37_79b9)
+        .rotate_left(byte as u32 % 32)
+}
+
+impl RollingHash for Cyclic {
+    fn window(&self) -> usize {
+        32
+    }
+
+    fn reset(&mut self) {
+        self.hash = 0;
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.hash = self.hash.rotate_left(1) ^ table(byte);
+    }
+
+    fn roll(&mut self, out: u8, inp: u8) {
+        self.hash = self.hash.rotate_left(1) ^ table(out) ^ table(inp);
+    }
+
+    fn digest(&self) -> u64 {
+        (self.hash as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+    }
+}
+
+fn spans<C: crate::chunker::Chunker>(chunker: &C, data: &[u8]) -> Vec<(u64, usize)> {
+    chunker
+        .boxed_chunks(Box::new(data))
+        .map(|c| c.map(|c| (c.offset, c.length)))
+        .collect::<io::Result<Vec<_>>>()
+        .unwrap()
+}
+
+// --- Rolling Hash Tests ---
+
+#[test]
+fn test_gear_hash_matches_fastcdc() {
+    let data = generate_noise_data(500_000);
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_one_byte_rolling(true);
+    let hashed = chunker.clone().with_rolling_hash(GearHash::new());
+
+    // The Gear hash reproduces the built-in one-byte rolling boundaries
+    assert_eq!(spans(&hashed, &data), spans(&chunker, &data));
+
+    // Also when the sub-minimum region is hashed
+    let chunker = chunker.with_min_size_skip(false);
+    let hashed = chunker.clone().with_rolling_hash(GearHash::new());
+    assert_eq!(spans(&hashed, &data), spans(&chunker, &data));
+}
+
+#[test]
+fn test_custom_hash() {
+    let data = generate_noise_data(1_000_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+        .with_rolling_hash(Cyclic::default());
+
+    // Chunks tile the input and respect the size limits
+    let spans = assert_tiles(&chunker, &data, MIN_SIZE);
+
+    // Normalization keeps most chunks content-defined
+    let forced = spans.iter().filter(|s| s.1 == MAX_SIZE).count();
+    assert!(
+        forced * 4 < spans.len(),
+        "{} of {} forced",
+        forced,
+        spans.len()
+    );
+}
+
+// --- Adjustment Tests ---
+
+#[test]
+fn test_adjustments_match_fastcdc() {
+    // Invalid sequences become the 3-byte replacement character, so many cuts split one
+    let data = String::from_utf8_lossy(&generate_noise_data(500_000)).into_owned();
+    let data = data.as_bytes();
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_one_byte_rolling(true);
+
+    // Alignment, delimiter, and UTF-8 snapping move the cuts as they do for FastCDC
+    for adjusted in [
+        chunker.clone().with_alignment(4_096, 1_024),
+        chunker.clone().with_delimiter(0x2a, 256),
+        chunker.clone().with_utf8_boundaries(true),
+    ] {
+        let hashed = adjusted.clone().with_rolling_hash(GearHash::new());
+        assert_eq!(spans(&hashed, data), spans(&adjusted, data));
+        assert_ne!(spans(&hashed, data), spans(&chunker, data));
+    }
+}
@@ -0,0 +1,73 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::{env, fs, path::PathBuf};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn write_temp_file(name: &str, data: &[u8]) -> PathBuf {
+    let path = env::temp_dir().join(format!("clast-{}-{}", std::process::id(), name));
+    fs::write(&path, data).expect("Failed to write temp file");
+    path
+}
+
+fn summarize<I: Iterator<Item = io::Result<Chunk>>>(chunks: I) -> Vec<(u64, usize, u64)> {
+    chunks
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect()
+}
+
+// --- Mapping Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let path = write_temp_file("mmap-match", &data);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected = summarize(chunker.chunks(&data[..]));
+
+    // A single window covers the file
+    assert_eq!(summarize(chunker.chunk_file(&path).unwrap()), expected);
+
+    // Small windows are remapped at unaligned chunk offsets without changing the chunks
+    let chunks = MmapChunks::new(&chunker, File::open(&path).unwrap(), 40_000).unwrap();
+    assert_eq!(summarize(chunks), expected);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_chunks_outlive_iterator() {
+    let data = generate_noise(100_000);
+    let path = write_temp_file("mmap-outlive", &data);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // Chunks keep their window mapped after the iterator and file are gone
+    let chunks: Vec<Chunk> = MmapChunks::new(&chunker, File::open(&path).unwrap(), 40_000)
+        .unwrap()
+        .map(|c| c.unwrap())
+        .collect();
+    fs::remove_file(&path).unwrap();
+
+    let joined: Vec<u8> = chunks.iter().flat_map(|c| c.data.iter().copied()).collect();
+    assert_eq!(joined, data);
+}
+
+#[test]
+fn test_empty_and_missing_files() {
+    let path = write_temp_file("mmap-empty", &[]);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // An empty file yields no chunks
+    assert!(chunker.chunk_file(&path).unwrap().next().is_none());
+    fs::remove_file(&path).unwrap();
+
+    // A missing file fails to open
+    let err = chunker.chunk_file(&path).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
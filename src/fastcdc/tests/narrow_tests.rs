@@ -0,0 +1,52 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, assert_tiles, generate_noise_data};
+use std::io;
+
+// --- Boundary Tests ---
+
+#[test]
+fn test_vectors() {
+    let data = generate_noise_data(100_000);
+    let chunker = FastCDC32::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let chunks = chunker
+        .chunks(&data[..])
+        .take(5)
+        .map(|c| c.map(|c| (c.offset, c.length, c.fp_hash)))
+        .collect::<io::Result<Vec<_>>>()
+        .unwrap();
+
+    // The documented boundaries for the default GEAR_SEED
+    assert_eq!(
+        chunks,
+        [
+            (0, 8_658, 0xd958_4814),
+            (8_658, 9_820, 0xd958_4814),
+            (18_478, 10_064, 0xd958_4814),
+            (28_542, 9_820, 0xd958_4814),
+            (38_362, 10_041, 0xa87c_0814),
+        ]
+    );
+}
+
+#[test]
+fn test_size_constraints() {
+    let data = generate_noise_data(1_000_000);
+    let chunker = FastCDC32::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // Chunks tile the input and respect the size limits
+    let spans = assert_tiles(&chunker, &data, MIN_SIZE);
+
+    // The hash is zero-extended from 32 bits
+    for chunk in chunker.chunks(&data[..]) {
+        assert!(chunk.unwrap().fp_hash <= u32::MAX as u64);
+    }
+
+    // The average lands between min_size and max_size
+    let avg = data.len() / spans.len();
+    assert!((MIN_SIZE..MAX_SIZE).contains(&avg), "average {}", avg);
+
+    // The size constraints are shared with FastCDC
+    assert!(FastCDC32::try_new(MIN_SIZE, AVG_SIZE, AVG_SIZE, Normal::Level2).is_err());
+    assert!(FastCDC32::try_new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Custom(9)).is_err());
+}
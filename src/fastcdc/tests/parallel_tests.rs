@@ -0,0 +1,211 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::sync::Mutex;
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn sequential(chunker: &FastCDC, data: &[u8]) -> Vec<(u64, usize, u64)> {
+    chunker
+        .chunk_slice(data)
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect()
+}
+
+fn parallel(chunks: Vec<ChunkRef<'_>>) -> Vec<(u64, usize, u64)> {
+    chunks
+        .into_iter()
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect()
+}
+
+// --- Parallel Chunking Tests ---
+
+#[test]
+fn test_matches_sequential() {
+    let data = generate_noise(1_000_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // The chunks match sequential chunking
+    assert_eq!(
+        parallel(chunker.par_chunks(&data)),
+        sequential(&chunker, &data)
+    );
+}
+
+#[test]
+fn test_segment_joins() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected = sequential(&chunker, &data);
+
+    // Any segment length stitches back to the sequential chunks, including segments
+    // shorter than a chunk, which never resynchronize on their own
+    for segment_len in [1_000, 7_777, MAX_SIZE, 50_000, 299_999, 300_000, 1_000_000] {
+        let actual = parallel(chunker.par_chunks_segmented(&data, segment_len, &|_| {}));
+        assert_eq!(actual, expected, "segment length {segment_len}");
+    }
+}
+
+#[test]
+fn test_offset_dependent_options() {
+    let mut data = generate_noise(300_000);
+    for i in (0..data.len()).step_by(997) {
+        data[i] = b'\n';
+    }
+
+    // Alignment and delimiters depend on absolute offsets and still match
+    let chunkers = [
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_alignment(512, 64),
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_delimiter(b'\n', 1_024),
+    ];
+    for chunker in &chunkers {
+        let expected = sequential(chunker, &data);
+        for segment_len in [10_000, 33_333, 100_000] {
+            let actual = parallel(chunker.par_chunks_segmented(&data, segment_len, &|_| {}));
+            assert_eq!(actual, expected, "segment length {segment_len}");
+        }
+    }
+}
+
+#[test]
+fn test_placement_hook() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let placed = Mutex::new(Vec::new());
+
+    let chunks = chunker.par_chunks_segmented(&data, 50_000, &|index| {
+        placed.lock().unwrap().push((index, thread::current().id()));
+    });
+
+    // The chunks are unchanged
+    assert_eq!(parallel(chunks), sequential(&chunker, &data));
+
+    // The hook runs once on each spawned thread, never on the calling one
+    let mut placed = placed.into_inner().unwrap();
+    placed.sort_by_key(|&(index, _)| index);
+    let indices: Vec<_> = placed.iter().map(|&(index, _)| index).collect();
+    assert_eq!(indices, [1, 2, 3, 4, 5]);
+    assert!(placed.iter().all(|&(_, id)| id != thread::current().id()));
+}
+
+#[test]
+fn test_empty_and_short_input() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // Empty input yields no chunks
+    assert!(chunker.par_chunks(&[]).is_empty());
+
+    // Input shorter than the minimum size is a single chunk
+    let data = generate_noise(100);
+    assert_eq!(
+        parallel(chunker.par_chunks(&data)),
+        sequential(&chunker, &data)
+    );
+}
+
+// --- File Tests ---
+
+fn write_temp_file(name: &str, data: &[u8]) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("clast-{}-{}", std::process::id(), name));
+    std::fs::write(&path, data).expect("Failed to write temp file");
+    path
+}
+
+fn summarize(chunks: Vec<Chunk>) -> Vec<(u64, usize, u64)> {
+    chunks
+        .into_iter()
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect()
+}
+
+#[test]
+fn test_file_matches_serial() {
+    let data = generate_noise(300_000);
+    let path = write_temp_file("parallel-file", &data);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected = sequential(&chunker, &data);
+
+    // Any number of ranges, including ranges shorter than a chunk, yields the serial chunks
+    for threads in [1, 2, 3, 7, 64] {
+        let chunks = chunk_file(&chunker, &path, threads).unwrap();
+        assert_eq!(summarize(chunks), expected, "{threads} threads");
+    }
+
+    // The chunk data is the file's data at each offset
+    for chunk in chunk_file(&chunker, &path, 4).unwrap() {
+        let start = chunk.offset as usize;
+        assert_eq!(&chunk.data[..], &data[start..start + chunk.length]);
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_file_offset_dependent_options() {
+    let mut data = generate_noise(300_000);
+    for i in (0..data.len()).step_by(997) {
+        data[i] = b'\n';
+    }
+    let path = write_temp_file("parallel-file-options", &data);
+
+    // Alignment and delimiters depend on absolute offsets and still match
+    let chunkers = [
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_alignment(512, 64),
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_delimiter(b'\n', 1_024),
+    ];
+    for chunker in &chunkers {
+        let expected = sequential(chunker, &data);
+        for threads in [3, 16] {
+            let chunks = chunk_file(chunker, &path, threads).unwrap();
+            assert_eq!(summarize(chunks), expected, "{threads} threads");
+        }
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_file_placement_hook() {
+    let data = generate_noise(300_000);
+    let path = write_temp_file("parallel-file-placed", &data);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let placed = Mutex::new(Vec::new());
+
+    let chunks = chunk_file_placed(&chunker, &path, 4, |index| {
+        placed.lock().unwrap().push(index);
+    })
+    .unwrap();
+
+    // The chunks are unchanged, and the hook runs once for every range
+    assert_eq!(summarize(chunks), sequential(&chunker, &data));
+    let mut placed = placed.into_inner().unwrap();
+    placed.sort();
+    assert_eq!(placed, [0, 1, 2, 3]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_file_empty_and_missing() {
+    let path = write_temp_file("parallel-file-empty", &[]);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // An empty file yields no chunks
+    assert!(chunk_file(&chunker, &path, 4).unwrap().is_empty());
+    std::fs::remove_file(&path).unwrap();
+
+    // A missing file is an error
+    assert!(chunk_file(&chunker, &path, 4).is_err());
+}
+
+#[test]
+#[should_panic(expected = "threads must be greater than zero")]
+fn test_zero_threads() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let _ = chunk_file(&chunker, "missing", 0);
+}
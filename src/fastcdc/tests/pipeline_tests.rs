@@ -0,0 +1,95 @@
+use super::*;
+use crate::digest::{Sha1, Sha256};
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::io::Cursor;
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+/// A reader that fails once its data runs out.
+struct FailingReader(Cursor<Vec<u8>>);
+
+impl AsyncRead for FailingReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.0.position() as usize == this.0.get_ref().len() {
+            return Poll::Ready(Err(io::Error::other("device gone")));
+        }
+        Pin::new(&mut this.0).poll_read(cx, buf)
+    }
+}
+
+// --- Pipeline Tests ---
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_matches_chunks_and_digests() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, [u8; 32])> = chunker
+        .chunk_slice(&data)
+        .map(|c| (c.offset, c.length, Sha256::digest(c.data)))
+        .collect();
+
+    // The chunks arrive in order, each with the digest of its data
+    let actual: Vec<(u64, usize, [u8; 32])> = chunker
+        .pipeline(Cursor::new(data))
+        .with_capacity(2)
+        .hash::<Sha256>()
+        .map(|item| item.map(|(c, digest)| (c.offset, c.length, digest)))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<io::Result<_>>()
+        .unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn test_single_threaded_runtime() {
+    let data = generate_noise(50_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // The stages interleave on a current-thread runtime too
+    let digests: Vec<[u8; 20]> = chunker
+        .pipeline(Cursor::new(data.clone()))
+        .hash::<Sha1>()
+        .map(|item| item.unwrap().1)
+        .collect()
+        .await;
+    let expected: Vec<[u8; 20]> = chunker
+        .chunk_slice(&data)
+        .map(|c| Sha1::digest(c.data))
+        .collect();
+    assert_eq!(digests, expected);
+}
+
+#[tokio::test]
+async fn test_error_ends_stream() {
+    let data = generate_noise(50_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let items: Vec<_> = chunker
+        .pipeline(FailingReader(Cursor::new(data)))
+        .hash::<Sha256>()
+        .collect()
+        .await;
+
+    // The reader error is the last item
+    let last = items.last().expect("stream yielded nothing");
+    assert_eq!(last.as_ref().unwrap_err().to_string(), "device gone");
+    assert!(items[..items.len() - 1].iter().all(|item| item.is_ok()));
+}
+
+#[tokio::test]
+#[should_panic(expected = "capacity must be greater than zero")]
+async fn test_zero_capacity() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let _ = chunker.pipeline(Cursor::new(Vec::new())).with_capacity(0);
+}
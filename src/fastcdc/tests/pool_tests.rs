@@ -0,0 +1,74 @@
+use super::*;
+use crate::fastcdc::{Chunk, FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::io;
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+// --- Pool Tests ---
+
+#[test]
+fn test_pooled_chunks_match_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<Chunk> = chunker
+        .chunks(&data[..])
+        .collect::<io::Result<_>>()
+        .unwrap();
+    let pool = BufferPool::new(8);
+
+    // Chunks copied into pooled buffers carry the same data
+    let mut count = 0;
+    for (chunk, expected) in chunker
+        .chunks(&data[..])
+        .with_pool(pool.clone())
+        .zip(&expected)
+    {
+        let chunk = chunk.unwrap();
+        assert_eq!(chunk.data, expected.data);
+        assert_eq!(
+            (chunk.offset, chunk.length, chunk.fp_hash),
+            (expected.offset, expected.length, expected.fp_hash)
+        );
+        count += 1;
+    }
+    assert_eq!(count, expected.len());
+
+    // Each chunk was dropped before the next, so a single buffer was recycled throughout
+    assert_eq!(pool.idle(), 1);
+}
+
+#[test]
+fn test_buffers_return_on_drop() {
+    let pool = BufferPool::new(2);
+    let first = pool.copy_from_slice(b"first");
+    let second = first.slice(1..);
+
+    // A buffer is only returned once every view of it is dropped
+    drop(first);
+    assert_eq!(pool.idle(), 0);
+    drop(second);
+    assert_eq!(pool.idle(), 1);
+
+    // A returned buffer is reused, and only holds the new data
+    let reused = pool.copy_from_slice(b"ab");
+    assert_eq!(pool.idle(), 0);
+    assert_eq!(&reused[..], b"ab");
+}
+
+#[test]
+fn test_max_idle() {
+    let pool = BufferPool::new(2);
+    let held: Vec<_> = (0..5).map(|_| pool.copy_from_slice(&[0; 64])).collect();
+
+    // Buffers beyond `max_idle` are freed rather than kept
+    drop(held);
+    assert_eq!(pool.idle(), 2);
+
+    // Clones share the pool
+    assert_eq!(pool.clone().idle(), 2);
+}
@@ -0,0 +1,37 @@
+use super::*;
+use crate::test_util::generate_noise_data;
+
+// --- Preset Tests ---
+
+#[test]
+fn test_presets_respect_sizes() {
+    let data = generate_noise_data(1_000_000);
+
+    for chunker in [
+        FastCDC::preset_small_files(),
+        FastCDC::preset_network_sync(),
+    ] {
+        let lengths: Vec<usize> = chunker
+            .chunks(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect();
+
+        // Every chunk but the last stays within the preset's bounds
+        assert_eq!(lengths.iter().sum::<usize>(), data.len());
+        assert!(
+            lengths[..lengths.len() - 1]
+                .iter()
+                .all(|&len| (chunker.min_size()..=chunker.max_size()).contains(&len))
+        );
+    }
+}
+
+#[test]
+fn test_backup_preset_limits() {
+    let chunker = FastCDC::preset_backup();
+
+    // The backup preset uses the largest accepted sizes
+    assert_eq!(chunker.min_size(), crate::fastcdc::core::MIN_CHUNK_SIZE_MAX);
+    assert_eq!(chunker.avg_size(), crate::fastcdc::core::AVG_CHUNK_SIZE_MAX);
+    assert_eq!(chunker.max_size(), crate::fastcdc::MAX_CHUNK_SIZE_MAX);
+}
@@ -0,0 +1,107 @@
+use super::*;
+use crate::manifest::Manifest;
+
+/// Xorshift64 noise, which the vectors below were computed over.
+fn generate_xorshift_data(len: usize) -> Vec<u8> {
+    let mut x: u64 = 0x9e37_79b9_7f4a_7c15;
+    (0..len)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            (x >> 56) as u8
+        })
+        .collect()
+}
+
+///
+/// `(length, fingerprint)` of each chunk of 200,000 bytes of xorshift noise at 2 / 8 / 64 KB
+/// and `Level2`.
+///
+/// These are regression values, computed from a transcription of the reference
+/// implementation's two-byte loop rather than by running it, so only the table and masks
+/// they are chunked with are published constants; the boundaries are not reference output.
+///
+const PAPER_VECTORS: [(usize, u64); 23] = [
+    (2903, 14406213117186978972),
+    (9821, 12342720293977247991),
+    (8392, 12777288367763691078),
+    (9164, 3478553454058188516),
+    (9141, 15798065167924739349),
+    (8787, 8529010964972989412),
+    (13013, 4526125282168176632),
+    (4740, 12593828869775616478),
+    (10014, 14488645135883073270),
+    (8361, 4865893856847372847),
+    (13111, 18204682604475643044),
+    (10424, 1530103127381903074),
+    (9808, 5066063117630960840),
+    (8193, 8963605996511691755),
+    (10961, 13244530214236610958),
+    (16418, 33220531315070158),
+    (3266, 3579246393518795936),
+    (3774, 14877641919999086444),
+    (9129, 16884846961046623010),
+    (8536, 9991249973424541590),
+    (10957, 10240381349620118795),
+    (2464, 13156782533109210418),
+    (8623, 5827114208091440857),
+];
+
+// --- Table Tests ---
+
+#[test]
+fn test_paper_masks_bit_counts() {
+    // Each used entry has as many bits set as its index
+    for (bits, mask) in PAPER_MASKS.iter().enumerate().skip(5) {
+        assert_eq!(
+            mask.count_ones() as usize,
+            bits,
+            "Mask {} has the wrong weight",
+            bits
+        );
+    }
+    assert_eq!(&PAPER_GEAR[..2], &[0x3b5d3c7d207e37dc, 0x784d68ba91123086]);
+}
+
+#[test]
+fn test_paper_masks_selection() {
+    let masks = Masks::paper(8192, Normal::Level2).unwrap();
+
+    // 8 KB with `Level2` uses the 15- and 11-bit masks
+    assert_eq!(masks.mask_s(), PAPER_MASKS[15]);
+    assert_eq!(masks.mask_l(), PAPER_MASKS[11]);
+    assert!(Masks::paper(8192, Normal::Custom(9)).is_err());
+}
+
+// --- Boundary Tests ---
+
+#[test]
+fn test_paper_vectors() {
+    let data = generate_xorshift_data(200_000);
+    let chunker = FastCDC::paper(2048, 8192, 65536, Normal::Level2);
+
+    let chunks: Vec<(usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.map(|c| (c.length, c.fp_hash)).unwrap())
+        .collect();
+
+    // Boundaries and fingerprints stay as recorded
+    assert_eq!(chunks, PAPER_VECTORS);
+}
+
+#[test]
+fn test_paper_manifest_rebuilds_table() {
+    let data = generate_xorshift_data(100_000);
+    let chunker = FastCDC::paper(2048, 8192, 65536, Normal::Level2);
+    let manifest = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    // The published table is recognized by its fingerprint, unlike other custom tables
+    let rebuilt = manifest
+        .chunker()
+        .expect("Paper chunkers must be reconstructed");
+    assert!(manifest.matches(&rebuilt));
+    manifest
+        .verify(&data[..])
+        .expect("Paper manifest must verify");
+}
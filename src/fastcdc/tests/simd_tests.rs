@@ -0,0 +1,93 @@
+use super::*;
+use crate::fastcdc::table::BUILTIN;
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+/// Masks from never matching to matching almost every byte.
+const MASKS: [u64; 5] = [
+    u64::MAX >> 1,
+    0x0000_d930_0353_0000,
+    0x0000_0000_0000_ff00,
+    0x3,
+    0,
+];
+
+fn assert_matches_scalar(
+    scan: impl Fn(&[u8], usize, usize, u64, u64, u64) -> ControlFlow<(u64, usize), u64>,
+) {
+    let data = generate_noise(40_000);
+
+    for mask in MASKS {
+        for (start, end) in [
+            (0, 20_000),
+            (7, 19_993),
+            (2_048, 4_096),
+            (100, 163),
+            (10, 10),
+        ] {
+            for prev_hash in [0, 0x1234_5678_9abc_def0] {
+                let expected =
+                    scan_pairs_scalar(&BUILTIN, &data, start, end, prev_hash, mask << 1, mask);
+                let actual = scan(&data, start, end, prev_hash, mask << 1, mask);
+
+                // Cut points, fingerprints and final hashes all match the serial scan
+                assert_eq!(actual, expected, "mask {mask:#x}, pairs {start}..{end}");
+            }
+        }
+    }
+
+    // Pairs past the end of the source are never read
+    let expected = scan_pairs_scalar(&BUILTIN, &data[..1_001], 0, 20_000, 0, 0, 0);
+    assert_eq!(scan(&data[..1_001], 0, 20_000, 0, 0, 0), expected);
+}
+
+// --- Dispatch Tests ---
+
+#[test]
+fn test_dispatch_matches_scalar() {
+    assert_matches_scalar(|source, start, end, hash, mask_ls, mask| {
+        scan_pairs(&BUILTIN, source, start, end, hash, mask_ls, mask)
+    });
+}
+
+// --- Kernel Tests ---
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn test_x86_kernels_match_scalar() {
+    // Each kernel is checked on its own, as dispatch only picks the widest
+    if std::arch::is_x86_feature_detected!("avx2") {
+        assert_matches_scalar(|source, start, end, hash, mask_ls, mask| {
+            let end = end.min(source.len() / 2);
+            scan_lanes(
+                &BUILTIN,
+                source,
+                start,
+                end,
+                hash,
+                mask_ls,
+                mask,
+                x86::block_avx2,
+            )
+        });
+    }
+    if std::arch::is_x86_feature_detected!("sse4.2") {
+        assert_matches_scalar(|source, start, end, hash, mask_ls, mask| {
+            let end = end.min(source.len() / 2);
+            scan_lanes(
+                &BUILTIN,
+                source,
+                start,
+                end,
+                hash,
+                mask_ls,
+                mask,
+                x86::block_sse42,
+            )
+        });
+    }
+}
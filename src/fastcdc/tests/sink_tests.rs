@@ -0,0 +1,82 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt, channel::mpsc, executor::block_on};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+// --- Forwarding Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // Frames sent to the sink arrive as chunks in the inner sink
+    let mut sink = chunker.chunks_sink(Vec::new());
+    block_on(async {
+        for frame in data.chunks(1_500) {
+            sink.send(Bytes::copy_from_slice(frame)).await.unwrap();
+        }
+        sink.close().await.unwrap();
+    });
+    let actual: Vec<(u64, usize, u64)> = sink
+        .into_inner()
+        .into_iter()
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_flush_keeps_tail() {
+    let data = generate_noise(MIN_SIZE - 1);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let mut sink = chunker.chunks_sink(Vec::new());
+
+    // Flushing does not cut the bytes after the last cut point
+    block_on(sink.send(Bytes::from(data.clone()))).unwrap();
+    assert_eq!(sink.buffered(), data.len());
+
+    // Closing forwards them as the last chunk
+    block_on(sink.close()).unwrap();
+    assert_eq!(sink.buffered(), 0);
+    let chunks = sink.into_inner();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(&chunks[0].data[..], &data[..]);
+}
+
+#[test]
+fn test_channel_backpressure() {
+    let data = generate_noise(200_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // A bounded channel receives every chunk while a consumer drains it
+    let (tx, rx) = mpsc::channel(1);
+    let mut sink = chunker.clone().into_sink(tx);
+    let send = async move {
+        for frame in data.chunks(64 * 1024) {
+            sink.send(Bytes::copy_from_slice(frame)).await.unwrap();
+        }
+        sink.close().await.unwrap();
+    };
+    let (_, actual) = block_on(futures::future::join(
+        send,
+        rx.map(|c| c.length).collect::<Vec<usize>>(),
+    ));
+    assert_eq!(actual, expected);
+}
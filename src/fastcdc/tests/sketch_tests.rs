@@ -0,0 +1,58 @@
+use super::*;
+use crate::corpus::{Edit, apply_edits};
+use crate::test_util::generate_noise_data;
+
+// --- Sketch Tests ---
+
+#[test]
+fn test_identical_data() {
+    let data = generate_noise_data(8_192);
+
+    // Equal data produces equal sketches
+    let a = Sketch::from_data(&data, 64);
+    assert_eq!(a, Sketch::from_data(&data, 64));
+    assert_eq!(a.similarity(&a), 1.0);
+    assert_eq!(a.mins().len(), 64);
+}
+
+#[test]
+fn test_similarity_tracks_edits() {
+    let data = generate_noise_data(8_192);
+    let base = Sketch::from_data(&data, 128);
+
+    let small = apply_edits(
+        &data,
+        &[Edit::Rewrite {
+            offset: 4_000,
+            len: 16,
+        }],
+        1,
+    );
+    let large = apply_edits(
+        &data,
+        &[Edit::Rewrite {
+            offset: 0,
+            len: 6_000,
+        }],
+        1,
+    );
+    let other: Vec<u8> = data.iter().map(|b| b ^ 0x5a).collect();
+
+    let near = base.similarity(&Sketch::from_data(&small, 128));
+    let far = base.similarity(&Sketch::from_data(&large, 128));
+    let unrelated = base.similarity(&Sketch::from_data(&other, 128));
+
+    // A small edit stays close, a large edit falls away, unrelated data is dissimilar
+    assert!(near > 0.9, "Small edit similarity {}", near);
+    assert!(far < near && far < 0.5, "Large edit similarity {}", far);
+    assert!(unrelated < 0.05, "Unrelated similarity {}", unrelated);
+}
+
+#[test]
+fn test_empty_data() {
+    let empty = Sketch::from_data(&[], 16);
+
+    // No fingerprints leave every bin empty; two empty sketches are alike
+    assert!(empty.mins().iter().all(|&m| m == u64::MAX));
+    assert_eq!(empty.similarity(&empty), 1.0);
+}
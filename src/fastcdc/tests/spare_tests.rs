@@ -0,0 +1,36 @@
+use super::*;
+
+// --- Spare Capacity Tests ---
+
+#[test]
+fn test_zeroes_fresh_capacity() {
+    let mut buf = BytesMut::new();
+    let mut init = SpareInit::default();
+
+    // Fresh capacity is reserved and zeroed
+    let spare = init.spare(&mut buf, 1_024);
+    assert_eq!(spare.len(), 1_024);
+    assert!(spare.iter().all(|&b| b == 0));
+    assert!(buf.capacity() >= 1_024);
+}
+
+#[test]
+fn test_zeroes_capacity_once() {
+    let mut buf = BytesMut::with_capacity(4_096);
+    let mut init = SpareInit::default();
+
+    // Bytes written but not appended are kept, as the capacity is not zeroed again
+    init.spare(&mut buf, 1_024)[..4].copy_from_slice(b"mark");
+    assert_eq!(&init.spare(&mut buf, 2_048)[..4], b"mark");
+
+    // Appending moves the spare capacity past the appended bytes
+    init.spare(&mut buf, 8).copy_from_slice(b"appended");
+    unsafe { buf.set_len(8) };
+    init.filled(8);
+    init.spare(&mut buf, 4).copy_from_slice(b"next");
+    assert_eq!(&buf[..], b"appended");
+
+    // Splitting off the front leaves the spare capacity in place
+    let _front = buf.split_to(4);
+    assert_eq!(&init.spare(&mut buf, 4)[..], b"next");
+}
@@ -0,0 +1,81 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn push_all(state: &mut ChunkerState<'_>, data: &[u8], step: usize) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    for piece in data.chunks(step) {
+        chunks.extend(state.push(piece));
+    }
+    while let Some(chunk) = state.finish() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+// --- Push Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // The chunks do not depend on how the input is split
+    for step in [1, 777, 16_384, 300_000] {
+        let actual: Vec<(u64, usize, u64)> = push_all(&mut chunker.state(), &data, step)
+            .into_iter()
+            .map(|c| (c.offset, c.length, c.fp_hash))
+            .collect();
+        assert_eq!(actual, expected, "step {}", step);
+    }
+}
+
+#[test]
+fn test_delimiter_flushes_every_chunk() {
+    let data: Vec<u8> = generate_noise(200_000)
+        .into_iter()
+        .map(|b| if b % 61 == 0 { b'\n' } else { b })
+        .collect();
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_delimiter(b'\n', 1_024);
+    let expected: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // Cut points awaiting a delimiter are flushed by repeated `finish` calls
+    let actual: Vec<usize> = push_all(&mut chunker.state(), &data, 50_000)
+        .into_iter()
+        .map(|c| c.length)
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_buffer_accounting() {
+    let data = generate_noise(MIN_SIZE - 1);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let mut state = chunker.into_state();
+
+    // Less than `min_size` stays buffered
+    assert!(state.push(&data).is_empty());
+    assert_eq!(state.buffered(), data.len());
+
+    // Finishing flushes it as the last chunk
+    let chunk = state.finish().unwrap();
+    assert_eq!(&chunk.data[..], &data[..]);
+    assert!(state.finish().is_none());
+    assert_eq!(state.buffered(), 0);
+    assert_eq!(state.processed(), data.len() as u64);
+}
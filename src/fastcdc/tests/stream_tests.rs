@@ -1,28 +1,10 @@
 use super::*;
 use crate::fastcdc::Normal;
-use futures::StreamExt;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+use futures::{StreamExt, stream::FusedStream};
 use std::{env, fs, io, path::PathBuf};
 use tokio::io::{AsyncRead, ReadBuf};
 
-const MIN_SIZE: usize = 4_069;
-const AVG_SIZE: usize = 8_192;
-const MAX_SIZE: usize = 16_384;
-
-fn generate_patterned_data(len: usize) -> Vec<u8> {
-    const BLOCKS: [&[u8]; 3] = [b"LOREM", b"IPSUM", b"DOLOR"];
-
-    let mut data = Vec::with_capacity(len);
-    let mut idx = 0;
-
-    while data.len() < len {
-        data.extend_from_slice(BLOCKS[idx % BLOCKS.len()]);
-        idx += 1;
-    }
-
-    data.truncate(len);
-    data
-}
-
 // --- Input Tests ---
 
 #[tokio::test]
@@ -93,6 +75,509 @@ async fn test_round_trip_chunking() {
     );
 }
 
+/// Yields at most 1,000 bytes per read so scans resume mid-chunk.
+struct TrickleReader<'a>(&'a [u8]);
+
+impl AsyncRead for TrickleReader<'_> {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> std::task::Poll<io::Result<()>> {
+        let n = self.0.len().min(buf.remaining()).min(1_001);
+        buf.put_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_resumed_scans_match_iterator() {
+    let data: Vec<u8> = (0..300_000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect();
+
+    for (one_byte, min_skip) in [(false, true), (true, true), (false, false), (true, false)] {
+        let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+            .with_one_byte_rolling(one_byte)
+            .with_min_size_skip(min_skip);
+
+        let expected: Vec<usize> = chunker
+            .chunks(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect();
+        let actual: Vec<usize> = chunker
+            .chunks_async(TrickleReader(&data))
+            .map(|c| c.unwrap().length)
+            .collect()
+            .await;
+
+        // Resuming a scan across reads must not change any boundary in any mode
+        assert_eq!(actual, expected, "Mismatch with {:?}", (one_byte, min_skip));
+    }
+}
+
+#[tokio::test]
+async fn test_delimiter_matches_iterator() {
+    let data: Vec<u8> = (0..300_000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .map(|b| if b % 97 == 0 { b'\n' } else { b'a' + b % 26 })
+        .collect();
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_delimiter(b'\n', 2_048);
+
+    let expected: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+    let actual: Vec<usize> = chunker
+        .chunks_async(TrickleReader(&data))
+        .map(|c| c.unwrap().length)
+        .collect()
+        .await;
+
+    // Snapping waits for the slack window instead of cutting short on partial reads
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn test_utf8_boundaries_match_iterator() {
+    const CHARS: [char; 4] = ['a', 'é', '한', '𝄞'];
+    let text: String = (0..100_000u32)
+        .map(|i| CHARS[((i.wrapping_mul(2_654_435_761) >> 13) % 4) as usize])
+        .collect();
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_utf8_boundaries(true);
+
+    let expected: Vec<usize> = chunker
+        .chunks(text.as_bytes())
+        .map(|c| c.unwrap().length)
+        .collect();
+    let actual: Vec<usize> = chunker
+        .chunks_async(TrickleReader(text.as_bytes()))
+        .map(|c| c.unwrap().length)
+        .collect()
+        .await;
+
+    // Adjusted cut points must not depend on how the data arrives
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn test_owned_stream() {
+    let data = generate_patterned_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // An owned stream can be spawned onto the runtime
+    let stream = chunker.into_stream(std::io::Cursor::new(data));
+    let actual = tokio::spawn(async move {
+        stream
+            .map(|c| c.unwrap().length)
+            .collect::<Vec<usize>>()
+            .await
+    })
+    .await
+    .unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn test_read_size_matches_iterator() {
+    let data = generate_patterned_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+
+    // The read size only changes how much is requested per poll
+    for read_size in [1_000, 1_048_576] {
+        let chunker = chunker.clone().with_read_size(read_size);
+        let actual: Vec<usize> = chunker
+            .chunks_async(&data[..])
+            .map(|c| c.unwrap().length)
+            .collect()
+            .await;
+        assert_eq!(actual, expected);
+    }
+}
+
+/// Records the length of every buffer it is asked to fill.
+struct RecordingReader<'a> {
+    data: &'a [u8],
+    reads: Vec<usize>,
+}
+
+impl AsyncRead for RecordingReader<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.reads.push(buf.remaining());
+        Pin::new(&mut this.data).poll_read(cx, buf)
+    }
+}
+
+#[tokio::test]
+async fn test_read_size_bounds_reads() {
+    let data = generate_patterned_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_read_size(1_000);
+    let mut reader = RecordingReader {
+        data: &data,
+        reads: Vec::new(),
+    };
+    let count = chunker.chunks_async(&mut reader).count().await;
+    assert_eq!(count, chunker.chunks(&data[..]).count());
+
+    // Every read asks for exactly the configured read size
+    assert!(reader.reads.iter().all(|&len| len == 1_000));
+    assert_eq!(reader.reads.len(), 301);
+}
+
+#[tokio::test]
+async fn test_pooled_stream() {
+    let data = generate_patterned_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<Chunk> = chunker.chunks(&data[..]).map(|c| c.unwrap()).collect();
+    let pool = BufferPool::new(4);
+
+    // Pooled chunks carry the same data, and dropping them refills the pool
+    let mut stream = chunker.chunks_async(&data[..]).with_pool(pool.clone());
+    for expected in &expected {
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(chunk.data, expected.data);
+        assert_eq!(
+            (chunk.offset, chunk.fp_hash),
+            (expected.offset, expected.fp_hash)
+        );
+    }
+    assert!(stream.next().await.is_none());
+    assert_eq!(pool.idle(), 1);
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn test_futures_reader_matches_iterator() {
+    let data: Vec<u8> = (0..300_000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect();
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // A `futures` reader is driven without a tokio runtime
+    let actual: Vec<(u64, usize, u64)> = futures::executor::block_on(
+        chunker
+            .chunks_futures(futures::io::Cursor::new(&data[..]))
+            .map(|c| c.unwrap())
+            .map(|c| (c.offset, c.length, c.fp_hash))
+            .collect(),
+    );
+    assert_eq!(actual, expected);
+
+    // The owned variant yields the same chunks
+    let owned: Vec<usize> = futures::executor::block_on(
+        chunker
+            .clone()
+            .into_futures_stream(futures::io::Cursor::new(data))
+            .map(|c| c.unwrap().length)
+            .collect(),
+    );
+    assert_eq!(owned, expected.iter().map(|c| c.1).collect::<Vec<_>>());
+}
+
+/// A `futures` reader that leaves a mark in the buffer and returns `Pending` before every read.
+#[cfg(feature = "futures-io")]
+struct MarkingReader<'a> {
+    data: &'a [u8],
+    marked: bool,
+    reads: Vec<usize>,
+}
+
+#[cfg(feature = "futures-io")]
+impl futures::io::AsyncRead for MarkingReader<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if !this.marked {
+            buf[0] = 0xAA;
+            this.marked = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        // The buffer is not zeroed again while the stream waits
+        assert_eq!(buf[0], 0xAA);
+        this.marked = false;
+        this.reads.push(buf.len());
+        futures::io::AsyncRead::poll_read(Pin::new(&mut this.data), cx, buf)
+    }
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn test_futures_reader_bounded_reads() {
+    let data = generate_patterned_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_read_size(1_000);
+    let mut reader = MarkingReader {
+        data: &data,
+        marked: false,
+        reads: Vec::new(),
+    };
+    let lengths: Vec<usize> = futures::executor::block_on(
+        chunker
+            .chunks_futures(&mut reader)
+            .map(|c| c.unwrap().length)
+            .collect(),
+    );
+    let expected: Vec<usize> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap().length)
+        .collect();
+    assert_eq!(lengths, expected);
+
+    // Every read asks for exactly the configured read size
+    assert!(reader.reads.iter().all(|&len| len == 1_000));
+}
+
+// --- Termination Tests ---
+
+#[tokio::test]
+async fn test_fused_after_completion() {
+    let data = generate_patterned_data(50_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut stream = chunker.chunks_async(&data[..]);
+    assert!(!stream.is_terminated());
+    while stream.next().await.is_some() {}
+
+    // Once finished, the stream reports termination and keeps yielding `None`
+    assert!(stream.is_terminated());
+    assert!(stream.next().await.is_none());
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_size_hint() {
+    let data: Vec<u8> = (0..300_000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect();
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // Without a declared length there is no estimate
+    assert_eq!(chunker.chunks_async(&data[..]).size_hint(), (0, None));
+
+    let mut stream = chunker
+        .chunks_async(&data[..])
+        .with_total_len(data.len() as u64);
+    let mut remaining = chunker.chunks(&data[..]).count();
+
+    loop {
+        // The hint always brackets the number of chunks still to come
+        let (lower, upper) = stream.size_hint();
+        assert!(lower <= remaining && remaining <= upper.unwrap());
+
+        if stream.next().await.is_none() {
+            break;
+        }
+        remaining -= 1;
+    }
+    assert_eq!(stream.size_hint(), (0, Some(0)));
+}
+
+#[tokio::test]
+async fn test_buffer_usage() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let mut stream = chunker.chunks_async(&data[..]);
+    while let Some(chunk) = stream.next().await {
+        chunk.expect("Failed to read chunk");
+
+        // The stream never buffers more than one max_size window
+        assert!(stream.buffer_usage().peak <= MAX_SIZE);
+    }
+
+    // Everything read has been emitted by the end of the stream
+    assert_eq!(stream.buffer_usage().buffered, 0);
+    assert!(stream.buffer_usage().peak > 0);
+}
+
+#[tokio::test]
+async fn test_memory_limit() {
+    use futures::FutureExt;
+
+    let data = generate_patterned_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length))
+        .collect();
+
+    let limit = 3 * MAX_SIZE;
+    let mut stream = chunker.chunks_async(&data[..]).with_memory_limit(limit);
+    let mut actual = Vec::new();
+    let mut held = Vec::new();
+    let mut stalls = 0;
+
+    loop {
+        match stream.next().now_or_never() {
+            Some(Some(chunk)) => {
+                held.push(chunk.expect("Failed to read chunk"));
+
+                // The stream never holds more than the limit
+                let in_flight = stream.in_flight().unwrap();
+                assert!(in_flight + stream.buffer_usage().buffered <= limit);
+            }
+            Some(None) => break,
+            None => {
+                // The stream stalls only with chunks held
+                assert!(!held.is_empty());
+                stalls += 1;
+
+                actual.extend(held.drain(..).map(|c| (c.offset, c.length)));
+                assert_eq!(stream.in_flight(), Some(0));
+            }
+        }
+    }
+    actual.extend(held.drain(..).map(|c| (c.offset, c.length)));
+
+    // Backpressure held the stream back without changing the chunks
+    assert!(stalls > 0);
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_memory_limit_wakes_on_drop() {
+    let data = generate_patterned_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected = chunker.chunks(&data[..]).count();
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    // A producer feeding an unbounded queue is held back until the consumer drops chunks
+    let producer = tokio::spawn(async move {
+        let mut stream = chunker
+            .into_stream(&data[..])
+            .with_memory_limit(2 * MAX_SIZE);
+        while let Some(chunk) = stream.next().await {
+            tx.unbounded_send(chunk.expect("Failed to read chunk"))
+                .unwrap();
+        }
+    });
+
+    let consumed = rx
+        .then(|chunk| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            chunk.length
+        })
+        .count()
+        .await;
+    producer.await.unwrap();
+    assert_eq!(consumed, expected);
+}
+
+#[test]
+fn test_yield_budget() {
+    use futures::task::{ArcWake, waker};
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    struct CountingWaker(AtomicUsize);
+
+    impl ArcWake for CountingWaker {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let data = generate_patterned_data(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected = chunker.chunks(&data[..]).count();
+
+    let wakes = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = waker(wakes.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut stream = chunker.chunks_async(&data[..]).with_yield_budget(3);
+    let (mut chunks, mut yields) = (0, 0);
+
+    loop {
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(chunk)) => {
+                chunk.expect("Failed to read chunk");
+                chunks += 1;
+            }
+            Poll::Ready(None) => break,
+            Poll::Pending => {
+                // Every third chunk, the stream yields and asks to be polled again at once
+                assert_eq!(chunks % 3, 0);
+                yields += 1;
+                assert_eq!(wakes.0.load(Ordering::SeqCst), yields);
+            }
+        }
+    }
+
+    assert_eq!(chunks, expected);
+    assert_eq!(yields, expected / 3);
+}
+
+#[test]
+#[should_panic(expected = "yield budget must be greater than zero")]
+fn test_zero_yield_budget() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let _ = chunker.chunks_async(&[][..]).with_yield_budget(0);
+}
+
+#[test]
+#[should_panic(expected = "memory limit must be at least max_size")]
+fn test_memory_limit_below_max_size() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let _ = chunker
+        .chunks_async(&[][..])
+        .with_memory_limit(MAX_SIZE - 1);
+}
+
+#[tokio::test]
+async fn test_alignment_matches_iterator() {
+    let data: Vec<u8> = (0..300_000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect();
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_alignment(4_096, 2_048);
+
+    let expected: Vec<(u64, usize)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.map(|c| (c.offset, c.length)))
+        .collect::<io::Result<_>>()
+        .expect("Failed to chunk");
+
+    let actual: Vec<(u64, usize)> = chunker
+        .chunks_async(&data[..])
+        .map(|c| c.map(|c| (c.offset, c.length)))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<io::Result<_>>()
+        .expect("Failed to chunk stream");
+
+    // Aligned boundaries must not depend on how the data arrives
+    assert_eq!(actual, expected);
+}
+
 #[tokio::test]
 async fn test_image_chunking() {
     let base_path = env!("CARGO_MANIFEST_DIR");
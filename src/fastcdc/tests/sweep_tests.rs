@@ -0,0 +1,81 @@
+use super::*;
+use crate::corpus::Corpus;
+use crate::fastcdc::Normal;
+
+/// A reader that returns at most `step` bytes per call.
+struct TrickleReader<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl Read for TrickleReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.step).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+fn expected_report(chunker: &FastCDC, data: &[u8]) -> SweepReport {
+    let mut seen = HashSet::new();
+    let mut report = SweepReport::default();
+
+    for chunk in chunker.chunks(data) {
+        let chunk = chunk.unwrap();
+        report.chunks += 1;
+        report.total_bytes += chunk.length as u64;
+        if seen.insert(Sha256::digest(&chunk.data)) {
+            report.unique_chunks += 1;
+            report.unique_bytes += chunk.length as u64;
+        }
+    }
+
+    report
+}
+
+// --- Sweep Tests ---
+
+#[test]
+fn test_matches_individual_runs() {
+    let data = Corpus::new(21, 2_000_000).with_duplication(0.4).generate();
+    let sweep = Sweep::new(vec![
+        FastCDC::new(2_048, 8_192, 65_536, Normal::Level2),
+        FastCDC::new(4_096, 16_384, 65_536, Normal::Level1),
+        FastCDC::new(1_024, 4_096, 16_384, Normal::None).with_one_byte_rolling(true),
+        FastCDC::new(2_048, 8_192, 32_768, Normal::Level3).with_alignment(4_096, 512),
+    ]);
+
+    let reports = sweep
+        .run(TrickleReader {
+            data: &data,
+            step: 7_777,
+        })
+        .unwrap();
+
+    // Every configuration reports exactly what a separate pass would
+    assert_eq!(reports.len(), sweep.chunkers().len());
+    for (chunker, report) in sweep.chunkers().iter().zip(&reports) {
+        assert_eq!(*report, expected_report(chunker, &data));
+        assert_eq!(report.total_bytes, data.len() as u64);
+        assert!(report.dedup_ratio() > 1.0, "Duplicated input must dedup");
+    }
+
+    // Smaller targets produce more chunks
+    assert!(reports[2].chunks > reports[0].chunks);
+    assert!(reports[0].chunks > reports[1].chunks);
+}
+
+#[test]
+fn test_empty_inputs() {
+    let sweep = Sweep::new(vec![FastCDC::new(2_048, 8_192, 65_536, Normal::Level2)]);
+
+    // No data yields empty reports, and no configurations yield no reports
+    assert_eq!(sweep.run(&[][..]).unwrap(), vec![SweepReport::default()]);
+    assert!(
+        Sweep::new(Vec::new())
+            .run(&[1u8, 2, 3][..])
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+/// The seed the build script used for the built-in tables.
+fn build_seed() -> u64 {
+    option_env!("GEAR_SEED")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(2_213_216_423_924_435_743)
+}
+
+// --- Table Tests ---
+
+#[test]
+fn test_seed_reproduces_build_tables() {
+    let table = GearTable::from_seed(build_seed());
+
+    // The runtime generator and mask derivation match the build script
+    assert_eq!(table.gear, GEAR);
+    assert_eq!(table.gear_ls, GEAR_LS);
+    assert_eq!(table.masks, MASK_TABLE);
+}
+
+#[test]
+fn test_seed_cache() {
+    let first = GearTable::from_seed(42);
+    let second = GearTable::from_seed(42);
+
+    // Repeated seeds share the cached table
+    assert!(Arc::ptr_eq(&first, &second));
+
+    // Different seeds produce different tables
+    assert_ne!(GearTable::from_seed(43).gear, first.gear);
+}
+
+#[test]
+fn test_derived_masks() {
+    let table = GearTable::from_seed(42);
+
+    // Every used slot holds a mask with exactly that many bits
+    for (bits, &mask) in table.masks.iter().enumerate() {
+        let expected = if bits < MASK_PADDING_SLOTS { 0 } else { bits };
+        assert_eq!(mask.count_ones() as usize, expected);
+    }
+}
@@ -0,0 +1,37 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+
+// --- Measurement Tests ---
+
+#[test]
+fn test_stops_at_end_of_input() {
+    let data: Vec<u8> = (0..200_000u32).map(|i| (i * 31 % 251) as u8).collect();
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let result = chunker
+        .measure_throughput(&data[..], Duration::from_secs(60))
+        .expect("Measurement failed");
+
+    // A short input ends the measurement early, having chunked all of it
+    assert_eq!(result.bytes, data.len() as u64);
+    assert_eq!(result.chunks, chunker.chunks(&data[..]).count() as u64);
+    assert!(result.elapsed < Duration::from_secs(60));
+    assert!(result.mb_per_sec() > 0.0 && result.chunks_per_sec() > 0.0);
+}
+
+#[test]
+fn test_stops_at_duration() {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    let result = chunker
+        .measure_throughput(io::repeat(0x5a), Duration::from_millis(20))
+        .expect("Measurement failed");
+
+    // An endless reader is only chunked for the given budget
+    assert!(result.elapsed >= Duration::from_millis(20));
+    assert!(result.bytes > 0);
+
+    #[cfg(target_arch = "x86_64")]
+    assert!(result.cycles_per_byte.is_some_and(|c| c > 0.0));
+}
@@ -0,0 +1,86 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+
+fn generate_noise(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<(u64, usize, u64)> = chunker
+        .chunks(&data[..])
+        .map(|c| c.unwrap())
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+
+    // The chunks match the reader-based iterator
+    let actual: Vec<(u64, usize, u64)> = chunker
+        .chunk_bytes(Bytes::from(data))
+        .map(|c| (c.offset, c.length, c.fp_hash))
+        .collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_shares_allocation() {
+    let data = Bytes::from(generate_noise(100_000));
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // Every chunk points into the original buffer
+    let base = data.as_ptr() as usize;
+    for chunk in chunker.chunk_bytes(data.clone()) {
+        assert_eq!(chunk.data.as_ptr() as usize, base + chunk.offset as usize);
+        assert_eq!(
+            chunk.data,
+            data.slice(chunk.offset as usize..)[..chunk.length]
+        );
+    }
+}
+
+#[test]
+fn test_size_hint() {
+    let data = Bytes::from(generate_noise(100_000));
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let count = chunker.chunk_bytes(data.clone()).count();
+
+    // The hint brackets the actual count
+    let (lower, upper) = chunker.chunk_bytes(data).size_hint();
+    assert!(lower <= count && count <= upper.unwrap());
+
+    // An empty buffer yields nothing
+    let mut iter = chunker.chunk_bytes(Bytes::new());
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_slice_matches_iterator() {
+    let data = generate_noise(300_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let expected: Vec<Chunk> = chunker.chunks(&data[..]).map(|c| c.unwrap()).collect();
+
+    // Borrowed chunks match the owned ones
+    let actual: Vec<ChunkRef<'_>> = chunker.chunk_slice(&data).collect();
+    assert_eq!(actual.len(), expected.len());
+    for (chunk, owned) in actual.iter().zip(&expected) {
+        assert_eq!(
+            (chunk.offset, chunk.length, chunk.fp_hash),
+            (owned.offset, owned.length, owned.fp_hash)
+        );
+        assert_eq!(chunk.data, &owned.data[..]);
+        assert_eq!(chunk.to_chunk().data, owned.data);
+    }
+
+    // Every chunk borrows from the input
+    for chunk in &actual {
+        let start = chunk.offset as usize;
+        assert!(std::ptr::eq(chunk.data, &data[start..start + chunk.length]));
+    }
+}
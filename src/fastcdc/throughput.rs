@@ -0,0 +1,102 @@
+use crate::fastcdc::FastCDC;
+use std::{
+    io::{self, Read},
+    time::{Duration, Instant},
+};
+
+/// Throughput measured by [`FastCDC::measure_throughput`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Throughput {
+    /// The number of bytes chunked.
+    pub bytes: u64,
+    /// The number of chunks produced.
+    pub chunks: u64,
+    /// The wall-clock time spent.
+    pub elapsed: Duration,
+    /// Timestamp-counter cycles per byte, where the CPU provides one (x86_64 only).
+    ///
+    /// The counter ticks at a constant reference rate, so this is an estimate that
+    /// drifts from core cycles under frequency scaling.
+    pub cycles_per_byte: Option<f64>,
+}
+
+impl Throughput {
+    /// Returns the throughput in megabytes (10^6 bytes) per second.
+    pub fn mb_per_sec(&self) -> f64 {
+        self.bytes as f64 / 1_000_000.0 / self.elapsed.as_secs_f64()
+    }
+
+    /// Returns the number of chunks produced per second.
+    pub fn chunks_per_sec(&self) -> f64 {
+        self.chunks as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+impl FastCDC {
+    ///
+    /// Measures chunking throughput of this configuration on the current machine.
+    ///
+    /// Chunks `reader` until `duration` has elapsed or the reader is exhausted, whichever
+    /// comes first. Reading is included in the measurement, so use an in-memory or
+    /// otherwise fast source to measure the chunker alone.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source of sample data (must implement `Read`).
+    /// * `duration`: The time budget for the measurement.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the first error raised by `reader`.
+    ///
+    pub fn measure_throughput<R: Read>(
+        &self,
+        reader: R,
+        duration: Duration,
+    ) -> io::Result<Throughput> {
+        let mut bytes = 0u64;
+        let mut chunks = 0u64;
+
+        let cycles_start = timestamp_counter();
+        let start = Instant::now();
+
+        for chunk in self.chunks(reader) {
+            bytes += chunk?.length as u64;
+            chunks += 1;
+
+            if start.elapsed() >= duration {
+                break;
+            }
+        }
+
+        let elapsed = start.elapsed();
+        let cycles_per_byte = match (cycles_start, timestamp_counter()) {
+            (Some(begin), Some(end)) if bytes > 0 => {
+                Some(end.wrapping_sub(begin) as f64 / bytes as f64)
+            }
+            _ => None,
+        };
+
+        Ok(Throughput {
+            bytes,
+            chunks,
+            elapsed,
+            cycles_per_byte,
+        })
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn timestamp_counter() -> Option<u64> {
+    // SAFETY: `rdtsc` is available on every x86_64 CPU and has no side effects.
+    Some(unsafe { std::arch::x86_64::_rdtsc() })
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn timestamp_counter() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+#[path = "tests/throughput_tests.rs"]
+mod tests;
@@ -0,0 +1,32 @@
+use std::{iter::Sum, ops::Add};
+
+///
+/// Buffer usage of a chunk iterator or stream.
+///
+/// Usage of several chunkers can be aggregated with `+` or `Iterator::sum`;
+/// the aggregated `peak` is then an upper bound, as the peaks may not coincide.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BufferUsage {
+    /// The number of bytes read but not yet emitted as chunks.
+    pub buffered: usize,
+    /// The largest value `buffered` has reached.
+    pub peak: usize,
+}
+
+impl Add for BufferUsage {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            buffered: self.buffered + rhs.buffered,
+            peak: self.peak + rhs.peak,
+        }
+    }
+}
+
+impl Sum for BufferUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
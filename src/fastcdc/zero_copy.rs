@@ -0,0 +1,155 @@
+use crate::fastcdc::{Chunk, ChunkRef, FastCDC, FastCDCDecoder};
+use bytes::Bytes;
+use std::borrow::Cow;
+
+impl FastCDC {
+    ///
+    /// Creates an iterator over the chunks of an in-memory buffer.
+    ///
+    /// Every chunk's data is a `slice()` of `data`, sharing its allocation, so nothing is
+    /// copied. Yields the same chunks as [`FastCDC::chunks`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `data`: The whole input.
+    ///
+    pub fn chunk_bytes(&self, data: Bytes) -> BytesChunks<'_> {
+        BytesChunks {
+            decoder: FastCDCDecoder::new(Cow::Borrowed(self)),
+            data,
+            pos: 0,
+        }
+    }
+
+    ///
+    /// Creates an iterator over the chunks of a borrowed slice, such as a memory-mapped file.
+    ///
+    /// Nothing is allocated or copied; every chunk borrows its data from `data`. Yields the
+    /// same chunks as [`FastCDC::chunks`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `data`: The whole input.
+    ///
+    pub fn chunk_slice<'a>(&self, data: &'a [u8]) -> SliceChunks<'_, 'a> {
+        SliceChunks {
+            decoder: FastCDCDecoder::new(Cow::Borrowed(self)),
+            data,
+        }
+    }
+}
+
+pub struct BytesChunks<'a> {
+    decoder: FastCDCDecoder<'a>,
+    data: Bytes,
+    pos: usize,
+}
+
+impl<'a> BytesChunks<'a> {
+    /// Chunks `data`, which starts at `offset` of the input, as if a chunk boundary had
+    /// been found there.
+    pub(super) fn at(chunker: &'a FastCDC, data: Bytes, offset: u64) -> Self {
+        let mut decoder = FastCDCDecoder::new(Cow::Borrowed(chunker));
+        decoder.processed = offset;
+
+        Self {
+            decoder,
+            data,
+            pos: 0,
+        }
+    }
+
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        self.decoder.stats()
+    }
+}
+
+impl<'a> Iterator for BytesChunks<'a> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The whole input is known, so every scan runs as if at the end of a stream.
+        let (cutpoint, fp_hash) = self.decoder.next_cutpoint(&self.data[self.pos..], true)?;
+        let data = self.data.slice(self.pos..self.pos + cutpoint);
+        self.pos += cutpoint;
+
+        Some(self.decoder.emit(data, fp_hash))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(&self.decoder.chunker, self.data.len() - self.pos)
+    }
+}
+
+pub struct SliceChunks<'c, 'a> {
+    decoder: FastCDCDecoder<'c>,
+    data: &'a [u8],
+}
+
+impl<'c, 'a> SliceChunks<'c, 'a> {
+    /// Chunks `data` from `offset` on, as if a chunk boundary had been found there.
+    pub(super) fn at(chunker: &'c FastCDC, data: &'a [u8], offset: usize) -> Self {
+        let mut decoder = FastCDCDecoder::new(Cow::Borrowed(chunker));
+        decoder.processed = offset as u64;
+
+        Self {
+            decoder,
+            data: &data[offset..],
+        }
+    }
+
+    /// Returns the cut-point counters collected so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &crate::fastcdc::ChunkerStats {
+        self.decoder.stats()
+    }
+}
+
+impl<'c, 'a> Iterator for SliceChunks<'c, 'a> {
+    type Item = ChunkRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (cutpoint, fp_hash) = self.decoder.next_cutpoint(self.data, true)?;
+        let (data, rest) = self.data.split_at(cutpoint);
+        self.data = rest;
+
+        let chunk = ChunkRef {
+            fp_hash,
+            data,
+            offset: self.decoder.processed,
+            length: cutpoint,
+        };
+        self.decoder.advance(cutpoint);
+
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(&self.decoder.chunker, self.data.len())
+    }
+}
+
+/// Bounds the number of chunks left in `remaining` bytes of a fully known input.
+fn size_hint(chunker: &FastCDC, remaining: usize) -> (usize, Option<usize>) {
+    if remaining == 0 {
+        return (0, Some(0));
+    }
+
+    // Every chunk but the last is at least `min_size`, less up to 3 bytes when
+    // cut points are moved off UTF-8 sequences.
+    let shortest = if chunker.utf8 {
+        chunker.min_size - 3
+    } else {
+        chunker.min_size
+    };
+    (
+        remaining.div_ceil(chunker.max_size),
+        Some(remaining / shortest + 1),
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/zero_copy_tests.rs"]
+mod tests;
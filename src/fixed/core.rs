@@ -0,0 +1,104 @@
+use crate::chunker::{ChunkIter, CutPoint};
+use crate::error::ClastError;
+use crate::fastcdc::MAX_CHUNK_SIZE_MAX;
+use std::io::Read;
+
+///
+/// A fixed-size chunker implementation.
+///
+/// Every chunk but the last is exactly `size` bytes long. No hash is computed, so chunks carry
+/// a zero `fp_hash`.
+///
+#[derive(Debug, Clone)]
+pub struct FixedSize {
+    size: usize,
+}
+
+impl FixedSize {
+    ///
+    /// Constructs a new `FixedSize` instance.
+    ///
+    /// ## Arguments
+    ///
+    /// * `size`: The size of every chunk but the last.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` is zero or greater than 16,777,216 (16 MB).
+    ///
+    pub fn new(size: usize) -> Self {
+        match Self::try_new(size) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a new `FixedSize` instance.
+    /// Unlike `new`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `size`: The size of every chunk but the last.
+    ///
+    /// ## Errors
+    ///
+    /// Returns `ClastError::InvalidSize` if `size` is zero or greater than 16,777,216 (16 MB).
+    ///
+    pub fn try_new(size: usize) -> Result<Self, ClastError> {
+        let bounds = 1..=MAX_CHUNK_SIZE_MAX;
+        if !bounds.contains(&size) {
+            return Err(ClastError::InvalidSize {
+                given: size,
+                bounds,
+            });
+        }
+
+        Ok(Self { size })
+    }
+
+    /// Returns the chunk size.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+}
+
+impl CutPoint for FixedSize {
+    fn max_size(&self) -> usize {
+        self.size
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        (0, source.len().min(self.size))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/core_tests.rs"]
+mod tests;
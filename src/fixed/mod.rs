@@ -0,0 +1,11 @@
+//!
+//! This module implements **fixed-size** chunking, which cuts the input every `size` bytes.
+//!
+//! Fixed-size boundaries shift with every insertion or deletion, so this chunker mainly serves
+//! as a baseline when measuring the deduplication gained by content-defined chunking, and as
+//! the block mode of applications that offer both.
+//!
+
+mod core;
+
+pub use core::FixedSize;
@@ -0,0 +1,65 @@
+use super::*;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::generate_noise_data;
+use std::collections::HashSet;
+use std::io;
+
+// --- Constructor Tests ---
+
+#[test]
+fn test_invalid_size() {
+    // Zero and sizes beyond the buffering limit are rejected
+    assert!(matches!(
+        FixedSize::try_new(0),
+        Err(ClastError::InvalidSize { given: 0, .. })
+    ));
+    assert!(FixedSize::try_new(16_777_217).is_err());
+    assert_eq!(FixedSize::new(1).size(), 1);
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_fixed_boundaries() {
+    let data = generate_noise_data(10_000);
+    let chunker = FixedSize::new(4_096);
+
+    let spans = chunker
+        .chunks(&data[..])
+        .map(|c| c.map(|c| (c.offset, c.length, c.fp_hash)))
+        .collect::<io::Result<Vec<_>>>()
+        .unwrap();
+
+    // Every chunk is full-sized except the tail
+    assert_eq!(spans, [(0, 4_096, 0), (4_096, 4_096, 0), (8_192, 1_808, 0)]);
+
+    // Empty input should not produce any chunks
+    assert!(chunker.chunks(&[][..]).next().is_none());
+}
+
+#[test]
+fn test_baseline_loses_shifted_duplicates() {
+    let data = generate_noise_data(400_000);
+    let mut edited = b"inserted prefix".to_vec();
+    edited.extend_from_slice(&data);
+
+    let contents = |chunks: Vec<crate::fastcdc::Chunk>| -> HashSet<Vec<u8>> {
+        chunks.into_iter().map(|c| c.data.to_vec()).collect()
+    };
+    let fixed = FixedSize::new(8_192);
+    let cdc = FastCDC::new(4_069, 8_192, 16_384, Normal::Level2);
+
+    let fixed_original = contents(fixed.chunks(&data[..]).collect::<io::Result<_>>().unwrap());
+    let fixed_edited = contents(
+        fixed
+            .chunks(&edited[..])
+            .collect::<io::Result<_>>()
+            .unwrap(),
+    );
+    let cdc_original = contents(cdc.chunks(&data[..]).collect::<io::Result<_>>().unwrap());
+    let cdc_edited = contents(cdc.chunks(&edited[..]).collect::<io::Result<_>>().unwrap());
+
+    // A prefix insertion shifts every fixed block, while content-defined chunks survive
+    assert_eq!(fixed_original.intersection(&fixed_edited).count(), 0);
+    assert!(cdc_original.intersection(&cdc_edited).count() > cdc_original.len() / 2);
+}
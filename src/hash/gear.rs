@@ -0,0 +1,91 @@
+use crate::fastcdc::GEAR;
+use crate::hash::RollingHash;
+
+///
+/// The Gear rolling hash used by FastCDC to find cut points.
+///
+/// Each byte shifts the hash left by one bit and adds the byte's entry in the Gear table,
+/// so the hash depends only on the last 64 bytes rolled in. It uses the same table as
+/// [`FastCDC`](crate::fastcdc::FastCDC), which lets custom boundary conditions reproduce
+/// its hash values.
+///
+/// ## Example
+///
+/// ```
+/// use clast::hash::GearHash;
+///
+/// let data = [7u8; 100];
+/// let mut whole = GearHash::new();
+/// whole.update(&data);
+///
+/// let mut tail = GearHash::new();
+/// tail.update(&data[36..]);
+/// assert_eq!(whole.digest(), tail.digest());
+/// ```
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GearHash {
+    hash: u64,
+}
+
+impl GearHash {
+    /// Creates a hash with an empty window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the Gear table, one random 64-bit value per byte.
+    pub fn table() -> &'static [u64; 256] {
+        &GEAR
+    }
+
+    /// Appends one byte to the window.
+    #[inline]
+    pub fn roll(&mut self, byte: u8) {
+        self.hash = (self.hash << 1).wrapping_add(GEAR[byte as usize]);
+    }
+
+    /// Appends every byte of `data` to the window.
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.roll(byte);
+        }
+    }
+
+    /// Clears the window.
+    pub fn reset(&mut self) {
+        self.hash = 0;
+    }
+
+    /// Returns the hash of the current window.
+    #[inline]
+    pub fn digest(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl RollingHash for GearHash {
+    fn window(&self) -> usize {
+        64
+    }
+
+    fn reset(&mut self) {
+        GearHash::reset(self);
+    }
+
+    fn push(&mut self, byte: u8) {
+        GearHash::roll(self, byte);
+    }
+
+    fn roll(&mut self, _out: u8, inp: u8) {
+        GearHash::roll(self, inp);
+    }
+
+    fn digest(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/gear_tests.rs"]
+mod tests;
@@ -0,0 +1,15 @@
+//!
+//! Rolling hashes and checksums.
+//!
+
+#[cfg(feature = "fastcdc")]
+mod gear;
+mod rolling;
+mod rsync;
+mod wide;
+
+#[cfg(feature = "fastcdc")]
+pub use gear::GearHash;
+pub use rolling::RollingHash;
+pub use rsync::RsyncChecksum;
+pub use wide::fingerprint128;
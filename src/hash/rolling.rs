@@ -0,0 +1,24 @@
+///
+/// A rolling hash over a fixed window of trailing bytes, usable as the boundary hash of
+/// [`FastCDC::with_rolling_hash`](crate::fastcdc::FastCDC::with_rolling_hash).
+///
+/// The window is filled with [`RollingHash::push`] and then slid with [`RollingHash::roll`],
+/// which is given the byte leaving it. Hashes that forget old bytes on their own (like
+/// [`GearHash`](crate::hash::GearHash)) can ignore `out`.
+///
+pub trait RollingHash {
+    /// Returns the number of trailing bytes the hash covers.
+    fn window(&self) -> usize;
+
+    /// Clears the window.
+    fn reset(&mut self);
+
+    /// Appends a byte while the window is not yet full.
+    fn push(&mut self, byte: u8);
+
+    /// Slides the full window by one byte, removing `out` and appending `inp`.
+    fn roll(&mut self, out: u8, inp: u8);
+
+    /// Returns the hash of the current window.
+    fn digest(&self) -> u64;
+}
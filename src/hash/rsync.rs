@@ -0,0 +1,67 @@
+///
+/// The rsync weak rolling checksum (a variant of Adler-32 without the modulus).
+///
+/// Values match rsync's `get_checksum1`, including its treatment of input bytes as
+/// signed characters, so signatures computed here can be consumed by
+/// rsync-algorithm-based tooling.
+///
+/// ## Example
+///
+/// ```
+/// use clast::hash::RsyncChecksum;
+///
+/// let data = b"abcdef";
+/// let mut rolling = RsyncChecksum::new(&data[..4]);
+/// rolling.roll(data[0], data[4]);
+/// assert_eq!(rolling.digest(), RsyncChecksum::new(&data[1..5]).digest());
+/// ```
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RsyncChecksum {
+    s1: u32,
+    s2: u32,
+    len: u32,
+}
+
+#[inline]
+fn signed(byte: u8) -> u32 {
+    byte as i8 as i32 as u32
+}
+
+impl RsyncChecksum {
+    /// Computes the checksum of a window.
+    pub fn new(window: &[u8]) -> Self {
+        let mut checksum = Self::default();
+        checksum.update(window);
+        checksum
+    }
+
+    /// Extends the window with `data`.
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.s1 = self.s1.wrapping_add(signed(byte));
+            self.s2 = self.s2.wrapping_add(self.s1);
+        }
+        self.len = self.len.wrapping_add(data.len() as u32);
+    }
+
+    /// Slides the window by one byte, removing `out` from the front and appending `inp`.
+    #[inline]
+    pub fn roll(&mut self, out: u8, inp: u8) {
+        self.s1 = self.s1.wrapping_sub(signed(out)).wrapping_add(signed(inp));
+        self.s2 = self
+            .s2
+            .wrapping_sub(self.len.wrapping_mul(signed(out)))
+            .wrapping_add(self.s1);
+    }
+
+    /// Returns the 32-bit checksum of the current window.
+    #[inline]
+    pub fn digest(&self) -> u32 {
+        (self.s1 & 0xffff).wrapping_add(self.s2 << 16)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/rsync_tests.rs"]
+mod tests;
@@ -0,0 +1,46 @@
+use super::*;
+use crate::fastcdc::{FastCDC, Normal};
+
+fn generate_noise_data(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+// --- Rolling Tests ---
+
+#[test]
+fn test_window_of_64_bytes() {
+    let data = generate_noise_data(1_000);
+
+    let mut whole = GearHash::new();
+    whole.update(&data);
+    let mut tail = GearHash::new();
+    tail.update(&data[data.len() - 64..]);
+
+    // Bytes older than 64 positions have been shifted out
+    assert_eq!(whole.digest(), tail.digest());
+
+    // Resetting clears the window
+    whole.reset();
+    assert_eq!(whole, GearHash::new());
+}
+
+#[test]
+fn test_matches_fastcdc() {
+    let data = generate_noise_data(200_000);
+    let chunker = FastCDC::new(4_069, 8_192, 16_384, Normal::Level2).with_one_byte_rolling(true);
+
+    // A content-defined cut at `end` follows the hash of the bytes up to and including `data[end]`
+    for chunk in chunker.chunks(&data[..]) {
+        let chunk = chunk.unwrap();
+        let end = chunk.offset as usize + chunk.length;
+        if chunk.length == 16_384 || end >= data.len() {
+            continue;
+        }
+
+        let mut gear = GearHash::new();
+        gear.update(&data[end - 63..=end]);
+        assert_eq!(gear.digest(), chunk.fp_hash);
+    }
+}
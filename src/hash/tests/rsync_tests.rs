@@ -0,0 +1,71 @@
+use super::*;
+
+/// A direct transcription of rsync's `get_checksum1` (with `CHAR_OFFSET == 0`).
+fn reference_checksum(buf: &[u8]) -> u32 {
+    let buf = buf.iter().map(|&b| b as i8 as i32).collect::<Vec<_>>();
+    let len = buf.len();
+    let (mut s1, mut s2) = (0u32, 0u32);
+    let mut i = 0;
+
+    while i + 4 < len {
+        s2 = s2.wrapping_add(
+            (4i32.wrapping_mul((s1 as i32).wrapping_add(buf[i])))
+                .wrapping_add(3 * buf[i + 1])
+                .wrapping_add(2 * buf[i + 2])
+                .wrapping_add(buf[i + 3]) as u32,
+        );
+        s1 = s1.wrapping_add((buf[i] + buf[i + 1] + buf[i + 2] + buf[i + 3]) as u32);
+        i += 4;
+    }
+    while i < len {
+        s1 = s1.wrapping_add(buf[i] as u32);
+        s2 = s2.wrapping_add(s1);
+        i += 1;
+    }
+
+    (s1 & 0xffff).wrapping_add(s2 << 16)
+}
+
+fn sample_data(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+// --- Compatibility Tests ---
+
+#[test]
+fn test_matches_rsync_reference() {
+    let data = sample_data(4_099);
+
+    // High bytes must be treated as signed characters, as rsync does
+    for len in [0, 1, 3, 4, 5, 700, 4_099] {
+        assert_eq!(
+            RsyncChecksum::new(&data[..len]).digest(),
+            reference_checksum(&data[..len]),
+            "Checksum differs from rsync for length {}",
+            len
+        );
+    }
+}
+
+// --- Rolling Tests ---
+
+#[test]
+fn test_rolling_matches_recompute() {
+    let data = sample_data(2_000);
+    let window = 700;
+
+    let mut rolling = RsyncChecksum::new(&data[..window]);
+
+    // Sliding the window must equal computing the checksum from scratch
+    for start in 1..data.len() - window {
+        rolling.roll(data[start - 1], data[start + window - 1]);
+        assert_eq!(
+            rolling.digest(),
+            RsyncChecksum::new(&data[start..start + window]).digest(),
+            "Rolling checksum diverged at offset {}",
+            start
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use super::*;
+use std::collections::HashSet;
+
+// --- Fingerprint Tests ---
+
+#[test]
+fn test_deterministic() {
+    let data = b"The quick brown fox jumps over the lazy dog";
+
+    // Equal inputs give equal fingerprints
+    let copy = *data;
+    assert_eq!(fingerprint128(data), fingerprint128(&copy));
+
+    // Both halves are populated
+    let fp = fingerprint128(data);
+    assert_ne!(fp >> 64, 0);
+    assert_ne!(fp as u64, 0);
+}
+
+#[test]
+fn test_distinguishes_inputs() {
+    // Trailing zeros and single-bit flips change the fingerprint
+    assert_ne!(fingerprint128(b"abc"), fingerprint128(b"abc\0"));
+    assert_ne!(fingerprint128(b""), fingerprint128(b"\0"));
+    assert_ne!(fingerprint128(&[0u8; 16]), fingerprint128(&[0u8; 24]));
+
+    let mut data = vec![0u8; 4_096];
+    let base = fingerprint128(&data);
+    let mut seen = HashSet::new();
+    for bit in 0..data.len() * 8 {
+        data[bit / 8] ^= 1 << (bit % 8);
+        let fp = fingerprint128(&data);
+        assert_ne!(fp, base);
+        seen.insert(fp);
+        data[bit / 8] ^= 1 << (bit % 8);
+    }
+    assert_eq!(seen.len(), data.len() * 8);
+}
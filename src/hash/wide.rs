@@ -0,0 +1,61 @@
+/// Multipliers of the two lanes, odd and with well-mixed bits.
+const LANE_KEYS: [u64; 2] = [0x9e37_79b9_7f4a_7c15, 0xc2b2_ae3d_27d4_eb4f];
+/// Initial states of the two lanes.
+const LANE_SEEDS: [u64; 2] = [0x243f_6a88_85a3_08d3, 0x1319_8a2e_0370_7344];
+
+/// Multiplies into 128 bits and folds the halves together.
+#[inline]
+fn fold_mul(a: u64, b: u64) -> u64 {
+    let product = (a as u128) * (b as u128);
+    (product as u64) ^ (product >> 64) as u64
+}
+
+/// The MurmurHash3 finalizer, so every input bit affects every output bit.
+#[inline]
+fn avalanche(mut h: u64) -> u64 {
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^ (h >> 33)
+}
+
+///
+/// Computes a 128-bit fingerprint of the whole of `data`.
+///
+/// Two lanes with independent keys each hash every 8-byte word of the input, and their results
+/// form the high and low halves. This is fast and collides far less often than a 64-bit key,
+/// but is not cryptographic: use a [`Sha256`](crate::digest::Sha256) digest where an adversary
+/// may craft collisions.
+///
+/// ## Arguments
+///
+/// * `data`: The bytes to fingerprint.
+///
+pub fn fingerprint128(data: &[u8]) -> u128 {
+    let mut lanes = LANE_SEEDS;
+
+    let mut words = data.chunks_exact(8);
+    for word in &mut words {
+        let word = u64::from_le_bytes(word.try_into().unwrap());
+        for (lane, key) in lanes.iter_mut().zip(LANE_KEYS) {
+            *lane = fold_mul(*lane ^ word, key);
+        }
+    }
+
+    // The length disambiguates inputs that differ only by trailing zero bytes.
+    let mut tail = [0u8; 8];
+    tail[..words.remainder().len()].copy_from_slice(words.remainder());
+    let tail = u64::from_le_bytes(tail);
+
+    let [high, low] = [0, 1].map(|i| {
+        let lane = fold_mul(lanes[i] ^ tail, LANE_KEYS[i]);
+        avalanche(lane ^ (data.len() as u64).wrapping_mul(LANE_KEYS[1 - i]))
+    });
+
+    ((high as u128) << 64) | low as u128
+}
+
+#[cfg(test)]
+#[path = "tests/wide_tests.rs"]
+mod tests;
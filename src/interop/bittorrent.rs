@@ -0,0 +1,155 @@
+use crate::digest::Sha256;
+
+/// The BitTorrent v2 merkle leaf size.
+pub const BTV2_BLOCK_SIZE: usize = 16_384; // 16 KB
+
+///
+/// Builds the BitTorrent v2 (BEP 52) merkle tree of a file from data fed in stream order.
+///
+/// Leaves are SHA-256 digests of 16 KiB blocks. Leaves beyond the end of the file are
+/// treated as zero, and the tree is padded to a power of two as the BEP specifies.
+///
+pub struct PieceLayerBuilder {
+    piece_length: usize,
+    block: Vec<u8>,
+    leaves: Vec<[u8; 32]>,
+    length: u64,
+}
+
+impl PieceLayerBuilder {
+    ///
+    /// Constructs a new `PieceLayerBuilder`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `piece_length`: The torrent piece length in bytes.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `piece_length` is not a power of two of at least 16 KiB.
+    ///
+    pub fn new(piece_length: usize) -> Self {
+        assert!(
+            piece_length.is_power_of_two() && piece_length >= BTV2_BLOCK_SIZE,
+            "piece length must be a power of two of at least 16 KiB"
+        );
+
+        Self {
+            piece_length,
+            block: Vec::with_capacity(BTV2_BLOCK_SIZE),
+            leaves: Vec::new(),
+            length: 0,
+        }
+    }
+
+    /// Feeds the next bytes of the file.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+
+        while !data.is_empty() {
+            let take = (BTV2_BLOCK_SIZE - self.block.len()).min(data.len());
+            self.block.extend_from_slice(&data[..take]);
+            data = &data[take..];
+
+            if self.block.len() == BTV2_BLOCK_SIZE {
+                self.leaves.push(Sha256::digest(&self.block));
+                self.block.clear();
+            }
+        }
+    }
+
+    /// Completes the tree, returning the pieces root and the piece layer.
+    pub fn finish(mut self) -> PieceLayers {
+        // The final partial block is hashed as-is, without padding.
+        if !self.block.is_empty() {
+            self.leaves.push(Sha256::digest(&self.block));
+        }
+
+        if self.leaves.is_empty() {
+            return PieceLayers {
+                length: 0,
+                pieces_root: None,
+                piece_layer: Vec::new(),
+            };
+        }
+
+        let piece_level = (self.piece_length / BTV2_BLOCK_SIZE).trailing_zeros();
+        let has_piece_layer = self.length > self.piece_length as u64;
+
+        let mut layer = self.leaves;
+        let mut pad = [0u8; 32];
+        let mut level = 0;
+        let mut piece_layer = Vec::new();
+
+        loop {
+            if level == piece_level && has_piece_layer {
+                piece_layer = layer.clone();
+            }
+            // A file larger than one piece always reaches the piece level before the root.
+            if layer.len() == 1 {
+                break;
+            }
+
+            if layer.len() % 2 == 1 {
+                layer.push(pad);
+            }
+            layer = layer
+                .chunks_exact(2)
+                .map(|p| hash_pair(&p[0], &p[1]))
+                .collect();
+            pad = hash_pair(&pad, &pad);
+            level += 1;
+        }
+
+        PieceLayers {
+            length: self.length,
+            pieces_root: Some(layer[0]),
+            piece_layer,
+        }
+    }
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize()
+}
+
+/// The BitTorrent v2 hashes of a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceLayers {
+    length: u64,
+    pieces_root: Option<[u8; 32]>,
+    piece_layer: Vec<[u8; 32]>,
+}
+
+impl PieceLayers {
+    /// Returns the length of the file.
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns the `pieces root` of the file tree entry, or `None` for an empty file.
+    pub fn pieces_root(&self) -> Option<[u8; 32]> {
+        self.pieces_root
+    }
+
+    ///
+    /// Returns the hashes of the piece layer, one per piece.
+    ///
+    /// Empty for files no larger than a single piece, which the BEP omits from `piece layers`.
+    ///
+    pub fn piece_layer(&self) -> &[[u8; 32]] {
+        &self.piece_layer
+    }
+
+    /// Returns the piece layer concatenated as stored in the `piece layers` dictionary.
+    pub fn piece_layer_bytes(&self) -> Vec<u8> {
+        self.piece_layer.concat()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/bittorrent_tests.rs"]
+mod tests;
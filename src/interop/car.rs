@@ -0,0 +1,258 @@
+use super::Cid;
+use crate::manifest::{Manifest, ManifestEntry};
+use std::collections::HashSet;
+use std::io::{self, Read, Write};
+
+/// The fixed bytes that open every CARv2 file.
+pub const CARV2_PRAGMA: [u8; 11] = [
+    0x0a, 0xa1, 0x67, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x02,
+];
+
+/// Length of the CARv2 header following the pragma.
+const CARV2_HEADER_LEN: usize = 40;
+
+/// The most links in one dag-pb node, as in the default IPFS importer.
+const LINKS_PER_NODE: usize = 174;
+
+/// The UnixFS `Data.Type` of a file node.
+const UNIXFS_FILE: u64 = 2;
+
+///
+/// Exports a manifest and the chunks it describes as a CARv2 file.
+///
+/// The first root is a UnixFS file: a balanced tree of dag-pb nodes, with at most 174
+/// links each, whose leaves are the chunks in order as raw blocks. After `ipfs dag import`,
+/// `ipfs cat` on it reproduces the source. The binary manifest is stored as a raw block and
+/// listed as the second root, so it is pinned alongside the file. Every distinct block is
+/// written once. The archive carries no index; IPFS tooling rebuilds it on import.
+///
+/// ## Arguments
+///
+/// * `manifest`: The manifest describing `reader`.
+/// * `reader`: The chunked source (must implement `Read`).
+/// * `writer`: The destination (must implement `Write`).
+///
+/// ## Errors
+///
+/// Returns an `std::io::Error` with `ErrorKind::InvalidData` if `reader` no longer matches
+/// the manifest, or the error raised while reading or writing. The output is incomplete
+/// in that case.
+///
+pub fn export_car<R: Read, W: Write>(
+    manifest: &Manifest,
+    reader: R,
+    mut writer: W,
+) -> io::Result<()> {
+    let mut manifest_block = Vec::new();
+    manifest.write_to(&mut manifest_block)?;
+    let manifest_cid = Cid::raw(&manifest_block);
+
+    let (root, nodes) = file_dag(manifest.entries());
+    let header = carv1_header(&[root, manifest_cid]);
+
+    // The tree is built from the manifest, so every block, and the payload size, is known
+    // before the chunks are read and no seeking is needed.
+    let mut seen = HashSet::new();
+    let mut data_size = varint_len(header.len() as u64) + header.len() as u64;
+    for (cid, block) in &nodes {
+        if seen.insert(*cid) {
+            data_size += section_len(block.len());
+        }
+    }
+    data_size += section_len(manifest_block.len());
+    for entry in manifest.entries() {
+        if seen.insert(Cid::from_digest(entry.digest)) {
+            data_size += section_len(entry.length);
+        }
+    }
+
+    writer.write_all(&CARV2_PRAGMA)?;
+    writer.write_all(&[0u8; 16])?; // characteristics
+    writer.write_all(&((CARV2_PRAGMA.len() + CARV2_HEADER_LEN) as u64).to_le_bytes())?;
+    writer.write_all(&data_size.to_le_bytes())?;
+    writer.write_all(&0u64.to_le_bytes())?; // no index
+
+    write_varint(&mut writer, header.len() as u64)?;
+    writer.write_all(&header)?;
+
+    seen.clear();
+    for (cid, block) in &nodes {
+        if seen.insert(*cid) {
+            write_section(&mut writer, cid, block)?;
+        }
+    }
+    write_section(&mut writer, &manifest_cid, &manifest_block)?;
+
+    let chunker = manifest.chunker()?;
+    let mut entries = manifest.entries().iter();
+
+    for chunk in chunker.chunks(reader) {
+        let chunk = chunk?;
+        let actual = ManifestEntry::from_chunk(&chunk);
+
+        if entries.next() != Some(&actual) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "chunk at offset {} does not match the manifest",
+                    actual.offset
+                ),
+            ));
+        }
+        let cid = Cid::from_digest(actual.digest);
+        if seen.insert(cid) {
+            write_section(&mut writer, &cid, &chunk.data)?;
+        }
+    }
+
+    if entries.next().is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "data ends before the end of the manifest",
+        ));
+    }
+
+    writer.flush()
+}
+
+/// A link to a chunk or file node.
+struct Link {
+    cid: Cid,
+    /// The file bytes below the link.
+    file_size: u64,
+    /// The cumulative size of every block below the link, as dag-pb `Tsize` records it.
+    tsize: u64,
+}
+
+///
+/// Builds the balanced UnixFS file tree over the chunks of `entries`.
+///
+/// Returns the root CID and the encoded nodes, root first.
+///
+fn file_dag(entries: &[ManifestEntry]) -> (Cid, Vec<(Cid, Vec<u8>)>) {
+    let mut level: Vec<Link> = entries
+        .iter()
+        .map(|entry| Link {
+            cid: Cid::from_digest(entry.digest),
+            file_size: entry.length as u64,
+            tsize: entry.length as u64,
+        })
+        .collect();
+    let mut nodes = Vec::new();
+
+    loop {
+        let mut parents: Vec<Link> = level
+            .chunks(LINKS_PER_NODE)
+            .map(|links| file_node(links, &mut nodes))
+            .collect();
+        if parents.is_empty() {
+            parents.push(file_node(&[], &mut nodes));
+        }
+
+        if parents.len() == 1 {
+            nodes.reverse();
+            return (parents[0].cid, nodes);
+        }
+        level = parents;
+    }
+}
+
+/// Encodes a UnixFS file node linking `links` in order, appending it to `nodes`.
+fn file_node(links: &[Link], nodes: &mut Vec<(Cid, Vec<u8>)>) -> Link {
+    let file_size = links.iter().map(|link| link.file_size).sum();
+
+    // UnixFS `Data`: Type, filesize, and one blocksize per link.
+    let mut data = Vec::new();
+    write_pb_varint(&mut data, 1, UNIXFS_FILE);
+    write_pb_varint(&mut data, 3, file_size);
+    for link in links {
+        write_pb_varint(&mut data, 4, link.file_size);
+    }
+
+    // dag-pb `PBNode`: the links (field 2) precede the data (field 1) in canonical form.
+    let mut node = Vec::new();
+    for link in links {
+        let mut pb_link = Vec::new();
+        write_pb_bytes(&mut pb_link, 1, &link.cid.to_bytes());
+        write_pb_varint(&mut pb_link, 3, link.tsize);
+        write_pb_bytes(&mut node, 2, &pb_link);
+    }
+    write_pb_bytes(&mut node, 1, &data);
+
+    let link = Link {
+        cid: Cid::dag_pb(&node),
+        file_size,
+        tsize: node.len() as u64 + links.iter().map(|link| link.tsize).sum::<u64>(),
+    };
+    nodes.push((link.cid, node));
+    link
+}
+
+fn write_pb_varint(buf: &mut Vec<u8>, field: u64, value: u64) {
+    push_varint(buf, field << 3);
+    push_varint(buf, value);
+}
+
+fn write_pb_bytes(buf: &mut Vec<u8>, field: u64, value: &[u8]) {
+    push_varint(buf, (field << 3) | 2);
+    push_varint(buf, value.len() as u64);
+    buf.extend_from_slice(value);
+}
+
+/// Encodes the DAG-CBOR CARv1 header `{"roots": roots, "version": 1}`.
+fn carv1_header(roots: &[Cid]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(roots.len() * 40 + 16);
+    header.push(0xa2); // map(2), keys in canonical (length-first) order
+    header.push(0x65);
+    header.extend_from_slice(b"roots");
+    header.push(0x80 | roots.len() as u8); // array(n), n < 24
+    for root in roots {
+        let cid = root.to_bytes();
+        header.extend_from_slice(&[0xd8, 0x2a]); // tag(42): CID
+        header.extend_from_slice(&[0x58, cid.len() as u8 + 1, 0x00]); // bytes, identity multibase
+        header.extend_from_slice(&cid);
+    }
+    header.push(0x67);
+    header.extend_from_slice(b"version");
+    header.push(0x01);
+    header
+}
+
+fn section_len(data_len: usize) -> u64 {
+    let len = (36 + data_len) as u64;
+    varint_len(len) + len
+}
+
+fn write_section<W: Write>(writer: &mut W, cid: &Cid, data: &[u8]) -> io::Result<()> {
+    let cid = cid.to_bytes();
+    write_varint(writer, (cid.len() + data.len()) as u64)?;
+    writer.write_all(&cid)?;
+    writer.write_all(data)
+}
+
+fn varint_len(mut value: u64) -> u64 {
+    let mut len = 1;
+    while value >= 0x80 {
+        value >>= 7;
+        len += 1;
+    }
+    len
+}
+
+fn write_varint<W: Write>(writer: &mut W, value: u64) -> io::Result<()> {
+    let mut buf = Vec::with_capacity(10);
+    push_varint(&mut buf, value);
+    writer.write_all(&buf)
+}
+
+fn push_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+#[cfg(test)]
+#[path = "tests/car_tests.rs"]
+mod tests;
@@ -0,0 +1,232 @@
+use std::io::{self, Read, Write};
+
+/// Object type of the index header.
+pub const CA_FORMAT_INDEX: u64 = 0x96824d9c7b129ff9;
+/// Object type of the chunk table.
+pub const CA_FORMAT_TABLE: u64 = 0xe75b9e112f17417d;
+/// Marker closing the chunk table.
+pub const CA_FORMAT_TABLE_TAIL_MARKER: u64 = 0x4b4f050e5549ecd1;
+/// Feature flag selecting SHA-512/256 chunk IDs instead of SHA-256.
+pub const CA_FORMAT_SHA512_256: u64 = 0x2000000000000000;
+
+const INDEX_HEADER_LEN: u64 = 48;
+
+/// A chunk listed in a casync index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CasyncChunk {
+    /// The absolute offset of the chunk in the blob.
+    pub offset: u64,
+    /// The length of the chunk in bytes.
+    pub length: u64,
+    /// The chunk ID: the digest of the uncompressed chunk data.
+    pub id: [u8; 32],
+}
+
+///
+/// A casync/desync index file (`.caibx` for blobs, `.caidx` for archives).
+///
+/// Both share the same layout: a header carrying the feature flags and chunker sizes,
+/// followed by a table of chunk end offsets and IDs. Manifests from this crate use
+/// SHA-256 IDs, so their feature flags leave `CA_FORMAT_SHA512_256` unset.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CasyncIndex {
+    feature_flags: u64,
+    min_size: u64,
+    avg_size: u64,
+    max_size: u64,
+    chunks: Vec<CasyncChunk>,
+}
+
+impl CasyncIndex {
+    ///
+    /// Constructs an empty index.
+    ///
+    /// ## Arguments
+    ///
+    /// * `feature_flags`: The casync feature flags (`0` for a SHA-256 blob index).
+    /// * `min_size`: The minimum chunk size.
+    /// * `avg_size`: The average chunk size.
+    /// * `max_size`: The maximum chunk size.
+    ///
+    pub fn new(feature_flags: u64, min_size: u64, avg_size: u64, max_size: u64) -> Self {
+        Self {
+            feature_flags,
+            min_size,
+            avg_size,
+            max_size,
+            chunks: Vec::new(),
+        }
+    }
+
+    ///
+    /// Converts a manifest into a blob index.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if the manifest's chunker
+    /// parameters are invalid.
+    ///
+    #[cfg(feature = "fastcdc")]
+    pub fn from_manifest(manifest: &crate::manifest::Manifest) -> io::Result<Self> {
+        let chunker = manifest.chunker_params()?;
+        let mut index = Self::new(
+            0,
+            chunker.min_size() as u64,
+            chunker.avg_size() as u64,
+            chunker.max_size() as u64,
+        );
+
+        for entry in manifest.entries() {
+            index.push(entry.length as u64, entry.digest);
+        }
+
+        Ok(index)
+    }
+
+    /// Appends a chunk directly after the previous one.
+    pub fn push(&mut self, length: u64, id: [u8; 32]) {
+        let offset = self.total_len();
+        self.chunks.push(CasyncChunk { offset, length, id });
+    }
+
+    /// Returns the feature flags.
+    pub fn feature_flags(&self) -> u64 {
+        self.feature_flags
+    }
+
+    /// Returns whether chunk IDs are SHA-512/256 digests rather than SHA-256.
+    pub fn uses_sha512_256(&self) -> bool {
+        self.feature_flags & CA_FORMAT_SHA512_256 != 0
+    }
+
+    /// Returns the chunker sizes as `(min, avg, max)`.
+    pub fn chunk_sizes(&self) -> (u64, u64, u64) {
+        (self.min_size, self.avg_size, self.max_size)
+    }
+
+    /// Returns the listed chunks.
+    pub fn chunks(&self) -> &[CasyncChunk] {
+        &self.chunks
+    }
+
+    /// Returns the total length of the indexed blob.
+    pub fn total_len(&self) -> u64 {
+        self.chunks.last().map_or(0, |c| c.offset + c.length)
+    }
+
+    ///
+    /// Writes the index file.
+    ///
+    /// ## Arguments
+    ///
+    /// * `writer`: The destination (must implement `Write`).
+    ///
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for value in [
+            INDEX_HEADER_LEN,
+            CA_FORMAT_INDEX,
+            self.feature_flags,
+            self.min_size,
+            self.avg_size,
+            self.max_size,
+        ] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        // The table size is unknown when streaming, so casync always writes `u64::MAX`.
+        writer.write_all(&u64::MAX.to_le_bytes())?;
+        writer.write_all(&CA_FORMAT_TABLE.to_le_bytes())?;
+
+        for chunk in &self.chunks {
+            writer.write_all(&(chunk.offset + chunk.length).to_le_bytes())?;
+            writer.write_all(&chunk.id)?;
+        }
+
+        let table_len = 16 + 40 * self.chunks.len() as u64 + 40;
+        for value in [
+            0,
+            0,
+            INDEX_HEADER_LEN,
+            table_len,
+            CA_FORMAT_TABLE_TAIL_MARKER,
+        ] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    ///
+    /// Reads an index file written by casync, desync, or [`CasyncIndex::write_to`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source (must implement `Read`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidData` if the data is not a valid index.
+    ///
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Self> {
+        if read_u64(&mut reader)? != INDEX_HEADER_LEN || read_u64(&mut reader)? != CA_FORMAT_INDEX {
+            return Err(invalid("not a casync index"));
+        }
+
+        let feature_flags = read_u64(&mut reader)?;
+        let min_size = read_u64(&mut reader)?;
+        let avg_size = read_u64(&mut reader)?;
+        let max_size = read_u64(&mut reader)?;
+
+        if read_u64(&mut reader)? != u64::MAX || read_u64(&mut reader)? != CA_FORMAT_TABLE {
+            return Err(invalid("missing chunk table"));
+        }
+
+        let mut index = Self::new(feature_flags, min_size, avg_size, max_size);
+
+        loop {
+            let end = read_u64(&mut reader)?;
+            let mut id = [0u8; 32];
+            reader.read_exact(&mut id)?;
+
+            // The tail starts with two zero words where an item's offset would be.
+            if end == 0 {
+                let tail = [&id[..8], &id[8..16], &id[16..24], &id[24..32]]
+                    .map(|b| u64::from_le_bytes(b.try_into().unwrap()));
+                if tail[0] != 0
+                    || tail[1] != INDEX_HEADER_LEN
+                    || tail[3] != CA_FORMAT_TABLE_TAIL_MARKER
+                {
+                    return Err(invalid("corrupt chunk table tail"));
+                }
+                break;
+            }
+
+            let offset = index.total_len();
+            if end <= offset {
+                return Err(invalid("chunk offsets must be increasing"));
+            }
+            index.chunks.push(CasyncChunk {
+                offset,
+                length: end - offset,
+                id,
+            });
+        }
+
+        Ok(index)
+    }
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+#[path = "tests/casync_tests.rs"]
+mod tests;
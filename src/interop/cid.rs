@@ -0,0 +1,105 @@
+use crate::digest::Sha256;
+use std::fmt;
+
+/// The multicodec code of raw binary blocks.
+pub const RAW_CODEC: u8 = 0x55;
+/// The multicodec code of dag-pb (UnixFS) nodes.
+pub const DAG_PB_CODEC: u8 = 0x70;
+/// The multihash code of SHA2-256.
+pub const SHA2_256_CODE: u8 = 0x12;
+
+/// Base32 (RFC 4648, lowercase, unpadded) alphabet used by the `b` multibase prefix.
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+///
+/// An IPFS CIDv1 identifying a raw block or a dag-pb node by its SHA2-256 multihash.
+///
+/// Each chunk maps onto exactly one IPFS block, so the CID of a chunk is the
+/// identifier IPFS assigns to the same bytes imported with `--raw-leaves`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cid {
+    codec: u8,
+    digest: [u8; 32],
+}
+
+impl Cid {
+    /// Computes the CID of a raw block.
+    pub fn raw(data: &[u8]) -> Self {
+        Self::from_digest(Sha256::digest(data))
+    }
+
+    /// Computes the CID of an encoded dag-pb node.
+    pub fn dag_pb(data: &[u8]) -> Self {
+        Self {
+            codec: DAG_PB_CODEC,
+            digest: Sha256::digest(data),
+        }
+    }
+
+    ///
+    /// Builds the CID of a raw block from an existing SHA-256 digest of it.
+    ///
+    /// Useful with [`crate::manifest::ManifestEntry::digest`], which already holds it.
+    ///
+    pub fn from_digest(digest: [u8; 32]) -> Self {
+        Self {
+            codec: RAW_CODEC,
+            digest,
+        }
+    }
+
+    /// Computes the CID of a chunk.
+    #[cfg(feature = "fastcdc")]
+    pub fn from_chunk(chunk: &crate::fastcdc::Chunk) -> Self {
+        Self::raw(&chunk.data)
+    }
+
+    /// Returns the multicodec code of the block ([`RAW_CODEC`] or [`DAG_PB_CODEC`]).
+    pub fn codec(&self) -> u8 {
+        self.codec
+    }
+
+    /// Returns the SHA-256 digest of the block.
+    pub fn digest(&self) -> [u8; 32] {
+        self.digest
+    }
+
+    /// Returns the binary form: version, codec, and multihash.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(36);
+        // All codes are below 0x80, so each unsigned varint is a single byte.
+        bytes.extend_from_slice(&[1, self.codec, SHA2_256_CODE, 32]);
+        bytes.extend_from_slice(&self.digest);
+        bytes
+    }
+}
+
+impl fmt::Display for Cid {
+    /// Formats the CID as a base32 multibase string (e.g. `bafkrei...`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.to_bytes();
+        let mut out = String::with_capacity(1 + (bytes.len() * 8).div_ceil(5));
+        out.push('b');
+
+        let mut acc: u32 = 0;
+        let mut bits = 0;
+        for &byte in &bytes {
+            acc = (acc << 8) | byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                out.push(BASE32_ALPHABET[((acc >> bits) & 31) as usize] as char);
+            }
+        }
+        if bits > 0 {
+            out.push(BASE32_ALPHABET[((acc << (5 - bits)) & 31) as usize] as char);
+        }
+
+        f.write_str(&out)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/cid_tests.rs"]
+mod tests;
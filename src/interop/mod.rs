@@ -0,0 +1,23 @@
+//!
+//! Exporters for formats used by other delta-transfer and content-addressing tools.
+//!
+//! The exporters consume the chunk data in stream order, so they can be fed from the
+//! same pass that produces the chunks (e.g. with `Iterator::inspect`).
+//!
+
+mod bittorrent;
+#[cfg(feature = "fastcdc")]
+mod car;
+mod casync;
+mod cid;
+mod zsync;
+
+pub use bittorrent::{BTV2_BLOCK_SIZE, PieceLayerBuilder, PieceLayers};
+#[cfg(feature = "fastcdc")]
+pub use car::{CARV2_PRAGMA, export_car};
+pub use casync::{
+    CA_FORMAT_INDEX, CA_FORMAT_SHA512_256, CA_FORMAT_TABLE, CA_FORMAT_TABLE_TAIL_MARKER,
+    CasyncChunk, CasyncIndex,
+};
+pub use cid::{Cid, DAG_PB_CODEC, RAW_CODEC, SHA2_256_CODE};
+pub use zsync::{ZsyncSignature, ZsyncSigner};
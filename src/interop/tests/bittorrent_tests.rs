@@ -0,0 +1,104 @@
+use super::*;
+
+fn sample_data(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn layers_of(data: &[u8], piece_length: usize) -> PieceLayers {
+    let mut builder = PieceLayerBuilder::new(piece_length);
+    builder.update(data);
+    builder.finish()
+}
+
+// --- Input Tests ---
+
+#[test]
+#[should_panic]
+fn test_piece_length_too_small() {
+    PieceLayerBuilder::new(8_192);
+}
+
+#[test]
+#[should_panic]
+fn test_piece_length_not_power_of_two() {
+    PieceLayerBuilder::new(3 * BTV2_BLOCK_SIZE);
+}
+
+#[test]
+fn test_empty_file() {
+    let layers = layers_of(&[], BTV2_BLOCK_SIZE);
+
+    // Empty files have no pieces root in the file tree
+    assert_eq!(layers.pieces_root(), None);
+    assert!(layers.piece_layer().is_empty());
+}
+
+// --- Tree Tests ---
+
+#[test]
+fn test_single_block_root() {
+    let data = sample_data(1_000);
+    let layers = layers_of(&data, BTV2_BLOCK_SIZE);
+
+    // A single (unpadded) block is its own root, and fits in one piece
+    assert_eq!(layers.pieces_root(), Some(Sha256::digest(&data)));
+    assert!(layers.piece_layer().is_empty());
+}
+
+#[test]
+fn test_tree_padding() {
+    let data = sample_data(3 * BTV2_BLOCK_SIZE + 100);
+    let layers = layers_of(&data, BTV2_BLOCK_SIZE);
+
+    let leaves: Vec<[u8; 32]> = data.chunks(BTV2_BLOCK_SIZE).map(Sha256::digest).collect();
+    let root = hash_pair(
+        &hash_pair(&leaves[0], &leaves[1]),
+        &hash_pair(&leaves[2], &leaves[3]),
+    );
+
+    // Four leaves, with the final one hashed over the 100-byte tail
+    assert_eq!(layers.pieces_root(), Some(root), "Root mismatch");
+    // With 16 KiB pieces the piece layer is the leaf layer
+    assert_eq!(layers.piece_layer(), &leaves[..], "Piece layer mismatch");
+}
+
+#[test]
+fn test_piece_layer_with_padding() {
+    // Five blocks over 32 KiB pieces: three pieces, the last one half past EOF
+    let data = sample_data(5 * BTV2_BLOCK_SIZE);
+    let layers = layers_of(&data, 2 * BTV2_BLOCK_SIZE);
+
+    let leaves: Vec<[u8; 32]> = data.chunks(BTV2_BLOCK_SIZE).map(Sha256::digest).collect();
+    let zero = [0u8; 32];
+    let pieces = [
+        hash_pair(&leaves[0], &leaves[1]),
+        hash_pair(&leaves[2], &leaves[3]),
+        hash_pair(&leaves[4], &zero),
+    ];
+    let pad = hash_pair(&zero, &zero);
+    let root = hash_pair(
+        &hash_pair(&pieces[0], &pieces[1]),
+        &hash_pair(&pieces[2], &pad),
+    );
+
+    // Leaves past EOF are zero, and padding above them hashes those zeros
+    assert_eq!(layers.piece_layer(), &pieces[..], "Piece layer mismatch");
+    assert_eq!(layers.pieces_root(), Some(root), "Root mismatch");
+    assert_eq!(layers.piece_layer_bytes().len(), 3 * 32);
+}
+
+#[test]
+fn test_split_updates() {
+    let data = sample_data(200_000);
+    let expected = layers_of(&data, 65_536);
+
+    let mut builder = PieceLayerBuilder::new(65_536);
+    for part in data.chunks(7_777) {
+        builder.update(part);
+    }
+
+    // Block boundaries must not depend on how the data is fed
+    assert_eq!(builder.finish(), expected);
+}
@@ -0,0 +1,231 @@
+use super::*;
+use crate::fastcdc::{FastCDC, Normal};
+use crate::interop::{DAG_PB_CODEC, RAW_CODEC};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::collections::HashMap;
+
+fn sample_data(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn build_manifest(data: &[u8]) -> Manifest {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    Manifest::from_reader(&chunker, data).expect("Failed to build manifest")
+}
+
+fn read_varint(buf: &mut &[u8]) -> u64 {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = buf[0];
+        *buf = &buf[1..];
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+type Section = (Vec<u8>, Vec<u8>);
+
+/// Splits a CARv1 payload into its header and `(cid, data)` sections.
+fn parse_payload(mut payload: &[u8]) -> (Vec<u8>, Vec<Section>) {
+    let len = read_varint(&mut payload) as usize;
+    let header = payload[..len].to_vec();
+    payload = &payload[len..];
+
+    let mut sections = Vec::new();
+    while !payload.is_empty() {
+        let len = read_varint(&mut payload) as usize;
+        sections.push((payload[..36].to_vec(), payload[36..len].to_vec()));
+        payload = &payload[len..];
+    }
+    (header, sections)
+}
+
+/// Splits a dag-pb node into its `(cid, tsize)` links and its data.
+fn parse_node(mut node: &[u8]) -> (Vec<(Vec<u8>, u64)>, Vec<u8>) {
+    let mut links = Vec::new();
+    let mut data = Vec::new();
+    while !node.is_empty() {
+        let key = read_varint(&mut node);
+        let len = read_varint(&mut node) as usize;
+        let (field, rest) = node.split_at(len);
+        node = rest;
+
+        match key {
+            0x12 => {
+                let mut link = field;
+                assert_eq!(read_varint(&mut link), 0x0a);
+                let cid_len = read_varint(&mut link) as usize;
+                let cid = link[..cid_len].to_vec();
+                link = &link[cid_len..];
+                assert_eq!(read_varint(&mut link), 0x18);
+                links.push((cid, read_varint(&mut link)));
+            }
+            0x0a => data = field.to_vec(),
+            _ => panic!("Unexpected dag-pb field {:#x}", key),
+        }
+    }
+    (links, data)
+}
+
+/// Concatenates the leaves below `cid`, as `ipfs cat` would.
+fn cat(blocks: &HashMap<Vec<u8>, Vec<u8>>, cid: &[u8], out: &mut Vec<u8>) {
+    let block = blocks
+        .get(cid)
+        .expect("Linked block missing from the archive");
+    match cid[1] {
+        RAW_CODEC => out.extend_from_slice(block),
+        DAG_PB_CODEC => {
+            let (links, _) = parse_node(block);
+            assert!(links.len() <= LINKS_PER_NODE, "Node exceeds the link limit");
+            for (link, _) in links {
+                cat(blocks, &link, out);
+            }
+        }
+        codec => panic!("Unexpected codec {:#x}", codec),
+    }
+}
+
+// --- Layout Tests ---
+
+#[test]
+fn test_carv2_header() {
+    let data = sample_data(100_000);
+    let manifest = build_manifest(&data);
+
+    let mut car = Vec::new();
+    export_car(&manifest, &data[..], &mut car).expect("Export failed");
+
+    let offset = u64::from_le_bytes(car[27..35].try_into().unwrap());
+    let size = u64::from_le_bytes(car[35..43].try_into().unwrap());
+
+    // Pragma, then the payload immediately after the 40-byte header
+    assert_eq!(&car[..11], &CARV2_PRAGMA);
+    assert_eq!(offset, 51, "Unexpected data offset");
+    assert_eq!(
+        size,
+        car.len() as u64 - 51,
+        "Data size must cover the payload"
+    );
+    // No index is written
+    assert_eq!(&car[43..51], &[0u8; 8]);
+}
+
+#[test]
+fn test_blocks_and_root() {
+    let data = sample_data(100_000);
+    let manifest = build_manifest(&data);
+
+    let mut car = Vec::new();
+    export_car(&manifest, &data[..], &mut car).expect("Export failed");
+    let (header, sections) = parse_payload(&car[51..]);
+
+    let mut manifest_block = Vec::new();
+    manifest.write_to(&mut manifest_block).unwrap();
+    let (root, root_block) = sections[0].clone();
+    let manifest_cid = Cid::raw(&manifest_block).to_bytes();
+
+    // The header lists the file node and then the manifest block as roots
+    assert_eq!(root[1], DAG_PB_CODEC);
+    let find = |cid: &[u8]| header.windows(cid.len()).position(|w| w == cid);
+    assert_eq!(header[7], 0x82, "Expected two roots");
+    assert!(
+        find(&root).expect("File root missing")
+            < find(&manifest_cid).expect("Manifest root missing")
+    );
+    assert_eq!(sections[1], (manifest_cid, manifest_block));
+
+    // The root links every chunk in order, sized by its length
+    let (links, unixfs) = parse_node(&root_block);
+    assert_eq!(links.len(), manifest.entries().len());
+    for ((cid, tsize), entry) in links.iter().zip(manifest.entries()) {
+        assert_eq!(cid, &Cid::from_digest(entry.digest).to_bytes());
+        assert_eq!(*tsize, entry.length as u64);
+    }
+    assert_eq!(&unixfs[..2], &[0x08, 0x02], "Root must be a UnixFS file");
+
+    // Every following section is a chunk addressed by its own CID
+    assert_eq!(sections.len(), manifest.entries().len() + 2);
+    for ((cid, block), entry) in sections[2..].iter().zip(manifest.entries()) {
+        let start = entry.offset as usize;
+        assert_eq!(block, &data[start..start + entry.length]);
+        assert_eq!(cid, &Cid::raw(block).to_bytes());
+    }
+}
+
+#[test]
+fn test_root_reassembles_source() {
+    // Enough chunks to need a second level of nodes
+    let data = sample_data(2_500_000);
+    let manifest = build_manifest(&data);
+    assert!(manifest.entries().len() > LINKS_PER_NODE);
+
+    let mut car = Vec::new();
+    export_car(&manifest, &data[..], &mut car).expect("Export failed");
+    let (_, sections) = parse_payload(&car[51..]);
+
+    // Reading the file from its root, through the intermediate nodes, restores the data
+    let root = sections[0].0.clone();
+    let blocks: HashMap<_, _> = sections.into_iter().collect();
+    let mut restored = Vec::new();
+    cat(&blocks, &root, &mut restored);
+    assert!(restored == data, "Root does not reproduce the source");
+}
+
+#[test]
+fn test_empty_source() {
+    let manifest = build_manifest(&[]);
+
+    let mut car = Vec::new();
+    export_car(&manifest, &[][..], &mut car).expect("Export failed");
+    let (_, sections) = parse_payload(&car[51..]);
+
+    // An empty file is a single node with no links, as IPFS encodes it
+    assert_eq!(
+        Cid::dag_pb(&sections[0].1).to_string(),
+        "bafybeif7ztnhq65lumvvtr4ekcwd2ifwgm3awq4zfr3srh462rwyinlb4y"
+    );
+    assert_eq!(sections.len(), 2);
+}
+
+#[test]
+fn test_duplicate_chunks_stored_once() {
+    // Repeating the same chunk-aligned block yields chunks with identical content
+    let block = sample_data(MAX_SIZE);
+    let data = block.repeat(4);
+    let manifest = build_manifest(&data);
+
+    let mut car = Vec::new();
+    export_car(&manifest, &data[..], &mut car).expect("Export failed");
+    let (_, sections) = parse_payload(&car[51..]);
+
+    let distinct: HashSet<_> = manifest.entries().iter().map(|e| e.digest).collect();
+
+    // One block for the file node, one for the manifest, and one per distinct chunk
+    assert!(
+        distinct.len() < manifest.entries().len(),
+        "Data must repeat"
+    );
+    assert_eq!(sections.len(), distinct.len() + 2);
+}
+
+// --- Error Tests ---
+
+#[test]
+fn test_mismatched_source() {
+    let data = sample_data(50_000);
+    let manifest = build_manifest(&data);
+
+    let mut modified = data.clone();
+    modified[10_000] ^= 0xff;
+
+    let err = export_car(&manifest, &modified[..], io::sink()).unwrap_err();
+
+    // Exporting stale chunks would produce CIDs that contradict the manifest
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,109 @@
+use super::*;
+
+fn sample_index() -> CasyncIndex {
+    let mut index = CasyncIndex::new(CA_FORMAT_SHA512_256, 16_384, 65_536, 262_144);
+    index.push(20_000, [1; 32]);
+    index.push(70_000, [2; 32]);
+    index.push(5, [3; 32]);
+    index
+}
+
+fn word(bytes: &[u8], index: usize) -> u64 {
+    u64::from_le_bytes(bytes[index * 8..index * 8 + 8].try_into().unwrap())
+}
+
+// --- Layout Tests ---
+
+#[test]
+fn test_binary_layout() {
+    let mut bytes = Vec::new();
+    sample_index().write_to(&mut bytes).unwrap();
+
+    // Header, table header, three 40-byte items, and the 40-byte tail
+    assert_eq!(bytes.len(), 48 + 16 + 3 * 40 + 40);
+    assert_eq!(word(&bytes, 0), 48);
+    assert_eq!(word(&bytes, 1), CA_FORMAT_INDEX);
+    assert_eq!(word(&bytes, 7), CA_FORMAT_TABLE);
+
+    // Items record the end offset of each chunk
+    assert_eq!(word(&bytes[64..], 0), 20_000);
+    assert_eq!(word(&bytes[104..], 0), 90_000);
+    assert_eq!(word(&bytes[144..], 0), 90_005);
+
+    let tail = &bytes[bytes.len() - 40..];
+    assert_eq!(
+        word(tail, 2),
+        48,
+        "Tail must point back at the index header"
+    );
+    assert_eq!(
+        word(tail, 3),
+        16 + 3 * 40 + 40,
+        "Tail must record the table size"
+    );
+    assert_eq!(word(tail, 4), CA_FORMAT_TABLE_TAIL_MARKER);
+}
+
+#[test]
+fn test_round_trip() {
+    let index = sample_index();
+
+    let mut bytes = Vec::new();
+    index.write_to(&mut bytes).unwrap();
+    let parsed = CasyncIndex::read_from(&bytes[..]).expect("Failed to read index");
+
+    // Reading must restore the flags, sizes, and chunk list exactly
+    assert_eq!(parsed, index);
+    assert!(parsed.uses_sha512_256());
+    assert_eq!(parsed.total_len(), 90_005);
+}
+
+#[test]
+#[cfg(feature = "fastcdc")]
+fn test_from_manifest() {
+    use crate::fastcdc::{FastCDC, Normal};
+    use crate::manifest::Manifest;
+
+    let data: Vec<u8> = (0..100_000u32).map(|i| (i * 7 % 251) as u8).collect();
+    let chunker = FastCDC::new(4_069, 8_192, 16_384, Normal::Level2);
+    let manifest = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    let index = CasyncIndex::from_manifest(&manifest).unwrap();
+
+    // Manifest digests are SHA-256, which casync selects by leaving the flag unset
+    assert!(!index.uses_sha512_256());
+    assert_eq!(index.chunk_sizes(), (4_069, 8_192, 16_384));
+    assert_eq!(index.total_len(), manifest.total_len());
+
+    for (chunk, entry) in index.chunks().iter().zip(manifest.entries()) {
+        assert_eq!((chunk.offset, chunk.id), (entry.offset, entry.digest));
+    }
+}
+
+// --- Error Tests ---
+
+#[test]
+fn test_rejects_invalid_data() {
+    let mut bytes = Vec::new();
+    sample_index().write_to(&mut bytes).unwrap();
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[8] ^= 0xff;
+    let mut bad_tail = bytes.clone();
+    *bad_tail.last_mut().unwrap() ^= 0xff;
+
+    // A wrong object type or tail marker must be reported as invalid data
+    assert_eq!(
+        CasyncIndex::read_from(&bad_magic[..]).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert_eq!(
+        CasyncIndex::read_from(&bad_tail[..]).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    // A truncated file ends before the tail
+    assert_eq!(
+        CasyncIndex::read_from(&bytes[..100]).unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+}
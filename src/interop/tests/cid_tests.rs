@@ -0,0 +1,43 @@
+use super::*;
+
+// --- Encoding Tests ---
+
+#[test]
+fn test_known_cids() {
+    // Values produced by `ipfs add --raw-leaves --cid-version 1`
+    assert_eq!(
+        Cid::raw(b"").to_string(),
+        "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+    );
+    assert_eq!(
+        Cid::raw(b"hello world").to_string(),
+        "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+    );
+}
+
+#[test]
+fn test_known_dag_pb_cid() {
+    // The UnixFS node of an empty file, `QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH` as CIDv0
+    let cid = Cid::dag_pb(&[0x0a, 0x04, 0x08, 0x02, 0x18, 0x00]);
+    assert_eq!(cid.codec(), DAG_PB_CODEC);
+    assert_eq!(
+        cid.to_string(),
+        "bafybeif7ztnhq65lumvvtr4ekcwd2ifwgm3awq4zfr3srh462rwyinlb4y"
+    );
+}
+
+#[test]
+fn test_binary_form() {
+    let cid = Cid::raw(b"abc");
+    let bytes = cid.to_bytes();
+
+    // CIDv1, raw codec, sha2-256 with a 32-byte digest
+    assert_eq!(&bytes[..4], &[0x01, 0x55, 0x12, 0x20]);
+    assert_eq!(&bytes[4..], &Sha256::digest(b"abc"));
+}
+
+#[test]
+fn test_from_digest() {
+    // A precomputed digest must produce the same CID as hashing the data
+    assert_eq!(Cid::from_digest(Sha256::digest(b"abc")), Cid::raw(b"abc"));
+}
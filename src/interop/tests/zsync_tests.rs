@@ -0,0 +1,96 @@
+use super::*;
+
+fn sample_data(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+// --- Checksum Tests ---
+
+#[test]
+fn test_rsum() {
+    // a = 1 + 2 + 3, b = 3 * 1 + 2 * 2 + 1 * 3
+    assert_eq!(rsum(&[1, 2, 3]), [0, 6, 0, 10]);
+}
+
+#[test]
+fn test_final_block_is_zero_padded() {
+    let data = sample_data(5_000);
+
+    let mut signer = ZsyncSigner::new(2_048);
+    signer.update(&data);
+    let signature = signer.finish();
+
+    // 5,000 bytes span three 2 KiB blocks
+    assert_eq!(signature.blocks().len(), 3);
+    assert_eq!(signature.length(), 5_000);
+
+    let mut last = data[4_096..].to_vec();
+    last.resize(2_048, 0);
+
+    // The short trailing block must be hashed as if padded with zeros
+    assert_eq!(signature.blocks()[2], (rsum(&last), Md4::digest(&last)));
+    assert_eq!(signature.sha1(), Sha1::digest(&data));
+}
+
+#[test]
+fn test_split_updates() {
+    let data = sample_data(20_000);
+
+    let mut whole = ZsyncSigner::new(1_024);
+    whole.update(&data);
+
+    let mut split = ZsyncSigner::new(1_024);
+    for part in data.chunks(777) {
+        split.update(part);
+    }
+
+    // Feeding data in chunk-sized pieces must not change the signature
+    assert_eq!(whole.finish().blocks(), split.finish().blocks());
+}
+
+// --- Format Tests ---
+
+#[test]
+fn test_control_file() {
+    let data = sample_data(10_000);
+
+    let mut signer = ZsyncSigner::new(2_048);
+    signer.update(&data);
+    let signature = signer.finish();
+
+    let (_, rsum_len, checksum_len) = signature.hash_lengths();
+    assert!((2..=4).contains(&rsum_len));
+    assert!((4..=16).contains(&checksum_len));
+
+    let mut out = Vec::new();
+    signature
+        .write_to(&mut out, "data.bin", "data.bin")
+        .expect("Failed to write control file");
+
+    let header_end = out
+        .windows(2)
+        .position(|w| w == b"\n\n")
+        .expect("Header must end with a blank line")
+        + 2;
+    let header = std::str::from_utf8(&out[..header_end]).expect("Header must be UTF-8");
+
+    // The header must describe the file and the hash-length layout
+    assert!(header.starts_with("zsync: 0.6.2\n"));
+    assert!(header.contains("Blocksize: 2048\n"));
+    assert!(header.contains("Length: 10000\n"));
+    assert!(header.contains(&format!("SHA-1: {}\n", hex(&Sha1::digest(&data)))));
+
+    // Each block contributes the truncated weak and strong sums
+    assert_eq!(
+        out.len() - header_end,
+        signature.blocks().len() * (rsum_len + checksum_len)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_invalid_block_size() {
+    let _ = ZsyncSigner::new(3_000);
+}
@@ -0,0 +1,218 @@
+use crate::digest::{Md4, Sha1};
+use std::io::{self, Write};
+
+/// The zsync format version written in the header.
+const ZSYNC_VERSION: &str = "0.6.2";
+
+///
+/// Builds zsync block signatures from data fed in stream order.
+///
+/// ## Example
+///
+/// ```
+/// use clast::fastcdc::{FastCDC, Normal};
+/// use clast::interop::ZsyncSigner;
+///
+/// let data = vec![7u8; 100_000];
+/// let chunker = FastCDC::new(4096, 8192, 16384, Normal::Level2);
+/// let mut signer = ZsyncSigner::new(2048);
+///
+/// for chunk in chunker.chunks(&data[..]) {
+///     signer.update(&chunk.unwrap().data);
+/// }
+///
+/// let signature = signer.finish();
+/// assert_eq!(signature.blocks().len(), 49);
+/// ```
+///
+pub struct ZsyncSigner {
+    block_size: usize,
+    block: Vec<u8>,
+    blocks: Vec<([u8; 4], [u8; 16])>,
+    sha1: Sha1,
+    length: u64,
+}
+
+impl ZsyncSigner {
+    ///
+    /// Constructs a new `ZsyncSigner`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `block_size`: The zsync block size in bytes. `2048` is zsyncmake's default for small files.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `block_size` is not a power of two.
+    ///
+    pub fn new(block_size: usize) -> Self {
+        assert!(
+            block_size.is_power_of_two(),
+            "zsync block size must be a power of two"
+        );
+
+        Self {
+            block_size,
+            block: Vec::with_capacity(block_size),
+            blocks: Vec::new(),
+            sha1: Sha1::new(),
+            length: 0,
+        }
+    }
+
+    /// Feeds the next bytes of the file.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.sha1.update(data);
+        self.length += data.len() as u64;
+
+        while !data.is_empty() {
+            let take = (self.block_size - self.block.len()).min(data.len());
+            self.block.extend_from_slice(&data[..take]);
+            data = &data[take..];
+
+            if self.block.len() == self.block_size {
+                self.push_block();
+            }
+        }
+    }
+
+    /// Completes the signature, padding the final block with zeros as zsyncmake does.
+    pub fn finish(mut self) -> ZsyncSignature {
+        if !self.block.is_empty() {
+            self.block.resize(self.block_size, 0);
+            self.push_block();
+        }
+
+        ZsyncSignature {
+            block_size: self.block_size,
+            length: self.length,
+            sha1: self.sha1.finalize(),
+            blocks: self.blocks,
+        }
+    }
+
+    fn push_block(&mut self) {
+        self.blocks
+            .push((rsum(&self.block), Md4::digest(&self.block)));
+        self.block.clear();
+    }
+}
+
+/// zsync's weak checksum: two 16-bit sums, written big-endian as `a` then `b`.
+fn rsum(block: &[u8]) -> [u8; 4] {
+    let mut a: u16 = 0;
+    let mut b: u16 = 0;
+
+    for (i, &c) in block.iter().enumerate() {
+        let weight = (block.len() - i) as u16;
+        a = a.wrapping_add(c as u16);
+        b = b.wrapping_add(weight.wrapping_mul(c as u16));
+    }
+
+    let mut out = [0u8; 4];
+    out[..2].copy_from_slice(&a.to_be_bytes());
+    out[2..].copy_from_slice(&b.to_be_bytes());
+    out
+}
+
+/// A complete zsync signature.
+#[derive(Debug, Clone)]
+pub struct ZsyncSignature {
+    block_size: usize,
+    length: u64,
+    sha1: [u8; 20],
+    blocks: Vec<([u8; 4], [u8; 16])>,
+}
+
+impl ZsyncSignature {
+    /// Returns the block size.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the total length of the signed data.
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns the SHA-1 digest of the whole file.
+    pub fn sha1(&self) -> [u8; 20] {
+        self.sha1
+    }
+
+    /// Returns the full (untruncated) weak and MD4 checksums of every block.
+    pub fn blocks(&self) -> &[([u8; 4], [u8; 16])] {
+        &self.blocks
+    }
+
+    ///
+    /// Returns the `Hash-Lengths` triple (sequential matches, weak bytes, strong bytes)
+    /// chosen with the same heuristics as zsyncmake.
+    ///
+    pub fn hash_lengths(&self) -> (usize, usize, usize) {
+        if self.length == 0 {
+            return (1, 2, 4);
+        }
+
+        let len = self.length as f64;
+        let block_size = self.block_size as f64;
+        let seq_matches = if self.length > self.block_size as u64 {
+            2
+        } else {
+            1
+        };
+        let seq = seq_matches as f64;
+
+        let rsum_len = (((len.ln() + block_size.ln()) / 2f64.ln() - 8.6) / seq / 8.0).ceil();
+        let rsum_len = (rsum_len as i64).clamp(2, 4) as usize;
+
+        let blocks = (1.0 + len / block_size).floor();
+        let checksum_len = ((20.0 + (len.ln() + blocks.ln()) / 2f64.ln()) / seq / 8.0).ceil();
+        let checksum_len_single = ((7.9 + (20.0 + blocks.ln() / 2f64.ln())) / 8.0).floor();
+        let checksum_len = (checksum_len.max(checksum_len_single) as i64).clamp(4, 16) as usize;
+
+        (seq_matches, rsum_len, checksum_len)
+    }
+
+    ///
+    /// Writes the signature as a `.zsync` control file.
+    ///
+    /// ## Arguments
+    ///
+    /// * `writer`: The destination (must implement `Write`).
+    /// * `filename`: The file name recorded in the header.
+    /// * `url`: The URL (absolute or relative to the control file) the data is served from.
+    ///
+    pub fn write_to<W: Write>(&self, mut writer: W, filename: &str, url: &str) -> io::Result<()> {
+        let (seq_matches, rsum_len, checksum_len) = self.hash_lengths();
+
+        writeln!(writer, "zsync: {}", ZSYNC_VERSION)?;
+        writeln!(writer, "Filename: {}", filename)?;
+        writeln!(writer, "Blocksize: {}", self.block_size)?;
+        writeln!(writer, "Length: {}", self.length)?;
+        writeln!(
+            writer,
+            "Hash-Lengths: {},{},{}",
+            seq_matches, rsum_len, checksum_len
+        )?;
+        writeln!(writer, "URL: {}", url)?;
+        writeln!(writer, "SHA-1: {}", hex(&self.sha1))?;
+        writeln!(writer)?;
+
+        // Weak sums keep their trailing bytes, strong sums their leading bytes.
+        for (weak, strong) in &self.blocks {
+            writer.write_all(&weak[4 - rsum_len..])?;
+            writer.write_all(&strong[..checksum_len])?;
+        }
+
+        writer.flush()
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+#[path = "tests/zsync_tests.rs"]
+mod tests;
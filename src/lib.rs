@@ -1,2 +1,31 @@
 #[cfg(feature = "fastcdc")]
 pub mod fastcdc;
+
+pub mod adapter;
+#[cfg(feature = "fastcdc")]
+pub mod ae;
+#[cfg(feature = "fastcdc")]
+pub mod buzhash;
+#[cfg(feature = "fastcdc")]
+pub mod chunker;
+pub mod codec;
+pub mod corpus;
+pub mod digest;
+pub mod error;
+#[cfg(feature = "fastcdc")]
+pub mod fixed;
+pub mod hash;
+pub mod interop;
+#[cfg(feature = "fastcdc")]
+pub mod manifest;
+#[cfg(feature = "fastcdc")]
+pub mod mii;
+#[cfg(feature = "fastcdc")]
+pub mod pci;
+#[cfg(feature = "fastcdc")]
+pub mod rabin;
+#[cfg(feature = "fastcdc")]
+pub mod seqcdc;
+
+#[cfg(all(test, feature = "fastcdc"))]
+pub(crate) mod test_util;
@@ -0,0 +1,185 @@
+use super::{ENTRY_LEN, Manifest, ManifestEntry, PARAMS_LEN, invalid};
+use crate::digest::Sha256;
+use crate::fastcdc::{Chunk, FastCDC};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Magic bytes at the start of every journal.
+pub const JOURNAL_MAGIC: [u8; 4] = *b"CBJ\0";
+/// The journal format version written by this crate.
+pub const JOURNAL_VERSION: u8 = 2;
+
+/// Size of the journal header.
+const HEADER_LEN: u64 = HEADER_LEN_V1 + PARAMS_LEN as u64;
+/// Size of a version 1 journal header, which lacks the chunker parameters.
+const HEADER_LEN_V1: u64 = 20;
+/// Size of a record: an encoded entry followed by a truncated SHA-256 of it.
+const RECORD_LEN: usize = ENTRY_LEN + 4;
+
+///
+/// A write-ahead journal of the chunks an ingest has completed.
+///
+/// Each chunk is appended as soon as it has been handed off (e.g. written to a store).
+/// After a crash, [`Journal::resume`] recovers every complete record, and chunking resumes
+/// with [`FastCDC::chunks_at`] at [`Journal::resume_offset`] instead of starting over.
+///
+/// The header uses the manifest's layout with the `CBJ\0` magic and no entry count.
+/// Each record is a manifest entry followed by the first four bytes of its SHA-256,
+/// so a torn or zero-filled tail is detected and discarded.
+///
+pub struct Journal {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    manifest: Manifest,
+}
+
+impl Journal {
+    ///
+    /// Creates a new journal at `path`, replacing any existing file.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The journal file.
+    /// * `chunker`: The chunker whose parameters are recorded.
+    ///
+    pub fn create<P: AsRef<Path>>(path: P, chunker: &FastCDC) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let manifest = Manifest::new(chunker);
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        manifest.write_header(&mut writer, JOURNAL_MAGIC, JOURNAL_VERSION)?;
+
+        let mut journal = Self {
+            path,
+            writer,
+            manifest,
+        };
+        journal.sync()?;
+
+        Ok(journal)
+    }
+
+    ///
+    /// Reopens an interrupted journal, recovering the chunks it recorded.
+    ///
+    /// Records after the first incomplete or corrupt one are truncated away, so new
+    /// records continue directly after the recovered ones.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The journal file.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidData` if the file is not a journal.
+    ///
+    pub fn resume<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new().read(true).write(true).open(&path)?;
+        let mut reader = BufReader::new(&file);
+
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header)?;
+
+        if header[..4] != JOURNAL_MAGIC {
+            return Err(invalid("not a clast journal"));
+        }
+        if !(1..=JOURNAL_VERSION).contains(&header[4]) {
+            return Err(invalid("unsupported journal version"));
+        }
+
+        let mut manifest = Manifest::read_header(&mut reader, &header)?;
+        manifest.chunker_params()?;
+        let header_len = match header[4] {
+            1 => HEADER_LEN_V1,
+            _ => HEADER_LEN,
+        };
+
+        let mut record = [0u8; RECORD_LEN];
+        while reader.read_exact(&mut record).is_ok() {
+            let (entry, checksum) = record.split_at(ENTRY_LEN);
+            if Sha256::digest(entry)[..4] != *checksum {
+                break;
+            }
+
+            let entry = ManifestEntry::from_bytes(entry.try_into().unwrap());
+            if entry.offset != manifest.total_len() {
+                break;
+            }
+            manifest.entries.push(entry);
+        }
+
+        let valid_len = header_len + (manifest.entries.len() * RECORD_LEN) as u64;
+        file.set_len(valid_len)?;
+        file.seek(SeekFrom::Start(valid_len))?;
+
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            manifest,
+        })
+    }
+
+    /// Returns the chunks recorded so far.
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Returns the stream offset to resume chunking at.
+    pub fn resume_offset(&self) -> u64 {
+        self.manifest.total_len()
+    }
+
+    ///
+    /// Appends a completed chunk.
+    ///
+    /// Records are buffered; call [`Journal::sync`] to make them durable.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if the chunk does not start
+    /// at [`Journal::resume_offset`].
+    ///
+    pub fn record(&mut self, chunk: &Chunk) -> io::Result<()> {
+        if chunk.offset != self.resume_offset() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "chunk does not continue the journal",
+            ));
+        }
+
+        let entry = ManifestEntry::from_chunk(chunk);
+        let bytes = entry.to_bytes();
+        self.writer.write_all(&bytes)?;
+        self.writer.write_all(&Sha256::digest(&bytes)[..4])?;
+        self.manifest.entries.push(entry);
+
+        Ok(())
+    }
+
+    /// Flushes buffered records and syncs them to disk.
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()
+    }
+
+    ///
+    /// Completes the ingest: persists the manifest to `path` and removes the journal.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The destination of the manifest.
+    ///
+    pub fn finish<P: AsRef<Path>>(mut self, path: P) -> io::Result<Manifest> {
+        self.sync()?;
+        self.manifest.persist(path)?;
+        fs::remove_file(&self.path)?;
+
+        Ok(self.manifest)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/journal_tests.rs"]
+mod tests;
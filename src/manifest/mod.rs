@@ -0,0 +1,560 @@
+//!
+//! A compact binary manifest (`.cbm`) describing how a file was chunked.
+//!
+//! The manifest records the chunker parameters and, for every chunk, its offset,
+//! length, fingerprint, and SHA-256 digest. It can be used to verify that a file
+//! still chunks to exactly the same contents.
+//!
+//! ## Format
+//!
+//! All integers are little-endian.
+//!
+//! | Field      | Size | Description                         |
+//! |------------|------|-------------------------------------|
+//! | magic      | 4    | `b"CBM\0"`                          |
+//! | version    | 1    | Format version (`2`)                |
+//! | normal     | 1    | Normalization level (`0` ~ `3`)     |
+//! | options    | 1    | Chunker option bits (`0` by default)|
+//! | reserved   | 1    | Zero                                |
+//! | min_size   | 4    | Minimum chunk size                  |
+//! | avg_size   | 4    | Average chunk size                  |
+//! | max_size   | 4    | Maximum chunk size                  |
+//! | table      | 1    | Gear table: `0` built-in, `1` seeded, `2` keyed or caller-supplied |
+//! | flags      | 1    | Bit 0: custom masks, bit 1: record delimiter |
+//! | delimiter  | 1    | Record delimiter byte               |
+//! | reserved   | 5    | Zero                                |
+//! | table_id   | 8    | The seed, or the first 8 bytes of the table's SHA-256 |
+//! | mask_s     | 8    | Custom mask below `avg_size`        |
+//! | mask_l     | 8    | Custom mask from `avg_size` on      |
+//! | alignment  | 8    | Boundary alignment (`0` if off)     |
+//! | tolerance  | 8    | Alignment tolerance                 |
+//! | slack      | 8    | Record delimiter slack              |
+//! | count      | 8    | Number of entries                   |
+//! | entries    | 56 * count | `offset: u64`, `length: u64`, `fp_hash: u64`, `digest: [u8; 32]` |
+//!
+//! Version `1` manifests, which lack the fields from `table` to `slack`, are still read;
+//! they describe chunkers with the built-in table and no masks, alignment, or delimiter.
+//!
+//! A keyed or caller-supplied Gear table cannot be recovered from its fingerprint, so its
+//! manifests are verified with [`Manifest::verify_with`] and the original chunker.
+//!
+
+mod journal;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod rechunk;
+
+pub use journal::{JOURNAL_MAGIC, JOURNAL_VERSION, Journal};
+pub use rechunk::{Rechunked, rechunk_with_baseline};
+
+use crate::digest::Sha256;
+use crate::fastcdc::{Chunk, FastCDC, Masks, Normal, TableId, published_gear};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of every manifest.
+pub const MANIFEST_MAGIC: [u8; 4] = *b"CBM\0";
+/// The manifest format version written by this crate.
+pub const MANIFEST_VERSION: u8 = 2;
+
+/// Size of an encoded entry.
+const ENTRY_LEN: usize = 56;
+/// Size of the chunker parameters that follow the sizes in a version 2 header.
+const PARAMS_LEN: usize = 56;
+
+/// A single chunk described by a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// The absolute offset of the chunk in the source.
+    pub offset: u64,
+    /// The length of the chunk in bytes.
+    pub length: usize,
+    /// The fingerprint (Gear Hash) of the chunk.
+    pub fp_hash: u64,
+    /// The SHA-256 digest of the chunk data.
+    pub digest: [u8; 32],
+}
+
+impl ManifestEntry {
+    /// Builds an entry from a chunk, hashing its data.
+    pub fn from_chunk(chunk: &Chunk) -> Self {
+        Self {
+            offset: chunk.offset,
+            length: chunk.length,
+            fp_hash: chunk.fp_hash,
+            digest: Sha256::digest(&chunk.data),
+        }
+    }
+
+    fn to_bytes(&self) -> [u8; ENTRY_LEN] {
+        let mut bytes = [0u8; ENTRY_LEN];
+        bytes[..8].copy_from_slice(&self.offset.to_le_bytes());
+        bytes[8..16].copy_from_slice(&(self.length as u64).to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.fp_hash.to_le_bytes());
+        bytes[24..].copy_from_slice(&self.digest);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; ENTRY_LEN]) -> Self {
+        let word = |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+        Self {
+            offset: word(0),
+            length: word(8) as usize,
+            fp_hash: word(16),
+            digest: bytes[24..].try_into().unwrap(),
+        }
+    }
+}
+
+/// The chunker parameters and chunk list of a chunked file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    normal: Normal,
+    options: u8,
+    table: TableId,
+    masks: Option<Masks>,
+    alignment: Option<(usize, usize)>,
+    delimiter: Option<(u8, usize)>,
+    entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    ///
+    /// Constructs an empty manifest for the given chunker's parameters.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The chunker whose parameters are recorded.
+    ///
+    pub fn new(chunker: &FastCDC) -> Self {
+        Self {
+            min_size: chunker.min_size(),
+            avg_size: chunker.avg_size(),
+            max_size: chunker.max_size(),
+            normal: chunker.normal(),
+            options: chunker.option_bits(),
+            table: chunker.table_id(),
+            masks: chunker.custom_masks(),
+            alignment: chunker.alignment(),
+            delimiter: chunker.delimiter(),
+            entries: Vec::new(),
+        }
+    }
+
+    ///
+    /// Chunks `reader` and records every chunk.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The chunker to use.
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn from_reader<R: Read>(chunker: &FastCDC, reader: R) -> io::Result<Self> {
+        let mut manifest = Self::new(chunker);
+        for chunk in chunker.chunks(reader) {
+            manifest.push(&chunk?);
+        }
+        Ok(manifest)
+    }
+
+    /// Appends a chunk to the manifest.
+    pub fn push(&mut self, chunk: &Chunk) {
+        self.entries.push(ManifestEntry::from_chunk(chunk));
+    }
+
+    /// Returns the recorded chunks.
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    /// Returns the total length of the described data.
+    pub fn total_len(&self) -> u64 {
+        self.entries.iter().map(|e| e.length as u64).sum()
+    }
+
+    ///
+    /// Returns `true` if `chunker` has exactly the boundary-affecting parameters recorded
+    /// in the manifest, including its Gear table.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The chunker to compare.
+    ///
+    pub fn matches(&self, chunker: &FastCDC) -> bool {
+        let other = Self::new(chunker);
+        self.min_size == other.min_size
+            && self.avg_size == other.avg_size
+            && self.max_size == other.max_size
+            && self.normal.offset() == other.normal.offset()
+            && self.options == other.options
+            && self.table == other.table
+            && self.masks == other.masks
+            && self.alignment == other.alignment
+            && self.delimiter == other.delimiter
+    }
+
+    ///
+    /// Reconstructs the chunker that produced this manifest.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if the recorded parameters
+    /// are invalid, or if the manifest was written with a keyed or caller-supplied Gear table
+    /// other than [`crate::fastcdc::PAPER_GEAR`], or with a build whose built-in table
+    /// differs (see `GEAR_SEED`).
+    ///
+    pub fn chunker(&self) -> io::Result<FastCDC> {
+        let chunker = self.chunker_params()?;
+        let chunker = match self.table {
+            TableId::Builtin(_) if self.table == TableId::builtin() => chunker,
+            TableId::Builtin(_) => {
+                return Err(unsupported(
+                    "manifest was written with a different built-in Gear table",
+                ));
+            }
+            TableId::Seed(seed) => chunker.with_seed(seed),
+            TableId::Custom(id) => match published_gear(id) {
+                Some(gear) => chunker.with_gear_table(gear),
+                None => {
+                    return Err(unsupported(
+                        "manifest was written with a keyed or custom Gear table",
+                    ));
+                }
+            },
+        };
+
+        // Table overrides re-derive the masks, so custom ones are applied last.
+        Ok(match self.masks {
+            Some(masks) => chunker.with_masks(masks),
+            None => chunker,
+        })
+    }
+
+    /// Rebuilds the recorded chunker except for its Gear table and custom masks, validating
+    /// every other parameter.
+    pub(crate) fn chunker_params(&self) -> io::Result<FastCDC> {
+        // Recorded sizes may come from a chunker built with extended bounds.
+        let chunker = FastCDC::try_new_unchecked_bounds(
+            self.min_size,
+            self.avg_size,
+            self.max_size,
+            self.normal,
+        )?
+        .with_option_bits(self.options)
+        .ok_or_else(|| unsupported("unknown chunker options"))?;
+
+        let chunker = match self.alignment {
+            Some((alignment, tolerance)) => chunker.with_alignment(alignment, tolerance),
+            None => chunker,
+        };
+        Ok(match self.delimiter {
+            Some((delimiter, slack)) => chunker.with_delimiter(delimiter, slack),
+            None => chunker,
+        })
+    }
+
+    ///
+    /// Re-chunks `reader` and confirms it matches the manifest.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to verify (must implement `Read`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if the chunker cannot be
+    /// reconstructed (see [`Manifest::chunker`]), `ErrorKind::InvalidData` describing the
+    /// first chunk that differs, or the error raised while reading.
+    ///
+    pub fn verify<R: Read>(&self, reader: R) -> io::Result<()> {
+        self.verify_with(&self.chunker()?, reader)
+    }
+
+    ///
+    /// Re-chunks `reader` with `chunker` and confirms it matches the manifest.
+    ///
+    /// Unlike [`Manifest::verify`], this also works for manifests written with a keyed or
+    /// caller-supplied Gear table, given the chunker that wrote them.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chunker`: The chunker that wrote the manifest.
+    /// * `reader`: The source to verify (must implement `Read`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if `chunker` does not have
+    /// the recorded parameters (see [`Manifest::matches`]), and otherwise as [`Manifest::verify`].
+    ///
+    pub fn verify_with<R: Read>(&self, chunker: &FastCDC, reader: R) -> io::Result<()> {
+        if !self.matches(chunker) {
+            return Err(unsupported("chunker does not match the manifest"));
+        }
+
+        let mut entries = self.entries.iter();
+
+        for (index, chunk) in chunker.chunks(reader).enumerate() {
+            let actual = ManifestEntry::from_chunk(&chunk?);
+
+            match entries.next() {
+                Some(expected) if *expected == actual => {}
+                Some(expected) => {
+                    return Err(invalid(&format!(
+                        "chunk {} at offset {} does not match the manifest",
+                        index, expected.offset
+                    )));
+                }
+                None => {
+                    return Err(invalid(&format!(
+                        "unexpected data at offset {} beyond the end of the manifest",
+                        actual.offset
+                    )));
+                }
+            }
+        }
+
+        match entries.next() {
+            Some(missing) => Err(invalid(&format!(
+                "data ends before chunk at offset {}",
+                missing.offset
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    ///
+    /// Writes the manifest in binary form.
+    ///
+    /// ## Arguments
+    ///
+    /// * `writer`: The destination (must implement `Write`).
+    ///
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.write_header(&mut writer, MANIFEST_MAGIC, MANIFEST_VERSION)?;
+        writer.write_all(&(self.entries.len() as u64).to_le_bytes())?;
+
+        for entry in &self.entries {
+            writer.write_all(&entry.to_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    ///
+    /// Atomically writes the manifest to `path`, replacing any existing file.
+    ///
+    /// The manifest is written to a temporary file in the same directory, flushed to disk,
+    /// and renamed over `path`, after which the directory itself is synced. A reader (or a
+    /// restore after power loss) sees either the previous file or the complete new one,
+    /// never a partial write.
+    ///
+    /// ## Arguments
+    ///
+    /// * `path`: The destination file.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the error raised while writing, syncing, or renaming. The temporary file is
+    /// removed on failure and `path` is left untouched.
+    ///
+    pub fn persist<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+
+        let mut tmp_name = std::ffi::OsString::from(".");
+        tmp_name.push(name);
+        tmp_name.push(format!(".{}.tmp", std::process::id()));
+        let tmp_path = dir.join(tmp_name);
+
+        let result = (|| {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            self.write_to(&mut writer)?;
+            writer
+                .into_inner()
+                .map_err(|e| e.into_error())?
+                .sync_all()?;
+            fs::rename(&tmp_path, path)
+        })();
+
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+            return result;
+        }
+
+        sync_dir(dir)
+    }
+
+    ///
+    /// Reads a manifest written by [`Manifest::write_to`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source (must implement `Read`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidData` if the data is not a valid manifest.
+    ///
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header)?;
+
+        if header[..4] != MANIFEST_MAGIC {
+            return Err(invalid("not a clast manifest"));
+        }
+        if !(1..=MANIFEST_VERSION).contains(&header[4]) {
+            return Err(invalid("unsupported manifest version"));
+        }
+
+        let mut manifest = Self::read_header(&mut reader, &header)?;
+        let count = read_u64(&mut reader)?;
+
+        for _ in 0..count {
+            let mut bytes = [0u8; ENTRY_LEN];
+            reader.read_exact(&mut bytes)?;
+            manifest.entries.push(ManifestEntry::from_bytes(&bytes));
+        }
+
+        Ok(manifest)
+    }
+
+    /// Writes the header up to the entry count, which manifests and journals share.
+    fn write_header<W: Write>(
+        &self,
+        writer: &mut W,
+        magic: [u8; 4],
+        version: u8,
+    ) -> io::Result<()> {
+        writer.write_all(&magic)?;
+        writer.write_all(&[version, self.normal.offset() as u8, self.options, 0])?;
+        for size in [self.min_size, self.avg_size, self.max_size] {
+            writer.write_all(&(size as u32).to_le_bytes())?;
+        }
+
+        let (kind, table_id) = table_parts(self.table);
+        let (mask_s, mask_l) = self.masks.map_or((0, 0), |m| (m.mask_s(), m.mask_l()));
+        let (alignment, tolerance) = self.alignment.unwrap_or((0, 0));
+        let (delimiter, slack) = self.delimiter.unwrap_or((0, 0));
+        let flags = self.masks.is_some() as u8 | (self.delimiter.is_some() as u8) << 1;
+
+        let mut params = [0u8; PARAMS_LEN];
+        params[..3].copy_from_slice(&[kind as u8, flags, delimiter]);
+        let words = [
+            table_id,
+            mask_s,
+            mask_l,
+            alignment as u64,
+            tolerance as u64,
+            slack as u64,
+        ];
+        for (bytes, word) in params[8..].chunks_exact_mut(8).zip(words) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        writer.write_all(&params)
+    }
+
+    /// Reads the rest of a header whose first 8 bytes are `header`, in the layout of its
+    /// version, leaving the entries empty.
+    fn read_header<R: Read>(reader: &mut R, header: &[u8; 8]) -> io::Result<Self> {
+        let mut manifest = Self {
+            min_size: read_u32(reader)? as usize,
+            avg_size: read_u32(reader)? as usize,
+            max_size: read_u32(reader)? as usize,
+            normal: Normal::from_offset(header[5] as u32),
+            options: header[6],
+            table: TableId::builtin(),
+            masks: None,
+            alignment: None,
+            delimiter: None,
+            entries: Vec::new(),
+        };
+        if header[4] == 1 {
+            return Ok(manifest);
+        }
+
+        let mut params = [0u8; PARAMS_LEN];
+        reader.read_exact(&mut params)?;
+        let word = |i: usize| u64::from_le_bytes(params[8 + 8 * i..16 + 8 * i].try_into().unwrap());
+        let [kind, flags, delimiter] = [params[0], params[1], params[2]];
+
+        manifest.table = table_from_parts(kind as u64, word(0))?;
+        if flags & !0b11 != 0 {
+            return Err(invalid("unknown parameter flags"));
+        }
+        if flags & 0b01 != 0 {
+            manifest.masks = Some(Masks::from_recorded(word(1), word(2)));
+        }
+        if word(3) > 0 {
+            manifest.alignment = Some((word(3) as usize, word(4) as usize));
+        }
+        if flags & 0b10 != 0 {
+            manifest.delimiter = Some((delimiter, word(5) as usize));
+        }
+
+        Ok(manifest)
+    }
+}
+
+/// Splits a table identity into its recorded kind and seed or fingerprint.
+fn table_parts(table: TableId) -> (u64, u64) {
+    match table {
+        TableId::Builtin(fingerprint) => (0, fingerprint),
+        TableId::Seed(seed) => (1, seed),
+        TableId::Custom(fingerprint) => (2, fingerprint),
+    }
+}
+
+fn table_from_parts(kind: u64, id: u64) -> io::Result<TableId> {
+    match kind {
+        0 => Ok(TableId::Builtin(id)),
+        1 => Ok(TableId::Seed(id)),
+        2 => Ok(TableId::Custom(id)),
+        _ => Err(invalid("unknown Gear table kind")),
+    }
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// Persists a rename by syncing the directory entry.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be synced portably outside Unix, so the rename is the last step.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn unsupported(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+#[cfg(test)]
+#[path = "tests/manifest_tests.rs"]
+mod tests;
@@ -0,0 +1,454 @@
+//!
+//! MessagePack encoding of manifests and their entries.
+//!
+//! Entries are maps with the keys `offset`, `length`, `fp_hash` (integers), and `digest`
+//! (32-byte binary). A manifest is a map with `version`, `normal`, `options`, `min_size`,
+//! `avg_size`, `max_size`, `table`, `table_id` (integers, as in the binary header), and
+//! `entries` (an array of entry maps), plus `mask_s` and `mask_l`, `alignment` and
+//! `tolerance`, or `delimiter` and `slack` for chunkers with those options set. Integers
+//! use their shortest encoding; on reading, keys may appear in any order and unknown keys
+//! are ignored.
+//!
+
+use super::{MANIFEST_VERSION, Manifest, ManifestEntry, invalid, table_from_parts, table_parts};
+use crate::fastcdc::{Masks, Normal, TableId};
+use std::io::{self, Read, Write};
+
+impl ManifestEntry {
+    ///
+    /// Writes the entry as a MessagePack map.
+    ///
+    /// ## Arguments
+    ///
+    /// * `writer`: The destination (must implement `Write`).
+    ///
+    pub fn write_msgpack<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_map_len(&mut writer, 4)?;
+        write_str(&mut writer, "offset")?;
+        write_uint(&mut writer, self.offset)?;
+        write_str(&mut writer, "length")?;
+        write_uint(&mut writer, self.length as u64)?;
+        write_str(&mut writer, "fp_hash")?;
+        write_uint(&mut writer, self.fp_hash)?;
+        write_str(&mut writer, "digest")?;
+        write_bin(&mut writer, &self.digest)
+    }
+
+    ///
+    /// Reads an entry written by [`ManifestEntry::write_msgpack`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source (must implement `Read`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidData` if the data is not a valid entry.
+    ///
+    pub fn read_msgpack<R: Read>(mut reader: R) -> io::Result<Self> {
+        Self::from_value(read_value(&mut reader, 0)?)
+    }
+
+    fn from_value(value: Value) -> io::Result<Self> {
+        let (mut offset, mut length, mut fp_hash, mut digest) = (None, None, None, None);
+
+        for (key, value) in value.into_map()? {
+            match key.as_str() {
+                "offset" => offset = Some(value.into_uint()?),
+                "length" => length = Some(value.into_uint()? as usize),
+                "fp_hash" => fp_hash = Some(value.into_uint()?),
+                "digest" => {
+                    let bytes = value.into_bin()?;
+                    digest = Some(
+                        bytes
+                            .try_into()
+                            .map_err(|_| invalid("digest must be 32 bytes"))?,
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            offset: offset.ok_or_else(|| missing("offset"))?,
+            length: length.ok_or_else(|| missing("length"))?,
+            fp_hash: fp_hash.ok_or_else(|| missing("fp_hash"))?,
+            digest: digest.ok_or_else(|| missing("digest"))?,
+        })
+    }
+}
+
+impl Manifest {
+    ///
+    /// Writes the manifest as a MessagePack map.
+    ///
+    /// ## Arguments
+    ///
+    /// * `writer`: The destination (must implement `Write`).
+    ///
+    pub fn write_msgpack<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let (table, table_id) = table_parts(self.table);
+        let mut fields = vec![
+            ("version", MANIFEST_VERSION as u64),
+            ("normal", self.normal.offset() as u64),
+            ("options", self.options as u64),
+            ("min_size", self.min_size as u64),
+            ("avg_size", self.avg_size as u64),
+            ("max_size", self.max_size as u64),
+            ("table", table),
+            ("table_id", table_id),
+        ];
+        if let Some(masks) = self.masks {
+            fields.extend([("mask_s", masks.mask_s()), ("mask_l", masks.mask_l())]);
+        }
+        if let Some((alignment, tolerance)) = self.alignment {
+            fields.extend([
+                ("alignment", alignment as u64),
+                ("tolerance", tolerance as u64),
+            ]);
+        }
+        if let Some((delimiter, slack)) = self.delimiter {
+            fields.extend([("delimiter", delimiter as u64), ("slack", slack as u64)]);
+        }
+
+        write_map_len(&mut writer, fields.len() + 1)?;
+        for (key, value) in fields {
+            write_str(&mut writer, key)?;
+            write_uint(&mut writer, value)?;
+        }
+
+        write_str(&mut writer, "entries")?;
+        write_array_len(&mut writer, self.entries.len())?;
+        for entry in &self.entries {
+            entry.write_msgpack(&mut writer)?;
+        }
+
+        writer.flush()
+    }
+
+    ///
+    /// Reads a manifest written by [`Manifest::write_msgpack`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source (must implement `Read`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidData` if the data is not a valid manifest.
+    ///
+    pub fn read_msgpack<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut manifest = Self {
+            min_size: 0,
+            avg_size: 0,
+            max_size: 0,
+            normal: Normal::None,
+            options: 0,
+            table: TableId::builtin(),
+            masks: None,
+            alignment: None,
+            delimiter: None,
+            entries: Vec::new(),
+        };
+        let mut version = None;
+        let (mut table, mut table_id) = (0, None);
+        let (mut mask_s, mut mask_l, mut tolerance, mut slack) = (None, None, 0, 0);
+
+        for (key, value) in read_value(&mut reader, 0)?.into_map()? {
+            match key.as_str() {
+                "version" => version = Some(value.into_uint()?),
+                "normal" => {
+                    manifest.normal = Normal::from_offset(value.into_uint()? as u32);
+                }
+                "options" => manifest.options = value.into_uint()? as u8,
+                "min_size" => manifest.min_size = value.into_uint()? as usize,
+                "avg_size" => manifest.avg_size = value.into_uint()? as usize,
+                "max_size" => manifest.max_size = value.into_uint()? as usize,
+                "table" => table = value.into_uint()?,
+                "table_id" => table_id = Some(value.into_uint()?),
+                "mask_s" => mask_s = Some(value.into_uint()?),
+                "mask_l" => mask_l = Some(value.into_uint()?),
+                "alignment" => {
+                    let alignment = value.into_uint()? as usize;
+                    manifest.alignment = (alignment > 0).then_some((alignment, 0));
+                }
+                "tolerance" => tolerance = value.into_uint()? as usize,
+                "delimiter" => {
+                    let delimiter = u8::try_from(value.into_uint()?)
+                        .map_err(|_| invalid("delimiter must be a byte"))?;
+                    manifest.delimiter = Some((delimiter, 0));
+                }
+                "slack" => slack = value.into_uint()? as usize,
+                "entries" => {
+                    manifest.entries = value
+                        .into_array()?
+                        .into_iter()
+                        .map(ManifestEntry::from_value)
+                        .collect::<io::Result<_>>()?;
+                }
+                _ => {}
+            }
+        }
+
+        match version {
+            Some(1) => return Ok(manifest),
+            Some(v) if v == MANIFEST_VERSION as u64 => {}
+            Some(_) => return Err(invalid("unsupported manifest version")),
+            None => return Err(missing("version")),
+        }
+
+        let table_id = table_id.ok_or_else(|| missing("table_id"))?;
+        manifest.table = table_from_parts(table, table_id)?;
+        manifest.masks = match (mask_s, mask_l) {
+            (Some(mask_s), Some(mask_l)) => Some(Masks::from_recorded(mask_s, mask_l)),
+            (None, None) => None,
+            _ => return Err(invalid("`mask_s` and `mask_l` must appear together")),
+        };
+        if let Some(alignment) = &mut manifest.alignment {
+            alignment.1 = tolerance;
+        }
+        if let Some(delimiter) = &mut manifest.delimiter {
+            delimiter.1 = slack;
+        }
+
+        Ok(manifest)
+    }
+}
+
+fn missing(key: &str) -> io::Error {
+    invalid(&format!("missing `{}`", key))
+}
+
+// --- Encoding ---
+
+fn write_uint<W: Write>(writer: &mut W, value: u64) -> io::Result<()> {
+    match value {
+        0..=0x7f => writer.write_all(&[value as u8]),
+        0x80..=0xff => writer.write_all(&[0xcc, value as u8]),
+        0x100..=0xffff => {
+            writer.write_all(&[0xcd])?;
+            writer.write_all(&(value as u16).to_be_bytes())
+        }
+        0x1_0000..=0xffff_ffff => {
+            writer.write_all(&[0xce])?;
+            writer.write_all(&(value as u32).to_be_bytes())
+        }
+        _ => {
+            writer.write_all(&[0xcf])?;
+            writer.write_all(&value.to_be_bytes())
+        }
+    }
+}
+
+/// Writes a length-prefixed header: the fix form, or the 8/16/32-bit length forms.
+fn write_len<W: Write>(
+    writer: &mut W,
+    len: usize,
+    fix: Option<(u8, usize)>,
+    tags: [u8; 3],
+) -> io::Result<()> {
+    match fix {
+        Some((base, limit)) if len < limit => writer.write_all(&[base | len as u8]),
+        _ if len <= 0xff && tags[0] != 0 => writer.write_all(&[tags[0], len as u8]),
+        _ if len <= 0xffff => {
+            writer.write_all(&[tags[1]])?;
+            writer.write_all(&(len as u16).to_be_bytes())
+        }
+        _ => {
+            writer.write_all(&[tags[2]])?;
+            writer.write_all(&(len as u32).to_be_bytes())
+        }
+    }
+}
+
+fn write_str<W: Write>(writer: &mut W, value: &str) -> io::Result<()> {
+    write_len(writer, value.len(), Some((0xa0, 32)), [0xd9, 0xda, 0xdb])?;
+    writer.write_all(value.as_bytes())
+}
+
+fn write_bin<W: Write>(writer: &mut W, value: &[u8]) -> io::Result<()> {
+    write_len(writer, value.len(), None, [0xc4, 0xc5, 0xc6])?;
+    writer.write_all(value)
+}
+
+fn write_array_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
+    write_len(writer, len, Some((0x90, 16)), [0, 0xdc, 0xdd])
+}
+
+fn write_map_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
+    write_len(writer, len, Some((0x80, 16)), [0, 0xde, 0xdf])
+}
+
+// --- Decoding ---
+
+/// Nesting limit, guarding against stack exhaustion on hostile input.
+const MAX_DEPTH: usize = 16;
+
+/// A decoded MessagePack value, limited to the types manifests can contain or skip.
+enum Value {
+    Nil,
+    Bool,
+    Int(i64),
+    Uint(u64),
+    Float,
+    Str(String),
+    Bin(Vec<u8>),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+}
+
+impl Value {
+    fn into_uint(self) -> io::Result<u64> {
+        match self {
+            Value::Uint(v) => Ok(v),
+            Value::Int(v) if v >= 0 => Ok(v as u64),
+            _ => Err(invalid("expected an unsigned integer")),
+        }
+    }
+
+    fn into_bin(self) -> io::Result<Vec<u8>> {
+        match self {
+            Value::Bin(v) => Ok(v),
+            _ => Err(invalid("expected binary data")),
+        }
+    }
+
+    fn into_array(self) -> io::Result<Vec<Value>> {
+        match self {
+            Value::Array(v) => Ok(v),
+            _ => Err(invalid("expected an array")),
+        }
+    }
+
+    /// Returns the pairs of a map with string keys, dropping pairs with other keys.
+    fn into_map(self) -> io::Result<Vec<(String, Value)>> {
+        match self {
+            Value::Map(pairs) => Ok(pairs
+                .into_iter()
+                .filter_map(|(k, v)| match k {
+                    Value::Str(k) => Some((k, v)),
+                    _ => None,
+                })
+                .collect()),
+            _ => Err(invalid("expected a map")),
+        }
+    }
+}
+
+fn read_bytes<R: Read>(reader: &mut R, len: usize) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader.take(len as u64).read_to_end(&mut buf)?;
+    if buf.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(buf)
+}
+
+fn read_be<R: Read>(reader: &mut R, width: usize) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf[8 - width..])?;
+    Ok(u64::from_be_bytes(buf))
+}
+
+fn read_value<R: Read>(reader: &mut R, depth: usize) -> io::Result<Value> {
+    if depth > MAX_DEPTH {
+        return Err(invalid("nesting too deep"));
+    }
+
+    let mut tag = [0u8; 1];
+    reader.read_exact(&mut tag)?;
+
+    let value = match tag[0] {
+        t @ 0x00..=0x7f => Value::Uint(t as u64),
+        t @ 0x80..=0x8f => read_map(reader, (t & 0x0f) as usize, depth)?,
+        t @ 0x90..=0x9f => read_array(reader, (t & 0x0f) as usize, depth)?,
+        t @ 0xa0..=0xbf => read_str(reader, (t & 0x1f) as usize)?,
+        0xc0 => Value::Nil,
+        0xc2 | 0xc3 => Value::Bool,
+        0xc4 => read_bin(reader, 1)?,
+        0xc5 => read_bin(reader, 2)?,
+        0xc6 => read_bin(reader, 4)?,
+        0xca => {
+            read_be(reader, 4)?;
+            Value::Float
+        }
+        0xcb => {
+            read_be(reader, 8)?;
+            Value::Float
+        }
+        0xcc => Value::Uint(read_be(reader, 1)?),
+        0xcd => Value::Uint(read_be(reader, 2)?),
+        0xce => Value::Uint(read_be(reader, 4)?),
+        0xcf => Value::Uint(read_be(reader, 8)?),
+        0xd0 => Value::Int(read_be(reader, 1)? as i8 as i64),
+        0xd1 => Value::Int(read_be(reader, 2)? as i16 as i64),
+        0xd2 => Value::Int(read_be(reader, 4)? as i32 as i64),
+        0xd3 => Value::Int(read_be(reader, 8)? as i64),
+        0xd9 => {
+            let len = read_be(reader, 1)? as usize;
+            read_str(reader, len)?
+        }
+        0xda => {
+            let len = read_be(reader, 2)? as usize;
+            read_str(reader, len)?
+        }
+        0xdb => {
+            let len = read_be(reader, 4)? as usize;
+            read_str(reader, len)?
+        }
+        0xdc => {
+            let len = read_be(reader, 2)? as usize;
+            read_array(reader, len, depth)?
+        }
+        0xdd => {
+            let len = read_be(reader, 4)? as usize;
+            read_array(reader, len, depth)?
+        }
+        0xde => {
+            let len = read_be(reader, 2)? as usize;
+            read_map(reader, len, depth)?
+        }
+        0xdf => {
+            let len = read_be(reader, 4)? as usize;
+            read_map(reader, len, depth)?
+        }
+        t @ 0xe0..=0xff => Value::Int(t as i8 as i64),
+        _ => return Err(invalid("unsupported MessagePack type")),
+    };
+
+    Ok(value)
+}
+
+fn read_bin<R: Read>(reader: &mut R, width: usize) -> io::Result<Value> {
+    let len = read_be(reader, width)? as usize;
+    Ok(Value::Bin(read_bytes(reader, len)?))
+}
+
+fn read_str<R: Read>(reader: &mut R, len: usize) -> io::Result<Value> {
+    String::from_utf8(read_bytes(reader, len)?)
+        .map(Value::Str)
+        .map_err(|_| invalid("string is not valid UTF-8"))
+}
+
+fn read_array<R: Read>(reader: &mut R, len: usize, depth: usize) -> io::Result<Value> {
+    // Capacity is not trusted from the input; elements are pushed as they are read.
+    let mut items = Vec::new();
+    for _ in 0..len {
+        items.push(read_value(reader, depth + 1)?);
+    }
+    Ok(Value::Array(items))
+}
+
+fn read_map<R: Read>(reader: &mut R, len: usize, depth: usize) -> io::Result<Value> {
+    let mut pairs = Vec::new();
+    for _ in 0..len {
+        let key = read_value(reader, depth + 1)?;
+        let value = read_value(reader, depth + 1)?;
+        pairs.push((key, value));
+    }
+    Ok(Value::Map(pairs))
+}
+
+#[cfg(test)]
+#[path = "tests/msgpack_tests.rs"]
+mod tests;
@@ -0,0 +1,243 @@
+use super::{Manifest, ManifestEntry};
+use crate::digest::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::Range;
+
+/// The result of [`rechunk_with_baseline`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rechunked {
+    /// The manifest of the new data.
+    pub manifest: Manifest,
+    /// Indices of the entries whose contents do not occur in the baseline.
+    pub changed: Vec<usize>,
+    /// The number of entries accepted from a prediction without scanning for a cut point.
+    pub predicted: usize,
+}
+
+impl Manifest {
+    ///
+    /// Updates the manifest after an edit by re-chunking only a window around it.
+    ///
+    /// `edit` is the byte range of the old data that was replaced by `new_len` bytes, and
+    /// `reader` holds the edited data. Chunking restarts at the last boundary the edit
+    /// cannot have influenced, and stops as soon as a new boundary at or past the edit
+    /// coincides with a (shifted) old boundary: from there on both versions hold the same
+    /// bytes from the same starting point, so every following chunk is provably unchanged
+    /// and is kept with its offset shifted. Aligned boundaries only carry over when the edit
+    /// changes the length by a multiple of the alignment; otherwise the data is re-chunked to
+    /// its end.
+    ///
+    /// Returns the range of entries that were re-chunked, as indices into the updated manifest.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The edited data (must implement `Read` and `Seek`).
+    /// * `edit`: The replaced byte range of the old data.
+    /// * `new_len`: The length of the replacement.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if `edit` lies outside the
+    /// described data, or the error raised while reading.
+    ///
+    pub fn rechunk_edit<R: Read + Seek>(
+        &mut self,
+        mut reader: R,
+        edit: Range<u64>,
+        new_len: u64,
+    ) -> io::Result<Range<usize>> {
+        if edit.start > edit.end || edit.end > self.total_len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "edit lies outside the described data",
+            ));
+        }
+
+        let chunker = self.chunker()?;
+        let delta = new_len as i128 - (edit.end - edit.start) as i128;
+
+        let lookahead = chunker.cut_lookahead();
+        // Alignment is absolute, so the old boundaries only carry over whole alignments.
+        let shiftable = chunker
+            .alignment()
+            .is_none_or(|(alignment, _)| delta % alignment as i128 == 0);
+
+        let first = self
+            .entries
+            .iter()
+            .take_while(|e| e.offset + e.length as u64 + lookahead <= edit.start)
+            .count();
+        let restart = self.entries.get(first).map_or(0, |e| e.offset);
+        let edit_end = edit.start + new_len;
+
+        reader.seek(SeekFrom::Start(restart))?;
+
+        let mut rechunked = Vec::new();
+        let mut resume = self.entries.len();
+        let mut old = first;
+
+        for chunk in chunker.chunks_at(reader, restart) {
+            let entry = ManifestEntry::from_chunk(&chunk?);
+            let end = entry.offset + entry.length as u64;
+            rechunked.push(entry);
+
+            if end < edit_end || !shiftable {
+                continue;
+            }
+
+            // The old boundary that `end` corresponds to, if the data follows the edit.
+            let old_end = (end as i128 - delta) as u64;
+            while old < self.entries.len() && self.entries[old].offset < old_end {
+                old += 1;
+            }
+            if old < self.entries.len() && self.entries[old].offset == old_end {
+                resume = old;
+                break;
+            }
+        }
+
+        let mut tail = self.entries.split_off(resume);
+        for entry in &mut tail {
+            entry.offset = (entry.offset as i128 + delta) as u64;
+        }
+
+        let count = rechunked.len();
+        self.entries.truncate(first);
+        self.entries.extend(rechunked);
+        self.entries.extend(tail);
+
+        Ok(first..first + count)
+    }
+}
+
+///
+/// Chunks `reader` using a manifest of a previous version to skip unchanged regions.
+///
+/// Following RapidCDC, every chunk of the baseline predicts the length of the chunk that
+/// follows it. When a prediction is available, that many bytes are hashed and, if their
+/// digest matches a baseline chunk, accepted as a chunk without scanning for a cut point.
+/// Otherwise the next chunk is found by a regular scan. Unchanged regions therefore cost
+/// one digest per chunk, and the full gear scan only runs over modified regions until
+/// chunking falls back into step with the baseline.
+///
+/// A cut point can depend on the first bytes after it, so a predicted chunk directly
+/// followed by modified data may end where a full scan would not have cut. The baseline's
+/// final chunk, which was ended by the data rather than a cut point, is never predicted.
+/// Neither is any chunk of an aligned baseline, as aligned cut points move with their
+/// absolute offsets.
+///
+/// ## Arguments
+///
+/// * `reader`: The new data (must implement `Read`).
+/// * `baseline`: The manifest of the previous version; its chunker parameters are used.
+///
+/// ## Errors
+///
+/// Returns an `std::io::Error` with `ErrorKind::InvalidInput` if the baseline parameters
+/// are invalid, or the error raised while reading.
+///
+pub fn rechunk_with_baseline<R: Read>(mut reader: R, baseline: &Manifest) -> io::Result<Rechunked> {
+    let chunker = baseline.chunker()?;
+    // Aligned cuts depend on absolute offsets, so a shifted chunk is no prediction.
+    let predictable = match chunker.alignment() {
+        Some(_) => 0,
+        None => baseline.entries.len().saturating_sub(1),
+    };
+
+    // The fingerprint of each (non-final) baseline chunk, and the length of its successor.
+    let mut successors: HashMap<[u8; 32], (u64, Option<usize>)> = HashMap::new();
+    for (i, entry) in baseline.entries[..predictable].iter().enumerate() {
+        let next = baseline.entries[..predictable].get(i + 1).map(|e| e.length);
+        successors
+            .entry(entry.digest)
+            .or_insert((entry.fp_hash, next));
+    }
+    let known: HashSet<_> = baseline.entries.iter().map(|e| e.digest).collect();
+
+    let mut manifest = Manifest {
+        entries: Vec::new(),
+        ..baseline.clone()
+    };
+    let mut changed = Vec::new();
+    let mut predicted = 0;
+
+    let mut window = Vec::with_capacity(chunker.max_size() * 2);
+    let mut start = 0;
+    let mut offset = 0u64;
+    let mut eof = false;
+    let mut prediction = baseline.entries[..predictable].first().map(|e| e.length);
+
+    loop {
+        // Keep at least `max_size` bytes ahead of the next chunk, as the iterator does.
+        while !eof && window.len() - start < chunker.max_size() {
+            window.drain(..start);
+            start = 0;
+
+            let len = window.len();
+            window.resize(len + chunker.max_size(), 0);
+            let n = loop {
+                match reader.read(&mut window[len..]) {
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    other => break other?,
+                }
+            };
+            window.truncate(len + n);
+            eof = n == 0;
+        }
+
+        let available = &window[start..];
+        if available.is_empty() {
+            break;
+        }
+
+        let accepted = prediction
+            .filter(|&length| length <= available.len())
+            .map(|length| (length, Sha256::digest(&available[..length])))
+            .filter(|(_, digest)| successors.contains_key(digest));
+
+        let (length, digest, fp_hash) = match accepted {
+            Some((length, digest)) => {
+                predicted += 1;
+                (length, digest, successors[&digest].0)
+            }
+            None => {
+                let scan_len = available.len().min(chunker.max_size());
+                let (fp_hash, mut cutpoint) =
+                    chunker.find_cutpoint_from(&available[..scan_len], 0, 0);
+
+                // Mirrors the iterator: the tail of the stream is never moved.
+                if !(eof && cutpoint == available.len()) {
+                    cutpoint = chunker
+                        .adjust_cutpoint(offset, &available[..scan_len], cutpoint, true)
+                        .unwrap_or(cutpoint);
+                }
+                (cutpoint, Sha256::digest(&available[..cutpoint]), fp_hash)
+            }
+        };
+
+        if !known.contains(&digest) {
+            changed.push(manifest.entries.len());
+        }
+        prediction = successors.get(&digest).and_then(|&(_, next)| next);
+
+        manifest.entries.push(ManifestEntry {
+            offset,
+            length,
+            fp_hash,
+            digest,
+        });
+        start += length;
+        offset += length as u64;
+    }
+
+    Ok(Rechunked {
+        manifest,
+        changed,
+        predicted,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/rechunk_tests.rs"]
+mod tests;
@@ -0,0 +1,149 @@
+use super::*;
+use crate::fastcdc::Normal;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::io::Cursor;
+
+fn sample_data(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("clast-{}-{}", std::process::id(), name))
+}
+
+fn chunker() -> FastCDC {
+    FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+}
+
+// --- Resume Tests ---
+
+#[test]
+fn test_resume_after_interruption() {
+    let data = sample_data(200_000);
+    let chunker = chunker();
+    let journal_path = temp_path("resume.cbj");
+    let manifest_path = temp_path("resume.cbm");
+
+    // Record the first few chunks, then "crash" by dropping the journal
+    let mut journal = Journal::create(&journal_path, &chunker).unwrap();
+    for chunk in chunker.chunks(&data[..]).take(5) {
+        journal.record(&chunk.unwrap()).unwrap();
+    }
+    journal.sync().unwrap();
+    drop(journal);
+
+    let mut journal = Journal::resume(&journal_path).expect("Failed to resume journal");
+    let offset = journal.resume_offset();
+
+    // The recovered prefix ends at a chunk boundary inside the data
+    assert_eq!(journal.manifest().entries().len(), 5);
+    assert!(offset > 0 && offset < data.len() as u64);
+
+    let mut reader = Cursor::new(&data);
+    reader.seek(SeekFrom::Start(offset)).unwrap();
+    for chunk in chunker.chunks_at(reader, offset) {
+        journal.record(&chunk.unwrap()).unwrap();
+    }
+    let resumed = journal.finish(&manifest_path).unwrap();
+
+    // A resumed ingest must produce the same manifest as an uninterrupted one
+    assert_eq!(resumed, Manifest::from_reader(&chunker, &data[..]).unwrap());
+    assert!(
+        !journal_path.exists(),
+        "Journal should be removed on finish"
+    );
+    assert_eq!(
+        Manifest::read_from(File::open(&manifest_path).unwrap()).unwrap(),
+        resumed
+    );
+
+    fs::remove_file(&manifest_path).unwrap();
+}
+
+#[test]
+fn test_resume_keeps_parameters() {
+    let data = sample_data(100_000);
+    let keyed = FastCDC::keyed(b"secret", MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+        .with_alignment(4096, 256);
+    let journal_path = temp_path("params.cbj");
+
+    let mut journal = Journal::create(&journal_path, &keyed).unwrap();
+    for chunk in keyed.chunks(&data[..]).take(3) {
+        journal.record(&chunk.unwrap()).unwrap();
+    }
+    journal.sync().unwrap();
+    drop(journal);
+
+    // A keyed chunker can resume, and the journal still identifies it
+    let journal = Journal::resume(&journal_path).expect("Failed to resume journal");
+    assert_eq!(journal.manifest().entries().len(), 3);
+    assert!(journal.manifest().matches(&keyed));
+    assert!(!journal.manifest().matches(&chunker()));
+
+    fs::remove_file(&journal_path).unwrap();
+}
+
+#[test]
+fn test_torn_tail_is_discarded() {
+    let data = sample_data(100_000);
+    let chunker = chunker();
+    let path = temp_path("torn.cbj");
+
+    let mut journal = Journal::create(&path, &chunker).unwrap();
+    for chunk in chunker.chunks(&data[..]).take(3) {
+        journal.record(&chunk.unwrap()).unwrap();
+    }
+    journal.sync().unwrap();
+    drop(journal);
+
+    // Simulate a partially written record followed by a zero-filled block
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(&[0xab; 30]).unwrap();
+    file.write_all(&[0; RECORD_LEN]).unwrap();
+    drop(file);
+
+    let journal = Journal::resume(&path).unwrap();
+
+    // Only complete, checksummed records are recovered, and the tail is truncated
+    assert_eq!(journal.manifest().entries().len(), 3);
+    assert_eq!(
+        fs::metadata(&path).unwrap().len(),
+        HEADER_LEN + 3 * RECORD_LEN as u64
+    );
+
+    drop(journal);
+    fs::remove_file(&path).unwrap();
+}
+
+// --- Error Tests ---
+
+#[test]
+fn test_rejects_out_of_order_chunks() {
+    let data = sample_data(50_000);
+    let chunker = chunker();
+    let path = temp_path("order.cbj");
+
+    let mut journal = Journal::create(&path, &chunker).unwrap();
+    let second = chunker.chunks(&data[..]).nth(1).unwrap().unwrap();
+
+    // Skipping a chunk would leave a hole the resume offset cannot describe
+    let err = journal.record(&second).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    drop(journal);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_rejects_non_journal() {
+    let path = temp_path("bogus.cbj");
+    fs::write(&path, b"definitely not a journal file").unwrap();
+
+    // A manifest or arbitrary file must not be mistaken for a journal
+    let err = Journal::resume(&path).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,220 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_patterned_data};
+
+fn build_manifest(data: &[u8]) -> Manifest {
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    Manifest::from_reader(&chunker, data).expect("Failed to build manifest")
+}
+
+// --- Serialization Tests ---
+
+#[test]
+fn test_binary_round_trip() {
+    let data = generate_patterned_data(100_000);
+    let manifest = build_manifest(&data);
+
+    let mut encoded = Vec::new();
+    manifest
+        .write_to(&mut encoded)
+        .expect("Failed to write manifest");
+
+    // The encoded size must follow the documented layout
+    assert_eq!(encoded.len(), 84 + 56 * manifest.entries().len());
+
+    let decoded = Manifest::read_from(&encoded[..]).expect("Failed to read manifest");
+
+    // Decoding must restore the parameters and every entry
+    assert_eq!(decoded, manifest, "Decoded manifest does not match");
+    assert_eq!(decoded.total_len(), data.len() as u64);
+}
+
+#[test]
+fn test_rejects_invalid_header() {
+    let data = generate_patterned_data(10_000);
+    let mut encoded = Vec::new();
+    build_manifest(&data)
+        .write_to(&mut encoded)
+        .expect("Failed to write manifest");
+
+    let mut bad_magic = encoded.clone();
+    bad_magic[0] = b'X';
+
+    let mut bad_version = encoded.clone();
+    bad_version[4] = 99;
+
+    // Corrupted headers and truncated input must be rejected
+    assert!(Manifest::read_from(&bad_magic[..]).is_err());
+    assert!(Manifest::read_from(&bad_version[..]).is_err());
+    assert!(Manifest::read_from(&encoded[..encoded.len() - 1]).is_err());
+}
+
+#[test]
+fn test_records_chunker_options() {
+    let data = generate_patterned_data(50_000);
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2).with_one_byte_rolling(true);
+    let manifest = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    let mut bytes = Vec::new();
+    manifest.write_to(&mut bytes).unwrap();
+    let parsed = Manifest::read_from(&bytes[..]).unwrap();
+
+    // The rolling mode survives a round trip, so verification re-chunks identically
+    assert!(parsed.chunker().unwrap().one_byte_rolling());
+    assert!(parsed.verify(&data[..]).is_ok());
+
+    // Unknown option bits are rejected rather than silently ignored
+    bytes[6] = 0x80;
+    let parsed = Manifest::read_from(&bytes[..]).unwrap();
+    assert_eq!(
+        parsed.chunker().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn test_records_boundary_parameters() {
+    let data = generate_patterned_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+        .with_seed(7)
+        .with_masks(Masks::custom((1 << 12) - 1, (1 << 10) - 1))
+        .with_alignment(4096, 512)
+        .with_delimiter(b'M', 64)
+        .with_utf8_boundaries(true);
+    let manifest = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    let mut bytes = Vec::new();
+    manifest.write_to(&mut bytes).unwrap();
+    let parsed = Manifest::read_from(&bytes[..]).unwrap();
+
+    // Every option that moves boundaries is reconstructed, not just the rolling mode
+    let rebuilt = parsed
+        .chunker()
+        .expect("Seeded chunkers must be reconstructed");
+    assert!(parsed.matches(&rebuilt));
+    assert_eq!(rebuilt.masks(), chunker.masks());
+    assert_eq!(rebuilt.alignment(), Some((4096, 512)));
+    assert_eq!(rebuilt.delimiter(), Some((b'M', 64)));
+    assert!(rebuilt.utf8_boundaries());
+    parsed
+        .verify(&data[..])
+        .expect("Manifest must verify with its own parameters");
+
+    // A chunker missing any one of them no longer matches
+    assert!(!parsed.matches(&chunker.clone().with_utf8_boundaries(false)));
+    assert!(!parsed.matches(&chunker.clone().with_seed(8)));
+}
+
+#[test]
+fn test_keyed_manifest_needs_chunker() {
+    let data = generate_patterned_data(50_000);
+    let keyed = FastCDC::keyed(b"secret", MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let manifest = Manifest::from_reader(&keyed, &data[..]).unwrap();
+
+    // The key cannot be recovered from the manifest, so no chunker is guessed
+    assert_eq!(
+        manifest.chunker().unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert!(manifest.verify(&data[..]).is_err());
+
+    // The original chunker verifies it; one with another key is refused up front
+    manifest
+        .verify_with(&keyed, &data[..])
+        .expect("Keyed manifest must verify with its chunker");
+    let other = FastCDC::keyed(b"other", MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    assert_eq!(
+        manifest.verify_with(&other, &data[..]).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn test_reads_version_1() {
+    let data = generate_patterned_data(50_000);
+    let manifest = build_manifest(&data);
+
+    let mut bytes = Vec::new();
+    manifest.write_to(&mut bytes).unwrap();
+
+    // A version 1 header is the version 2 one without the parameters block
+    bytes[4] = 1;
+    bytes.drain(20..20 + PARAMS_LEN);
+
+    let parsed = Manifest::read_from(&bytes[..]).expect("Version 1 manifests must be read");
+    assert_eq!(parsed, manifest);
+}
+
+// --- Verification Tests ---
+
+#[test]
+fn test_verify_unchanged_data() {
+    let data = generate_patterned_data(100_000);
+    let manifest = build_manifest(&data);
+
+    // Re-chunking the same data must match the manifest
+    manifest
+        .verify(&data[..])
+        .expect("Unchanged data failed verification");
+}
+
+#[test]
+fn test_verify_detects_changes() {
+    let data = generate_patterned_data(100_000);
+    let manifest = build_manifest(&data);
+
+    let mut modified = data.clone();
+    modified[50_000] ^= 0xff;
+
+    // A flipped byte, truncation, and appended data must all be reported
+    for changed in [
+        &modified[..],
+        &data[..90_000],
+        &[&data[..], b"extra"].concat()[..],
+    ] {
+        let err = manifest
+            .verify(changed)
+            .expect_err("Modified data passed verification");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
+
+// --- Persistence Tests ---
+
+#[test]
+fn test_persist_replaces_atomically() {
+    let dir = std::env::temp_dir().join(format!("clast-persist-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("data.cbm");
+
+    let first = build_manifest(&generate_patterned_data(30_000));
+    let second = build_manifest(&generate_patterned_data(60_000));
+
+    first.persist(&path).expect("Failed to persist manifest");
+    second.persist(&path).expect("Failed to replace manifest");
+
+    // The destination holds the latest manifest in full
+    let read = Manifest::read_from(File::open(&path).unwrap()).unwrap();
+    assert_eq!(read, second);
+
+    // No temporary file is left behind
+    let leftovers: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+    assert_eq!(leftovers.len(), 1, "Only the manifest should remain");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_persist_failure_leaves_no_file() {
+    let path = std::env::temp_dir()
+        .join(format!("clast-missing-{}", std::process::id()))
+        .join("data.cbm");
+
+    let err = build_manifest(&generate_patterned_data(1_000))
+        .persist(&path)
+        .unwrap_err();
+
+    // A missing directory is reported without creating anything
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    assert!(!path.exists());
+}
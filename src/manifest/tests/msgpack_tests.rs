@@ -0,0 +1,126 @@
+use super::*;
+use crate::fastcdc::FastCDC;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, generate_noise_data};
+
+// --- Entry Tests ---
+
+#[test]
+fn test_entry_encoding() {
+    let entry = ManifestEntry {
+        offset: 1,
+        length: 300,
+        fp_hash: u64::MAX,
+        digest: [7u8; 32],
+    };
+
+    let mut bytes = Vec::new();
+    entry.write_msgpack(&mut bytes).unwrap();
+
+    // A fixmap of four string keys with shortest-form integers and a bin8 digest
+    let mut expected = vec![0x84];
+    expected.extend_from_slice(b"\xa6offset\x01");
+    expected.extend_from_slice(b"\xa6length\xcd\x01\x2c");
+    expected.extend_from_slice(b"\xa7fp_hash\xcf\xff\xff\xff\xff\xff\xff\xff\xff");
+    expected.extend_from_slice(b"\xa6digest\xc4\x20");
+    expected.extend_from_slice(&[7u8; 32]);
+    assert_eq!(bytes, expected);
+
+    // The encoding round-trips
+    assert_eq!(ManifestEntry::read_msgpack(&bytes[..]).unwrap(), entry);
+}
+
+#[test]
+fn test_entry_ignores_unknown_keys() {
+    // Keys in another order, plus an unknown key holding a nested value
+    let mut bytes = vec![0x85];
+    bytes.extend_from_slice(b"\xa6digest\xc4\x20");
+    bytes.extend_from_slice(&[1u8; 32]);
+    bytes.extend_from_slice(b"\xa4note\x92\xc0\xa1x");
+    bytes.extend_from_slice(b"\xa7fp_hash\x05");
+    bytes.extend_from_slice(b"\xa6length\x0a");
+    bytes.extend_from_slice(b"\xa6offset\x00");
+
+    let entry = ManifestEntry::read_msgpack(&bytes[..]).unwrap();
+    assert_eq!(entry.length, 10);
+    assert_eq!(entry.fp_hash, 5);
+    assert_eq!(entry.digest, [1u8; 32]);
+}
+
+#[test]
+fn test_entry_rejects_invalid_data() {
+    // A missing key, a wrong type, and a truncated value are all rejected
+    let missing = b"\x81\xa6offset\x00";
+    assert_eq!(
+        ManifestEntry::read_msgpack(&missing[..])
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidData
+    );
+    let not_a_map = b"\x93\x01\x02\x03";
+    assert_eq!(
+        ManifestEntry::read_msgpack(&not_a_map[..])
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidData
+    );
+    let truncated = b"\x84\xa6offset\xcf\x00";
+    assert_eq!(
+        ManifestEntry::read_msgpack(&truncated[..])
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+}
+
+// --- Manifest Tests ---
+
+#[test]
+fn test_manifest_round_trip() {
+    let data = generate_noise_data(200_000);
+    let chunker =
+        FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level3).with_one_byte_rolling(true);
+    let manifest = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    let mut bytes = Vec::new();
+    manifest.write_msgpack(&mut bytes).unwrap();
+
+    // Parameters, options, and entries all survive the round trip
+    let decoded = Manifest::read_msgpack(&bytes[..]).unwrap();
+    assert_eq!(decoded, manifest);
+    decoded
+        .verify(&data[..])
+        .expect("Decoded manifest must verify");
+}
+
+#[test]
+fn test_manifest_records_parameters() {
+    let data = generate_noise_data(100_000);
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2)
+        .with_seed(7)
+        .with_masks(Masks::custom((1 << 12) - 1, (1 << 10) - 1))
+        .with_alignment(4096, 512)
+        .with_delimiter(0, 64);
+    let manifest = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    let mut bytes = Vec::new();
+    manifest.write_msgpack(&mut bytes).unwrap();
+
+    // The optional keys restore the parameters the binary header records
+    let decoded = Manifest::read_msgpack(&bytes[..]).unwrap();
+    assert_eq!(decoded, manifest);
+    assert!(decoded.matches(&chunker));
+}
+
+#[test]
+fn test_manifest_rejects_wrong_version() {
+    let manifest = Manifest::new(&FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2));
+    let mut bytes = Vec::new();
+    manifest.write_msgpack(&mut bytes).unwrap();
+
+    // The version is the first value, right after its key
+    assert_eq!(&bytes[1..9], b"\xa7version");
+    bytes[9] = 99;
+
+    let err = Manifest::read_msgpack(&bytes[..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
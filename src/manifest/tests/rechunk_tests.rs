@@ -0,0 +1,222 @@
+use super::*;
+use crate::corpus::{Corpus, Edit, apply_edits};
+use crate::fastcdc::{FastCDC, Normal};
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE};
+use std::io::Cursor;
+
+/// Applies `edit` and checks the spliced manifest against a full re-chunk.
+fn check_edit(chunker: &FastCDC, data: &[u8], edit: Edit) -> Range<usize> {
+    let mut manifest = Manifest::from_reader(chunker, data).unwrap();
+    let edited = apply_edits(data, &[edit], 1);
+
+    let (range, new_len) = match edit {
+        Edit::Insert { offset, len } => (offset as u64..offset as u64, len as u64),
+        Edit::Delete { offset, len } => (offset as u64..(offset + len) as u64, 0),
+        Edit::Rewrite { offset, len } => (offset as u64..(offset + len) as u64, len as u64),
+    };
+
+    let rechunked = manifest
+        .rechunk_edit(Cursor::new(&edited), range, new_len)
+        .unwrap();
+
+    // The spliced manifest equals one built from scratch
+    assert_eq!(
+        manifest,
+        Manifest::from_reader(chunker, &edited[..]).unwrap()
+    );
+    rechunked
+}
+
+// --- Rechunk Tests ---
+
+#[test]
+fn test_rechunks_only_a_window() {
+    let data = Corpus::new(5, 2_000_000).generate();
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let total = Manifest::from_reader(&chunker, &data[..])
+        .unwrap()
+        .entries()
+        .len();
+
+    for edit in [
+        Edit::Insert {
+            offset: 1_000_000,
+            len: 100,
+        },
+        Edit::Delete {
+            offset: 1_000_000,
+            len: 1_000,
+        },
+        Edit::Rewrite {
+            offset: 1_000_000,
+            len: 1,
+        },
+    ] {
+        let rechunked = check_edit(&chunker, &data, edit);
+
+        // Only a handful of chunks around the edit are recomputed
+        assert!(rechunked.len() <= 4, "{:?} rechunked {:?}", edit, rechunked);
+        assert!(rechunked.start > total / 3);
+    }
+}
+
+#[test]
+fn test_edits_at_the_edges() {
+    let data = Corpus::new(6, 300_000).generate();
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+
+    // Edits at the start and the end of the data
+    let start = check_edit(&chunker, &data, Edit::Insert { offset: 0, len: 10 });
+    assert_eq!(start.start, 0);
+    check_edit(
+        &chunker,
+        &data,
+        Edit::Insert {
+            offset: data.len(),
+            len: 50_000,
+        },
+    );
+    check_edit(
+        &chunker,
+        &data,
+        Edit::Delete {
+            offset: data.len() - 20_000,
+            len: 20_000,
+        },
+    );
+}
+
+#[test]
+fn test_edits_near_adjusted_boundaries() {
+    let data = Corpus::new(7, 400_000).generate();
+    let base = FastCDC::new(4_096, 16_384, 65_536, Normal::Level2);
+
+    for chunker in [
+        base.clone().with_alignment(512, 256),
+        base.clone().with_delimiter(b'\n', 64),
+    ] {
+        let manifest = Manifest::from_reader(&chunker, &data[..]).unwrap();
+        let boundary = manifest.entries()[manifest.entries().len() / 2].offset as usize;
+
+        // Edits just past a boundary the adjustment may have moved
+        for distance in [3, 10, 40, 100] {
+            let offset = boundary + distance;
+            check_edit(&chunker, &data, Edit::Rewrite { offset, len: 1 });
+            check_edit(&chunker, &data, Edit::Insert { offset, len: 7 });
+        }
+
+        // Edits that shift the tail by whole alignments
+        check_edit(
+            &chunker,
+            &data,
+            Edit::Insert {
+                offset: boundary + 10,
+                len: 1_024,
+            },
+        );
+        check_edit(
+            &chunker,
+            &data,
+            Edit::Delete {
+                offset: boundary + 10,
+                len: 512,
+            },
+        );
+    }
+}
+
+#[test]
+fn test_rejects_out_of_range_edit() {
+    let data = Corpus::new(8, 50_000).generate();
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let mut manifest = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    // An edit past the end is rejected and the manifest is left untouched
+    let err = manifest
+        .rechunk_edit(Cursor::new(&data), 40_000..60_000, 0)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(manifest.total_len(), data.len() as u64);
+}
+
+// --- Baseline Tests ---
+
+#[test]
+fn test_baseline_unchanged_data() {
+    let data = Corpus::new(9, 1_000_000).generate();
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let baseline = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    let result = rechunk_with_baseline(&data[..], &baseline).unwrap();
+
+    // Every chunk but the final one is predicted, and nothing changed
+    assert_eq!(result.manifest, baseline);
+    assert_eq!(result.predicted, baseline.entries().len() - 1);
+    assert!(result.changed.is_empty());
+}
+
+#[test]
+fn test_baseline_modified_data() {
+    let data = Corpus::new(10, 2_000_000).generate();
+    let chunker = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let baseline = Manifest::from_reader(&chunker, &data[..]).unwrap();
+
+    let edited = apply_edits(
+        &data,
+        &[
+            Edit::Insert {
+                offset: 500_123,
+                len: 300,
+            },
+            Edit::Delete {
+                offset: 1_500_321,
+                len: 5_000,
+            },
+        ],
+        2,
+    );
+    let result = rechunk_with_baseline(&edited[..], &baseline).unwrap();
+
+    // The result matches a full re-chunk
+    assert_eq!(
+        result.manifest,
+        Manifest::from_reader(&chunker, &edited[..]).unwrap()
+    );
+
+    // Only chunks around the edits changed, and most chunks were predicted
+    assert!(!result.changed.is_empty() && result.changed.len() <= 8);
+    for &index in &result.changed {
+        let entry = &result.manifest.entries()[index];
+        assert!(!baseline.entries().iter().any(|e| e.digest == entry.digest));
+    }
+    assert!(result.predicted > result.manifest.entries().len() * 9 / 10);
+}
+
+#[test]
+fn test_baseline_with_adjustments() {
+    let data = Corpus::new(11, 1_000_000).generate();
+    let base = FastCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE, Normal::Level2);
+    let edited = apply_edits(
+        &data,
+        &[Edit::Insert {
+            offset: 400_000,
+            len: 7,
+        }],
+        3,
+    );
+
+    for chunker in [
+        base.clone().with_delimiter(b'\n', 64),
+        base.clone().with_alignment(512, 256),
+        base.clone().with_utf8_boundaries(true),
+    ] {
+        let baseline = Manifest::from_reader(&chunker, &data[..]).unwrap();
+        let result = rechunk_with_baseline(&edited[..], &baseline).unwrap();
+
+        // Scanned cut points are adjusted as in a full re-chunk
+        assert_eq!(
+            result.manifest,
+            Manifest::from_reader(&chunker, &edited[..]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::chunker::{ChunkIter, CutPoint, Sizes};
+use crate::error::ClastError;
+use std::io::Read;
+
+///
+/// An MII (Minimal Incremental Interval) chunker implementation.
+///
+/// MII computes no hash, so its chunks carry a zero `fp_hash`.
+///
+#[derive(Debug, Clone)]
+pub struct MII {
+    sizes: Sizes,
+    interval: usize,
+}
+
+impl MII {
+    ///
+    /// Constructs a new `MII` instance.
+    ///
+    /// The interval is the run length whose expected distance on random data, `(interval + 1)!`
+    /// bytes, is closest to `avg_size - min_size`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`FastCDC::new`](crate::fastcdc::FastCDC::new).
+    ///
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        match Self::try_new(min_size, avg_size, max_size) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a new `MII` instance.
+    /// Unlike `new`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as
+    /// [`FastCDC::try_new`](crate::fastcdc::FastCDC::try_new).
+    ///
+    pub fn try_new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self, ClastError> {
+        let sizes = Sizes::new(min_size, avg_size, max_size)?;
+
+        let target = ((avg_size - min_size) as f64).ln();
+        let distance = |interval: usize| {
+            let expected: f64 = (2..=interval + 1).map(|k| (k as f64).ln()).sum();
+            (expected - target).abs()
+        };
+        let interval = (1..=16)
+            .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+            .unwrap();
+
+        Ok(Self { sizes, interval })
+    }
+
+    ///
+    /// Sets the run length of strictly increasing bytes that ends a chunk.
+    ///
+    /// ## Arguments
+    ///
+    /// * `interval`: The number of increases in a row.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `interval` is zero.
+    ///
+    pub fn with_interval(mut self, interval: usize) -> Self {
+        assert!(interval > 0, "interval must be greater than zero");
+
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.sizes.min
+    }
+
+    /// Returns the target average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.sizes.avg
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    /// Returns the run length of strictly increasing bytes that ends a chunk.
+    pub fn interval(&self) -> usize {
+        self.interval
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+}
+
+impl CutPoint for MII {
+    fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        let len = source.len().min(self.sizes.max);
+        if len <= self.sizes.min {
+            return (0, len);
+        }
+
+        let mut run = 0;
+
+        for idx in self.sizes.min..len {
+            if source[idx] > source[idx - 1] {
+                run += 1;
+                if run == self.interval {
+                    return (0, idx + 1);
+                }
+            } else {
+                run = 0;
+            }
+        }
+
+        (0, len)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/core_tests.rs"]
+mod tests;
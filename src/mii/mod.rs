@@ -0,0 +1,18 @@
+//!
+//! This module implements the **MII** (Minimal Incremental Interval) chunking algorithm, as described in the paper:
+//! **"MII: A Novel Content Defined Chunking Algorithm for Finding Incremental Data in Data Synchronization"**.
+//!
+//! ## Reference
+//! * **Title**: MII: A Novel Content Defined Chunking Algorithm for Finding Incremental Data in Data Synchronization
+//! * **Authors**: Changjian Zhang, Deyu Qi, Zhe Cai, Wenhao Huang, Xinyang Wang, Wenlin Li, and Jing Guo.
+//! * **Journal**: IEEE Access, Vol. 7, 2019.
+//!
+//! ## Algorithm
+//! MII keeps a single counter of how many successive bytes have been strictly greater than
+//! the one before. A chunk ends once that run reaches the configured interval, so boundary
+//! detection costs one comparison per byte and no tables.
+//!
+
+mod core;
+
+pub use core::MII;
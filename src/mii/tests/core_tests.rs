@@ -0,0 +1,75 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, assert_tiles, generate_random_data};
+
+// --- Constructor Tests ---
+
+#[test]
+fn test_interval() {
+    // (6 + 1)! = 5040 is the closest expected distance to 8192 - 4069
+    assert_eq!(MII::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).interval(), 6);
+    assert_eq!(
+        MII::new(MIN_SIZE, AVG_SIZE, MAX_SIZE)
+            .with_interval(3)
+            .interval(),
+        3
+    );
+
+    // The size constraints are shared with FastCDC
+    let err = MII::try_new(MIN_SIZE, AVG_SIZE, AVG_SIZE).unwrap_err();
+    assert!(matches!(err, ClastError::InvalidOrdering { .. }));
+}
+
+#[test]
+#[should_panic(expected = "interval must be greater than zero")]
+fn test_zero_interval() {
+    MII::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_interval(0);
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_cut_after_run() {
+    let mut data = vec![0u8; MAX_SIZE];
+    for (i, byte) in data[MIN_SIZE + 10..MIN_SIZE + 20].iter_mut().enumerate() {
+        *byte = i as u8 + 1;
+    }
+    let chunker = MII::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_interval(4);
+
+    // The cut follows the fourth byte in a row that is greater than its predecessor
+    let chunk = chunker.chunks(&data[..]).next().unwrap().unwrap();
+    assert_eq!(chunk.length, MIN_SIZE + 14);
+    assert_eq!(chunk.fp_hash, 0);
+}
+
+#[test]
+fn test_size_constraints() {
+    let data = generate_random_data(1_000_000);
+    let chunker = MII::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // Chunks tile the input and respect the size limits
+    let spans = assert_tiles(&chunker, &data, MIN_SIZE);
+
+    // The average lands between min_size and max_size
+    let avg = data.len() / spans.len();
+    assert!((MIN_SIZE..MAX_SIZE).contains(&avg), "average {}", avg);
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_stream_matches_iter() {
+    use crate::test_util::spans;
+    use futures::StreamExt;
+
+    let data = generate_random_data(200_000);
+    let chunker = MII::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    let mut stream = chunker.chunks_async(&data[..]);
+    let mut actual = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.unwrap();
+        actual.push((chunk.offset, chunk.length));
+    }
+
+    // The async stream finds the same boundaries as the iterator
+    assert_eq!(actual, spans(&chunker, &data));
+}
@@ -0,0 +1,201 @@
+use crate::chunker::{ChunkIter, CutPoint, Sizes};
+use crate::error::ClastError;
+use std::io::Read;
+
+/// Default size of the sliding window, in bytes.
+pub const DEFAULT_WINDOW: usize = 8;
+
+/// Largest accepted window, in bytes.
+const WINDOW_MAX: usize = 64;
+
+///
+/// A PCI (Parity Check of Interval) chunker implementation.
+///
+/// PCI computes no hash, so its chunks carry the popcount of the window that ended them
+/// (or zero) as `fp_hash`.
+///
+#[derive(Debug, Clone)]
+pub struct PCI {
+    sizes: Sizes,
+    window: usize,
+    threshold: u32,
+}
+
+impl PCI {
+    ///
+    /// Constructs a new `PCI` instance with the default window.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`FastCDC::new`](crate::fastcdc::FastCDC::new).
+    ///
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        match Self::try_new(min_size, avg_size, max_size) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a new `PCI` instance with the default window.
+    /// Unlike `new`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as
+    /// [`FastCDC::try_new`](crate::fastcdc::FastCDC::try_new).
+    ///
+    pub fn try_new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self, ClastError> {
+        let sizes = Sizes::new(min_size, avg_size, max_size)?;
+
+        Ok(Self {
+            sizes,
+            window: DEFAULT_WINDOW,
+            threshold: threshold(DEFAULT_WINDOW, avg_size - min_size),
+        })
+    }
+
+    ///
+    /// Sets the size of the sliding window and recomputes the threshold for it.
+    ///
+    /// ## Arguments
+    ///
+    /// * `window`: The number of trailing bytes whose bits are counted.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `window` is zero or greater than 64.
+    ///
+    pub fn with_window(mut self, window: usize) -> Self {
+        assert!(
+            (1..=WINDOW_MAX).contains(&window),
+            "window must be between 1 and {}",
+            WINDOW_MAX
+        );
+
+        self.window = window;
+        self.threshold = threshold(window, self.sizes.avg - self.sizes.min);
+        self
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.sizes.min
+    }
+
+    /// Returns the target average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.sizes.avg
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    /// Returns the size of the sliding window.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the number of `1` bits in the window that ends a chunk.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+}
+
+/// Returns the popcount whose tail probability over `window` random bytes is closest to `1 / distance`.
+fn threshold(window: usize, distance: usize) -> u32 {
+    let bits = (window * 8) as u32;
+    let target = -(distance as f64).ln();
+
+    // ln C(bits, k) - bits * ln 2, accumulated from k = bits downwards.
+    let ln_half = bits as f64 * -std::f64::consts::LN_2;
+    let mut ln_pmf = ln_half;
+    let mut tail = 0.0f64;
+    let mut best = (bits, f64::INFINITY);
+
+    for k in (1..=bits).rev() {
+        tail += ln_pmf.exp();
+        let error = (tail.ln() - target).abs();
+        if error < best.1 {
+            best = (k, error);
+        }
+        // C(bits, k - 1) = C(bits, k) * k / (bits - k + 1)
+        ln_pmf += (k as f64).ln() - ((bits - k + 1) as f64).ln();
+    }
+
+    best.0
+}
+
+impl CutPoint for PCI {
+    fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        let len = source.len().min(self.sizes.max);
+        if len <= self.sizes.min {
+            return (0, len);
+        }
+
+        // Only the window ending at `min_size` affects the first boundary check.
+        let start = self.sizes.min.saturating_sub(self.window);
+        let mut count = 0u32;
+
+        for idx in start..len {
+            count += source[idx].count_ones();
+            if idx >= start + self.window {
+                count -= source[idx - self.window].count_ones();
+            }
+
+            if idx + 1 >= self.sizes.min && count >= self.threshold {
+                return (count as u64, idx + 1);
+            }
+        }
+
+        (0, len)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/core_tests.rs"]
+mod tests;
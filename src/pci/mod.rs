@@ -0,0 +1,14 @@
+//!
+//! This module implements **PCI** (Parity Check of Interval) chunking.
+//!
+//! ## Algorithm
+//! PCI keeps a running count of the `1` bits in a sliding window of bytes, adding the
+//! popcount of each incoming byte and subtracting that of the outgoing one. A chunk ends
+//! where the count reaches a threshold, once it is at least `min_size` long, or at
+//! `max_size` otherwise. The threshold is chosen so that, on random data, a window
+//! reaches it about once every `avg_size - min_size` bytes.
+//!
+
+mod core;
+
+pub use core::{DEFAULT_WINDOW, PCI};
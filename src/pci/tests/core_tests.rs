@@ -0,0 +1,53 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, assert_tiles, generate_random_data};
+
+// --- Constructor Tests ---
+
+#[test]
+fn test_threshold() {
+    // P(popcount >= 46 of 64 bits) is about 1 / 4123
+    let chunker = PCI::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+    assert_eq!(chunker.window(), DEFAULT_WINDOW);
+    assert_eq!(chunker.threshold(), 46);
+
+    // A wider window needs a higher count
+    assert!(chunker.clone().with_window(16).threshold() > 46);
+
+    // The size constraints are shared with FastCDC
+    let err = PCI::try_new(MIN_SIZE, AVG_SIZE, AVG_SIZE).unwrap_err();
+    assert!(matches!(err, ClastError::InvalidOrdering { .. }));
+}
+
+#[test]
+#[should_panic(expected = "window must be between 1 and 64")]
+fn test_window_too_large() {
+    PCI::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_window(65);
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_cut_at_dense_window() {
+    let mut data = vec![0u8; MAX_SIZE];
+    data[MIN_SIZE + 50..MIN_SIZE + 58].fill(0xff);
+    let chunker = PCI::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // The cut follows the byte that brings the window to the threshold
+    let chunk = chunker.chunks(&data[..]).next().unwrap().unwrap();
+    let needed = chunker.threshold().div_ceil(8) as usize;
+    assert_eq!(chunk.length, MIN_SIZE + 50 + needed);
+    assert_eq!(chunk.fp_hash, (needed * 8) as u64);
+}
+
+#[test]
+fn test_size_constraints() {
+    let data = generate_random_data(1_000_000);
+    let chunker = PCI::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // Chunks tile the input and respect the size limits
+    let spans = assert_tiles(&chunker, &data, MIN_SIZE);
+
+    // The average lands between min_size and max_size
+    let avg = data.len() / spans.len();
+    assert!((MIN_SIZE..MAX_SIZE).contains(&avg), "average {}", avg);
+}
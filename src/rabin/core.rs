@@ -0,0 +1,247 @@
+use crate::chunker::{ChunkIter, CutPoint, Sizes};
+use crate::error::ClastError;
+use crate::rabin::poly::{degree, is_irreducible, mul_rem, rem};
+use std::io::Read;
+
+/// Default size of the rolling hash window, in bytes.
+pub const DEFAULT_WINDOW: usize = 64;
+
+/// Default irreducible polynomial of degree 53, as used by restic.
+pub const DEFAULT_POLYNOMIAL: u64 = 0x3d_a335_8b4d_c173;
+
+/// Lowest accepted polynomial degree, which leaves room for the largest mask.
+const DEGREE_MIN: u32 = 24;
+/// Highest accepted polynomial degree, so an appended byte never overflows 64 bits.
+const DEGREE_MAX: u32 = 56;
+
+/// A Rabin fingerprint chunker implementation.
+#[derive(Debug, Clone)]
+pub struct Rabin {
+    sizes: Sizes,
+    polynomial: u64,
+    window: usize,
+    mask: u64,
+    shift: u32,
+    mod_table: [u64; 256],
+    out_table: [u64; 256],
+}
+
+impl Rabin {
+    ///
+    /// Constructs a new `Rabin` instance with the default polynomial and window.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`FastCDC::new`](crate::fastcdc::FastCDC::new).
+    ///
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        match Self::try_new(min_size, avg_size, max_size) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a new `Rabin` instance with the default polynomial and window.
+    /// Unlike `new`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as
+    /// [`FastCDC::try_new`](crate::fastcdc::FastCDC::try_new).
+    ///
+    pub fn try_new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self, ClastError> {
+        let sizes = Sizes::new(min_size, avg_size, max_size)?;
+
+        // The mask with the bit count closest to log2(avg_size).
+        let bits = (avg_size as f64).log2().round() as u32;
+
+        let mut instance = Self {
+            sizes,
+            polynomial: DEFAULT_POLYNOMIAL,
+            window: DEFAULT_WINDOW,
+            mask: (1u64 << bits) - 1,
+            shift: 0,
+            mod_table: [0; 256],
+            out_table: [0; 256],
+        };
+        instance.build_tables();
+
+        Ok(instance)
+    }
+
+    ///
+    /// Sets the irreducible polynomial the fingerprints are computed modulo.
+    ///
+    /// ## Arguments
+    ///
+    /// * `polynomial`: The polynomial, with bit `i` the coefficient of `x^i`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `polynomial` is not irreducible (see [`is_irreducible`](crate::rabin::is_irreducible))
+    /// or its degree is not between 24 and 56.
+    ///
+    pub fn with_polynomial(mut self, polynomial: u64) -> Self {
+        let degree = degree(polynomial as u128).unwrap_or(0);
+        assert!(
+            (DEGREE_MIN..=DEGREE_MAX).contains(&degree),
+            "polynomial degree must be between {} and {}",
+            DEGREE_MIN,
+            DEGREE_MAX
+        );
+        assert!(is_irreducible(polynomial), "polynomial must be irreducible");
+
+        self.polynomial = polynomial;
+        self.build_tables();
+        self
+    }
+
+    ///
+    /// Sets the size of the rolling hash window.
+    ///
+    /// ## Arguments
+    ///
+    /// * `window`: The number of trailing bytes the fingerprint covers.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `window` is zero or greater than `min_size`.
+    ///
+    pub fn with_window(mut self, window: usize) -> Self {
+        assert!(
+            (1..=self.sizes.min).contains(&window),
+            "window must be between 1 and min_size"
+        );
+
+        self.window = window;
+        self.build_tables();
+        self
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.sizes.min
+    }
+
+    /// Returns the target average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.sizes.avg
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    /// Returns the irreducible polynomial.
+    pub fn polynomial(&self) -> u64 {
+        self.polynomial
+    }
+
+    /// Returns the size of the rolling hash window.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+
+    /// Returns the fingerprint of `data`, read as a polynomial with its first byte highest.
+    pub fn fingerprint(&self, data: &[u8]) -> u64 {
+        data.iter()
+            .fold(0, |digest, &byte| self.append(digest, byte))
+    }
+
+    fn append(&self, digest: u64, byte: u8) -> u64 {
+        let index = (digest >> self.shift) as usize;
+        ((digest << 8) | byte as u64) ^ self.mod_table[index]
+    }
+
+    fn build_tables(&mut self) {
+        let pol = self.polynomial as u128;
+        let d = degree(pol).unwrap_or(0);
+        self.shift = d - 8;
+
+        // Reduces the byte shifted past the degree and clears it in the same XOR.
+        for (b, entry) in self.mod_table.iter_mut().enumerate() {
+            let t = (b as u128) << d;
+            *entry = (rem(t, pol) | t) as u64;
+        }
+
+        // The contribution of a byte once `window - 1` further bytes were appended.
+        let factor = (1..self.window).fold(1u128, |f, _| rem(f << 8, pol));
+        for (b, entry) in self.out_table.iter_mut().enumerate() {
+            *entry = mul_rem(b as u128, factor, pol) as u64;
+        }
+    }
+}
+
+impl CutPoint for Rabin {
+    fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        let len = source.len().min(self.sizes.max);
+        if len <= self.sizes.min {
+            return (0, len);
+        }
+
+        // Only the window ending at `min_size` affects the first boundary check.
+        let start = self.sizes.min - self.window;
+        let mut digest = 0u64;
+
+        for idx in start..len {
+            if idx >= start + self.window {
+                digest ^= self.out_table[source[idx - self.window] as usize];
+            }
+            digest = self.append(digest, source[idx]);
+
+            if idx + 1 >= self.sizes.min && digest & self.mask == 0 {
+                return (digest, idx + 1);
+            }
+        }
+
+        (digest, len)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/core_tests.rs"]
+mod tests;
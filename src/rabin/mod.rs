@@ -0,0 +1,24 @@
+//!
+//! This module implements content-defined chunking with **Rabin fingerprints**,
+//! the rolling hash behind LBFS and many later deduplication systems.
+//!
+//! ## Reference
+//! * **Title**: Fingerprinting by Random Polynomials
+//! * **Authors**: Michael O. Rabin
+//! * **Report**: TR-15-81, Center for Research in Computing Technology, Harvard University, 1981.
+//!
+//! ## Algorithm
+//! The fingerprint of a sliding window of bytes is the remainder of the window, read as a
+//! polynomial over GF(2), modulo an irreducible polynomial. Removing the outgoing byte and
+//! appending the incoming one are table lookups. A chunk ends where the low bits of the
+//! fingerprint are all zero, once it is at least `min_size` long, or at `max_size` otherwise.
+//!
+//! With the same polynomial, window, and mask, boundaries match other implementations that
+//! reset the window at each chunk and start hashing `window` bytes before `min_size`.
+//!
+
+mod core;
+mod poly;
+
+pub use core::{DEFAULT_POLYNOMIAL, DEFAULT_WINDOW, Rabin};
+pub use poly::is_irreducible;
@@ -0,0 +1,67 @@
+//! Arithmetic on polynomials over GF(2), stored as bit masks with bit `i` the coefficient of `x^i`.
+
+/// Returns the degree of `p`, or `None` for the zero polynomial.
+pub(super) fn degree(p: u128) -> Option<u32> {
+    (p != 0).then(|| 127 - p.leading_zeros())
+}
+
+/// Returns `a mod m`. `m` must not be zero.
+pub(super) fn rem(mut a: u128, m: u128) -> u128 {
+    let dm = degree(m).expect("modulus must not be zero");
+
+    while let Some(da) = degree(a).filter(|&da| da >= dm) {
+        a ^= m << (da - dm);
+    }
+
+    a
+}
+
+/// Returns `a * b mod m` for operands of degree below 64.
+pub(super) fn mul_rem(a: u128, b: u128, m: u128) -> u128 {
+    let mut product = 0u128;
+    for i in 0..64 {
+        if b >> i & 1 == 1 {
+            product ^= a << i;
+        }
+    }
+    rem(product, m)
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, rem(a, b));
+    }
+    a
+}
+
+///
+/// Returns `true` if `polynomial` is irreducible over GF(2).
+///
+/// Uses Ben-Or's test: a polynomial `f` of degree `d` is irreducible if `gcd(x^(2^i) - x, f) = 1`
+/// for every `i` up to `d / 2`.
+///
+/// ## Arguments
+///
+/// * `polynomial`: The polynomial, with bit `i` the coefficient of `x^i`.
+///
+pub fn is_irreducible(polynomial: u64) -> bool {
+    let f = polynomial as u128;
+    let Some(d) = degree(f) else {
+        return false;
+    };
+    if d == 0 {
+        return false;
+    }
+
+    let x = 0b10u128;
+    let mut power = x;
+
+    for _ in 0..d / 2 {
+        power = mul_rem(power, power, f);
+        if gcd(power ^ x, f) != 1 {
+            return false;
+        }
+    }
+
+    true
+}
@@ -0,0 +1,85 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, assert_tiles, generate_noise_data, spans};
+
+// --- Polynomial Tests ---
+
+#[test]
+fn test_irreducible() {
+    // x^2 + x + 1 and the default polynomial are irreducible
+    assert!(is_irreducible(0b111));
+    assert!(is_irreducible(DEFAULT_POLYNOMIAL));
+
+    // (x + 1)^2 and polynomials divisible by x are not
+    assert!(!is_irreducible(0b101));
+    assert!(!is_irreducible(DEFAULT_POLYNOMIAL ^ 1));
+    assert!(!is_irreducible(0));
+    assert!(!is_irreducible(1));
+}
+
+#[test]
+#[should_panic(expected = "polynomial must be irreducible")]
+fn test_reducible_polynomial() {
+    Rabin::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_polynomial(DEFAULT_POLYNOMIAL ^ 1);
+}
+
+#[test]
+#[should_panic(expected = "polynomial degree must be between 24 and 56")]
+fn test_polynomial_degree() {
+    Rabin::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_polynomial(0b111);
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_invalid_sizes() {
+    // The size constraints are shared with FastCDC
+    let err = Rabin::try_new(MIN_SIZE, AVG_SIZE, AVG_SIZE).unwrap_err();
+    assert!(matches!(err, ClastError::InvalidOrdering { .. }));
+}
+
+#[test]
+fn test_size_constraints() {
+    let data = generate_noise_data(1_000_000);
+    let chunker = Rabin::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // Chunks tile the input and respect the size limits
+    assert_tiles(&chunker, &data, MIN_SIZE);
+}
+
+#[test]
+fn test_fingerprint_covers_window() {
+    let data = generate_noise_data(200_000);
+    let window = 100;
+    let chunker = Rabin::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_window(window);
+
+    // A content-defined cut carries the fingerprint of the window that ends it
+    let mut checked = 0;
+    for chunk in chunker.chunks(&data[..]) {
+        let chunk = chunk.unwrap();
+        if chunk.length < MAX_SIZE {
+            let end = (chunk.offset as usize) + chunk.length;
+            if end < data.len() {
+                assert_eq!(chunk.fp_hash, chunker.fingerprint(&data[end - window..end]));
+                checked += 1;
+            }
+        }
+    }
+    assert!(checked > 0);
+}
+
+#[test]
+fn test_polynomial_changes_boundaries() {
+    let data = generate_noise_data(500_000);
+    let default = Rabin::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // Another irreducible polynomial of degree 53 yields different cut points
+    let other_poly = (0x3d_a335_8b4d_c173u64..)
+        .step_by(2)
+        .skip(1)
+        .find(|&p| is_irreducible(p))
+        .unwrap();
+    let other = Rabin::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_polynomial(other_poly);
+
+    assert_eq!(other.polynomial(), other_poly);
+    assert_ne!(spans(&default, &data), spans(&other, &data));
+}
@@ -0,0 +1,215 @@
+use crate::chunker::{ChunkIter, CutPoint, Sizes};
+use crate::error::ClastError;
+use std::io::Read;
+
+/// Default length of the monotonic sequence that ends a chunk.
+const DEFAULT_SEQ_LENGTH: usize = 5;
+/// Default number of opposing bytes that triggers a skip.
+const DEFAULT_SKIP_TRIGGER: usize = 50;
+
+/// The direction of the byte sequences that end a chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeqOrder {
+    /// Each byte is strictly greater than the one before.
+    Increasing,
+    /// Each byte is strictly less than the one before.
+    Decreasing,
+}
+
+///
+/// A SeqCDC chunker implementation.
+///
+/// SeqCDC computes no hash, so its chunks carry a zero `fp_hash`.
+///
+#[derive(Debug, Clone)]
+pub struct SeqCDC {
+    sizes: Sizes,
+    order: SeqOrder,
+    seq_length: usize,
+    skip_trigger: usize,
+    skip_size: usize,
+}
+
+impl SeqCDC {
+    ///
+    /// Constructs a new `SeqCDC` instance.
+    ///
+    /// Uses increasing sequences of 5 bytes and skips `avg_size / 32` bytes after 50 opposing
+    /// bytes, the configuration evaluated in the paper.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`FastCDC::new`](crate::fastcdc::FastCDC::new).
+    ///
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Self {
+        match Self::try_new(min_size, avg_size, max_size) {
+            Ok(instance) => instance,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Constructs a new `SeqCDC` instance.
+    /// Unlike `new`, this method returns a `Result` instead of panicking on invalid arguments.
+    ///
+    /// ## Arguments
+    ///
+    /// * `min_size`: The minimum size of a chunk.
+    /// * `avg_size`: The target average size of a chunk.
+    /// * `max_size`: The maximum size of a chunk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ClastError`] under the same conditions as
+    /// [`FastCDC::try_new`](crate::fastcdc::FastCDC::try_new).
+    ///
+    pub fn try_new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self, ClastError> {
+        let sizes = Sizes::new(min_size, avg_size, max_size)?;
+
+        Ok(Self {
+            sizes,
+            order: SeqOrder::Increasing,
+            seq_length: DEFAULT_SEQ_LENGTH,
+            skip_trigger: DEFAULT_SKIP_TRIGGER,
+            skip_size: avg_size / 32,
+        })
+    }
+
+    ///
+    /// Sets the direction and length of the sequences that end a chunk.
+    ///
+    /// ## Arguments
+    ///
+    /// * `order`: Whether the sequence increases or decreases.
+    /// * `length`: The number of consecutive bytes moving in that direction.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `length` is zero.
+    ///
+    pub fn with_sequence(mut self, order: SeqOrder, length: usize) -> Self {
+        assert!(length > 0, "sequence length must be greater than zero");
+
+        self.order = order;
+        self.seq_length = length;
+        self
+    }
+
+    ///
+    /// Sets the content-based skipping.
+    ///
+    /// ## Arguments
+    ///
+    /// * `trigger`: The number of opposing bytes in a row that triggers a skip (`0` disables skipping).
+    /// * `size`: The number of bytes skipped.
+    ///
+    pub fn with_skip(mut self, trigger: usize, size: usize) -> Self {
+        self.skip_trigger = trigger;
+        self.skip_size = size;
+        self
+    }
+
+    /// Returns the minimum chunk size.
+    pub fn min_size(&self) -> usize {
+        self.sizes.min
+    }
+
+    /// Returns the target average chunk size.
+    pub fn avg_size(&self) -> usize {
+        self.sizes.avg
+    }
+
+    /// Returns the maximum chunk size.
+    pub fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    /// Returns the direction and length of the sequences that end a chunk.
+    pub fn sequence(&self) -> (SeqOrder, usize) {
+        (self.order, self.seq_length)
+    }
+
+    /// Returns the skip trigger and skip size.
+    pub fn skip(&self) -> (usize, usize) {
+        (self.skip_trigger, self.skip_size)
+    }
+
+    ///
+    /// Creates an iterator that yields chunks from the provided reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `Read`).
+    ///
+    pub fn chunks<R: Read>(&self, reader: R) -> ChunkIter<'_, Self, R> {
+        ChunkIter::new(self, reader)
+    }
+
+    ///
+    /// Creates a stream that yields chunks from the provided async reader.
+    ///
+    /// ## Arguments
+    ///
+    /// * `reader`: The source to read data from (must implement `AsyncRead`).
+    ///
+    #[cfg(feature = "async")]
+    pub fn chunks_async<R>(&self, reader: R) -> crate::chunker::ChunkStream<'_, Self, R>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        crate::chunker::ChunkStream::new(self, reader)
+    }
+}
+
+impl CutPoint for SeqCDC {
+    fn max_size(&self) -> usize {
+        self.sizes.max
+    }
+
+    fn find_cutpoint(&self, source: &[u8]) -> (u64, usize) {
+        let len = source.len().min(self.sizes.max);
+        if len <= self.sizes.min {
+            return (0, len);
+        }
+
+        let mut seq = 0;
+        let mut opposing = 0;
+        let mut idx = self.sizes.min;
+
+        while idx < len {
+            let (prev, byte) = (source[idx - 1], source[idx]);
+            let follows = match self.order {
+                SeqOrder::Increasing => byte > prev,
+                SeqOrder::Decreasing => byte < prev,
+            };
+
+            if follows {
+                seq += 1;
+                if seq == self.seq_length {
+                    return (0, idx + 1);
+                }
+            } else {
+                seq = 0;
+                opposing += 1;
+                if opposing == self.skip_trigger {
+                    opposing = 0;
+                    idx += self.skip_size;
+                }
+            }
+
+            idx += 1;
+        }
+
+        (0, len)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/core_tests.rs"]
+mod tests;
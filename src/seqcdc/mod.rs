@@ -0,0 +1,18 @@
+//!
+//! This module implements the **SeqCDC** chunking algorithm, as described in the paper:
+//! **"SeqCDC: Hashless Content-Defined Data Chunking for Data Deduplication"**.
+//!
+//! ## Reference
+//! * **Title**: SeqCDC: Hashless Content-Defined Data Chunking for Data Deduplication
+//! * **Authors**: Sreeharsha Udayashankar, Abdelrahman Baba, and Samer Al-Kiswany.
+//! * **Conference**: ACM/IFIP International Middleware Conference (Middleware), 2024.
+//!
+//! ## Algorithm
+//! SeqCDC cuts at the end of a monotonic byte sequence of a fixed length. Bytes that break
+//! the sequence are counted, and once enough of them were seen in a row of mismatches, the
+//! search jumps ahead, skipping data that is unlikely to hold a boundary.
+//!
+
+mod core;
+
+pub use core::{SeqCDC, SeqOrder};
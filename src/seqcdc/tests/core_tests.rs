@@ -0,0 +1,65 @@
+use super::*;
+use crate::test_util::{AVG_SIZE, MAX_SIZE, MIN_SIZE, assert_tiles, generate_random_data};
+
+// --- Constructor Tests ---
+
+#[test]
+fn test_defaults() {
+    let chunker = SeqCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // The defaults follow the paper's configuration
+    assert_eq!(chunker.sequence(), (SeqOrder::Increasing, 5));
+    assert_eq!(chunker.skip(), (50, 256));
+
+    // The size constraints are shared with FastCDC
+    let err = SeqCDC::try_new(MIN_SIZE, AVG_SIZE, AVG_SIZE).unwrap_err();
+    assert!(matches!(err, ClastError::InvalidOrdering { .. }));
+}
+
+// --- Chunking Tests ---
+
+#[test]
+fn test_cut_after_sequence() {
+    let mut data = vec![0x80u8; MAX_SIZE];
+    for (i, byte) in data[MIN_SIZE + 10..MIN_SIZE + 20].iter_mut().enumerate() {
+        *byte = 0x70 - i as u8;
+    }
+    let increasing = SeqCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_skip(0, 0);
+    let decreasing = increasing.clone().with_sequence(SeqOrder::Decreasing, 3);
+
+    // A decreasing run does not end an increasing-order chunk
+    let chunk = increasing.chunks(&data[..]).next().unwrap().unwrap();
+    assert_eq!(chunk.length, MAX_SIZE);
+
+    // It ends a decreasing-order chunk once three bytes in a row decrease
+    let chunk = decreasing.chunks(&data[..]).next().unwrap().unwrap();
+    assert_eq!(chunk.length, MIN_SIZE + 13);
+    assert_eq!(chunk.fp_hash, 0);
+}
+
+#[test]
+fn test_skip_passes_over_opposing_bytes() {
+    let mut data = vec![0x80u8; MAX_SIZE];
+    for (i, byte) in data[MIN_SIZE + 100..MIN_SIZE + 110].iter_mut().enumerate() {
+        *byte = 0x81 + i as u8;
+    }
+    let no_skip = SeqCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE).with_skip(0, 0);
+    let skip = no_skip.clone().with_skip(50, 256);
+
+    // Without skipping, the sequence past 100 flat bytes ends the chunk
+    let chunk = no_skip.chunks(&data[..]).next().unwrap().unwrap();
+    assert_eq!(chunk.length, MIN_SIZE + 105);
+
+    // With skipping, 50 flat bytes jump the search past the sequence
+    let chunk = skip.chunks(&data[..]).next().unwrap().unwrap();
+    assert_eq!(chunk.length, MAX_SIZE);
+}
+
+#[test]
+fn test_size_constraints() {
+    let data = generate_random_data(1_000_000);
+    let chunker = SeqCDC::new(MIN_SIZE, AVG_SIZE, MAX_SIZE);
+
+    // Chunks tile the input and respect the size limits
+    assert_tiles(&chunker, &data, MIN_SIZE);
+}
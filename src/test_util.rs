@@ -0,0 +1,84 @@
+//!
+//! Chunk sizes, data generators, and chunking checks shared by the unit tests.
+//!
+
+use crate::chunker::{ChunkIter, CutPoint};
+use std::io;
+
+/// The minimum chunk size most tests chunk with (deliberately not a power of two).
+pub(crate) const MIN_SIZE: usize = 4_069;
+/// The average chunk size most tests chunk with.
+pub(crate) const AVG_SIZE: usize = 8_192;
+/// The maximum chunk size most tests chunk with.
+pub(crate) const MAX_SIZE: usize = 16_384;
+
+/// Repeats `LOREM`, `IPSUM`, and `DOLOR` up to `len` bytes.
+pub(crate) fn generate_patterned_data(len: usize) -> Vec<u8> {
+    const BLOCKS: [&[u8]; 3] = [b"LOREM", b"IPSUM", b"DOLOR"];
+
+    let mut data = Vec::with_capacity(len);
+    let mut idx = 0;
+
+    while data.len() < len {
+        data.extend_from_slice(BLOCKS[idx % BLOCKS.len()]);
+        idx += 1;
+    }
+
+    data.truncate(len);
+    data
+}
+
+/// Maps each index `i` to `(i * 2_654_435_761 >> 13) as u8`, with wrapping 32-bit multiplication.
+pub(crate) fn generate_noise_data(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect()
+}
+
+/// Returns `len` bytes of xorshift64 output, which unlike the noise above has no short period.
+pub(crate) fn generate_random_data(len: usize) -> Vec<u8> {
+    let mut state = 0x853c_49e6_748f_ea9bu64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 56) as u8
+        })
+        .collect()
+}
+
+/// Chunks `data` and returns the offset and length of each chunk.
+pub(crate) fn spans<C: CutPoint>(chunker: &C, data: &[u8]) -> Vec<(u64, usize)> {
+    ChunkIter::new(chunker, data)
+        .map(|c| c.map(|c| (c.offset, c.length)))
+        .collect::<io::Result<Vec<_>>>()
+        .unwrap()
+}
+
+///
+/// Chunks `data` and asserts that the chunks tile it, that none is longer than the chunker's
+/// `max_size`, and that none but the last is shorter than `min_size`.
+///
+/// Returns the offset and length of each chunk.
+///
+pub(crate) fn assert_tiles<C: CutPoint>(
+    chunker: &C,
+    data: &[u8],
+    min_size: usize,
+) -> Vec<(u64, usize)> {
+    let spans = spans(chunker, data);
+
+    let mut offset = 0;
+    for (i, &(chunk_offset, length)) in spans.iter().enumerate() {
+        assert_eq!(chunk_offset, offset);
+        assert!(length <= chunker.max_size());
+        if i + 1 < spans.len() {
+            assert!(length >= min_size);
+        }
+        offset += length as u64;
+    }
+    assert_eq!(offset, data.len() as u64);
+
+    spans
+}
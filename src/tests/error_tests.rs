@@ -0,0 +1,49 @@
+use super::*;
+
+// --- Display Tests ---
+
+#[test]
+fn test_messages() {
+    let err = ClastError::InvalidMinSize {
+        given: 32,
+        bounds: 64..=1_048_576,
+    };
+
+    // Bounds errors name the parameter, its bounds, and the given value
+    assert_eq!(
+        err.to_string(),
+        "min_size must be between 64 and 1048576 (got 32)"
+    );
+    assert_eq!(
+        ClastError::InvalidOrdering {
+            min_size: 3,
+            avg_size: 2,
+            max_size: 1,
+        }
+        .to_string(),
+        "must satisfy the condition: min_size < avg_size < max_size"
+    );
+}
+
+// --- Conversion Tests ---
+
+#[test]
+fn test_io_round_trip() {
+    let err = ClastError::InvalidAvgSize {
+        given: 1,
+        bounds: 256..=4_194_304,
+    };
+
+    // Validation errors become InvalidInput and convert back to the same variant
+    let io_err = io::Error::from(err);
+    assert_eq!(io_err.kind(), io::ErrorKind::InvalidInput);
+    assert!(matches!(
+        ClastError::from(io_err),
+        ClastError::InvalidAvgSize { given: 1, .. }
+    ));
+
+    // Other I/O errors are read failures, and unwrap to the original error
+    let read = ClastError::from(io::Error::new(io::ErrorKind::UnexpectedEof, "eof"));
+    assert!(read.source().is_some());
+    assert_eq!(io::Error::from(read).kind(), io::ErrorKind::UnexpectedEof);
+}